@@ -123,7 +123,7 @@ export function useNftContract() {
       const result = await signingClient.execute(
         account.bech32Address,
         CONTRACT_ADDRESS,
-        { mint: { owner: account.bech32Address, extension: {} } },
+        { mint: { owner: account.bech32Address, extension: null } },
         "auto",
         "",
         coins(mintPrice * 1000000, "uom")
@@ -0,0 +1,1741 @@
+use cosmwasm_std::testing::{
+    mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage, MOCK_CONTRACT_ADDR,
+};
+use cosmwasm_std::{
+    coin, coins, from_json, to_json_binary, to_json_vec, BankMsg, Binary, Coin, ContractResult,
+    CosmosMsg, Empty, Env, HexBinary, OwnedDeps, Reply, ReplyOn, Response, SubMsgResponse,
+    SubMsgResult, SystemResult, Uint128, WasmMsg, WasmQuery,
+};
+use cw721::{Cw721Query, Cw721ReceiveMsg, OwnerOfResponse};
+use k256::ecdsa::signature::Signer;
+use k256::ecdsa::{Signature, SigningKey};
+use sha2::{Digest, Sha256};
+
+use crate::msg::{
+    ArtworkExtension, BuyBackProgram, CompanionPurchase, Cw2981QueryMsg, ExecuteMsg,
+    InstallmentPlan, InstantiateMsg, KeyRotationSignDoc, LazyMintSignDoc, LazyMintVoucher,
+    LenderExecuteMsg, LoanCollateralMsg, MarketplaceFeeConfig, MintMsg, OraclePriceResponse,
+    PresaleConfig, RoyaltiesInfoResponse, RoyaltyConfig, SwapExecuteMsg, TransferCondition,
+    TreasuryAccount, VaultDepositMsg,
+};
+use crate::state::{AutoBuyRules, SaleFeeOverride};
+use crate::{ContractError, Cw721Contract, Extension};
+
+const MINTER: &str = "minter";
+const ARTIST: &str = "artist";
+const MARKET: &str = "market";
+const DENOM: &str = "uom";
+
+type Contract<'a> = Cw721Contract<'a, Extension, Empty>;
+type Deps = OwnedDeps<MockStorage, MockApi, MockQuerier>;
+
+/// a collection with a 5% default royalty to the artist and a 2.5%
+/// marketplace fee, and token "1" minted to alice
+fn setup() -> (Contract<'static>, Deps) {
+    let contract = Contract::default();
+    let mut deps = mock_dependencies();
+    let msg = InstantiateMsg {
+        name: "Gallery".to_string(),
+        symbol: "ART".to_string(),
+        minter: MINTER.to_string(),
+        max_mints: 100,
+        mint_price: coin(0, DENOM),
+        token_uri: None,
+        size_limits: None,
+        royalty: Some(RoyaltyConfig {
+            payment_address: ARTIST.to_string(),
+            share_bps: 500,
+        }),
+        blind_drop: None,
+        profile: Default::default(),
+    };
+    contract
+        .instantiate(deps.as_mut(), mock_env(), mock_info(MINTER, &[]), msg)
+        .unwrap();
+    let fee = MarketplaceFeeConfig {
+        recipient: MARKET.to_string(),
+        share_bps: 250,
+    };
+    execute(
+        &contract,
+        &mut deps,
+        MINTER,
+        &[],
+        ExecuteMsg::SetMarketplaceFee { fee: Some(fee) },
+    )
+    .unwrap();
+    mint(&contract, &mut deps, "alice");
+    (contract, deps)
+}
+
+fn mint(contract: &Contract, deps: &mut Deps, owner: &str) {
+    let msg = MintMsg {
+        owner: owner.to_string(),
+        token_uri: None,
+        referral: None,
+        content_hash: None,
+        extension: None,
+        transferable: true,
+    };
+    execute(contract, deps, MINTER, &[], ExecuteMsg::Mint(msg)).unwrap();
+}
+
+fn execute(
+    contract: &Contract,
+    deps: &mut Deps,
+    sender: &str,
+    funds: &[Coin],
+    msg: ExecuteMsg<Extension>,
+) -> Result<Response, ContractError> {
+    execute_at(contract, deps, mock_env(), sender, funds, msg)
+}
+
+fn execute_at(
+    contract: &Contract,
+    deps: &mut Deps,
+    env: Env,
+    sender: &str,
+    funds: &[Coin],
+    msg: ExecuteMsg<Extension>,
+) -> Result<Response, ContractError> {
+    contract.execute(deps.as_mut(), env, mock_info(sender, funds), msg)
+}
+
+fn env_after(seconds: u64) -> Env {
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(seconds);
+    env
+}
+
+/// (recipient, amount) of every bank send, in order
+fn sends(res: &Response) -> Vec<(String, u128)> {
+    res.messages
+        .iter()
+        .filter_map(|sub| match &sub.msg {
+            CosmosMsg::Bank(BankMsg::Send { to_address, amount }) => {
+                assert_eq!(amount.len(), 1);
+                assert_eq!(amount[0].denom, DENOM);
+                Some((to_address.clone(), amount[0].amount.u128()))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+fn send(to: &str, amount: u128) -> (String, u128) {
+    (to.to_string(), amount)
+}
+
+fn owner_of(contract: &Contract, deps: &Deps, token_id: &str) -> String {
+    let res: OwnerOfResponse = contract
+        .owner_of(deps.as_ref(), mock_env(), token_id.to_string(), false)
+        .unwrap();
+    res.owner
+}
+
+#[test]
+fn buy_token_splits_price_and_refunds_excess() {
+    let (contract, mut deps) = setup();
+    let list = ExecuteMsg::ListForSale {
+        token_id: "1".to_string(),
+        price: coin(1000, DENOM),
+        royalty_waiver: None,
+    };
+    execute(&contract, &mut deps, "alice", &[], list).unwrap();
+
+    let buy = ExecuteMsg::BuyToken {
+        token_id: "1".to_string(),
+    };
+    let err = execute(&contract, &mut deps, "bob", &coins(999, DENOM), buy.clone()).unwrap_err();
+    assert!(matches!(err, ContractError::InsufficientPayment { .. }));
+
+    let res = execute(
+        &contract,
+        &mut deps,
+        "bob",
+        &coins(1100, DENOM),
+        buy.clone(),
+    )
+    .unwrap();
+    assert_eq!(
+        sends(&res),
+        vec![
+            send("bob", 100),
+            send(ARTIST, 50),
+            send(MARKET, 25),
+            send("alice", 925)
+        ]
+    );
+    assert_eq!(owner_of(&contract, &deps, "1"), "bob");
+
+    // the listing lapsed with the sale
+    let err = execute(&contract, &mut deps, "carol", &coins(1000, DENOM), buy).unwrap_err();
+    assert!(matches!(err, ContractError::Std(_)));
+}
+
+#[test]
+fn buy_token_rejects_a_listing_the_seller_no_longer_holds() {
+    let (contract, mut deps) = setup();
+    let list = ExecuteMsg::ListForSale {
+        token_id: "1".to_string(),
+        price: coin(1000, DENOM),
+        royalty_waiver: None,
+    };
+    execute(&contract, &mut deps, "alice", &[], list).unwrap();
+    let auction = ExecuteMsg::CreateAuction {
+        token_id: "1".to_string(),
+        reserve_price: coin(100, DENOM),
+        duration: 100,
+    };
+    execute(&contract, &mut deps, "alice", &[], auction).unwrap();
+
+    let buy = ExecuteMsg::BuyToken {
+        token_id: "1".to_string(),
+    };
+    execute(&contract, &mut deps, "bob", &coins(1000, DENOM), buy).unwrap_err();
+    assert_eq!(owner_of(&contract, &deps, "1"), mock_env().contract.address);
+}
+
+#[test]
+fn settle_auction_refunds_outbid_and_caps_royalties() {
+    let (contract, mut deps) = setup();
+    let auction = ExecuteMsg::CreateAuction {
+        token_id: "1".to_string(),
+        reserve_price: coin(100, DENOM),
+        duration: 100,
+    };
+    execute(&contract, &mut deps, "alice", &[], auction).unwrap();
+
+    let bid = ExecuteMsg::PlaceBid { auction_id: 1 };
+    let err = execute(&contract, &mut deps, "bob", &coins(99, DENOM), bid.clone()).unwrap_err();
+    assert!(matches!(err, ContractError::BidTooLow {}));
+    let res = execute(&contract, &mut deps, "bob", &coins(200, DENOM), bid.clone()).unwrap();
+    assert!(sends(&res).is_empty());
+    let res = execute(&contract, &mut deps, "carol", &coins(300, DENOM), bid).unwrap();
+    assert_eq!(sends(&res), vec![send("bob", 200)]);
+
+    // bids in the last 10 minutes extend the auction to 10 minutes after them
+    let settle = ExecuteMsg::SettleAuction { auction_id: 1 };
+    let err = execute_at(
+        &contract,
+        &mut deps,
+        env_after(100),
+        "dave",
+        &[],
+        settle.clone(),
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::AuctionNotEnded {}));
+    let res = execute_at(
+        &contract,
+        &mut deps,
+        env_after(600),
+        "dave",
+        &[],
+        settle.clone(),
+    )
+    .unwrap();
+    assert_eq!(
+        sends(&res),
+        vec![send(ARTIST, 15), send(MARKET, 7), send("alice", 278)]
+    );
+    assert_eq!(owner_of(&contract, &deps, "1"), "carol");
+    let receipt = contract.sale_receipts.load(&deps.storage, "1").unwrap();
+    assert_eq!(receipt.marketplace_fee, Some(coin(7, DENOM)));
+
+    let err = execute_at(&contract, &mut deps, env_after(600), "dave", &[], settle).unwrap_err();
+    assert!(matches!(err, ContractError::AuctionSettled {}));
+}
+
+#[test]
+fn settle_auction_honours_a_waived_marketplace_fee() {
+    let (contract, mut deps) = setup();
+    let auction = ExecuteMsg::CreateAuction {
+        token_id: "1".to_string(),
+        reserve_price: coin(100, DENOM),
+        duration: 100,
+    };
+    execute(&contract, &mut deps, "alice", &[], auction).unwrap();
+    let fee_override = SaleFeeOverride {
+        waive_marketplace_fee: true,
+        ..Default::default()
+    };
+    let msg = ExecuteMsg::SetAuctionFeeOverride {
+        auction_id: 1,
+        fee_override: Some(fee_override),
+    };
+    execute(&contract, &mut deps, MINTER, &[], msg).unwrap();
+    let bid = ExecuteMsg::PlaceBid { auction_id: 1 };
+    execute(&contract, &mut deps, "bob", &coins(300, DENOM), bid).unwrap();
+
+    let settle = ExecuteMsg::SettleAuction { auction_id: 1 };
+    let res = execute_at(&contract, &mut deps, env_after(600), "dave", &[], settle).unwrap();
+    assert_eq!(sends(&res), vec![send(ARTIST, 15), send("alice", 285)]);
+    let receipt = contract.sale_receipts.load(&deps.storage, "1").unwrap();
+    assert_eq!(receipt.marketplace_fee, None);
+}
+
+#[test]
+fn proxy_bid_answers_lower_bids_and_refunds_change() {
+    let (contract, mut deps) = setup();
+    let auction = ExecuteMsg::CreateAuction {
+        token_id: "1".to_string(),
+        reserve_price: coin(100, DENOM),
+        duration: 100,
+    };
+    execute(&contract, &mut deps, "alice", &[], auction).unwrap();
+
+    let max_bid = ExecuteMsg::PlaceMaxBid { auction_id: 1 };
+    execute(&contract, &mut deps, "bob", &coins(500, DENOM), max_bid).unwrap();
+    // carol is outbid straight away, bob leads at 301
+    let bid = ExecuteMsg::PlaceBid { auction_id: 1 };
+    let res = execute(&contract, &mut deps, "carol", &coins(300, DENOM), bid).unwrap();
+    assert_eq!(sends(&res), vec![send("carol", 300)]);
+
+    let settle = ExecuteMsg::SettleAuction { auction_id: 1 };
+    let res = execute_at(&contract, &mut deps, env_after(600), "dave", &[], settle).unwrap();
+    assert_eq!(
+        sends(&res),
+        vec![
+            send("bob", 199),
+            send(ARTIST, 15),
+            send(MARKET, 7),
+            send("alice", 279)
+        ]
+    );
+    assert_eq!(owner_of(&contract, &deps, "1"), "bob");
+}
+
+#[test]
+fn settle_sealed_auction_pays_fees_and_forfeited_deposits() {
+    let (contract, mut deps) = setup();
+    let auction = ExecuteMsg::CreateSealedAuction {
+        token_id: "1".to_string(),
+        reserve_price: coin(100, DENOM),
+        commit_duration: 100,
+        reveal_duration: 100,
+        deposit: Uint128::new(50),
+    };
+    execute(&contract, &mut deps, "alice", &[], auction).unwrap();
+
+    let commitment = |bidder: &str, amount: u128| {
+        let preimage = format!("1/{}/{}/salt", bidder, amount);
+        HexBinary::from(Sha256::digest(preimage.as_bytes()).to_vec())
+    };
+    for (bidder, amount) in [("bob", 400), ("carol", 300), ("dave", 500)] {
+        let commit = ExecuteMsg::CommitBid {
+            auction_id: 1,
+            commitment: commitment(bidder, amount),
+        };
+        execute(&contract, &mut deps, bidder, &coins(50, DENOM), commit).unwrap();
+    }
+
+    let reveal = |amount: u128| ExecuteMsg::RevealBid {
+        auction_id: 1,
+        amount: Uint128::new(amount),
+        salt: "salt".to_string(),
+    };
+    let env = env_after(100);
+    let msg = reveal(400);
+    execute_at(
+        &contract,
+        &mut deps,
+        env.clone(),
+        "bob",
+        &coins(350, DENOM),
+        msg,
+    )
+    .unwrap();
+    // a losing bid is refunded on reveal
+    let msg = reveal(300);
+    let res = execute_at(
+        &contract,
+        &mut deps,
+        env.clone(),
+        "carol",
+        &coins(250, DENOM),
+        msg,
+    )
+    .unwrap();
+    assert_eq!(sends(&res), vec![send("carol", 300)]);
+    let err = execute_at(&contract, &mut deps, env, "carol", &[], reveal(300)).unwrap_err();
+    assert!(matches!(err, ContractError::BidAlreadyRevealed {}));
+
+    // dave never reveals and forfeits the deposit to alice
+    let settle = ExecuteMsg::SettleSealedAuction { auction_id: 1 };
+    let res = execute_at(&contract, &mut deps, env_after(200), "eve", &[], settle).unwrap();
+    assert_eq!(
+        sends(&res),
+        vec![send(ARTIST, 20), send(MARKET, 10), send("alice", 420)]
+    );
+    assert_eq!(owner_of(&contract, &deps, "1"), "bob");
+}
+
+#[test]
+fn settle_sealed_auction_caps_fees_raised_after_bidding() {
+    let (contract, mut deps) = setup();
+    let auction = ExecuteMsg::CreateSealedAuction {
+        token_id: "1".to_string(),
+        reserve_price: coin(100, DENOM),
+        commit_duration: 100,
+        reveal_duration: 100,
+        deposit: Uint128::new(50),
+    };
+    execute(&contract, &mut deps, "alice", &[], auction).unwrap();
+    let preimage = "1/bob/400/salt";
+    let commit = ExecuteMsg::CommitBid {
+        auction_id: 1,
+        commitment: HexBinary::from(Sha256::digest(preimage.as_bytes()).to_vec()),
+    };
+    execute(&contract, &mut deps, "bob", &coins(50, DENOM), commit).unwrap();
+    let reveal = ExecuteMsg::RevealBid {
+        auction_id: 1,
+        amount: Uint128::new(400),
+        salt: "salt".to_string(),
+    };
+    execute_at(
+        &contract,
+        &mut deps,
+        env_after(100),
+        "bob",
+        &coins(350, DENOM),
+        reveal,
+    )
+    .unwrap();
+
+    // with the royalty, a 100% fee no longer fits in the price
+    let fee = MarketplaceFeeConfig {
+        recipient: MARKET.to_string(),
+        share_bps: 10_000,
+    };
+    let msg = ExecuteMsg::SetMarketplaceFee { fee: Some(fee) };
+    execute(&contract, &mut deps, MINTER, &[], msg).unwrap();
+
+    let settle = ExecuteMsg::SettleSealedAuction { auction_id: 1 };
+    let res = execute_at(&contract, &mut deps, env_after(200), "eve", &[], settle).unwrap();
+    assert_eq!(sends(&res), vec![send(ARTIST, 20), send(MARKET, 380)]);
+    assert_eq!(owner_of(&contract, &deps, "1"), "bob");
+}
+
+#[test]
+fn accept_collection_bid_splits_price_and_cancel_refunds_the_rest() {
+    let (contract, mut deps) = setup();
+    let bid = ExecuteMsg::PlaceCollectionBid {
+        price: coin(400, DENOM),
+        quantity: 2,
+    };
+    let err = execute(&contract, &mut deps, "bob", &coins(400, DENOM), bid.clone()).unwrap_err();
+    assert!(matches!(err, ContractError::IncorrectPayment {}));
+    execute(&contract, &mut deps, "bob", &coins(800, DENOM), bid).unwrap();
+
+    let accept = ExecuteMsg::AcceptCollectionBid {
+        bid_id: 1,
+        token_id: "1".to_string(),
+    };
+    let err = execute(&contract, &mut deps, "carol", &[], accept.clone()).unwrap_err();
+    assert!(matches!(err, ContractError::Unauthorized {}));
+    let res = execute(&contract, &mut deps, "alice", &[], accept).unwrap();
+    assert_eq!(
+        sends(&res),
+        vec![send(ARTIST, 20), send(MARKET, 10), send("alice", 370)]
+    );
+    assert_eq!(owner_of(&contract, &deps, "1"), "bob");
+
+    let cancel = ExecuteMsg::CancelCollectionBid { bid_id: 1 };
+    let err = execute(&contract, &mut deps, "alice", &[], cancel.clone()).unwrap_err();
+    assert!(matches!(err, ContractError::Unauthorized {}));
+    let res = execute(&contract, &mut deps, "bob", &[], cancel).unwrap();
+    assert_eq!(sends(&res), vec![send("bob", 400)]);
+}
+
+#[test]
+fn offers_stay_escrowed_until_accepted_or_withdrawn() {
+    let (contract, mut deps) = setup();
+    let offer = ExecuteMsg::MakeOffer {
+        token_id: "1".to_string(),
+    };
+    execute(
+        &contract,
+        &mut deps,
+        "bob",
+        &coins(600, DENOM),
+        offer.clone(),
+    )
+    .unwrap();
+    execute(&contract, &mut deps, "carol", &coins(500, DENOM), offer).unwrap();
+
+    let withdraw = ExecuteMsg::WithdrawOffer {
+        token_id: "1".to_string(),
+    };
+    let res = execute(&contract, &mut deps, "carol", &[], withdraw.clone()).unwrap();
+    assert_eq!(sends(&res), vec![send("carol", 500)]);
+    execute(&contract, &mut deps, "carol", &[], withdraw).unwrap_err();
+
+    let accept = ExecuteMsg::AcceptOffer {
+        token_id: "1".to_string(),
+        bidder: "bob".to_string(),
+    };
+    let res = execute(&contract, &mut deps, "alice", &[], accept).unwrap();
+    assert_eq!(
+        sends(&res),
+        vec![send(ARTIST, 30), send(MARKET, 15), send("alice", 555)]
+    );
+    assert_eq!(owner_of(&contract, &deps, "1"), "bob");
+}
+
+#[test]
+fn pause_blocks_everything_but_admin_configuration() {
+    let (contract, mut deps) = setup();
+    let err = execute(&contract, &mut deps, "alice", &[], ExecuteMsg::Pause {}).unwrap_err();
+    assert!(matches!(err, ContractError::Unauthorized {}));
+    execute(&contract, &mut deps, MINTER, &[], ExecuteMsg::Pause {}).unwrap();
+
+    let list = ExecuteMsg::ListForSale {
+        token_id: "1".to_string(),
+        price: coin(1000, DENOM),
+        royalty_waiver: None,
+    };
+    let err = execute(&contract, &mut deps, "alice", &[], list.clone()).unwrap_err();
+    assert!(matches!(err, ContractError::ContractPaused {}));
+    let offer = ExecuteMsg::MakeOffer {
+        token_id: "1".to_string(),
+    };
+    let err = execute(&contract, &mut deps, "bob", &coins(10, DENOM), offer).unwrap_err();
+    assert!(matches!(err, ContractError::ContractPaused {}));
+
+    // the admin may still configure, but not move anything
+    let toggle = ExecuteMsg::ToggleMinting {};
+    execute(&contract, &mut deps, MINTER, &[], toggle.clone()).unwrap();
+    let err = execute(&contract, &mut deps, "alice", &[], toggle).unwrap_err();
+    assert!(matches!(err, ContractError::ContractPaused {}));
+    let transfer = ExecuteMsg::TransferNft {
+        recipient: "bob".to_string(),
+        token_id: "1".to_string(),
+    };
+    let err = execute(&contract, &mut deps, MINTER, &[], transfer).unwrap_err();
+    assert!(matches!(err, ContractError::ContractPaused {}));
+
+    execute(&contract, &mut deps, MINTER, &[], ExecuteMsg::Unpause {}).unwrap();
+    execute(&contract, &mut deps, "alice", &[], list).unwrap();
+}
+
+/// artist is onboarded through a work of record, with key as signing key
+fn onboard_artist(contract: &Contract, deps: &mut Deps, key: &SigningKey) {
+    let artist = ExecuteMsg::SetArtistOfRecord {
+        token_id: "1".to_string(),
+        artist: Some(ARTIST.to_string()),
+    };
+    execute(contract, deps, MINTER, &[], artist).unwrap();
+    let register = ExecuteMsg::RegisterArtistKey {
+        pubkey: pubkey(key),
+    };
+    execute(contract, deps, ARTIST, &[], register).unwrap();
+}
+
+fn signing_key(seed: u8) -> SigningKey {
+    SigningKey::from_bytes(&[seed; 32].into()).unwrap()
+}
+
+fn pubkey(key: &SigningKey) -> Binary {
+    Binary::from(key.verifying_key().to_encoded_point(true).as_bytes())
+}
+
+/// signature of key over the sha256 of the JSON of doc
+fn sign<D: serde::Serialize>(key: &SigningKey, doc: &D) -> Binary {
+    let signature: Signature = key.sign(&to_json_vec(doc).unwrap());
+    Binary::from(signature.to_bytes().to_vec())
+}
+
+fn voucher(nonce: u64, price: u128) -> LazyMintVoucher<Extension> {
+    LazyMintVoucher {
+        artist: ARTIST.to_string(),
+        token_uri: "ipfs://work".to_string(),
+        price: coin(price, DENOM),
+        royalty: None,
+        nonce,
+        artist_share_bps: 8000,
+        expires: mock_env().block.time.plus_seconds(100),
+        extension: None,
+    }
+}
+
+fn sign_voucher(key: &SigningKey, voucher: &LazyMintVoucher<Extension>) -> Binary {
+    let doc = LazyMintSignDoc {
+        contract: mock_env().contract.address.to_string(),
+        voucher: voucher.clone(),
+    };
+    sign(key, &doc)
+}
+
+#[test]
+fn lazy_mint_verifies_the_voucher_and_pays_the_artist() {
+    let (contract, mut deps) = setup();
+    let key = signing_key(1);
+    onboard_artist(&contract, &mut deps, &key);
+
+    // a voucher with a lowered price no longer matches the signature
+    let signed = voucher(1, 1000);
+    let signature = sign_voucher(&key, &signed);
+    let mut tampered = signed.clone();
+    tampered.price = coin(1, DENOM);
+    let msg = ExecuteMsg::LazyMint {
+        voucher: tampered,
+        signature: signature.clone(),
+    };
+    let err = execute(&contract, &mut deps, "bob", &coins(1, DENOM), msg).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidSignature {}));
+
+    let msg = ExecuteMsg::LazyMint {
+        voucher: signed.clone(),
+        signature: sign_voucher(&signing_key(2), &signed),
+    };
+    let err = execute(&contract, &mut deps, "bob", &coins(1000, DENOM), msg).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidSignature {}));
+
+    let msg = ExecuteMsg::LazyMint {
+        voucher: signed.clone(),
+        signature: signature.clone(),
+    };
+    let res = execute(
+        &contract,
+        &mut deps,
+        "bob",
+        &coins(1000, DENOM),
+        msg.clone(),
+    )
+    .unwrap();
+    assert_eq!(sends(&res), vec![send(ARTIST, 800)]);
+    assert_eq!(owner_of(&contract, &deps, "2"), "bob");
+
+    let err = execute(&contract, &mut deps, "carol", &coins(1000, DENOM), msg).unwrap_err();
+    assert!(matches!(err, ContractError::VoucherRedeemed {}));
+
+    let late = voucher(2, 1000);
+    let msg = ExecuteMsg::LazyMint {
+        signature: sign_voucher(&key, &late),
+        voucher: late,
+    };
+    let err = execute_at(
+        &contract,
+        &mut deps,
+        env_after(100),
+        "bob",
+        &coins(1000, DENOM),
+        msg,
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::VoucherExpired {}));
+}
+
+#[test]
+fn key_rotation_needs_the_current_key_and_retires_it() {
+    let (contract, mut deps) = setup();
+    let old_key = signing_key(1);
+    let new_key = signing_key(2);
+    onboard_artist(&contract, &mut deps, &old_key);
+
+    let doc = KeyRotationSignDoc {
+        contract: mock_env().contract.address.to_string(),
+        artist: ARTIST.to_string(),
+        version: 1,
+        pubkey: pubkey(&new_key),
+    };
+    // the new key cannot vouch for itself
+    let rotate = ExecuteMsg::RotateArtistKey {
+        pubkey: pubkey(&new_key),
+        signature: sign(&new_key, &doc),
+    };
+    let err = execute(&contract, &mut deps, ARTIST, &[], rotate).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidSignature {}));
+    // nor can a signature for another version be replayed
+    let stale = KeyRotationSignDoc {
+        version: 0,
+        ..doc.clone()
+    };
+    let rotate = ExecuteMsg::RotateArtistKey {
+        pubkey: pubkey(&new_key),
+        signature: sign(&old_key, &stale),
+    };
+    let err = execute(&contract, &mut deps, ARTIST, &[], rotate).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidSignature {}));
+
+    let rotate = ExecuteMsg::RotateArtistKey {
+        pubkey: pubkey(&new_key),
+        signature: sign(&old_key, &doc),
+    };
+    execute(&contract, &mut deps, ARTIST, &[], rotate).unwrap();
+
+    // vouchers signed by the retired key are no longer honoured
+    let offered = voucher(1, 1000);
+    let msg = ExecuteMsg::LazyMint {
+        voucher: offered.clone(),
+        signature: sign_voucher(&old_key, &offered),
+    };
+    let err = execute(&contract, &mut deps, "bob", &coins(1000, DENOM), msg).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidSignature {}));
+    let msg = ExecuteMsg::LazyMint {
+        voucher: offered.clone(),
+        signature: sign_voucher(&new_key, &offered),
+    };
+    execute(&contract, &mut deps, "bob", &coins(1000, DENOM), msg).unwrap();
+}
+
+#[test]
+fn first_refusal_holds_an_accepted_offer_for_the_artist() {
+    let (contract, mut deps) = setup();
+    onboard_artist(&contract, &mut deps, &signing_key(1));
+    mint(&contract, &mut deps, "alice");
+    let artist = ExecuteMsg::SetArtistOfRecord {
+        token_id: "2".to_string(),
+        artist: Some(ARTIST.to_string()),
+    };
+    execute(&contract, &mut deps, MINTER, &[], artist).unwrap();
+    let window = ExecuteMsg::SetFirstRefusal { window: Some(100) };
+    execute(&contract, &mut deps, ARTIST, &[], window).unwrap();
+
+    for token_id in ["1", "2"] {
+        let offer = ExecuteMsg::MakeOffer {
+            token_id: token_id.to_string(),
+        };
+        execute(&contract, &mut deps, "bob", &coins(600, DENOM), offer).unwrap();
+        let accept = ExecuteMsg::AcceptOffer {
+            token_id: token_id.to_string(),
+            bidder: "bob".to_string(),
+        };
+        let res = execute(&contract, &mut deps, "alice", &[], accept).unwrap();
+        assert!(sends(&res).is_empty());
+        assert_eq!(
+            owner_of(&contract, &deps, token_id),
+            mock_env().contract.address
+        );
+    }
+    let withdraw = ExecuteMsg::WithdrawOffer {
+        token_id: "1".to_string(),
+    };
+    let err = execute(&contract, &mut deps, "bob", &[], withdraw).unwrap_err();
+    assert!(matches!(err, ContractError::FirstRefusalPending { .. }));
+
+    // the artist matches the first and bob's offer is refunded
+    let matched = ExecuteMsg::MatchFirstRefusal {
+        token_id: "1".to_string(),
+    };
+    let err = execute(
+        &contract,
+        &mut deps,
+        "carol",
+        &coins(600, DENOM),
+        matched.clone(),
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::Unauthorized {}));
+    let res = execute(&contract, &mut deps, ARTIST, &coins(600, DENOM), matched).unwrap();
+    assert_eq!(
+        sends(&res),
+        vec![
+            send(ARTIST, 30),
+            send(MARKET, 15),
+            send("alice", 555),
+            send("bob", 600)
+        ]
+    );
+    assert_eq!(owner_of(&contract, &deps, "1"), ARTIST);
+
+    // and lets the second lapse, after which anyone completes it
+    let complete = ExecuteMsg::CompleteFirstRefusal {
+        token_id: "2".to_string(),
+    };
+    let err = execute(&contract, &mut deps, "dave", &[], complete.clone()).unwrap_err();
+    assert!(matches!(err, ContractError::FirstRefusalPending { .. }));
+    let matched = ExecuteMsg::MatchFirstRefusal {
+        token_id: "2".to_string(),
+    };
+    let err = execute_at(
+        &contract,
+        &mut deps,
+        env_after(100),
+        ARTIST,
+        &coins(600, DENOM),
+        matched,
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::FirstRefusalLapsed {}));
+    let res = execute_at(&contract, &mut deps, env_after(100), "dave", &[], complete).unwrap();
+    assert_eq!(
+        sends(&res),
+        vec![send(ARTIST, 30), send(MARKET, 15), send("alice", 555)]
+    );
+    assert_eq!(owner_of(&contract, &deps, "2"), "bob");
+}
+
+#[test]
+fn auto_buy_spends_the_budget_on_matching_new_listings() {
+    let (contract, mut deps) = setup();
+    let list = |price: u128| ExecuteMsg::ListForSale {
+        token_id: "1".to_string(),
+        price: coin(price, DENOM),
+        royalty_waiver: None,
+    };
+    execute(&contract, &mut deps, "alice", &[], list(1000)).unwrap();
+    let rules = AutoBuyRules {
+        max_price: Uint128::new(1000),
+        artist_names: vec![],
+        mediums: vec![],
+        min_year: None,
+        max_year: None,
+    };
+    let fund = ExecuteMsg::FundAutoBuy { rules };
+    let env = env_after(10);
+    execute_at(
+        &contract,
+        &mut deps,
+        env.clone(),
+        "bob",
+        &coins(1500, DENOM),
+        fund,
+    )
+    .unwrap();
+
+    let auto_buy = ExecuteMsg::AutoBuy {
+        collector: "bob".to_string(),
+        token_id: "1".to_string(),
+    };
+    // listed before the rules were set
+    let err = execute_at(
+        &contract,
+        &mut deps,
+        env.clone(),
+        "dave",
+        &[],
+        auto_buy.clone(),
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::AutoBuyRulesNotMet {}));
+    // priced above the rules
+    execute_at(&contract, &mut deps, env.clone(), "alice", &[], list(1001)).unwrap();
+    let err = execute_at(
+        &contract,
+        &mut deps,
+        env.clone(),
+        "dave",
+        &[],
+        auto_buy.clone(),
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::AutoBuyRulesNotMet {}));
+
+    execute_at(&contract, &mut deps, env.clone(), "alice", &[], list(1000)).unwrap();
+    let res = execute_at(&contract, &mut deps, env.clone(), "dave", &[], auto_buy).unwrap();
+    assert_eq!(
+        sends(&res),
+        vec![send(ARTIST, 50), send(MARKET, 25), send("alice", 925)]
+    );
+    assert_eq!(owner_of(&contract, &deps, "1"), "bob");
+
+    let res = execute(
+        &contract,
+        &mut deps,
+        "bob",
+        &[],
+        ExecuteMsg::WithdrawAutoBuy {},
+    )
+    .unwrap();
+    assert_eq!(sends(&res), vec![send("bob", 500)]);
+    execute(
+        &contract,
+        &mut deps,
+        "bob",
+        &[],
+        ExecuteMsg::WithdrawAutoBuy {},
+    )
+    .unwrap_err();
+}
+
+#[test]
+fn auto_buy_rules_match_artwork_traits() {
+    let rules = AutoBuyRules {
+        max_price: Uint128::new(1000),
+        artist_names: vec!["Hilma af Klint".to_string()],
+        mediums: vec!["oil on canvas".to_string()],
+        min_year: Some(1900),
+        max_year: None,
+    };
+    let mut artwork = ArtworkExtension {
+        artist_name: "hilma AF klint".to_string(),
+        title: "The Ten Largest".to_string(),
+        year: Some(1907),
+        medium: Some("Oil on canvas".to_string()),
+        dimensions: None,
+        edition: None,
+        provenance_notes: None,
+    };
+    assert!(rules.matches(Some(&artwork)));
+    assert!(!rules.matches(None));
+
+    artwork.year = Some(1890);
+    assert!(!rules.matches(Some(&artwork)));
+    artwork.year = None;
+    assert!(!rules.matches(Some(&artwork)));
+
+    let any = AutoBuyRules {
+        artist_names: vec![],
+        mediums: vec![],
+        min_year: None,
+        ..rules
+    };
+    assert!(any.matches(Some(&artwork)));
+    assert!(any.matches(None));
+}
+
+/// a 1000 mint price payable as 400 down and two installments of 300,
+/// due every 100 seconds with 50 seconds of grace
+fn enable_installments(contract: &Contract, deps: &mut Deps) {
+    let msg = ExecuteMsg::SetMintConfig {
+        max_mints: 100,
+        mint_price: coin(1000, DENOM),
+    };
+    execute(contract, deps, MINTER, &[], msg).unwrap();
+    let plan = InstallmentPlan {
+        down_payment: Uint128::new(400),
+        installments: 2,
+        interval: 100,
+        grace_period: 50,
+        late_fee: Uint128::zero(),
+        forfeit_refund_bps: 5000,
+    };
+    let msg = ExecuteMsg::SetInstallmentPlan { plan: Some(plan) };
+    execute(contract, deps, MINTER, &[], msg).unwrap();
+}
+
+fn reserve(contract: &Contract, deps: &mut Deps, buyer: &str) {
+    let msg = MintMsg {
+        owner: buyer.to_string(),
+        token_uri: None,
+        referral: None,
+        content_hash: None,
+        extension: None,
+        transferable: true,
+    };
+    let msg = ExecuteMsg::ReserveMint(msg);
+    execute(contract, deps, buyer, &coins(400, DENOM), msg).unwrap();
+}
+
+fn withdraw(contract: &Contract, deps: &mut Deps) -> Result<Response, ContractError> {
+    let msg = ExecuteMsg::Withdraw {
+        amount: None,
+        recipient: None,
+    };
+    execute(contract, deps, MINTER, &[], msg)
+}
+
+#[test]
+fn installments_stay_escrowed_until_the_token_is_delivered() {
+    let (contract, mut deps) = setup();
+    enable_installments(&contract, &mut deps);
+    reserve(&contract, &mut deps, "bob");
+    assert_eq!(owner_of(&contract, &deps, "2"), MOCK_CONTRACT_ADDR);
+
+    let pay = || ExecuteMsg::PayInstallment {
+        token_id: "2".to_string(),
+    };
+    execute(&contract, &mut deps, "bob", &coins(300, DENOM), pay()).unwrap();
+    deps.querier
+        .update_balance(MOCK_CONTRACT_ADDR, coins(700, DENOM));
+    let err = withdraw(&contract, &mut deps).unwrap_err();
+    assert!(matches!(err, ContractError::NothingToWithdraw {}));
+
+    execute(&contract, &mut deps, "bob", &coins(300, DENOM), pay()).unwrap();
+    deps.querier
+        .update_balance(MOCK_CONTRACT_ADDR, coins(1000, DENOM));
+    assert_eq!(owner_of(&contract, &deps, "2"), "bob");
+    let res = withdraw(&contract, &mut deps).unwrap();
+    assert_eq!(sends(&res), vec![send(MINTER, 1000)]);
+}
+
+/// (account, amount) of every treasury ledger entry
+fn ledger(contract: &Contract, deps: &Deps) -> Vec<(TreasuryAccount, u128)> {
+    contract
+        .treasury_entries(&deps.storage)
+        .unwrap()
+        .into_iter()
+        .map(|entry| (entry.account, entry.amount.u128()))
+        .collect()
+}
+
+#[test]
+fn forfeit_refunds_from_escrow_and_keeps_the_rest_as_proceeds() {
+    let (contract, mut deps) = setup();
+    enable_installments(&contract, &mut deps);
+    reserve(&contract, &mut deps, "bob");
+    let pay = ExecuteMsg::PayInstallment {
+        token_id: "2".to_string(),
+    };
+    execute(&contract, &mut deps, "bob", &coins(300, DENOM), pay).unwrap();
+    assert_eq!(
+        ledger(&contract, &deps),
+        vec![(TreasuryAccount::Reservations, 700)]
+    );
+
+    let forfeit = || ExecuteMsg::ForfeitReservation {
+        token_id: "2".to_string(),
+    };
+    let err = execute_at(&contract, &mut deps, env_after(250), "eve", &[], forfeit()).unwrap_err();
+    assert!(matches!(err, ContractError::ReservationNotOverdue {}));
+    let res = execute_at(&contract, &mut deps, env_after(251), "eve", &[], forfeit()).unwrap();
+    assert_eq!(sends(&res), vec![send("bob", 350)]);
+    assert_eq!(owner_of(&contract, &deps, "2"), MINTER);
+    assert_eq!(
+        ledger(&contract, &deps),
+        vec![(TreasuryAccount::Proceeds, 350)]
+    );
+}
+
+#[test]
+fn destroying_a_reserved_token_refunds_the_buyer_from_escrow() {
+    let (contract, mut deps) = setup();
+    enable_installments(&contract, &mut deps);
+    reserve(&contract, &mut deps, "bob");
+
+    let destroy = ExecuteMsg::Destroy {
+        token_id: "2".to_string(),
+        evidence_hash: "hash".to_string(),
+        attestation: "burnt".to_string(),
+    };
+    let res = execute(&contract, &mut deps, MINTER, &[], destroy).unwrap();
+    assert_eq!(sends(&res), vec![send("bob", 400)]);
+    assert_eq!(ledger(&contract, &deps), vec![]);
+}
+
+#[test]
+fn frozen_tokens_stay_in_escrow_until_seized() {
+    let (contract, mut deps) = setup();
+    let gift = ExecuteMsg::SendGift {
+        token_id: "1".to_string(),
+        recipient: "bob".to_string(),
+        message: None,
+        deadline: None,
+    };
+    execute(&contract, &mut deps, "alice", &[], gift).unwrap();
+    mint(&contract, &mut deps, MINTER);
+    let trade_in = ExecuteMsg::OpenTradeIn {
+        token_id: "2".to_string(),
+        top_up: coin(100, DENOM),
+        accepted_token_ids: vec!["3".to_string()],
+    };
+    execute(&contract, &mut deps, MINTER, &[], trade_in).unwrap();
+    mint(&contract, &mut deps, "carol");
+    for token_id in ["1", "2"] {
+        let freeze = ExecuteMsg::FreezeToken {
+            token_id: token_id.to_string(),
+            reason: "dispute".to_string(),
+        };
+        execute(&contract, &mut deps, MINTER, &[], freeze).unwrap();
+    }
+
+    let claim = || ExecuteMsg::ClaimGift {
+        token_id: "1".to_string(),
+    };
+    let give_back = ExecuteMsg::ReturnGift {
+        token_id: "1".to_string(),
+    };
+    let take = || ExecuteMsg::TradeIn {
+        token_id: "2".to_string(),
+        offered_token_id: "3".to_string(),
+    };
+    let close = ExecuteMsg::CloseTradeIn {
+        token_id: "2".to_string(),
+    };
+    let attempts = [
+        ("bob", vec![], claim()),
+        ("alice", vec![], give_back),
+        ("carol", coins(100, DENOM), take()),
+        (MINTER, vec![], close),
+    ];
+    for (sender, funds, msg) in attempts {
+        let err = execute(&contract, &mut deps, sender, &funds, msg).unwrap_err();
+        assert!(matches!(err, ContractError::TokenFrozen { .. }));
+    }
+
+    // seizure calls the gift and the trade-in off
+    for token_id in ["1", "2"] {
+        let seize = ExecuteMsg::SeizeToken {
+            token_id: token_id.to_string(),
+            custody_address: "custody".to_string(),
+            reason: "court order".to_string(),
+        };
+        execute(&contract, &mut deps, MINTER, &[], seize).unwrap();
+        assert_eq!(owner_of(&contract, &deps, token_id), "custody");
+    }
+    let err = execute(&contract, &mut deps, "bob", &[], claim()).unwrap_err();
+    assert!(matches!(err, ContractError::Std(_)));
+    let err = execute(&contract, &mut deps, "carol", &coins(100, DENOM), take()).unwrap_err();
+    assert!(matches!(err, ContractError::Std(_)));
+}
+
+/// a 2-edition pre-sale at a 100 mint price, open for 100 seconds, with
+/// commitments from bob, carol and dave
+fn open_presale(contract: &Contract, deps: &mut Deps) {
+    let msg = ExecuteMsg::SetMintConfig {
+        max_mints: 100,
+        mint_price: coin(100, DENOM),
+    };
+    execute(contract, deps, MINTER, &[], msg).unwrap();
+    let now = mock_env().block.time;
+    let config = PresaleConfig {
+        editions: 2,
+        start: now,
+        end: now.plus_seconds(100),
+        seed_commitment: HexBinary::from(Sha256::digest(b"seed").to_vec()),
+    };
+    let msg = ExecuteMsg::SetPresale {
+        config: Some(config),
+    };
+    execute(contract, deps, MINTER, &[], msg).unwrap();
+    for buyer in ["bob", "carol", "dave"] {
+        let msg = MintMsg {
+            owner: buyer.to_string(),
+            token_uri: None,
+            referral: None,
+            content_hash: None,
+            extension: None,
+            transferable: true,
+        };
+        let msg = ExecuteMsg::CommitPresale(msg);
+        execute(contract, deps, buyer, &coins(100, DENOM), msg).unwrap();
+    }
+}
+
+#[test]
+fn presale_is_drawn_at_once_by_the_admin_with_the_committed_seed() {
+    let (contract, mut deps) = setup();
+    open_presale(&contract, &mut deps);
+    let draw = |seed: &str| ExecuteMsg::DrawPresale {
+        seed: Some(seed.to_string()),
+    };
+
+    let err = execute(&contract, &mut deps, MINTER, &[], draw("seed")).unwrap_err();
+    assert!(matches!(err, ContractError::PresaleRunning {}));
+    let ended = env_after(100);
+    let err = execute_at(
+        &contract,
+        &mut deps,
+        ended.clone(),
+        "eve",
+        &[],
+        draw("seed"),
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::Unauthorized {}));
+    let err = execute_at(
+        &contract,
+        &mut deps,
+        ended.clone(),
+        MINTER,
+        &[],
+        draw("guess"),
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::PresaleSeedMismatch {}));
+    let refund = ExecuteMsg::DrawPresale { seed: None };
+    let err = execute_at(&contract, &mut deps, ended.clone(), "eve", &[], refund).unwrap_err();
+    assert!(matches!(err, ContractError::Unauthorized {}));
+
+    // both editions are allocated and the third commitment refunded
+    let res = execute_at(
+        &contract,
+        &mut deps,
+        ended.clone(),
+        MINTER,
+        &[],
+        draw("seed"),
+    )
+    .unwrap();
+    let refunds = sends(&res);
+    assert_eq!(refunds.len(), 1);
+    assert_eq!(refunds[0].1, 100);
+    let mut owners = vec![
+        owner_of(&contract, &deps, "2"),
+        owner_of(&contract, &deps, "3"),
+        refunds[0].0.clone(),
+    ];
+    owners.sort();
+    assert_eq!(owners, vec!["bob", "carol", "dave"]);
+
+    let err = execute_at(&contract, &mut deps, ended, MINTER, &[], draw("seed")).unwrap_err();
+    assert!(matches!(err, ContractError::PresaleDrawn {}));
+}
+
+#[test]
+fn presale_the_admin_never_draws_is_refunded() {
+    let (contract, mut deps) = setup();
+    open_presale(&contract, &mut deps);
+    let refund = || ExecuteMsg::DrawPresale { seed: None };
+
+    let week = 7 * 24 * 60 * 60;
+    let err = execute_at(
+        &contract,
+        &mut deps,
+        env_after(99 + week),
+        "eve",
+        &[],
+        refund(),
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::Unauthorized {}));
+    let res = execute_at(
+        &contract,
+        &mut deps,
+        env_after(100 + week),
+        "eve",
+        &[],
+        refund(),
+    )
+    .unwrap();
+    assert_eq!(
+        sends(&res),
+        vec![send("dave", 100), send("carol", 100), send("bob", 100)]
+    );
+    assert_eq!(ledger(&contract, &deps), vec![]);
+}
+
+#[test]
+fn royalties_swap_into_the_payout_denom_or_accrue_when_the_swap_fails() {
+    let (contract, mut deps) = setup();
+    let set_swap = ExecuteMsg::SetRoyaltySwap {
+        swap: Some("swap".to_string()),
+    };
+    execute(&contract, &mut deps, MINTER, &[], set_swap).unwrap();
+    let set_denom = ExecuteMsg::SetRoyaltyPayoutDenom {
+        denom: Some("uusdc".to_string()),
+    };
+    execute(&contract, &mut deps, ARTIST, &[], set_denom).unwrap();
+    let list = ExecuteMsg::ListForSale {
+        token_id: "1".to_string(),
+        price: coin(1000, DENOM),
+        royalty_waiver: None,
+    };
+    execute(&contract, &mut deps, "alice", &[], list).unwrap();
+
+    let buy = ExecuteMsg::BuyToken {
+        token_id: "1".to_string(),
+    };
+    let res = execute(&contract, &mut deps, "bob", &coins(1000, DENOM), buy).unwrap();
+    assert_eq!(sends(&res), vec![send(MARKET, 25), send("alice", 925)]);
+    let swap = res
+        .messages
+        .iter()
+        .find(|sub| matches!(sub.msg, CosmosMsg::Wasm(_)))
+        .unwrap();
+    assert_eq!(swap.reply_on, ReplyOn::Always);
+    let expected = WasmMsg::Execute {
+        contract_addr: "swap".to_string(),
+        msg: to_json_binary(&SwapExecuteMsg::Swap {
+            ask_denom: "uusdc".to_string(),
+            recipient: ARTIST.to_string(),
+        })
+        .unwrap(),
+        funds: coins(50, DENOM),
+    };
+    assert_eq!(swap.msg, CosmosMsg::Wasm(expected));
+
+    // the swap failed: the royalty accrues in the sale's denom
+    let reply = Reply {
+        id: swap.id,
+        result: SubMsgResult::Err("no pool".to_string()),
+    };
+    contract.reply(deps.as_mut(), mock_env(), reply).unwrap();
+    let payout = contract
+        .royalty_payout(deps.as_ref(), ARTIST.to_string())
+        .unwrap();
+    assert_eq!(payout.denom, Some("uusdc".to_string()));
+    assert_eq!(payout.accrued, coins(50, DENOM));
+    assert!(ledger(&contract, &deps).contains(&(TreasuryAccount::AccruedRoyalties, 50)));
+
+    let claim = ExecuteMsg::ClaimAccruedRoyalties {};
+    let res = execute(&contract, &mut deps, ARTIST, &[], claim.clone()).unwrap();
+    assert_eq!(sends(&res), vec![send(ARTIST, 50)]);
+    let err = execute(&contract, &mut deps, ARTIST, &[], claim).unwrap_err();
+    assert!(matches!(err, ContractError::NothingToWithdraw {}));
+}
+
+/// deposits "ext-1" of the vault collection "origin" for alice, minting
+/// receipt "2"
+fn deposit_in_vault(contract: &Contract, deps: &mut Deps) {
+    let vault = ExecuteMsg::UpdateVaultCollections {
+        add: vec!["origin".to_string()],
+        remove: vec![],
+    };
+    execute(contract, deps, MINTER, &[], vault).unwrap();
+    let deposit = ExecuteMsg::ReceiveNft(Cw721ReceiveMsg {
+        sender: "alice".to_string(),
+        token_id: "ext-1".to_string(),
+        msg: to_json_binary(&VaultDepositMsg::<Extension> { extension: None }).unwrap(),
+    });
+    execute(contract, deps, "origin", &[], deposit).unwrap();
+}
+
+/// mocks the origin collection "origin", answering the cw2981 extension
+/// query with royalty_amount bps of the sale price to "painter"
+fn mock_origin(deps: &mut Deps, royalty_bps: Option<u128>) {
+    deps.querier.update_wasm(move |query| match query {
+        WasmQuery::Smart { contract_addr, msg } if contract_addr == "origin" => {
+            match (from_json(msg), royalty_bps) {
+                (Ok(Cw2981QueryMsg::Extension { msg }), Some(bps)) => {
+                    let Cw2981QueryMsg::RoyaltyInfo { sale_price, .. } = *msg else {
+                        panic!("unexpected query")
+                    };
+                    let info = RoyaltiesInfoResponse {
+                        address: "painter".to_string(),
+                        royalty_amount: sale_price.multiply_ratio(bps, 10_000u128),
+                        payees: vec![],
+                    };
+                    SystemResult::Ok(ContractResult::Ok(to_json_binary(&info).unwrap()))
+                }
+                _ => SystemResult::Ok(ContractResult::Err("unknown query".to_string())),
+            }
+        }
+        _ => panic!("unexpected query"),
+    });
+}
+
+#[test]
+fn vault_receipt_sales_pay_the_origin_collections_royalty() {
+    let (contract, mut deps) = setup();
+    deposit_in_vault(&contract, &mut deps);
+    let list = |price| ExecuteMsg::ListForSale {
+        token_id: "2".to_string(),
+        price: coin(price, DENOM),
+        royalty_waiver: None,
+    };
+    let buy = ExecuteMsg::BuyToken {
+        token_id: "2".to_string(),
+    };
+
+    // the origin's 10% stands in for the collection's 5% default
+    mock_origin(&mut deps, Some(1000));
+    let info = contract
+        .royalty_info(
+            deps.as_ref(),
+            mock_env(),
+            "2".to_string(),
+            Uint128::new(1000),
+        )
+        .unwrap();
+    assert_eq!(info.address, "painter");
+    assert_eq!(info.royalty_amount, Uint128::new(100));
+    execute(&contract, &mut deps, "alice", &[], list(1000)).unwrap();
+    let res = execute(
+        &contract,
+        &mut deps,
+        "bob",
+        &coins(1000, DENOM),
+        buy.clone(),
+    )
+    .unwrap();
+    assert_eq!(
+        sends(&res),
+        vec![send("painter", 100), send(MARKET, 25), send("alice", 875)]
+    );
+
+    // an origin without cw2981 royalties leaves the collection's own
+    mock_origin(&mut deps, None);
+    execute(&contract, &mut deps, "bob", &[], list(1000)).unwrap();
+    let res = execute(&contract, &mut deps, "carol", &coins(1000, DENOM), buy).unwrap();
+    assert_eq!(
+        sends(&res),
+        vec![send(ARTIST, 50), send(MARKET, 25), send("bob", 925)]
+    );
+}
+
+#[test]
+fn bundle_checkout_buys_listings_and_fails_with_any_companion() {
+    let (contract, mut deps) = setup();
+    mint(&contract, &mut deps, "carol");
+    for (seller, token_id) in [("alice", "1"), ("carol", "2")] {
+        let list = ExecuteMsg::ListForSale {
+            token_id: token_id.to_string(),
+            price: coin(1000, DENOM),
+            royalty_waiver: None,
+        };
+        execute(&contract, &mut deps, seller, &[], list).unwrap();
+    }
+    let frame = to_json_binary(&"buy_frame").unwrap();
+    let buy = ExecuteMsg::BuyBundle {
+        token_ids: vec!["1".to_string(), "2".to_string()],
+        companions: vec![CompanionPurchase {
+            contract: "framer".to_string(),
+            msg: frame.clone(),
+            funds: coins(200, DENOM),
+        }],
+    };
+    let err = execute(
+        &contract,
+        &mut deps,
+        "bob",
+        &coins(2199, DENOM),
+        buy.clone(),
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::InsufficientPayment { .. }));
+
+    let res = execute(&contract, &mut deps, "bob", &coins(2300, DENOM), buy).unwrap();
+    assert_eq!(
+        sends(&res),
+        vec![
+            send("bob", 100),
+            send(ARTIST, 50),
+            send(MARKET, 25),
+            send("alice", 925),
+            send(ARTIST, 50),
+            send(MARKET, 25),
+            send("carol", 925)
+        ]
+    );
+    assert_eq!(owner_of(&contract, &deps, "1"), "bob");
+    assert_eq!(owner_of(&contract, &deps, "2"), "bob");
+    let companion = res.messages.last().unwrap();
+    assert_eq!(companion.reply_on, ReplyOn::Always);
+    let expected = WasmMsg::Execute {
+        contract_addr: "framer".to_string(),
+        msg: frame,
+        funds: coins(200, DENOM),
+    };
+    assert_eq!(companion.msg, CosmosMsg::Wasm(expected));
+
+    // a failed companion fails the checkout, reverting the listings sold
+    let reply = Reply {
+        id: companion.id,
+        result: SubMsgResult::Err("sold out".to_string()),
+    };
+    let err = contract
+        .reply(deps.as_mut(), mock_env(), reply.clone())
+        .unwrap_err();
+    assert!(matches!(err, ContractError::CompanionPurchaseFailed { .. }));
+    // the reply is only answered once
+    let err = contract
+        .reply(deps.as_mut(), mock_env(), reply)
+        .unwrap_err();
+    assert!(matches!(err, ContractError::Std(_)));
+}
+
+#[test]
+fn loan_financed_buy_completes_once_the_lender_pays_in() {
+    let (contract, mut deps) = setup();
+    let set_lender = ExecuteMsg::SetLender {
+        lender: Some("lender".to_string()),
+    };
+    execute(&contract, &mut deps, MINTER, &[], set_lender).unwrap();
+    let list = ExecuteMsg::ListForSale {
+        token_id: "1".to_string(),
+        price: coin(1000, DENOM),
+        royalty_waiver: None,
+    };
+    execute(&contract, &mut deps, "alice", &[], list).unwrap();
+
+    let buy = |loan| ExecuteMsg::BuyWithLoan {
+        token_id: "1".to_string(),
+        loan: coin(loan, DENOM),
+    };
+    let err = execute(&contract, &mut deps, "bob", &[], buy(1001)).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidLoan {}));
+    let err = execute(&contract, &mut deps, "bob", &coins(399, DENOM), buy(600)).unwrap_err();
+    assert!(matches!(err, ContractError::InsufficientPayment { .. }));
+    // the down payment has arrived by the time the contract runs
+    deps.querier
+        .update_balance(MOCK_CONTRACT_ADDR, coins(400, DENOM));
+    let res = execute(&contract, &mut deps, "bob", &coins(400, DENOM), buy(600)).unwrap();
+    assert_eq!(sends(&res), vec![]);
+    let [open_loan] = &res.messages[..] else {
+        panic!("expected the loan request alone")
+    };
+    assert_eq!(open_loan.reply_on, ReplyOn::Success);
+    let expected = WasmMsg::Execute {
+        contract_addr: "lender".to_string(),
+        msg: to_json_binary(&LenderExecuteMsg::OpenLoan {
+            borrower: "bob".to_string(),
+            token_id: "1".to_string(),
+            amount: coin(600, DENOM),
+        })
+        .unwrap(),
+        funds: vec![],
+    };
+    assert_eq!(open_loan.msg, CosmosMsg::Wasm(expected));
+    let reply = Reply {
+        id: open_loan.id,
+        result: SubMsgResult::Ok(SubMsgResponse {
+            events: vec![],
+            data: None,
+        }),
+    };
+
+    // a lender that did not pay in fails the purchase
+    let err = contract
+        .reply(deps.as_mut(), mock_env(), reply.clone())
+        .unwrap_err();
+    assert!(matches!(err, ContractError::LoanNotFunded {}));
+
+    // the failure reverted the purchase, bob buys again
+    let res = execute(&contract, &mut deps, "bob", &coins(400, DENOM), buy(600)).unwrap();
+    let reply = Reply {
+        id: res.messages[0].id,
+        ..reply
+    };
+    deps.querier
+        .update_balance(MOCK_CONTRACT_ADDR, coins(1000, DENOM));
+    let res = contract.reply(deps.as_mut(), mock_env(), reply).unwrap();
+    assert_eq!(
+        sends(&res),
+        vec![send(ARTIST, 50), send(MARKET, 25), send("alice", 925)]
+    );
+    // the lender holds the token as collateral
+    assert_eq!(owner_of(&contract, &deps, "1"), "lender");
+    let collateral = Cw721ReceiveMsg {
+        sender: "bob".to_string(),
+        token_id: "1".to_string(),
+        msg: to_json_binary(&LoanCollateralMsg {
+            borrower: "bob".to_string(),
+        })
+        .unwrap(),
+    };
+    assert_eq!(
+        res.messages.last().unwrap().msg,
+        collateral.into_cosmos_msg("lender").unwrap()
+    );
+}
+
+#[test]
+fn max_mints_counts_burned_tokens_but_not_receipts() {
+    let (contract, mut deps) = setup();
+    contract.max_mints.save(&mut deps.storage, &3).unwrap();
+    deposit_in_vault(&contract, &mut deps);
+    let redeem = ExecuteMsg::RedeemVaultReceipt {
+        token_id: "2".to_string(),
+    };
+    execute(&contract, &mut deps, "alice", &[], redeem).unwrap();
+    assert_eq!(contract.remaining_mints(&deps.storage).unwrap(), 2);
+
+    mint(&contract, &mut deps, "alice");
+    let burn = ExecuteMsg::Burn {
+        token_id: "3".to_string(),
+    };
+    execute(&contract, &mut deps, "alice", &[], burn).unwrap();
+    // the burned token's slot stays taken
+    assert_eq!(contract.remaining_mints(&deps.storage).unwrap(), 1);
+    mint(&contract, &mut deps, "alice");
+    let msg = MintMsg {
+        owner: "alice".to_string(),
+        token_uri: None,
+        referral: None,
+        content_hash: None,
+        extension: None,
+        transferable: true,
+    };
+    let err = execute(&contract, &mut deps, MINTER, &[], ExecuteMsg::Mint(msg)).unwrap_err();
+    assert!(matches!(err, ContractError::MaxMintsReached {}));
+}
+
+#[test]
+fn gifts_are_claimed_by_the_recipient_or_returned_once_expired() {
+    let (contract, mut deps) = setup();
+    let gift = |recipient: &str| ExecuteMsg::SendGift {
+        token_id: "1".to_string(),
+        recipient: recipient.to_string(),
+        message: None,
+        deadline: Some(mock_env().block.time.plus_seconds(100)),
+    };
+    let claim = || ExecuteMsg::ClaimGift {
+        token_id: "1".to_string(),
+    };
+    let give_back = || ExecuteMsg::ReturnGift {
+        token_id: "1".to_string(),
+    };
+
+    execute(&contract, &mut deps, "alice", &[], gift("bob")).unwrap();
+    assert_eq!(owner_of(&contract, &deps, "1"), MOCK_CONTRACT_ADDR);
+    let err = execute(&contract, &mut deps, "carol", &[], claim()).unwrap_err();
+    assert!(matches!(err, ContractError::Unauthorized {}));
+    execute(&contract, &mut deps, "bob", &[], claim()).unwrap();
+    assert_eq!(owner_of(&contract, &deps, "1"), "bob");
+
+    execute(&contract, &mut deps, "bob", &[], gift("carol")).unwrap();
+    let err = execute(&contract, &mut deps, "dave", &[], give_back()).unwrap_err();
+    assert!(matches!(err, ContractError::Unauthorized {}));
+    let err = execute_at(&contract, &mut deps, env_after(100), "carol", &[], claim()).unwrap_err();
+    assert!(matches!(err, ContractError::GiftExpired {}));
+    // anyone takes an expired gift back to its sender
+    execute_at(
+        &contract,
+        &mut deps,
+        env_after(100),
+        "dave",
+        &[],
+        give_back(),
+    )
+    .unwrap();
+    assert_eq!(owner_of(&contract, &deps, "1"), "bob");
+    let err = execute(&contract, &mut deps, "carol", &[], claim()).unwrap_err();
+    assert!(matches!(err, ContractError::Std(_)));
+}
+
+/// mocks the price oracle "oracle", quoting every pair at price
+fn mock_oracle(deps: &mut Deps, price: &str) {
+    let price = OraclePriceResponse {
+        price: price.parse().unwrap(),
+        updated_at: None,
+    };
+    deps.querier.update_wasm(move |query| match query {
+        WasmQuery::Smart { contract_addr, .. } if contract_addr == "oracle" => {
+            SystemResult::Ok(ContractResult::Ok(to_json_binary(&price).unwrap()))
+        }
+        _ => panic!("unexpected query"),
+    });
+}
+
+#[test]
+fn conditional_transfers_deliver_once_the_condition_is_met() {
+    let (contract, mut deps) = setup();
+    let transfer = |recipient: &str, condition| ExecuteMsg::CreateConditionalTransfer {
+        token_id: "1".to_string(),
+        recipient: recipient.to_string(),
+        condition,
+        expires: mock_env().block.time.plus_seconds(100),
+    };
+    let deliver = || ExecuteMsg::ExecuteConditionalTransfer {
+        token_id: "1".to_string(),
+    };
+    let cancel = || ExecuteMsg::CancelConditionalTransfer {
+        token_id: "1".to_string(),
+    };
+
+    let at_least = TransferCondition::PriceAtLeast {
+        oracle: "oracle".to_string(),
+        base: "OM".to_string(),
+        quote: "USD".to_string(),
+        price: "2".parse().unwrap(),
+    };
+    execute(
+        &contract,
+        &mut deps,
+        "alice",
+        &[],
+        transfer("bob", at_least),
+    )
+    .unwrap();
+    assert_eq!(owner_of(&contract, &deps, "1"), MOCK_CONTRACT_ADDR);
+    mock_oracle(&mut deps, "1.5");
+    let err = execute(&contract, &mut deps, "dave", &[], deliver()).unwrap_err();
+    assert!(matches!(err, ContractError::ConditionNotMet {}));
+    // anyone delivers it once the price is reached
+    mock_oracle(&mut deps, "2.5");
+    execute(&contract, &mut deps, "dave", &[], deliver()).unwrap();
+    assert_eq!(owner_of(&contract, &deps, "1"), "bob");
+
+    let at_most = TransferCondition::PriceAtMost {
+        oracle: "oracle".to_string(),
+        base: "OM".to_string(),
+        quote: "USD".to_string(),
+        price: "1".parse().unwrap(),
+    };
+    execute(&contract, &mut deps, "bob", &[], transfer("carol", at_most)).unwrap();
+    let err = execute(&contract, &mut deps, "carol", &[], cancel()).unwrap_err();
+    assert!(matches!(err, ContractError::Unauthorized {}));
+    mock_oracle(&mut deps, "0.5");
+    let err = execute_at(
+        &contract,
+        &mut deps,
+        env_after(100),
+        "carol",
+        &[],
+        deliver(),
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::ConditionalTransferExpired {}));
+    // anyone returns an expired one to its sender
+    execute_at(&contract, &mut deps, env_after(100), "dave", &[], cancel()).unwrap();
+    assert_eq!(owner_of(&contract, &deps, "1"), "bob");
+}
+
+#[test]
+fn trade_ins_swap_an_accepted_token_and_the_top_up_for_the_escrowed_one() {
+    let (contract, mut deps) = setup();
+    mint(&contract, &mut deps, MINTER);
+    mint(&contract, &mut deps, "carol");
+    mint(&contract, &mut deps, "dave");
+    let open = |token_id: &str, accepted: &str| ExecuteMsg::OpenTradeIn {
+        token_id: token_id.to_string(),
+        top_up: coin(100, DENOM),
+        accepted_token_ids: vec![accepted.to_string()],
+    };
+    // only the artist of record or a treasurer opens one
+    let err = execute(&contract, &mut deps, "alice", &[], open("1", "3")).unwrap_err();
+    assert!(matches!(err, ContractError::Unauthorized {}));
+    execute(&contract, &mut deps, MINTER, &[], open("2", "3")).unwrap();
+    assert_eq!(owner_of(&contract, &deps, "2"), MOCK_CONTRACT_ADDR);
+
+    let take = |offered: &str| ExecuteMsg::TradeIn {
+        token_id: "2".to_string(),
+        offered_token_id: offered.to_string(),
+    };
+    let err = execute(&contract, &mut deps, "dave", &coins(100, DENOM), take("4")).unwrap_err();
+    assert!(matches!(err, ContractError::TradeInNotAccepted {}));
+    let err = execute(&contract, &mut deps, "carol", &coins(99, DENOM), take("3")).unwrap_err();
+    assert!(matches!(err, ContractError::InsufficientPayment { .. }));
+    let res = execute(&contract, &mut deps, "carol", &coins(150, DENOM), take("3")).unwrap();
+    assert_eq!(sends(&res), vec![send("carol", 50), send(MINTER, 100)]);
+    assert_eq!(owner_of(&contract, &deps, "2"), "carol");
+    assert_eq!(owner_of(&contract, &deps, "3"), MINTER);
+
+    // the holder calls one off and gets the token back
+    execute(&contract, &mut deps, MINTER, &[], open("3", "4")).unwrap();
+    let close = || ExecuteMsg::CloseTradeIn {
+        token_id: "3".to_string(),
+    };
+    let err = execute(&contract, &mut deps, "dave", &[], close()).unwrap_err();
+    assert!(matches!(err, ContractError::Unauthorized {}));
+    execute(&contract, &mut deps, MINTER, &[], close()).unwrap();
+    assert_eq!(owner_of(&contract, &deps, "3"), MINTER);
+}
+
+#[test]
+fn burn_for_refund_pays_holders_out_of_the_refund_pool() {
+    let (contract, mut deps) = setup();
+    mint(&contract, &mut deps, "bob");
+    let program = BuyBackProgram {
+        price: coin(100, DENOM),
+        start: mock_env().block.time,
+        end: mock_env().block.time.plus_seconds(100),
+    };
+    let set = ExecuteMsg::SetBuyBack {
+        program: Some(program),
+    };
+    execute(&contract, &mut deps, MINTER, &[], set).unwrap();
+    let fund = ExecuteMsg::FundRefundPool {};
+    execute(&contract, &mut deps, MINTER, &coins(150, DENOM), fund).unwrap();
+
+    let burn = |token_id: &str| ExecuteMsg::BurnForRefund {
+        token_id: token_id.to_string(),
+    };
+    let err = execute(&contract, &mut deps, "bob", &[], burn("1")).unwrap_err();
+    assert!(matches!(err, ContractError::Unauthorized {}));
+    let res = execute(&contract, &mut deps, "alice", &[], burn("1")).unwrap();
+    assert_eq!(sends(&res), vec![send("alice", 100)]);
+    let refund = contract.refund(deps.as_ref(), "1".to_string()).unwrap();
+    assert_eq!(refund.refund.holder, "alice");
+    assert_eq!(refund.refund.amount, coin(100, DENOM));
+    assert!(ledger(&contract, &deps).contains(&(TreasuryAccount::RefundPool, 50)));
+    let err = execute(&contract, &mut deps, "bob", &[], burn("2")).unwrap_err();
+    assert!(matches!(
+        err,
+        ContractError::RefundPoolInsufficient { balance } if balance.u128() == 50
+    ));
+
+    // the pool stays promised to holders until the window closes
+    let take = || ExecuteMsg::WithdrawRefundPool {
+        amount: Uint128::new(50),
+    };
+    let err = execute(&contract, &mut deps, MINTER, &[], take()).unwrap_err();
+    assert!(matches!(err, ContractError::BuyBackRunning {}));
+    let err = execute_at(&contract, &mut deps, env_after(100), "bob", &[], burn("2")).unwrap_err();
+    assert!(matches!(err, ContractError::BuyBackNotOpen {}));
+    let res = execute_at(&contract, &mut deps, env_after(100), MINTER, &[], take()).unwrap();
+    assert_eq!(sends(&res), vec![send(MINTER, 50)]);
+}
@@ -1,4 +1,5 @@
-use cosmwasm_std::StdError;
+use cosmwasm_std::{Coin, StdError, Timestamp, Uint128};
+use cw_utils::PaymentError;
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq)]
@@ -6,6 +7,9 @@ pub enum ContractError {
     #[error("{0}")]
     Std(#[from] StdError),
 
+    #[error("{0}")]
+    Payment(#[from] PaymentError),
+
     #[error("Unauthorized")]
     Unauthorized {},
 
@@ -21,12 +25,509 @@ pub enum ContractError {
     #[error("Minting is currently disabled")]
     MintingDisabled {},
 
+    #[error("No minter transfer is pending for the sender")]
+    NotPendingMinter {},
+
+    #[error("Content is already tokenized as token {token_id}")]
+    DuplicateContent { token_id: String },
+
     #[error("Maximum number of mints reached")]
     MaxMintsReached {},
 
+    #[error("A batch covers between 1 and {max} tokens")]
+    InvalidBatchSize { max: usize },
+
+    #[error("Wallet has reached its limit of {limit} mints")]
+    WalletMintLimitReached { limit: u64 },
+
     #[error("Incorrect payment amount")]
     IncorrectPayment {},
 
     #[error("Insufficient funds")]
     InsufficientFunds {},
+
+    #[error("Invalid allowlist config")]
+    InvalidAllowlistConfig {},
+
+    #[error("Minting is limited to the allowlist")]
+    NotAllowlisted {},
+
+    #[error("Merkle root must be 32 bytes")]
+    InvalidMerkleRoot {},
+
+    #[error("Invalid Merkle proof")]
+    InvalidMerkleProof {},
+
+    #[error("At most {limit} mints per address during the allowlist phase")]
+    AllowlistLimitReached { limit: u32 },
+
+    #[error("Payment must be sent in {denom}")]
+    WrongDenom { denom: String },
+
+    #[error("Insufficient payment: {required} required, {sent} sent")]
+    InsufficientPayment { required: Coin, sent: Coin },
+
+    #[error("Display name must be between 1 and {max} characters")]
+    InvalidDisplayName { max: usize },
+
+    #[error("Profile has been suspended by the admin")]
+    ProfileSuspended {},
+
+    #[error("Contract {contract} is not on the send allowlist")]
+    SendTargetNotAllowed { contract: String },
+
+    #[error("Installment purchases are not enabled")]
+    InstallmentsDisabled {},
+
+    #[error("Invalid installment plan")]
+    InvalidInstallmentPlan {},
+
+    #[error("Installment payment must be at least {required}")]
+    InstallmentTooSmall { required: Uint128 },
+
+    #[error("Reservation is not overdue")]
+    ReservationNotOverdue {},
+
+    #[error("Rate must not exceed 10000 basis points")]
+    InvalidRate {},
+
+    #[error("Attestation already revoked")]
+    AttestationRevoked {},
+
+    #[error("Auction is not accepting bids")]
+    AuctionNotOpen {},
+
+    #[error("Auction is not in its reveal phase")]
+    AuctionNotInRevealPhase {},
+
+    #[error("Auction has not ended yet")]
+    AuctionNotEnded {},
+
+    #[error("Auction already settled")]
+    AuctionSettled {},
+
+    #[error("Bid already committed")]
+    BidAlreadyCommitted {},
+
+    #[error("Bid already revealed")]
+    BidAlreadyRevealed {},
+
+    #[error("Revealed bid does not match its commitment")]
+    InvalidReveal {},
+
+    #[error("Invalid auction duration")]
+    InvalidDuration {},
+
+    #[error("Metadata variant window must end after it starts")]
+    InvalidMetadataWindow {},
+
+    #[error("At most {max} metadata variants per token")]
+    TooManyMetadataVariants { max: usize },
+
+    #[error("No buy-back program is configured")]
+    BuyBackDisabled {},
+
+    #[error("Buy-back is not open")]
+    BuyBackNotOpen {},
+
+    #[error("Buy-back is running")]
+    BuyBackRunning {},
+
+    #[error("Invalid buy-back program")]
+    InvalidBuyBack {},
+
+    #[error("Refund pool holds {balance}, not enough for this payout")]
+    RefundPoolInsufficient { balance: Uint128 },
+
+    #[error("Refund pool must be empty to change its denom")]
+    RefundPoolNotEmpty {},
+
+    #[error("No keeper bounty is configured")]
+    KeeperBountyDisabled {},
+
+    #[error("Invalid keeper bounty")]
+    InvalidKeeperBounty {},
+
+    #[error("Keeper treasury holds {balance}, not enough for this payout")]
+    KeeperTreasuryInsufficient { balance: Uint128 },
+
+    #[error("Keeper treasury must be empty to change its denom")]
+    KeeperTreasuryNotEmpty {},
+
+    #[error("Vault does not accept tokens from {collection}")]
+    VaultCollectionNotAllowed { collection: String },
+
+    #[error("Vault receipts can only be removed by redeeming them")]
+    VaultReceipt {},
+
+    #[error("Vouchers cannot be issued for a voucher")]
+    VoucherParent {},
+
+    #[error("At most {limit} vouchers for this artwork")]
+    VoucherLimitReached { limit: u32 },
+
+    #[error("Token is not a voucher")]
+    NotVoucher {},
+
+    #[error("Not a partner gallery")]
+    NotPartnerGallery {},
+
+    #[error("Invalid size limits")]
+    InvalidSizeLimits {},
+
+    #[error("URI exceeds {max} bytes")]
+    UriTooLong { max: u32 },
+
+    #[error("Token extension exceeds {max} bytes")]
+    ExtensionTooLarge { max: u32 },
+
+    #[error("Provenance field exceeds {max} bytes")]
+    ProvenanceEntryTooLong { max: u32 },
+
+    #[error("Artwork needs an artist name and title, and a valid edition number")]
+    InvalidArtwork {},
+
+    #[error("Gift deadline has passed")]
+    GiftExpired {},
+
+    #[error("Conditional transfer has expired")]
+    ConditionalTransferExpired {},
+
+    #[error("Transfer condition is not met")]
+    ConditionNotMet {},
+
+    #[error("Price commitment must be a 32 byte sha256 hash")]
+    InvalidPriceCommitment {},
+
+    #[error("Price does not match the sale's commitment")]
+    PriceCommitmentMismatch {},
+
+    #[error("Sale price has already been revealed")]
+    PriceAlreadyRevealed {},
+
+    #[error("Token is not sealed")]
+    NotSealed {},
+
+    #[error("No token URIs left to reveal")]
+    MysteryPoolEmpty {},
+
+    #[error("No pre-sale is configured")]
+    PresaleDisabled {},
+
+    #[error("Pre-sale is not open")]
+    PresaleNotOpen {},
+
+    #[error("Pre-sale has commitments left to draw")]
+    PresaleRunning {},
+
+    #[error("Invalid pre-sale")]
+    InvalidPresale {},
+
+    #[error("Already committed to this pre-sale")]
+    AlreadyCommitted {},
+
+    #[error("Pre-sale is fully drawn")]
+    PresaleDrawn {},
+
+    #[error("Public key must be a 33 or 65 byte secp256k1 key")]
+    InvalidPubkey {},
+
+    #[error("Exhibition must end after it starts")]
+    InvalidExhibition {},
+
+    #[error("Invalid signature")]
+    InvalidSignature {},
+
+    #[error("Artist key already registered, rotate it instead")]
+    ArtistKeyExists {},
+
+    #[error("No artist key registered")]
+    NoArtistKey {},
+
+    #[error("Exhibition already logged")]
+    ExhibitionLogged {},
+
+    #[error("Invalid price floor")]
+    InvalidPriceFloor {},
+
+    #[error("Price is below the artist's resale floor of {floor}")]
+    PriceBelowFloor { floor: Coin },
+
+    #[error("Invalid curation fee")]
+    InvalidCurationFee {},
+
+    #[error("Invalid royalty")]
+    InvalidRoyalty {},
+
+    #[error("At most {max} royalty payees")]
+    TooManyRoyaltyPayees { max: usize },
+
+    #[error("Royalty shares add up to more than 10000 bps")]
+    RoyaltySplitExceeded {},
+
+    #[error("At most {max} royalty successions can be scheduled")]
+    TooManyRoyaltySuccessions { max: usize },
+
+    #[error("Royalty succession must take effect in the future")]
+    RoyaltySuccessionInPast {},
+
+    #[error("No royalty succession scheduled at that time")]
+    NoRoyaltySuccession {},
+
+    #[error("Invalid referral id")]
+    InvalidReferral {},
+
+    #[error("Unknown referral {referral_id}")]
+    UnknownReferral { referral_id: String },
+
+    #[error("Invalid token range")]
+    InvalidRange {},
+
+    #[error("At most {max} range approvals per operator")]
+    TooManyRangeApprovals { max: u32 },
+
+    #[error("Already confirmed")]
+    AlreadyConfirmed {},
+
+    #[error("Contract is frozen for export")]
+    Frozen {},
+
+    #[error("Contract is paused")]
+    ContractPaused {},
+
+    #[error("Contract is not being exported")]
+    NotExporting {},
+
+    #[error("Auction already has bids")]
+    AuctionHasBids {},
+
+    #[error("Only {available} can be withdrawn")]
+    InsufficientWithdrawable { available: Uint128 },
+
+    #[error("Nothing to withdraw")]
+    NothingToWithdraw {},
+
+    #[error("Recipient is not an active withdrawal recipient")]
+    RecipientNotAllowed {},
+
+    #[error("Withdrawal cap leaves {available} for today")]
+    WithdrawalCapExceeded { available: Uint128 },
+
+    #[error("Storage fee is not set")]
+    StorageFeeDisabled {},
+
+    #[error("Invalid storage fee")]
+    InvalidStorageFee {},
+
+    #[error("Storage fee cannot change while tokens are in vault custody")]
+    StorageFeeLocked {},
+
+    #[error("Token is not in vault custody")]
+    NotCustodied {},
+
+    #[error("Token is already in vault custody")]
+    AlreadyCustodied {},
+
+    #[error("Unpaid storage fees of {owed}")]
+    StorageFeeOutstanding { owed: Uint128 },
+
+    #[error("Payment exceeds the unpaid storage fees of {owed}")]
+    StorageFeeOverpaid { owed: Uint128 },
+
+    #[error("Right was assigned as transferable and is out of the owner's hands")]
+    RightAssigned {},
+
+    #[error("Right is not transferable")]
+    RightNotTransferable {},
+
+    #[error("Invalid collection bid")]
+    InvalidCollectionBid {},
+
+    #[error("At most {max} featured tokens")]
+    TooManyFeatured { max: usize },
+
+    #[error("Token {token_id} is featured twice")]
+    DuplicateFeatured { token_id: String },
+
+    #[error("Blurb exceeds {max} characters")]
+    BlurbTooLong { max: usize },
+
+    #[error("A token cannot link to itself")]
+    InvalidLink {},
+
+    #[error("Token is already linked to that token")]
+    LinkExists {},
+
+    #[error("No matching link proposal")]
+    LinkNotProposed {},
+
+    #[error("Drop limit must allow at least one token per address")]
+    InvalidDropLimit {},
+
+    #[error("Address reached the drop limit of {limit} tokens")]
+    DropLimitReached { limit: u64 },
+
+    #[error("Listing price must be above zero")]
+    InvalidListing {},
+
+    #[error("Marketplace fee must be between 1 and 10000 bps")]
+    InvalidMarketplaceFee {},
+
+    #[error("Royalties and fees exceed the sale price")]
+    SaleFeesExceedPrice {},
+
+    #[error("Only unordered channels are supported")]
+    OnlyUnorderedChannel {},
+
+    #[error("Channel version must be {expected}, got {version}")]
+    InvalidIbcVersion { expected: String, version: String },
+
+    #[error("No open ICS-721 channel {channel_id}")]
+    UnknownChannel { channel_id: String },
+
+    #[error("A transfer carries this collection's own tokens or vouchers going back over their channel, not a mix")]
+    IbcClassMismatch {},
+
+    #[error("Class {class_id} did not originate here")]
+    UnknownClass { class_id: String },
+
+    #[error("Token {token_id} is not escrowed for this channel")]
+    NotEscrowed { token_id: String },
+
+    #[error("Token {token_id} was already received")]
+    IbcTokenExists { token_id: String },
+
+    #[error("Cannot migrate from contract {previous_contract}")]
+    CannotMigrate { previous_contract: String },
+
+    #[error("Cannot migrate from newer version {previous_version}")]
+    CannotMigrateVersion { previous_version: String },
+
+    #[error("Mint price is stored without a denom, migrate with legacy_price_denom set")]
+    LegacyPriceDenomRequired {},
+
+    #[error("Royalty waiver is not yours or not from this token's artist")]
+    RoyaltyWaiverNotApplicable {},
+
+    #[error("An artist can issue at most {max} royalty waivers")]
+    TooManyRoyaltyWaivers { max: u32 },
+
+    #[error("Not supported for this auction format")]
+    WrongAuctionFormat {},
+
+    #[error("Bid must reach the reserve price and beat the highest bid")]
+    BidTooLow {},
+
+    #[error("Pin needs a provider, a receipt hash and a future expiry")]
+    InvalidPin {},
+
+    #[error("Dutch auction must fall from a higher to a lower price in one denom over a non-zero interval")]
+    InvalidDutchAuction {},
+
+    #[error("Currency must be a three-letter ISO 4217 code")]
+    InvalidCurrencyCode {},
+
+    #[error("Offer already made, withdraw it first")]
+    OfferExists {},
+
+    #[error("Token {token_id} is frozen")]
+    TokenFrozen { token_id: String },
+
+    #[error("Token is not frozen")]
+    TokenNotFrozen {},
+
+    #[error("Token is held in escrow by the contract")]
+    TokenEscrowed {},
+
+    #[error("Token {token_id} is soulbound and cannot be transferred or approved")]
+    NonTransferable { token_id: String },
+
+    #[error(
+        "Alias must be up to {max} lowercase letters, digits and single hyphens, and not a number"
+    )]
+    InvalidTokenAlias { max: usize },
+
+    #[error("Alias is already used by another token")]
+    AliasTaken {},
+
+    #[error("Token metadata is frozen")]
+    MetadataFrozen {},
+
+    #[error("Invalid mint price peg")]
+    InvalidMintPricePeg {},
+
+    #[error("The collection is not a blind drop")]
+    NoBlindDrop {},
+
+    #[error("The blind drop is already revealed")]
+    AlreadyRevealed {},
+
+    #[error("Metadata commitment must be a 32 byte sha256 hash")]
+    InvalidMetadataCommitment {},
+
+    #[error("Base URI does not match the metadata commitment")]
+    MetadataCommitmentMismatch {},
+
+    #[error("Invalid series")]
+    InvalidSeries {},
+
+    #[error("Series {series_id} is sold out")]
+    SeriesSoldOut { series_id: u64 },
+
+    #[error("Only available in the extended deployment profile")]
+    ExtendedProfileRequired {},
+
+    #[error("Lazy mint voucher has already been redeemed")]
+    VoucherRedeemed {},
+
+    #[error("Listing is stale, the seller no longer owns the token")]
+    StaleListing {},
+
+    #[error("A trade-in must accept between 1 and {max} tokens")]
+    InvalidTradeInTokens { max: usize },
+
+    #[error("The trade-in does not accept this token")]
+    TradeInNotAccepted {},
+
+    #[error("Lazy mint voucher has expired")]
+    VoucherExpired {},
+
+    #[error("Artist share must be at most 10000 bps")]
+    InvalidArtistShare {},
+
+    #[error("First refusal window must be between 1 and {max} seconds")]
+    InvalidFirstRefusalWindow { max: u64 },
+
+    #[error("The artist has a right of first refusal until {until}")]
+    FirstRefusalPending { until: Timestamp },
+
+    #[error("The artist's right of first refusal has lapsed")]
+    FirstRefusalLapsed {},
+
+    #[error("The listing does not match the collector's auto-buy rules")]
+    AutoBuyRulesNotMet {},
+
+    #[error("Auto-buy budget of {balance} is short of the listing price")]
+    AutoBuyBudgetExceeded { balance: Uint128 },
+
+    #[error("Pre-sale takes at most {max} commitments")]
+    PresaleFull { max: u64 },
+
+    #[error("Seed does not match the pre-sale's seed commitment")]
+    PresaleSeedMismatch {},
+
+    #[error("Invalid payout denom")]
+    InvalidPayoutDenom {},
+
+    #[error("Companion purchase from {contract} failed: {error}")]
+    CompanionPurchaseFailed { contract: String, error: String },
+
+    #[error("No lender is set")]
+    NoLender {},
+
+    #[error("Loan must be in the price's denom and at most the price")]
+    InvalidLoan {},
+
+    #[error("The lender did not pay in the loan")]
+    LoanNotFunded {},
 }
@@ -1,19 +1,92 @@
 use serde::de::DeserializeOwned;
 use serde::Serialize;
 
-use cosmwasm_std::{Binary, Coin, Deps, DepsMut, Env, MessageInfo, Response, StdResult};
+use cosmwasm_std::{
+    coins, from_json, to_json_binary, to_json_vec, Addr, BankMsg, Binary, Coin, Deps, DepsMut,
+    Empty, Env, Event, HexBinary, IbcMsg, IbcTimeout, MessageInfo, Order, Response, StdError,
+    StdResult, Storage, SubMsg, SubMsgResult, Timestamp, Uint128, WasmMsg,
+};
 
-use cw2::set_contract_version;
-use cw721::{ContractInfoResponse, CustomMsg, Cw721Execute, Cw721ReceiveMsg, Expiration};
+use cw2::{get_contract_version, set_contract_version};
+use cw721::{
+    ContractInfoResponse, CustomMsg, Cw721Execute, Cw721ExecuteMsg, Cw721ReceiveMsg, Expiration,
+};
+use cw_storage_plus::Bound;
+use cw_utils::{may_pay, must_pay, one_coin};
+use sha2::{Digest, Sha256};
 
 use crate::error::ContractError;
-use crate::msg::{ExecuteMsg, InstantiateMsg, MintMsg};
-use crate::state::{Approval, Cw721Contract, TokenInfo};
-
+use crate::msg::{
+    AllowlistConfig, ArtworkExtension, AttestationKind, BuyBackProgram, CertificateSignDoc,
+    CompanionPurchase, Cw2981QueryMsg, DeploymentProfile, DropLimitConfig, DutchAuctionConfig,
+    EventSubsystem, ExecuteMsg, ExhibitionDetails, ExhibitionSignDoc, FeaturedToken, FiatPrice,
+    GiftMessage, InstallmentPlan, InstantiateMsg, KeyRotationSignDoc, LazyMintSignDoc,
+    LazyMintVoucher, LenderExecuteMsg, LinkCallback, LinkKind, LoanCollateralMsg,
+    MarketplaceFeeConfig, MetadataVariant, MigrateMsg, MintMsg, MintPricePeg,
+    NonFungibleTokenPacketData, PresaleConfig, PriceFloor, RedactedField, RevocationReason, Role,
+    RoyaltiesInfoResponse, RoyaltyConfig, SizeLimits, StorageFeeSchedule, SwapExecuteMsg,
+    TokenRight, TransferCondition, TreasuryAccount, VaultDepositMsg, EVENT_SCHEMA_VERSION,
+};
+use crate::state::{
+    content_keys, token_key, AllowlistEntry, Approval, ArtistKey, Attestation, Attestor, Auction,
+    AuctionFormat, AutoBuyRules, AutoBuyer, Bid, BidRecord, BlindDrop, Certificate, CollectionBid,
+    ConditionalTransfer, CurationFee, Cw721Contract, DropLimit, DropMint, Exhibition,
+    ExhibitionEvent, ExpiryKind, ExportMode, FirstRefusal, FrozenToken, Gift, IbcEscrow, Listing,
+    ListingApproval, ListingFiatPrice, MarketplaceFee, Offer, OperatorRevocation, PendingReply,
+    PendingWithdrawalCap, PermissionSweep, PinRecord, PinStatus, Presale, PresaleEntry,
+    PresaleOutcome, PrivateSale, Profile, ProvenanceEntry, ProvenanceEvent, ProxyBid,
+    RangeApproval, Refund, RemovalKind, Reservation, RevealedPrice, RightAssignment, Royalty,
+    RoyaltySplit, RoyaltySuccession, RoyaltyWaiver, Sale, SaleFeeOverride, SaleReceipt, SealedBid,
+    Series, StorageAccount, StorageFee, SweepStage, TokenInfo, TokenLink, Tombstone, TradeIn,
+    VaultDeposit, Venue, Voucher, VoucherIssuance, VoucherRedemption, WithdrawalCap, Withholding,
+    WithholdingRule, WITHDRAWAL_WINDOW,
+};
 
 const CONTRACT_NAME: &str = "crates.io:cw721-base";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+const MAX_DISPLAY_NAME_LENGTH: usize = 64;
+
+const MAX_ALIAS_LENGTH: usize = 64;
+
+const MAX_METADATA_VARIANTS: usize = 16;
+
+const MAX_FEATURED: usize = 50;
+const MAX_TRADE_IN_TOKENS: usize = 50;
+const MAX_BLURB_LENGTH: usize = 280;
+
+const DEFAULT_MIGRATION_BATCH: u32 = 30;
+const MAX_MIGRATION_BATCH: u32 = 100;
+
+/// bounds the pre-sale draw, which allocates every commitment at once
+const MAX_PRESALE_COMMITMENTS: u64 = 300;
+/// seconds the admin has to draw a pre-sale before anyone can refund it
+const PRESALE_DRAW_WINDOW: u64 = 7 * 24 * 60 * 60;
+
+const DEFAULT_CLEANUP_BATCH: u32 = 30;
+const MAX_CLEANUP_BATCH: u32 = 100;
+
+const MAX_RANGE_APPROVALS: usize = 10;
+
+const MAX_ROYALTY_PAYEES: usize = 10;
+const MAX_ROYALTY_SUCCESSIONS: usize = 10;
+const MAX_ROYALTY_WAIVERS: u32 = 100;
+
+/// Most tokens a batch mint, transfer or burn can cover
+const MAX_BATCH_SIZE: usize = 100;
+
+/// Seconds an ICS-721 packet may wait for relaying, unless given
+const DEFAULT_IBC_TIMEOUT: u64 = 10 * 60;
+
+/// Delay before loosened withdrawal limits take effect
+const WITHDRAWAL_POLICY_DELAY: u64 = 24 * 60 * 60;
+
+/// English auctions end no sooner than this long after their last bid
+const AUCTION_EXTENSION_WINDOW: u64 = 10 * 60;
+
+/// Longest an artist can hold up a resale to match it
+const MAX_FIRST_REFUSAL_WINDOW: u64 = 30 * 24 * 60 * 60;
+
 impl<'a, T, C> Cw721Contract<'a, T, C>
 where
     T: Serialize + DeserializeOwned + Clone,
@@ -25,13 +98,49 @@ where
         _env: Env,
         _info: MessageInfo,
         msg: InstantiateMsg,
-    ) -> StdResult<Response<C>> {
-        // Existing CW721 initialization code...
-    
-        // TODO: Initialize minting parameters (allowed, max mints, price)
-        // TODO: Save token URI for the art collection
-        // Any other art-specific initializations?
-    
+    ) -> Result<Response<C>, ContractError> {
+        set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+        let info = ContractInfoResponse {
+            name: msg.name,
+            symbol: msg.symbol,
+        };
+        self.contract_info.save(deps.storage, &info)?;
+        let minter = deps.api.addr_validate(&msg.minter)?;
+        self.minter.save(deps.storage, &minter)?;
+
+        // art collection minting parameters
+        self.mint_allowed.save(deps.storage, &true)?;
+        self.max_mints.save(deps.storage, &msg.max_mints)?;
+        self.mint_price.save(deps.storage, &msg.mint_price)?;
+        let size_limits = msg.size_limits.unwrap_or_default();
+        self.save_size_limits(deps.storage, &size_limits)?;
+        if let Some(token_uri) = &msg.token_uri {
+            self.check_uri(deps.storage, token_uri)?;
+        }
+        self.token_uri.save(deps.storage, &msg.token_uri)?;
+        self.deployment_profile.save(deps.storage, &msg.profile)?;
+        if let Some(royalty) = msg.royalty {
+            if msg.profile == DeploymentProfile::Minimal {
+                return Err(ContractError::ExtendedProfileRequired {});
+            }
+            let royalty = self.check_royalty(deps.as_ref(), royalty)?;
+            self.default_royalty.save(deps.storage, &royalty)?;
+        }
+        if let Some(config) = msg.blind_drop {
+            self.check_uri(deps.storage, &config.placeholder_uri)?;
+            if config.metadata_commitment.len() != 32 {
+                return Err(ContractError::InvalidMetadataCommitment {});
+            }
+            let drop = BlindDrop {
+                placeholder_uri: config.placeholder_uri,
+                metadata_commitment: config.metadata_commitment,
+                base_uri: None,
+                revealed_at: None,
+            };
+            self.blind_drop.save(deps.storage, &drop)?;
+        }
+
         Ok(Response::default())
     }
 
@@ -40,11 +149,543 @@ where
         deps: DepsMut,
         env: Env,
         info: MessageInfo,
-        msg: ExecuteMsg<T>,
+        mut msg: ExecuteMsg<T>,
     ) -> Result<Response<C>, ContractError> {
-        match msg {
-            // TODO: Add cases for different message types
-
+        for token_id in msg.token_ids_mut() {
+            self.resolve_token_alias(deps.storage, token_id)?;
+        }
+        // nothing may change while state is being exported
+        if self.export_mode.may_load(deps.storage)?.is_some()
+            && !matches!(msg, ExecuteMsg::EndExport {})
+        {
+            return Err(ContractError::Frozen {});
+        }
+        if self.paused.may_load(deps.storage)?.is_some()
+            && !matches!(msg, ExecuteMsg::Pause {} | ExecuteMsg::Unpause {})
+        {
+            // the admin can still fix configuration, but nothing moves tokens
+            // or funds until the pause is lifted
+            if !is_pausable_config(&msg)
+                || !self.has_role(deps.storage, &info.sender, Role::Admin)?
+            {
+                return Err(ContractError::ContractPaused {});
+            }
+        }
+        let subsystem = event_subsystem(&msg);
+        if (subsystem.is_some() || is_royalty_config(&msg)) && self.is_minimal(deps.storage)? {
+            return Err(ContractError::ExtendedProfileRequired {});
+        }
+        let res = match msg {
+            ExecuteMsg::Mint(msg) => self.mint(deps, env, info, msg, None),
+            ExecuteMsg::MintWithProof { mint, proof } => {
+                self.mint(deps, env, info, mint, Some(proof))
+            }
+            ExecuteMsg::CreateSeries {
+                name,
+                max_editions,
+                price,
+                base_uri,
+            } => self.create_series(deps, env, info, name, max_editions, price, base_uri),
+            ExecuteMsg::MintSeries { series_id, mint } => {
+                self.mint_series(deps, env, info, series_id, mint)
+            }
+            ExecuteMsg::BatchMint { mints } => self.batch_mint(deps, env, info, mints),
+            ExecuteMsg::SetMintConfig {
+                max_mints,
+                mint_price,
+            } => self.set_mint_config(deps, env, info, max_mints, mint_price),
+            ExecuteMsg::SetMaxMintsPerAddress { limit } => {
+                self.set_max_mints_per_address(deps, env, info, limit)
+            }
+            ExecuteMsg::SetDropLimit { limit } => self.set_drop_limit(deps, env, info, limit),
+            ExecuteMsg::SetUniqueContent { enabled } => {
+                self.set_unique_content(deps, env, info, enabled)
+            }
+            ExecuteMsg::ToggleMinting {} => self.toggle_minting(deps, env, info),
+            ExecuteMsg::Pause {} => self.pause(deps, env, info),
+            ExecuteMsg::Unpause {} => self.unpause(deps, env, info),
+            ExecuteMsg::SetSizeLimits { limits } => self.set_size_limits(deps, env, info, limits),
+            ExecuteMsg::RegisterProfile {
+                display_name,
+                profile_uri,
+            } => self.register_profile(deps, env, info, display_name, profile_uri),
+            ExecuteMsg::ClearProfile {} => self.clear_profile(deps, env, info),
+            ExecuteMsg::ModerateProfile { address, suspended } => {
+                self.moderate_profile(deps, env, info, address, suspended)
+            }
+            ExecuteMsg::UpdateSendAllowlist {
+                enabled,
+                add,
+                remove,
+            } => self.update_send_allowlist(deps, env, info, enabled, add, remove),
+            ExecuteMsg::SetInstallmentPlan { plan } => {
+                self.set_installment_plan(deps, env, info, plan)
+            }
+            ExecuteMsg::ReserveMint(msg) => self.reserve_mint(deps, env, info, msg),
+            ExecuteMsg::PayInstallment { token_id } => {
+                self.pay_installment(deps, env, info, token_id)
+            }
+            ExecuteMsg::ForfeitReservation { token_id } => {
+                self.forfeit_reservation(deps, env, info, token_id)
+            }
+            ExecuteMsg::SetWithholdingRule {
+                jurisdiction,
+                rate_bps,
+                escrow,
+            } => self.set_withholding_rule(deps, env, info, jurisdiction, rate_bps, escrow),
+            ExecuteMsg::RemoveWithholdingRule { jurisdiction } => {
+                self.remove_withholding_rule(deps, env, info, jurisdiction)
+            }
+            ExecuteMsg::SetAccountJurisdiction {
+                address,
+                jurisdiction,
+            } => self.set_account_jurisdiction(deps, env, info, address, jurisdiction),
+            ExecuteMsg::Destroy {
+                token_id,
+                evidence_hash,
+                attestation,
+            } => self.destroy(deps, env, info, token_id, evidence_hash, attestation),
+            ExecuteMsg::AddAttestor { address, name } => {
+                self.add_attestor(deps, env, info, address, name)
+            }
+            ExecuteMsg::RemoveAttestor { address } => {
+                self.remove_attestor(deps, env, info, address)
+            }
+            ExecuteMsg::Attest {
+                token_id,
+                kind,
+                uri,
+                content_hash,
+            } => self.attest(deps, env, info, token_id, kind, uri, content_hash),
+            ExecuteMsg::RevokeAttestation { token_id, index } => {
+                self.revoke_attestation(deps, env, info, token_id, index)
+            }
+            ExecuteMsg::CreateSealedAuction {
+                token_id,
+                reserve_price,
+                commit_duration,
+                reveal_duration,
+                deposit,
+            } => self.create_sealed_auction(
+                deps,
+                env,
+                info,
+                token_id,
+                reserve_price,
+                commit_duration,
+                reveal_duration,
+                deposit,
+            ),
+            ExecuteMsg::CommitBid {
+                auction_id,
+                commitment,
+            } => self.commit_bid(deps, env, info, auction_id, commitment),
+            ExecuteMsg::RevealBid {
+                auction_id,
+                amount,
+                salt,
+            } => self.reveal_bid(deps, env, info, auction_id, amount, salt),
+            ExecuteMsg::SettleSealedAuction { auction_id } => {
+                self.settle_sealed_auction(deps, env, info, auction_id)
+            }
+            ExecuteMsg::Finalize { auction_id } => self.finalize(deps, env, info, auction_id),
+            ExecuteMsg::CreateAuction {
+                token_id,
+                reserve_price,
+                duration,
+            } => self.create_auction(deps, env, info, token_id, reserve_price, duration),
+            ExecuteMsg::PlaceBid { auction_id } => self.place_bid(deps, env, info, auction_id),
+            ExecuteMsg::PlaceMaxBid { auction_id } => {
+                self.place_max_bid(deps, env, info, auction_id)
+            }
+            ExecuteMsg::SettleAuction { auction_id } => {
+                self.settle_auction(deps, env, info, auction_id)
+            }
+            ExecuteMsg::SetAuctionBeneficiary {
+                auction_id,
+                beneficiary,
+            } => self.set_auction_beneficiary(deps, env, info, auction_id, beneficiary),
+            ExecuteMsg::SetAuctionFeeOverride {
+                auction_id,
+                fee_override,
+            } => self.set_auction_fee_override(deps, env, info, auction_id, fee_override),
+            ExecuteMsg::RenounceOperator { owner } => {
+                self.renounce_operator(deps, env, info, owner)
+            }
+            ExecuteMsg::MigrateTokenKeys { limit } => {
+                self.migrate_token_keys(deps, env, info, limit)
+            }
+            ExecuteMsg::SetRedactions { token_id, fields } => {
+                self.set_redactions(deps, env, info, token_id, fields)
+            }
+            ExecuteMsg::ProposeLink {
+                token_id,
+                contract,
+                linked_token_id,
+                kind,
+            } => self.propose_link(deps, env, info, token_id, contract, linked_token_id, kind),
+            ExecuteMsg::AcceptLink {
+                token_id,
+                contract,
+                linked_token_id,
+                kind,
+            } => self.accept_link(deps, env, info, token_id, contract, linked_token_id, kind),
+            ExecuteMsg::RemoveLink {
+                token_id,
+                contract,
+                linked_token_id,
+            } => self.remove_link(deps, env, info, token_id, contract, linked_token_id),
+            ExecuteMsg::ReceiveLink(msg) => self.receive_link(deps, env, info, msg),
+            ExecuteMsg::RecordPin {
+                token_id,
+                provider,
+                receipt_hash,
+                expires_at,
+            } => self.record_pin(
+                deps,
+                env,
+                info,
+                token_id,
+                provider,
+                receipt_hash,
+                expires_at,
+            ),
+            ExecuteMsg::SetPinStatus {
+                token_id,
+                provider,
+                status,
+            } => self.set_pin_status(deps, env, info, token_id, provider, status),
+            ExecuteMsg::SetMetadataVariants { token_id, variants } => {
+                self.set_metadata_variants(deps, env, info, token_id, variants)
+            }
+            ExecuteMsg::UpdateTokenMetadata {
+                token_id,
+                token_uri,
+                extension,
+            } => self.update_token_metadata(deps, env, info, token_id, token_uri, extension),
+            ExecuteMsg::FreezeTokenMetadata { token_id } => {
+                self.freeze_token_metadata(deps, env, info, token_id)
+            }
+            ExecuteMsg::SetBuyBack { program } => self.set_buyback(deps, env, info, program),
+            ExecuteMsg::FundRefundPool {} => self.fund_refund_pool(deps, env, info),
+            ExecuteMsg::WithdrawRefundPool { amount } => {
+                self.withdraw_refund_pool(deps, env, info, amount)
+            }
+            ExecuteMsg::BurnForRefund { token_id } => {
+                self.burn_for_refund(deps, env, info, token_id)
+            }
+            ExecuteMsg::ReceiveNft(msg) => self.receive_nft(deps, env, info, msg),
+            ExecuteMsg::UpdateVaultCollections { add, remove } => {
+                self.update_vault_collections(deps, env, info, add, remove)
+            }
+            ExecuteMsg::RedeemVaultReceipt { token_id } => {
+                self.redeem_vault_receipt(deps, env, info, token_id)
+            }
+            ExecuteMsg::UpdatePartnerGalleries { add, remove } => {
+                self.update_partner_galleries(deps, env, info, add, remove)
+            }
+            ExecuteMsg::SetVoucherLimit {
+                parent_token_id,
+                limit,
+            } => self.set_voucher_limit(deps, env, info, parent_token_id, limit),
+            ExecuteMsg::IssueVoucher {
+                parent_token_id,
+                owner,
+                token_uri,
+                extension,
+            } => self.issue_voucher(
+                deps,
+                env,
+                info,
+                parent_token_id,
+                owner,
+                token_uri,
+                extension,
+            ),
+            ExecuteMsg::RedeemVoucher { token_id } => {
+                self.redeem_voucher(deps, env, info, token_id)
+            }
+            ExecuteMsg::SetArtistOfRecord { token_id, artist } => {
+                self.set_artist_of_record(deps, env, info, token_id, artist)
+            }
+            ExecuteMsg::RegisterArtistKey { pubkey } => {
+                self.register_artist_key(deps, env, info, pubkey)
+            }
+            ExecuteMsg::RotateArtistKey { pubkey, signature } => {
+                self.rotate_artist_key(deps, env, info, pubkey, signature)
+            }
+            ExecuteMsg::IssueCertificate {
+                token_id,
+                signature,
+            } => self.issue_certificate(deps, env, info, token_id, signature),
+            ExecuteMsg::LazyMint { voucher, signature } => {
+                self.lazy_mint(deps, env, info, voucher, signature)
+            }
+            ExecuteMsg::SetPriceFloor { floor } => self.set_price_floor(deps, env, info, floor),
+            ExecuteMsg::SendGift {
+                token_id,
+                recipient,
+                message,
+                deadline,
+            } => self.send_gift(deps, env, info, token_id, recipient, message, deadline),
+            ExecuteMsg::ClaimGift { token_id } => self.claim_gift(deps, env, info, token_id),
+            ExecuteMsg::ReturnGift { token_id } => self.return_gift(deps, env, info, token_id),
+            ExecuteMsg::CreateConditionalTransfer {
+                token_id,
+                recipient,
+                condition,
+                expires,
+            } => self.create_conditional_transfer(
+                deps, env, info, token_id, recipient, condition, expires,
+            ),
+            ExecuteMsg::ExecuteConditionalTransfer { token_id } => {
+                self.execute_conditional_transfer(deps, env, info, token_id)
+            }
+            ExecuteMsg::CancelConditionalTransfer { token_id } => {
+                self.cancel_conditional_transfer(deps, env, info, token_id)
+            }
+            ExecuteMsg::RecordPrivateSale {
+                token_id,
+                buyer,
+                price_commitment,
+            } => self.record_private_sale(deps, env, info, token_id, buyer, price_commitment),
+            ExecuteMsg::RevealPrivateSale {
+                token_id,
+                sale_id,
+                price,
+                salt,
+            } => self.reveal_private_sale(deps, env, info, token_id, sale_id, price, salt),
+            ExecuteMsg::SetMysteryMode { sealed_uri } => {
+                self.set_mystery_mode(deps, env, info, sealed_uri)
+            }
+            ExecuteMsg::AddMysteryUris { uris } => self.add_mystery_uris(deps, env, info, uris),
+            ExecuteMsg::RevealToken { token_id } => self.reveal_token(deps, env, info, token_id),
+            ExecuteMsg::Reveal { base_uri } => self.reveal(deps, env, info, base_uri),
+            ExecuteMsg::SetKeeperBounty { bounty } => {
+                self.set_keeper_bounty(deps, env, info, bounty)
+            }
+            ExecuteMsg::FundKeeperTreasury {} => self.fund_keeper_treasury(deps, env, info),
+            ExecuteMsg::WithdrawKeeperTreasury { amount } => {
+                self.withdraw_keeper_treasury(deps, env, info, amount)
+            }
+            ExecuteMsg::CleanExpired { limit } => self.clean_expired(deps, env, info, limit),
+            ExecuteMsg::SweepPermissions { address, limit } => {
+                self.sweep_permissions(deps, env, info, address, limit)
+            }
+            ExecuteMsg::TransferMinter { new_minter } => {
+                self.transfer_minter(deps, env, info, new_minter)
+            }
+            ExecuteMsg::AcceptMinter {} => self.accept_minter(deps, env, info),
+            ExecuteMsg::GrantRole { address, role } => {
+                self.grant_role(deps, env, info, address, role)
+            }
+            ExecuteMsg::RevokeRole { address, role } => {
+                self.revoke_role(deps, env, info, address, role)
+            }
+            ExecuteMsg::SetNameResolver { resolver } => {
+                self.set_name_resolver(deps, env, info, resolver)
+            }
+            ExecuteMsg::SetMetadataResolver { resolver } => {
+                self.set_metadata_resolver(deps, env, info, resolver)
+            }
+            ExecuteMsg::SetPresale { config } => self.set_presale(deps, env, info, config),
+            ExecuteMsg::CommitPresale(msg) => self.commit_presale(deps, env, info, msg),
+            ExecuteMsg::DrawPresale { seed } => self.draw_presale(deps, env, info, seed),
+            ExecuteMsg::SetAllowlistConfig { config } => {
+                self.set_allowlist_config(deps, env, info, config)
+            }
+            ExecuteMsg::SetDutchAuction { config } => {
+                self.set_dutch_auction(deps, env, info, config)
+            }
+            ExecuteMsg::SetMintPricePeg { peg } => self.set_mint_price_peg(deps, env, info, peg),
+            ExecuteMsg::AddToAllowlist { addresses } => {
+                self.add_to_allowlist(deps, env, info, addresses)
+            }
+            ExecuteMsg::RemoveFromAllowlist { addresses } => {
+                self.remove_from_allowlist(deps, env, info, addresses)
+            }
+            ExecuteMsg::SetAllowlistMerkleRoot { root } => {
+                self.set_allowlist_merkle_root(deps, env, info, root)
+            }
+            ExecuteMsg::SetCurator { curator } => self.set_curator(deps, env, info, curator),
+            ExecuteMsg::SetFeatured { tokens } => self.set_featured(deps, env, info, tokens),
+            ExecuteMsg::SetCurationFee { bps } => self.set_curation_fee(deps, env, info, bps),
+            ExecuteMsg::SetReferral {
+                referral_id,
+                curator,
+            } => self.set_referral(deps, env, info, referral_id, curator),
+            ExecuteMsg::SetTokenRoyalty { token_id, royalty } => {
+                self.set_token_royalty(deps, env, info, token_id, royalty)
+            }
+            ExecuteMsg::SetRoyaltySplit { token_id, payees } => {
+                self.set_royalty_split(deps, env, info, token_id, payees)
+            }
+            ExecuteMsg::ScheduleRoyaltySuccession {
+                token_id,
+                effective_at,
+                payees,
+            } => self.schedule_royalty_succession(deps, env, info, token_id, effective_at, payees),
+            ExecuteMsg::CancelRoyaltySuccession {
+                token_id,
+                effective_at,
+            } => self.cancel_royalty_succession(deps, env, info, token_id, effective_at),
+            ExecuteMsg::SetRoyaltySwap { swap } => self.set_royalty_swap(deps, env, info, swap),
+            ExecuteMsg::SetRoyaltyPayoutDenom { denom } => {
+                self.set_royalty_payout_denom(deps, env, info, denom)
+            }
+            ExecuteMsg::ClaimAccruedRoyalties {} => self.claim_accrued_royalties(deps, env, info),
+            ExecuteMsg::PlaceCollectionBid { price, quantity } => {
+                self.place_collection_bid(deps, env, info, price, quantity)
+            }
+            ExecuteMsg::AcceptCollectionBid { bid_id, token_id } => {
+                self.accept_collection_bid(deps, env, info, bid_id, token_id)
+            }
+            ExecuteMsg::CancelCollectionBid { bid_id } => {
+                self.cancel_collection_bid(deps, env, info, bid_id)
+            }
+            ExecuteMsg::ListForSale {
+                token_id,
+                price,
+                royalty_waiver,
+            } => self.list_for_sale(deps, env, info, token_id, price, royalty_waiver),
+            ExecuteMsg::CancelListing { token_id } => {
+                self.cancel_listing(deps, env, info, token_id)
+            }
+            ExecuteMsg::SetListingFeeOverride {
+                token_id,
+                fee_override,
+            } => self.set_listing_fee_override(deps, env, info, token_id, fee_override),
+            ExecuteMsg::SetListingFiatPrice {
+                token_id,
+                fiat_price,
+            } => self.set_listing_fiat_price(deps, env, info, token_id, fiat_price),
+            ExecuteMsg::BuyToken { token_id } => self.buy_token(deps, env, info, token_id),
+            ExecuteMsg::BuyBundle {
+                token_ids,
+                companions,
+            } => self.buy_bundle(deps, env, info, token_ids, companions),
+            ExecuteMsg::BuyWithLoan { token_id, loan } => {
+                self.buy_with_loan(deps, env, info, token_id, loan)
+            }
+            ExecuteMsg::SetLender { lender } => self.set_lender(deps, env, info, lender),
+            ExecuteMsg::SetMarketplaceFee { fee } => self.set_marketplace_fee(deps, env, info, fee),
+            ExecuteMsg::IssueRoyaltyWaiver { holder } => {
+                self.issue_royalty_waiver(deps, env, info, holder)
+            }
+            ExecuteMsg::TransferRoyaltyWaiver {
+                waiver_id,
+                recipient,
+            } => self.transfer_royalty_waiver(deps, env, info, waiver_id, recipient),
+            ExecuteMsg::MakeOffer { token_id } => self.make_offer(deps, env, info, token_id),
+            ExecuteMsg::WithdrawOffer { token_id } => {
+                self.withdraw_offer(deps, env, info, token_id)
+            }
+            ExecuteMsg::AcceptOffer { token_id, bidder } => {
+                self.accept_offer(deps, env, info, token_id, bidder)
+            }
+            ExecuteMsg::SetFirstRefusal { window } => {
+                self.set_first_refusal(deps, env, info, window)
+            }
+            ExecuteMsg::MatchFirstRefusal { token_id } => {
+                self.match_first_refusal(deps, env, info, token_id)
+            }
+            ExecuteMsg::CompleteFirstRefusal { token_id } => {
+                self.complete_first_refusal(deps, env, info, token_id)
+            }
+            ExecuteMsg::FundAutoBuy { rules } => self.fund_auto_buy(deps, env, info, rules),
+            ExecuteMsg::WithdrawAutoBuy {} => self.withdraw_auto_buy(deps, env, info),
+            ExecuteMsg::AutoBuy {
+                collector,
+                token_id,
+            } => self.auto_buy(deps, env, info, collector, token_id),
+            ExecuteMsg::OpenTradeIn {
+                token_id,
+                top_up,
+                accepted_token_ids,
+            } => self.open_trade_in(deps, env, info, token_id, top_up, accepted_token_ids),
+            ExecuteMsg::CloseTradeIn { token_id } => self.close_trade_in(deps, env, info, token_id),
+            ExecuteMsg::TradeIn {
+                token_id,
+                offered_token_id,
+            } => self.take_trade_in(deps, env, info, token_id, offered_token_id),
+            ExecuteMsg::SetTokenAlias { token_id, alias } => {
+                self.set_token_alias(deps, env, info, token_id, alias)
+            }
+            ExecuteMsg::FreezeToken { token_id, reason } => {
+                self.freeze_token(deps, env, info, token_id, reason)
+            }
+            ExecuteMsg::UnfreezeToken { token_id } => {
+                self.unfreeze_token(deps, env, info, token_id)
+            }
+            ExecuteMsg::SeizeToken {
+                token_id,
+                custody_address,
+                reason,
+            } => self.seize_token(deps, env, info, token_id, custody_address, reason),
+            ExecuteMsg::AddVenue {
+                address,
+                name,
+                pubkey,
+            } => self.add_venue(deps, env, info, address, name, pubkey),
+            ExecuteMsg::RemoveVenue { address } => self.remove_venue(deps, env, info, address),
+            ExecuteMsg::StartExport {} => self.start_export(deps, env, info),
+            ExecuteMsg::Withdraw { amount, recipient } => {
+                self.withdraw(deps, env, info, amount, recipient)
+            }
+            ExecuteMsg::UpdateWithdrawalRecipients { add, remove } => {
+                self.update_withdrawal_recipients(deps, env, info, add, remove)
+            }
+            ExecuteMsg::SetWithdrawalCap { denom, amount } => {
+                self.set_withdrawal_cap(deps, env, info, denom, amount)
+            }
+            ExecuteMsg::SetStorageFee { schedule } => {
+                self.set_storage_fee(deps, env, info, schedule)
+            }
+            ExecuteMsg::SetVaultCustody {
+                token_id,
+                custodied,
+            } => self.set_vault_custody(deps, env, info, token_id, custodied),
+            ExecuteMsg::PayStorageFee { token_id } => {
+                self.pay_storage_fee(deps, env, info, token_id)
+            }
+            ExecuteMsg::AssignRight {
+                token_id,
+                right,
+                holder,
+                transferable,
+            } => self.assign_right(deps, env, info, token_id, right, holder, transferable),
+            ExecuteMsg::TransferRight {
+                token_id,
+                right,
+                recipient,
+            } => self.transfer_right(deps, env, info, token_id, right, recipient),
+            ExecuteMsg::ReleaseRight { token_id, right } => {
+                self.release_right(deps, env, info, token_id, right)
+            }
+            ExecuteMsg::ApproveListing {
+                spender,
+                token_id,
+                expires,
+            } => self.approve_listing(deps, env, info, spender, token_id, expires),
+            ExecuteMsg::RevokeListing { spender, token_id } => {
+                self.revoke_listing(deps, env, info, spender, token_id)
+            }
+            ExecuteMsg::CancelSealedAuction { auction_id } => {
+                self.cancel_sealed_auction(deps, env, info, auction_id)
+            }
+            ExecuteMsg::EndExport {} => self.end_export(deps, env, info),
+            ExecuteMsg::RegisterEvent { details } => self.register_event(deps, env, info, details),
+            ExecuteMsg::ConfirmEventWork {
+                event_id,
+                collection,
+                token_id,
+            } => self.confirm_event_work(deps, env, info, event_id, collection, token_id),
+            ExecuteMsg::LogExhibition {
+                token_id,
+                venue,
+                exhibition,
+                signature,
+            } => self.log_exhibition(deps, env, info, token_id, venue, exhibition, signature),
             ExecuteMsg::Approve {
                 spender,
                 token_id,
@@ -56,7 +697,18 @@ where
             ExecuteMsg::ApproveAll { operator, expires } => {
                 self.approve_all(deps, env, info, operator, expires)
             }
-            ExecuteMsg::RevokeAll { operator } => self.revoke_all(deps, env, info, operator),
+            ExecuteMsg::ApproveRange {
+                operator,
+                first,
+                last,
+                expires,
+            } => self.approve_range(deps, env, info, operator, first, last, expires),
+            ExecuteMsg::RevokeRange { operator, first } => {
+                self.revoke_range(deps, env, info, operator, first)
+            }
+            ExecuteMsg::RevokeAll { operator, reason } => {
+                self.revoke_operator(deps, env, info, operator, reason)
+            }
             ExecuteMsg::TransferNft {
                 recipient,
                 token_id,
@@ -67,12 +719,32 @@ where
                 msg,
             } => self.send_nft(deps, env, info, contract, token_id, msg),
             ExecuteMsg::Burn { token_id } => self.burn(deps, env, info, token_id),
-        }
+            ExecuteMsg::BatchTransferNft { recipients } => {
+                self.batch_transfer_nft(deps, env, info, recipients)
+            }
+            ExecuteMsg::IbcTransfer {
+                channel_id,
+                receiver,
+                token_ids,
+                timeout_seconds,
+            } => self.ibc_transfer(
+                deps,
+                env,
+                info,
+                channel_id,
+                receiver,
+                token_ids,
+                timeout_seconds,
+            ),
+            ExecuteMsg::BatchBurn { token_ids } => self.batch_burn(deps, env, info, token_ids),
+        }?;
+        Ok(match subsystem {
+            Some(subsystem) => add_subsystem_event(res, subsystem),
+            None => res,
+        })
     }
-
 }
 
-
 impl<'a, T, C> Cw721Contract<'a, T, C>
 where
     T: Serialize + DeserializeOwned + Clone,
@@ -81,167 +753,9133 @@ where
     pub fn mint(
         &self,
         deps: DepsMut,
-        _env: Env,
+        env: Env,
         info: MessageInfo,
         msg: MintMsg<T>,
+        proof: Option<Vec<HexBinary>>,
     ) -> Result<Response<C>, ContractError> {
-        // TODO: Check if minting is allowed
-        // TODO: Check if we've reached max mints
-        // TODO: Verify correct payment
-        // TODO: Create and store the new token
-        // TODO: Update total token count
-        // TODO: Return success response
+        self.check_mint_available(deps.storage)?;
+        self._count_wallet_mint(deps.storage, &info.sender)?;
+        self._count_drop_acquisition(deps.storage, &info.sender)?;
+
+        // verify payment
+        let mint_price = match self.allowlist_phase(deps.storage, &env.block)? {
+            Some(phase) => {
+                self._use_allowlist(deps.storage, &info.sender, &phase, proof.as_deref())?;
+                phase.price
+            }
+            None => self.public_mint_price(deps.as_ref(), &env.block)?,
+        };
+        let excess = take_payment(&info, &mint_price)?;
+        self.check_token_uri(deps.as_ref(), &info, &msg.token_uri)?;
+
+        let mut res = Response::new();
+        if !excess.is_empty() {
+            res = res.add_message(BankMsg::Send {
+                to_address: info.sender.to_string(),
+                amount: excess,
+            });
+        }
+        let (mut res, token_id, owner, _) =
+            self._sell_mint(deps, &env, &info, msg, &mint_price, res)?;
+        if mint_price.amount.is_zero() {
+            res = res.add_attribute("complimentary", "true");
+        }
+
+        Ok(res
+            .add_attribute("action", "mint")
+            .add_attribute("minter", info.sender.clone())
+            .add_attribute("payer", info.sender)
+            .add_attribute("owner", owner)
+            .add_attribute("token_id", token_id))
     }
 
-    //Add set_mint_config and toggle_minting
-}
+    pub fn batch_mint(
+        &self,
+        mut deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        mints: Vec<MintMsg<T>>,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(deps.as_ref(), &info, Role::Minter)?;
+        check_batch_size(mints.len())?;
+        self.check_mint_available(deps.storage)?;
+        if self.remaining_mints(deps.storage)? < mints.len() as u64 {
+            return Err(ContractError::MaxMintsReached {});
+        }
 
-impl<'a, T, C> Cw721Execute<T, C> for Cw721Contract<'a, T, C>
-where
-    T: Serialize + DeserializeOwned + Clone,
-    C: CustomMsg,
-{
-    type Err = ContractError;
+        // one payment covers the whole batch at the public mint price
+        let mint_price = self.public_mint_price(deps.as_ref(), &env.block)?;
+        let total = Coin {
+            denom: mint_price.denom.clone(),
+            amount: mint_price
+                .amount
+                .checked_mul(Uint128::from(mints.len() as u64))
+                .map_err(StdError::from)?,
+        };
+        let excess = take_payment(&info, &total)?;
 
-    fn transfer_nft(
+        let mut res = Response::new();
+        if !excess.is_empty() {
+            res = res.add_message(BankMsg::Send {
+                to_address: info.sender.to_string(),
+                amount: excess,
+            });
+        }
+        let count = mints.len();
+        for msg in mints {
+            self.check_token_uri(deps.as_ref(), &info, &msg.token_uri)?;
+            let (next, token_id, owner, _) =
+                self._sell_mint(deps.branch(), &env, &info, msg, &mint_price, res)?;
+            res = next.add_event(
+                Event::new("mint")
+                    .add_attribute("token_id", token_id)
+                    .add_attribute("owner", owner),
+            );
+        }
+        if mint_price.amount.is_zero() {
+            res = res.add_attribute("complimentary", "true");
+        }
+
+        Ok(res
+            .add_attribute("action", "batch_mint")
+            .add_attribute("minter", info.sender)
+            .add_attribute("count", count.to_string()))
+    }
+
+    /// creates a token sold at price to the sender, who has already paid,
+    /// settling withholding, curation fee and proceeds, and returns the
+    /// proceeds credited. The caller checks msg's token_uri
+    fn _sell_mint(
         &self,
         deps: DepsMut,
-        env: Env,
+        env: &Env,
+        info: &MessageInfo,
+        msg: MintMsg<T>,
+        price: &Coin,
+        mut res: Response<C>,
+    ) -> Result<(Response<C>, String, Addr, Uint128), ContractError> {
+        self.check_transferable_flag(deps.as_ref(), info, msg.transferable)?;
+        self.check_unique_content(deps.storage, &msg.token_uri, &msg.content_hash)?;
+        let owner = self.resolve_addr(deps.as_ref(), &msg.owner)?;
+        let referrer = self.referral_curator(deps.storage, &msg.referral)?;
+        let content = content_keys(&msg.token_uri, &msg.content_hash);
+        let token_id = self._create_token(
+            deps.storage,
+            env,
+            owner.clone(),
+            &info.sender,
+            msg.token_uri,
+            msg.extension,
+        )?;
+        self._index_content(deps.storage, &token_id, content, msg.content_hash)?;
+        self._seal_if_mystery(deps.storage, &token_id)?;
+        self._set_transferable(deps.storage, &token_id, msg.transferable)?;
+        self._record_drop_mint(deps.storage, env, &token_id)?;
+
+        let withholding = self.withholding_for(deps.storage, &info.sender, price)?;
+        if let Some(withholding) = &withholding {
+            res = self.add_withholding(res, withholding, &price.denom);
+        }
+        let curation = self.curation_fee_for(
+            deps.storage,
+            referrer,
+            price,
+            price.amount - withheld(&withholding),
+        )?;
+        if let Some(curation) = &curation {
+            res = self.add_curation_fee(deps.storage, res, curation, &price.denom)?;
+        }
+        let proceeds = price.amount - withheld(&withholding) - curated(&curation);
+        self._credit_treasury(
+            deps.storage,
+            TreasuryAccount::Proceeds,
+            &price.denom,
+            proceeds,
+        )?;
+        let receipt = SaleReceipt {
+            buyer: info.sender.clone(),
+            price: price.clone(),
+            withholding,
+            proceeds_to: None,
+            curation,
+            fee_override: None,
+            marketplace_fee: None,
+        };
+        self._record_sale(deps.storage, env, &token_id, &receipt)?;
+        Ok((res, token_id, owner, proceeds))
+    }
+
+    pub fn set_mint_config(
+        &self,
+        deps: DepsMut,
+        _env: Env,
         info: MessageInfo,
-        recipient: String,
-        token_id: String,
+        max_mints: u64,
+        mint_price: Coin,
     ) -> Result<Response<C>, ContractError> {
-        self._transfer_nft(deps, &env, &info, &recipient, &token_id)?;
+        self.assert_role(deps.as_ref(), &info, Role::Admin)?;
+
+        self.max_mints.save(deps.storage, &max_mints)?;
+        self.mint_price.save(deps.storage, &mint_price)?;
 
         Ok(Response::new()
-            .add_attribute("action", "transfer_nft")
-            .add_attribute("sender", info.sender)
-            .add_attribute("recipient", recipient)
-            .add_attribute("token_id", token_id))
+            .add_attribute("action", "set_mint_config")
+            .add_attribute("max_mints", max_mints.to_string())
+            .add_attribute("mint_price", mint_price.to_string()))
     }
 
-    fn send_nft(
+    pub fn set_max_mints_per_address(
         &self,
         deps: DepsMut,
-        env: Env,
+        _env: Env,
         info: MessageInfo,
-        contract: String,
-        token_id: String,
-        msg: Binary,
+        limit: Option<u64>,
     ) -> Result<Response<C>, ContractError> {
-        // Transfer token
-        self._transfer_nft(deps, &env, &info, &contract, &token_id)?;
+        self.assert_role(deps.as_ref(), &info, Role::Admin)?;
 
-        let send = Cw721ReceiveMsg {
-            sender: info.sender.to_string(),
-            token_id: token_id.clone(),
-            msg,
-        };
+        match limit {
+            Some(limit) => self.max_mints_per_address.save(deps.storage, &limit)?,
+            None => self.max_mints_per_address.remove(deps.storage),
+        }
 
-        // Send message
         Ok(Response::new()
-            .add_message(send.into_cosmos_msg(contract.clone())?)
-            .add_attribute("action", "send_nft")
+            .add_attribute("action", "set_max_mints_per_address")
             .add_attribute("sender", info.sender)
-            .add_attribute("recipient", contract)
-            .add_attribute("token_id", token_id))
+            .add_attribute(
+                "limit",
+                limit.map_or("none".to_string(), |limit| limit.to_string()),
+            ))
     }
 
-    fn approve(
+    /// Every new limit starts a new drop, with no acquisitions counted yet
+    pub fn set_drop_limit(
         &self,
         deps: DepsMut,
         env: Env,
         info: MessageInfo,
-        spender: String,
-        token_id: String,
-        expires: Option<Expiration>,
+        limit: Option<DropLimitConfig>,
     ) -> Result<Response<C>, ContractError> {
-        self._update_approvals(deps, &env, &info, &spender, &token_id, true, expires)?;
+        self.assert_role(deps.as_ref(), &info, Role::Admin)?;
+
+        let mut res = Response::new()
+            .add_attribute("action", "set_drop_limit")
+            .add_attribute("sender", info.sender);
+        match limit {
+            Some(limit) => {
+                if limit.max_per_address == 0 {
+                    return Err(ContractError::InvalidDropLimit {});
+                }
+                let drop_id = self.drop_count.may_load(deps.storage)?.unwrap_or_default() + 1;
+                self.drop_count.save(deps.storage, &drop_id)?;
+                let drop = DropLimit {
+                    drop_id,
+                    max_per_address: limit.max_per_address,
+                    resale_window: limit.resale_window,
+                    started_at: env.block.time,
+                };
+                self.drop_limit.save(deps.storage, &drop)?;
+                res = res.add_attribute("drop_id", drop_id.to_string());
+            }
+            None => self.drop_limit.remove(deps.storage),
+        }
+        Ok(res)
+    }
+
+    /// Tokens minted while the guard was off keep their index entries, so
+    /// turning it on also protects their content
+    pub fn set_unique_content(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        enabled: bool,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(deps.as_ref(), &info, Role::Admin)?;
+
+        self.unique_content.save(deps.storage, &enabled)?;
 
         Ok(Response::new()
-            .add_attribute("action", "approve")
+            .add_attribute("action", "set_unique_content")
             .add_attribute("sender", info.sender)
-            .add_attribute("spender", spender)
-            .add_attribute("token_id", token_id))
+            .add_attribute("enabled", enabled.to_string()))
     }
 
-    fn revoke(
+    pub fn toggle_minting(
         &self,
         deps: DepsMut,
-        env: Env,
+        _env: Env,
         info: MessageInfo,
-        spender: String,
-        token_id: String,
     ) -> Result<Response<C>, ContractError> {
-        self._update_approvals(deps, &env, &info, &spender, &token_id, false, None)?;
+        self.assert_role(deps.as_ref(), &info, Role::Admin)?;
+
+        let mint_allowed = !self.mint_allowed.load(deps.storage)?;
+        self.mint_allowed.save(deps.storage, &mint_allowed)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "toggle_minting")
+            .add_attribute("mint_allowed", mint_allowed.to_string()))
+    }
+
+    pub fn transfer_minter(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        new_minter: String,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_minter(deps.as_ref(), &info)?;
+
+        let new_minter = self.resolve_addr(deps.as_ref(), &new_minter)?;
+        if new_minter == info.sender {
+            self.pending_minter.remove(deps.storage);
+        } else {
+            self.pending_minter.save(deps.storage, &new_minter)?;
+        }
+
+        Ok(Response::new()
+            .add_attribute("action", "transfer_minter")
+            .add_attribute("sender", info.sender)
+            .add_attribute("new_minter", new_minter))
+    }
+
+    pub fn accept_minter(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+    ) -> Result<Response<C>, ContractError> {
+        let pending = self.pending_minter.may_load(deps.storage)?;
+        if pending.as_ref() != Some(&info.sender) {
+            return Err(ContractError::NotPendingMinter {});
+        }
+
+        let previous = self.minter.load(deps.storage)?;
+        self.minter.save(deps.storage, &info.sender)?;
+        self.pending_minter.remove(deps.storage);
+
+        Ok(Response::new()
+            .add_attribute("action", "accept_minter")
+            .add_attribute("previous_minter", previous)
+            .add_attribute("minter", info.sender))
+    }
+
+    pub fn grant_role(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        address: String,
+        role: Role,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_minter(deps.as_ref(), &info)?;
+
+        let addr = self.resolve_addr(deps.as_ref(), &address)?;
+        self.roles
+            .save(deps.storage, (&addr, role.key()), &Empty {})?;
+
+        Ok(Response::new()
+            .add_attribute("action", "grant_role")
+            .add_attribute("sender", info.sender)
+            .add_attribute("address", addr)
+            .add_attribute("role", role.key()))
+    }
+
+    pub fn revoke_role(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        address: String,
+        role: Role,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_minter(deps.as_ref(), &info)?;
+
+        let addr = self.resolve_addr(deps.as_ref(), &address)?;
+        self.roles.remove(deps.storage, (&addr, role.key()));
+
+        Ok(Response::new()
+            .add_attribute("action", "revoke_role")
+            .add_attribute("sender", info.sender)
+            .add_attribute("address", addr)
+            .add_attribute("role", role.key()))
+    }
+
+    pub fn pause(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(deps.as_ref(), &info, Role::Admin)?;
+
+        if self.paused.may_load(deps.storage)?.is_none() {
+            self.paused.save(deps.storage, &env.block.time)?;
+        }
+
+        Ok(Response::new()
+            .add_attribute("action", "pause")
+            .add_attribute("sender", info.sender))
+    }
+
+    pub fn unpause(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(deps.as_ref(), &info, Role::Admin)?;
+
+        self.paused.remove(deps.storage);
+
+        Ok(Response::new()
+            .add_attribute("action", "unpause")
+            .add_attribute("sender", info.sender))
+    }
+
+    pub fn set_name_resolver(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        resolver: Option<String>,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(deps.as_ref(), &info, Role::Admin)?;
+
+        match resolver {
+            Some(resolver) => {
+                let resolver = deps.api.addr_validate(&resolver)?;
+                self.name_resolver.save(deps.storage, &resolver)?;
+            }
+            None => self.name_resolver.remove(deps.storage),
+        }
+
+        Ok(Response::new()
+            .add_attribute("action", "set_name_resolver")
+            .add_attribute("sender", info.sender))
+    }
+
+    pub fn set_metadata_resolver(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        resolver: Option<String>,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(deps.as_ref(), &info, Role::Admin)?;
+
+        match resolver {
+            Some(resolver) => {
+                let resolver = deps.api.addr_validate(&resolver)?;
+                self.metadata_resolver.save(deps.storage, &resolver)?;
+            }
+            None => self.metadata_resolver.remove(deps.storage),
+        }
+
+        Ok(Response::new()
+            .add_attribute("action", "set_metadata_resolver")
+            .add_attribute("sender", info.sender))
+    }
+
+    pub fn set_size_limits(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        limits: SizeLimits,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(deps.as_ref(), &info, Role::Admin)?;
+        self.save_size_limits(deps.storage, &limits)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "set_size_limits")
+            .add_attribute("max_uri_length", limits.max_uri_length.to_string())
+            .add_attribute(
+                "max_extension_bytes",
+                limits.max_extension_bytes.to_string(),
+            )
+            .add_attribute(
+                "max_provenance_entry_length",
+                limits.max_provenance_entry_length.to_string(),
+            ))
+    }
+}
+
+/// Checks the funds cover price in its denom and returns the excess to refund,
+/// including any other coins sent along. A zero price needs no funds, all of
+/// them are refunded
+fn take_payment(info: &MessageInfo, price: &Coin) -> Result<Vec<Coin>, ContractError> {
+    if price.amount.is_zero() {
+        return Ok(info
+            .funds
+            .iter()
+            .filter(|coin| !coin.amount.is_zero())
+            .cloned()
+            .collect());
+    }
+    let sent = info
+        .funds
+        .iter()
+        .find(|coin| coin.denom == price.denom)
+        .ok_or(ContractError::WrongDenom {
+            denom: price.denom.clone(),
+        })?;
+    if sent.amount < price.amount {
+        return Err(ContractError::InsufficientPayment {
+            required: price.clone(),
+            sent: sent.clone(),
+        });
+    }
+
+    let mut excess: Vec<Coin> = info
+        .funds
+        .iter()
+        .filter(|coin| coin.denom != price.denom && !coin.amount.is_zero())
+        .cloned()
+        .collect();
+    if sent.amount > price.amount {
+        excess.push(Coin {
+            denom: price.denom.clone(),
+            amount: sent.amount - price.amount,
+        });
+    }
+    Ok(excess)
+}
+
+/// takes every coin of required out of the funds sent, returns the excess
+fn take_payments(info: &MessageInfo, required: &[Coin]) -> Result<Vec<Coin>, ContractError> {
+    let mut excess: Vec<Coin> = info
+        .funds
+        .iter()
+        .filter(|coin| !coin.amount.is_zero())
+        .cloned()
+        .collect();
+    for price in required.iter().filter(|price| !price.amount.is_zero()) {
+        let sent = excess
+            .iter_mut()
+            .find(|coin| coin.denom == price.denom)
+            .ok_or(ContractError::WrongDenom {
+                denom: price.denom.clone(),
+            })?;
+        if sent.amount < price.amount {
+            return Err(ContractError::InsufficientPayment {
+                required: price.clone(),
+                sent: sent.clone(),
+            });
+        }
+        sent.amount -= price.amount;
+    }
+    excess.retain(|coin| !coin.amount.is_zero());
+    Ok(excess)
+}
+
+// profiles
+impl<'a, T, C> Cw721Contract<'a, T, C>
+where
+    T: Serialize + DeserializeOwned + Clone,
+    C: CustomMsg,
+{
+    pub fn register_profile(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        display_name: String,
+        profile_uri: Option<String>,
+    ) -> Result<Response<C>, ContractError> {
+        let display_name = display_name.trim().to_string();
+        if display_name.is_empty() || display_name.chars().count() > MAX_DISPLAY_NAME_LENGTH {
+            return Err(ContractError::InvalidDisplayName {
+                max: MAX_DISPLAY_NAME_LENGTH,
+            });
+        }
+        if let Some(existing) = self.profiles.may_load(deps.storage, &info.sender)? {
+            if existing.suspended {
+                return Err(ContractError::ProfileSuspended {});
+            }
+        }
+
+        if let Some(profile_uri) = &profile_uri {
+            self.check_uri(deps.storage, profile_uri)?;
+        }
+
+        let profile = Profile {
+            display_name: display_name.clone(),
+            profile_uri,
+            suspended: false,
+        };
+        self.profiles.save(deps.storage, &info.sender, &profile)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "register_profile")
+            .add_attribute("sender", info.sender)
+            .add_attribute("display_name", display_name))
+    }
+
+    pub fn clear_profile(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+    ) -> Result<Response<C>, ContractError> {
+        let profile = self.profiles.load(deps.storage, &info.sender)?;
+        // suspended profiles stay in place so they cannot simply be re-registered
+        if profile.suspended {
+            return Err(ContractError::ProfileSuspended {});
+        }
+        self.profiles.remove(deps.storage, &info.sender);
+
+        Ok(Response::new()
+            .add_attribute("action", "clear_profile")
+            .add_attribute("sender", info.sender))
+    }
+
+    pub fn moderate_profile(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        address: String,
+        suspended: bool,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(deps.as_ref(), &info, Role::Admin)?;
+
+        let addr = self.resolve_addr(deps.as_ref(), &address)?;
+        let mut profile = self.profiles.load(deps.storage, &addr)?;
+        profile.suspended = suspended;
+        self.profiles.save(deps.storage, &addr, &profile)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "moderate_profile")
+            .add_attribute("sender", info.sender)
+            .add_attribute("address", address)
+            .add_attribute("suspended", suspended.to_string()))
+    }
+}
+
+// send allowlist
+impl<'a, T, C> Cw721Contract<'a, T, C>
+where
+    T: Serialize + DeserializeOwned + Clone,
+    C: CustomMsg,
+{
+    pub fn update_send_allowlist(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        enabled: Option<bool>,
+        add: Vec<String>,
+        remove: Vec<String>,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(deps.as_ref(), &info, Role::Admin)?;
+
+        if let Some(enabled) = enabled {
+            self.send_allowlist_enabled.save(deps.storage, &enabled)?;
+        }
+        for contract in &add {
+            let addr = self.resolve_addr(deps.as_ref(), contract)?;
+            self.send_allowlist.save(deps.storage, &addr, &Empty {})?;
+        }
+        for contract in &remove {
+            let addr = self.resolve_addr(deps.as_ref(), contract)?;
+            self.send_allowlist.remove(deps.storage, &addr);
+        }
+
+        Ok(Response::new()
+            .add_attribute("action", "update_send_allowlist")
+            .add_attribute("sender", info.sender)
+            .add_attribute("added", add.len().to_string())
+            .add_attribute("removed", remove.len().to_string()))
+    }
+
+    /// returns an error if the allowlist is enabled and the contract is not on it
+    pub fn check_send_allowed(&self, deps: Deps, contract: &Addr) -> Result<(), ContractError> {
+        let enabled = self
+            .send_allowlist_enabled
+            .may_load(deps.storage)?
+            .unwrap_or(false);
+        if !enabled {
+            return Ok(());
+        }
+        if !self.send_allowlist.has(deps.storage, contract) {
+            return Err(ContractError::SendTargetNotAllowed {
+                contract: contract.to_string(),
+            });
+        }
+        Ok(())
+    }
+}
+
+// installment purchases
+impl<'a, T, C> Cw721Contract<'a, T, C>
+where
+    T: Serialize + DeserializeOwned + Clone,
+    C: CustomMsg,
+{
+    pub fn set_installment_plan(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        plan: Option<InstallmentPlan>,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(deps.as_ref(), &info, Role::Admin)?;
+
+        match plan {
+            Some(plan) => {
+                if plan.installments == 0 || plan.interval == 0 || plan.forfeit_refund_bps > 10_000
+                {
+                    return Err(ContractError::InvalidInstallmentPlan {});
+                }
+                self.installment_plan.save(deps.storage, &plan)?;
+            }
+            None => self.installment_plan.remove(deps.storage),
+        }
+
+        Ok(Response::new()
+            .add_attribute("action", "set_installment_plan")
+            .add_attribute("sender", info.sender))
+    }
+
+    pub fn reserve_mint(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        msg: MintMsg<T>,
+    ) -> Result<Response<C>, ContractError> {
+        let plan = self
+            .installment_plan
+            .may_load(deps.storage)?
+            .ok_or(ContractError::InstallmentsDisabled {})?;
+        self.check_mint_available(deps.storage)?;
+        // installments are at the public price, not open during the allowlist phase
+        if self.allowlist_phase(deps.storage, &env.block)?.is_some() {
+            return Err(ContractError::NotAllowlisted {});
+        }
+        self._count_wallet_mint(deps.storage, &info.sender)?;
+        self._count_drop_acquisition(deps.storage, &info.sender)?;
+
+        let price = self.public_mint_price(deps.as_ref(), &env.block)?;
+        if plan.down_payment >= price.amount {
+            return Err(ContractError::InvalidInstallmentPlan {});
+        }
+        if must_pay(&info, &price.denom)? != plan.down_payment {
+            return Err(ContractError::IncorrectPayment {});
+        }
+
+        // the token is held by the contract until fully paid
+        self.check_token_uri(deps.as_ref(), &info, &msg.token_uri)?;
+        self.check_transferable_flag(deps.as_ref(), &info, msg.transferable)?;
+        self.check_unique_content(deps.storage, &msg.token_uri, &msg.content_hash)?;
+        let recipient = self.resolve_addr(deps.as_ref(), &msg.owner)?;
+        let referrer = self.referral_curator(deps.storage, &msg.referral)?;
+        let content = content_keys(&msg.token_uri, &msg.content_hash);
+        let token_id = self._create_token(
+            deps.storage,
+            &env,
+            env.contract.address.clone(),
+            &info.sender,
+            msg.token_uri,
+            msg.extension,
+        )?;
+        self._index_content(deps.storage, &token_id, content, msg.content_hash)?;
+        self._seal_if_mystery(deps.storage, &token_id)?;
+        self._set_transferable(deps.storage, &token_id, msg.transferable)?;
+        self._record_drop_mint(deps.storage, &env, &token_id)?;
+
+        let mut res = Response::new();
+        let down_payment = Coin {
+            denom: price.denom.clone(),
+            amount: plan.down_payment,
+        };
+        let withholding = self.withholding_for(deps.storage, &info.sender, &down_payment)?;
+        if let Some(withholding) = &withholding {
+            res = self.add_withholding(res, withholding, &price.denom);
+        }
+        // held for the buyer until the token is delivered
+        self._credit_treasury(
+            deps.storage,
+            TreasuryAccount::Reservations,
+            &price.denom,
+            plan.down_payment - withheld(&withholding),
+        )?;
+
+        let remaining = price.amount - plan.down_payment;
+        let installments = Uint128::from(plan.installments);
+        let reservation = Reservation {
+            buyer: info.sender.clone(),
+            recipient,
+            price,
+            paid: plan.down_payment,
+            // round up so the installments always cover the full price
+            installment_amount: (remaining + installments - Uint128::one()) / installments,
+            interval: plan.interval,
+            next_due: env.block.time.plus_seconds(plan.interval),
+            withholding,
+            grace_period: plan.grace_period,
+            late_fee: plan.late_fee,
+            forfeit_refund_bps: plan.forfeit_refund_bps,
+            late_fees_paid: Uint128::zero(),
+            referrer,
+        };
+        self.reservations
+            .save(deps.storage, &token_id, &reservation)?;
+        self._schedule_expiry(
+            deps.storage,
+            reservation.grace_until(),
+            installment_expiry(&token_id),
+        )?;
+
+        Ok(res
+            .add_attribute("action", "reserve_mint")
+            .add_attribute("buyer", info.sender)
+            .add_attribute("owner", msg.owner)
+            .add_attribute("token_id", token_id)
+            .add_attribute("next_due", reservation.next_due.to_string()))
+    }
+
+    pub fn pay_installment(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        token_id: String,
+    ) -> Result<Response<C>, ContractError> {
+        let mut reservation = self.reservations.load(deps.storage, &token_id)?;
+        let sent = must_pay(&info, &reservation.price.denom)?;
+
+        // late payments are accepted until the reservation is forfeited
+        let late_fee = reservation.late_fee_at(env.block.time);
+        let remaining = reservation.price.amount - reservation.paid;
+        let required = reservation.installment_amount.min(remaining) + late_fee;
+        if sent < required {
+            return Err(ContractError::InstallmentTooSmall { required });
+        }
+        if sent > remaining + late_fee {
+            return Err(ContractError::IncorrectPayment {});
+        }
+        let payment = sent - late_fee;
+        reservation.paid += payment;
+        reservation.late_fees_paid += late_fee;
+
+        let withheld = reservation
+            .withholding
+            .as_ref()
+            .map(|w| withholding_amount(w.rate_bps, payment))
+            .unwrap_or_default();
+        self._credit_treasury(
+            deps.storage,
+            TreasuryAccount::Reservations,
+            &reservation.price.denom,
+            payment - withheld,
+        )?;
+        // late fees are not refundable
+        self._credit_treasury(
+            deps.storage,
+            TreasuryAccount::Proceeds,
+            &reservation.price.denom,
+            late_fee,
+        )?;
+
+        let mut res = Response::new();
+        if let Some(withholding) = reservation.withholding.as_mut() {
+            let this_payment = Withholding {
+                amount: withheld,
+                ..withholding.clone()
+            };
+            res = self.add_withholding(res, &this_payment, &reservation.price.denom);
+            withholding.amount += withheld;
+        }
+        let res = res
+            .add_attribute("action", "pay_installment")
+            .add_attribute("sender", info.sender)
+            .add_attribute("token_id", token_id.clone())
+            .add_attribute("paid", reservation.paid)
+            .add_attribute("late_fee", late_fee);
+
+        if reservation.paid == reservation.price.amount {
+            // fully paid, release the token to its owner
+            self.check_not_frozen(deps.storage, &token_id)?;
+            let mut token = self.tokens.load(deps.storage, token_key(&token_id)?)?;
+            token.owner = reservation.recipient.clone();
+            self._track_transfer(
+                deps.storage,
+                &env,
+                &token_id,
+                &env.contract.address,
+                &token.owner,
+            )?;
+            self.tokens
+                .save(deps.storage, token_key(&token_id)?, &token)?;
+            self.reservations.remove(deps.storage, &token_id)?;
+            self._clear_expiry(deps.storage, &installment_expiry(&token_id))?;
+
+            // delivered, the installments are now sale proceeds
+            let mut res = res;
+            let net = reservation.price.amount
+                - reservation
+                    .withholding
+                    .as_ref()
+                    .map_or(Uint128::zero(), |w| w.amount);
+            self._debit_treasury(
+                deps.storage,
+                TreasuryAccount::Reservations,
+                &reservation.price.denom,
+                net,
+            )?;
+            self._credit_treasury(
+                deps.storage,
+                TreasuryAccount::Proceeds,
+                &reservation.price.denom,
+                net,
+            )?;
+            let curation =
+                self.curation_fee_for(deps.storage, reservation.referrer, &reservation.price, net)?;
+            if let Some(curation) = &curation {
+                self._debit_treasury(
+                    deps.storage,
+                    TreasuryAccount::Proceeds,
+                    &reservation.price.denom,
+                    curation.amount,
+                )?;
+                res =
+                    self.add_curation_fee(deps.storage, res, curation, &reservation.price.denom)?;
+            }
+            let receipt = SaleReceipt {
+                buyer: reservation.buyer,
+                price: reservation.price,
+                withholding: reservation.withholding,
+                proceeds_to: None,
+                curation,
+                fee_override: None,
+                marketplace_fee: None,
+            };
+            self._record_sale(deps.storage, &env, &token_id, &receipt)?;
+
+            return Ok(res
+                .add_attribute("completed", "true")
+                .add_attribute("owner", reservation.recipient));
+        }
+
+        let covered = (payment / reservation.installment_amount).u128() as u64;
+        reservation.next_due = reservation
+            .next_due
+            .plus_seconds(reservation.interval * covered);
+        self.reservations
+            .save(deps.storage, &token_id, &reservation)?;
+        self._schedule_expiry(
+            deps.storage,
+            reservation.grace_until(),
+            installment_expiry(&token_id),
+        )?;
+
+        Ok(res
+            .add_attribute("completed", "false")
+            .add_attribute("next_due", reservation.next_due.to_string()))
+    }
+
+    pub fn forfeit_reservation(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        token_id: String,
+    ) -> Result<Response<C>, ContractError> {
+        let reservation = self.reservations.load(deps.storage, &token_id)?;
+        if env.block.time <= reservation.grace_until() {
+            return Err(ContractError::ReservationNotOverdue {});
+        }
+        self.check_not_frozen(deps.storage, &token_id)?;
+
+        // the token returns to the seller
+        let minter = self.minter.load(deps.storage)?;
+        let mut token = self.tokens.load(deps.storage, token_key(&token_id)?)?;
+        token.owner = minter.clone();
+        self._track_transfer(
+            deps.storage,
+            &env,
+            &token_id,
+            &env.contract.address,
+            &token.owner,
+        )?;
+        self.tokens
+            .save(deps.storage, token_key(&token_id)?, &token)?;
+        self.reservations.remove(deps.storage, &token_id)?;
+        self._clear_expiry(deps.storage, &installment_expiry(&token_id))?;
+
+        // withheld amounts already left the contract, only the rest is split
+        let withheld = reservation
+            .withholding
+            .as_ref()
+            .map(|w| w.amount)
+            .unwrap_or_default();
+        let net = reservation.paid - withheld;
+        let refund = net.multiply_ratio(reservation.forfeit_refund_bps, 10_000u128);
+        // the seller keeps the rest of the escrowed payments
+        self._debit_treasury(
+            deps.storage,
+            TreasuryAccount::Reservations,
+            &reservation.price.denom,
+            net,
+        )?;
+        self._credit_treasury(
+            deps.storage,
+            TreasuryAccount::Proceeds,
+            &reservation.price.denom,
+            net - refund,
+        )?;
+
+        let mut res = Response::new()
+            .add_attribute("action", "forfeit_reservation")
+            .add_attribute("sender", info.sender.clone())
+            .add_attribute("token_id", token_id)
+            .add_attribute("buyer", reservation.buyer.clone())
+            .add_attribute("forfeited", reservation.paid - refund)
+            .add_attribute("refunded", refund);
+        if !refund.is_zero() {
+            res = res.add_message(BankMsg::Send {
+                to_address: reservation.buyer.into_string(),
+                amount: coins(refund.u128(), reservation.price.denom),
+            });
+        }
+        if info.sender == minter {
+            return Ok(res);
+        }
+        self._pay_keeper(deps.storage, &info.sender, res)
+    }
+}
+
+// physical destruction
+impl<'a, T, C> Cw721Contract<'a, T, C>
+where
+    T: Serialize + DeserializeOwned + Clone,
+    C: CustomMsg,
+{
+    pub fn destroy(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        token_id: String,
+        evidence_hash: String,
+        attestation: String,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(deps.as_ref(), &info, Role::Custodian)?;
+        self.check_provenance_field(deps.storage, &evidence_hash)?;
+        self.check_provenance_field(deps.storage, &attestation)?;
+
+        let token = self.tokens.load(deps.storage, token_key(&token_id)?)?;
+        // an auction, gift, trade-in, conditional transfer or bridge still
+        // owes the token to someone
+        let reservation = self.reservations.may_load(deps.storage, &token_id)?;
+        if token.owner == env.contract.address && reservation.is_none() {
+            return Err(ContractError::TokenEscrowed {});
+        }
+        let kind = RemovalKind::Destroyed {
+            evidence_hash: evidence_hash.clone(),
+            attestation,
+        };
+        self._remove_token(
+            deps.storage,
+            &env,
+            &token_id,
+            &token.owner,
+            kind,
+            &info.sender,
+        )?;
+
+        let mut res = Response::new()
+            .add_attribute("action", "destroy")
+            .add_attribute("sender", info.sender)
+            .add_attribute("owner", token.owner)
+            .add_attribute("token_id", token_id.clone())
+            .add_attribute("evidence_hash", evidence_hash);
+        // a destroyed work can no longer be paid off
+        if let Some(reservation) = reservation {
+            res = self._refund_reservation(deps.storage, &token_id, reservation, res)?;
+        }
+        Ok(res)
+    }
+
+    /// calls off the reservation of token_id, the buyer gets back what
+    /// they paid. Withheld amounts already left the contract
+    pub fn _refund_reservation(
+        &self,
+        storage: &mut dyn Storage,
+        token_id: &str,
+        reservation: Reservation,
+        res: Response<C>,
+    ) -> Result<Response<C>, ContractError> {
+        self.reservations.remove(storage, token_id)?;
+        self._clear_expiry(storage, &installment_expiry(token_id))?;
+        let withheld = reservation
+            .withholding
+            .as_ref()
+            .map(|w| w.amount)
+            .unwrap_or_default();
+        let refund = reservation.paid - withheld;
+        let mut res = res;
+        if !refund.is_zero() {
+            self._debit_treasury(
+                storage,
+                TreasuryAccount::Reservations,
+                &reservation.price.denom,
+                refund,
+            )?;
+            res = res.add_message(BankMsg::Send {
+                to_address: reservation.buyer.to_string(),
+                amount: coins(refund.u128(), reservation.price.denom),
+            });
+        }
+        Ok(res
+            .add_attribute("buyer", reservation.buyer)
+            .add_attribute("refunded", refund))
+    }
+}
+
+// attestations
+impl<'a, T, C> Cw721Contract<'a, T, C>
+where
+    T: Serialize + DeserializeOwned + Clone,
+    C: CustomMsg,
+{
+    pub fn add_attestor(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        address: String,
+        name: String,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(deps.as_ref(), &info, Role::Admin)?;
+
+        // re-adding keeps the reputation counters
+        let addr = self.resolve_addr(deps.as_ref(), &address)?;
+        let attestor = match self.attestors.may_load(deps.storage, &addr)? {
+            Some(existing) => Attestor {
+                name: name.clone(),
+                active: true,
+                ..existing
+            },
+            None => Attestor {
+                name: name.clone(),
+                active: true,
+                issued: 0,
+                revoked: 0,
+            },
+        };
+        self.attestors.save(deps.storage, &addr, &attestor)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "add_attestor")
+            .add_attribute("attestor", address)
+            .add_attribute("name", name))
+    }
+
+    pub fn remove_attestor(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        address: String,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(deps.as_ref(), &info, Role::Admin)?;
+
+        let addr = self.resolve_addr(deps.as_ref(), &address)?;
+        let mut attestor = self.attestors.load(deps.storage, &addr)?;
+        attestor.active = false;
+        self.attestors.save(deps.storage, &addr, &attestor)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "remove_attestor")
+            .add_attribute("attestor", address))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn attest(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        token_id: String,
+        kind: AttestationKind,
+        uri: String,
+        content_hash: Option<String>,
+    ) -> Result<Response<C>, ContractError> {
+        self.check_uri(deps.storage, &uri)?;
+        if let Some(content_hash) = &content_hash {
+            self.check_provenance_field(deps.storage, content_hash)?;
+        }
+        if let AttestationKind::Other { label } = &kind {
+            self.check_provenance_field(deps.storage, label)?;
+        }
+        let mut attestor = self
+            .attestors
+            .may_load(deps.storage, &info.sender)?
+            .filter(|a| a.active)
+            .ok_or(ContractError::Unauthorized {})?;
+        // only existing tokens can be attested
+        self.tokens.load(deps.storage, token_key(&token_id)?)?;
+
+        let index = self
+            .attestation_count
+            .may_load(deps.storage, &token_id)?
+            .unwrap_or_default();
+        let attestation = Attestation {
+            attestor: info.sender.clone(),
+            kind,
+            uri,
+            content_hash,
+            created_at: env.block.time,
+            revoked: false,
+        };
+        self.attestations
+            .save(deps.storage, (&token_id, index), &attestation)?;
+        self.attestation_count
+            .save(deps.storage, &token_id, &(index + 1))?;
+
+        attestor.issued += 1;
+        self.attestors.save(deps.storage, &info.sender, &attestor)?;
+        let event = format!(
+            "attest/{}/{}/{}/{}",
+            index,
+            info.sender,
+            attestation.uri,
+            attestation.content_hash.unwrap_or_default()
+        );
+        self._append_provenance(deps.storage, &env, &token_id, event)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "attest")
+            .add_attribute("attestor", info.sender)
+            .add_attribute("token_id", token_id)
+            .add_attribute("index", index.to_string()))
+    }
+
+    pub fn revoke_attestation(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        token_id: String,
+        index: u64,
+    ) -> Result<Response<C>, ContractError> {
+        let mut attestation = self.attestations.load(deps.storage, (&token_id, index))?;
+        if attestation.attestor != info.sender {
+            self.assert_role(deps.as_ref(), &info, Role::Admin)?;
+        }
+        if attestation.revoked {
+            return Err(ContractError::AttestationRevoked {});
+        }
+        attestation.revoked = true;
+        self.attestations
+            .save(deps.storage, (&token_id, index), &attestation)?;
+
+        let mut attestor = self.attestors.load(deps.storage, &attestation.attestor)?;
+        attestor.revoked += 1;
+        self.attestors
+            .save(deps.storage, &attestation.attestor, &attestor)?;
+        let event = format!("revoke_attestation/{}", index);
+        self._append_provenance(deps.storage, &env, &token_id, event)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "revoke_attestation")
+            .add_attribute("sender", info.sender)
+            .add_attribute("token_id", token_id)
+            .add_attribute("index", index.to_string()))
+    }
+}
+
+// exhibitions
+impl<'a, T, C> Cw721Contract<'a, T, C>
+where
+    T: Serialize + DeserializeOwned + Clone,
+    C: CustomMsg,
+{
+    #[allow(clippy::too_many_arguments)]
+    pub fn add_venue(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        address: String,
+        name: String,
+        pubkey: Binary,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(deps.as_ref(), &info, Role::Admin)?;
+        check_pubkey(&pubkey)?;
+
+        // re-adding keeps the exhibition counter
+        let addr = self.resolve_addr(deps.as_ref(), &address)?;
+        let exhibitions = self
+            .venues
+            .may_load(deps.storage, &addr)?
+            .map(|v| v.exhibitions)
+            .unwrap_or_default();
+        let venue = Venue {
+            name: name.clone(),
+            pubkey,
+            active: true,
+            exhibitions,
+        };
+        self.venues.save(deps.storage, &addr, &venue)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "add_venue")
+            .add_attribute("venue", address)
+            .add_attribute("name", name))
+    }
+
+    pub fn remove_venue(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        address: String,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(deps.as_ref(), &info, Role::Admin)?;
+
+        let addr = self.resolve_addr(deps.as_ref(), &address)?;
+        let mut venue = self.venues.load(deps.storage, &addr)?;
+        venue.active = false;
+        self.venues.save(deps.storage, &addr, &venue)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "remove_venue")
+            .add_attribute("venue", address))
+    }
+
+    /// Owner: record that a token was exhibited at a venue. `signature` is the
+    /// venue's secp256k1 signature over the sha256 of the JSON `ExhibitionSignDoc`
+    #[allow(clippy::too_many_arguments)]
+    pub fn log_exhibition(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        token_id: String,
+        venue: String,
+        exhibition: ExhibitionDetails,
+        signature: Binary,
+    ) -> Result<Response<C>, ContractError> {
+        let token = self.tokens.load(deps.storage, token_key(&token_id)?)?;
+        if token.owner != info.sender {
+            return Err(ContractError::Unauthorized {});
+        }
+        self.check_provenance_field(deps.storage, &exhibition.title)?;
+        if exhibition.end < exhibition.start {
+            return Err(ContractError::InvalidExhibition {});
+        }
+        let venue_addr = self.resolve_addr(deps.as_ref(), &venue)?;
+        let mut venue = self
+            .venues
+            .may_load(deps.storage, &venue_addr)?
+            .filter(|v| v.active)
+            .ok_or(ContractError::Unauthorized {})?;
+
+        let doc = ExhibitionSignDoc {
+            contract: env.contract.address.to_string(),
+            token_id: token_id.clone(),
+            venue: venue_addr.to_string(),
+            exhibition: exhibition.clone(),
+        };
+        let hash = Sha256::digest(to_json_vec(&doc)?);
+        let valid = deps
+            .api
+            .secp256k1_verify(&hash, &signature, &venue.pubkey)
+            .map_err(StdError::from)?;
+        if !valid {
+            return Err(ContractError::InvalidSignature {});
+        }
+        // a signature can only be used once
+        if self.exhibition_digests.has(deps.storage, &hash) {
+            return Err(ContractError::ExhibitionLogged {});
+        }
+        self.exhibition_digests
+            .save(deps.storage, &hash, &Empty {})?;
+
+        let index = self
+            .exhibition_count
+            .may_load(deps.storage, &token_id)?
+            .unwrap_or_default();
+        let event = format!(
+            "exhibit/{}/{}/{}/{}",
+            venue_addr,
+            exhibition.title,
+            exhibition.start.seconds(),
+            exhibition.end.seconds()
+        );
+        let record = Exhibition {
+            venue: venue_addr.clone(),
+            details: exhibition,
+            logged_by: info.sender.clone(),
+            logged_at: env.block.time,
+        };
+        self.exhibitions
+            .save(deps.storage, (&token_id, index), &record)?;
+        self.exhibition_count
+            .save(deps.storage, &token_id, &(index + 1))?;
+        venue.exhibitions += 1;
+        self.venues.save(deps.storage, &venue_addr, &venue)?;
+        self._append_provenance(deps.storage, &env, &token_id, event)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "log_exhibition")
+            .add_attribute("sender", info.sender)
+            .add_attribute("token_id", token_id)
+            .add_attribute("venue", venue_addr)
+            .add_attribute("index", index.to_string()))
+    }
+}
+
+// group exhibitions
+impl<'a, T, C> Cw721Contract<'a, T, C>
+where
+    T: Serialize + DeserializeOwned + Clone,
+    C: CustomMsg,
+{
+    pub fn register_event(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        details: ExhibitionDetails,
+    ) -> Result<Response<C>, ContractError> {
+        self.check_provenance_field(deps.storage, &details.title)?;
+        if details.end < details.start {
+            return Err(ContractError::InvalidExhibition {});
+        }
+
+        let event_id = self.event_count.may_load(deps.storage)?.unwrap_or_default() + 1;
+        self.event_count.save(deps.storage, &event_id)?;
+        let event = ExhibitionEvent {
+            curator: info.sender.clone(),
+            details,
+            confirmed_works: 0,
+            registered_at: env.block.time,
+        };
+        self.events.save(deps.storage, event_id, &event)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "register_event")
+            .add_attribute("curator", info.sender)
+            .add_attribute("event_id", event_id.to_string()))
+    }
+
+    pub fn confirm_event_work(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        event_id: u64,
+        collection: Option<String>,
+        token_id: String,
+    ) -> Result<Response<C>, ContractError> {
+        let mut event = self.events.load(deps.storage, event_id)?;
+        let collection = match collection {
+            Some(collection) => deps.api.addr_validate(&collection)?,
+            None => env.contract.address.clone(),
+        };
+        let owner = if collection == env.contract.address {
+            self.tokens.load(deps.storage, token_key(&token_id)?)?.owner
+        } else {
+            let res = crate::helpers::Cw721Contract(collection.clone()).owner_of(
+                &deps.querier,
+                token_id.clone(),
+                false,
+            )?;
+            deps.api.addr_validate(&res.owner)?
+        };
+
+        let key = (event_id, &collection, token_id.as_str());
+        let mut work = self
+            .event_works
+            .may_load(deps.storage, key)?
+            .unwrap_or_default();
+        if work.confirmed_at.is_some() {
+            return Err(ContractError::AlreadyConfirmed {});
+        }
+        // the curator may also be the owner
+        let mut authorized = false;
+        if info.sender == event.curator {
+            work.curator_confirmed = true;
+            authorized = true;
+        }
+        if info.sender == owner {
+            work.owner_confirmed = Some(owner.clone());
+            authorized = true;
+        }
+        if !authorized {
+            return Err(ContractError::Unauthorized {});
+        }
+
+        let complete = work.curator_confirmed && work.owner_confirmed.as_ref() == Some(&owner);
+        if complete {
+            work.confirmed_at = Some(env.block.time);
+            event.confirmed_works += 1;
+            self.events.save(deps.storage, event_id, &event)?;
+            if collection == env.contract.address {
+                let event_entry = format!(
+                    "event/{}/{}/{}/{}",
+                    event_id,
+                    event.details.title,
+                    event.details.start.seconds(),
+                    event.details.end.seconds()
+                );
+                self._append_provenance(deps.storage, &env, &token_id, event_entry)?;
+            }
+        }
+        self.event_works.save(deps.storage, key, &work)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "confirm_event_work")
+            .add_attribute("sender", info.sender)
+            .add_attribute("event_id", event_id.to_string())
+            .add_attribute("collection", collection)
+            .add_attribute("token_id", token_id)
+            .add_attribute("complete", complete.to_string()))
+    }
+}
+
+// sealed-bid auctions
+impl<'a, T, C> Cw721Contract<'a, T, C>
+where
+    T: Serialize + DeserializeOwned + Clone,
+    C: CustomMsg,
+{
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_sealed_auction(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        token_id: String,
+        reserve_price: Coin,
+        commit_duration: u64,
+        reveal_duration: u64,
+        deposit: Uint128,
+    ) -> Result<Response<C>, ContractError> {
+        if commit_duration == 0 || reveal_duration == 0 {
+            return Err(ContractError::InvalidDuration {});
+        }
+
+        let commit_end = env.block.time.plus_seconds(commit_duration);
+        let reveal_end = commit_end.plus_seconds(reveal_duration);
+        let format = AuctionFormat::SealedBid {
+            commit_end,
+            reveal_end,
+            deposit,
+            unrevealed_deposits: Uint128::zero(),
+        };
+        let (auction_id, seller) = self._open_auction(
+            deps,
+            &env,
+            &info,
+            &token_id,
+            reserve_price,
+            format,
+            reveal_end,
+        )?;
+
+        Ok(Response::new()
+            .add_attribute("action", "create_sealed_auction")
+            .add_attribute("seller", seller)
+            .add_attribute("token_id", token_id)
+            .add_attribute("auction_id", auction_id.to_string()))
+    }
+
+    /// escrows token_id in the contract and saves a new auction for it ending
+    /// at end, returns the auction id and seller
+    #[allow(clippy::too_many_arguments)]
+    fn _open_auction(
+        &self,
+        deps: DepsMut,
+        env: &Env,
+        info: &MessageInfo,
+        token_id: &str,
+        reserve_price: Coin,
+        format: AuctionFormat,
+        end: Timestamp,
+    ) -> Result<(u64, Addr), ContractError> {
+        let mut token = self.tokens.load(deps.storage, token_key(token_id)?)?;
+        self.check_can_list(deps.as_ref(), env, info, token_id, &token)?;
+        self.check_storage_fee(deps.storage, env, token_id)?;
+        self.check_price_floor(deps.storage, env, token_id, &reserve_price)?;
+        let seller = token.owner.clone();
+        token.owner = env.contract.address.clone();
+        self._track_transfer(deps.storage, env, token_id, &seller, &token.owner)?;
+        self._clear_approvals(deps.storage, token_id, &mut token)?;
+        self.tokens
+            .save(deps.storage, token_key(token_id)?, &token)?;
+
+        let auction = Auction {
+            token_id: token_id.to_string(),
+            seller: seller.clone(),
+            reserve_price,
+            format,
+            highest_bid: None,
+            settled: false,
+            beneficiary: None,
+            bid_count: 0,
+            fee_override: None,
+        };
+        let auction_id = self
+            .auction_count
+            .may_load(deps.storage)?
+            .unwrap_or_default()
+            + 1;
+        self.auction_count.save(deps.storage, &auction_id)?;
+        self.auctions.save(deps.storage, auction_id, &auction)?;
+        self._schedule_expiry(deps.storage, end, ExpiryKind::AuctionEnd { auction_id })?;
+        Ok((auction_id, seller))
+    }
+
+    pub fn commit_bid(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        auction_id: u64,
+        commitment: HexBinary,
+    ) -> Result<Response<C>, ContractError> {
+        let mut auction = self.auctions.load(deps.storage, auction_id)?;
+        let AuctionFormat::SealedBid {
+            commit_end,
+            deposit,
+            unrevealed_deposits,
+            ..
+        } = &mut auction.format
+        else {
+            return Err(ContractError::WrongAuctionFormat {});
+        };
+        if env.block.time >= *commit_end || info.sender == auction.seller {
+            return Err(ContractError::AuctionNotOpen {});
+        }
+        if must_pay(&info, &auction.reserve_price.denom)? != *deposit {
+            return Err(ContractError::IncorrectPayment {});
+        }
+        if self
+            .sealed_bids
+            .has(deps.storage, (auction_id, &info.sender))
+        {
+            return Err(ContractError::BidAlreadyCommitted {});
+        }
+
+        let bid = SealedBid {
+            commitment,
+            escrow: *deposit,
+            revealed_amount: None,
+        };
+        *unrevealed_deposits += *deposit;
+        self._credit_treasury(
+            deps.storage,
+            TreasuryAccount::AuctionEscrow,
+            &auction.reserve_price.denom,
+            *deposit,
+        )?;
+        self.sealed_bids
+            .save(deps.storage, (auction_id, &info.sender), &bid)?;
+        self.auctions.save(deps.storage, auction_id, &auction)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "commit_bid")
+            .add_attribute("bidder", info.sender)
+            .add_attribute("auction_id", auction_id.to_string()))
+    }
+
+    pub fn reveal_bid(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        auction_id: u64,
+        amount: Uint128,
+        salt: String,
+    ) -> Result<Response<C>, ContractError> {
+        let mut auction = self.auctions.load(deps.storage, auction_id)?;
+        let AuctionFormat::SealedBid {
+            commit_end,
+            reveal_end,
+            deposit,
+            unrevealed_deposits,
+        } = &mut auction.format
+        else {
+            return Err(ContractError::WrongAuctionFormat {});
+        };
+        if env.block.time < *commit_end || env.block.time >= *reveal_end {
+            return Err(ContractError::AuctionNotInRevealPhase {});
+        }
+
+        let mut bid = self
+            .sealed_bids
+            .load(deps.storage, (auction_id, &info.sender))?;
+        if bid.revealed_amount.is_some() {
+            return Err(ContractError::BidAlreadyRevealed {});
+        }
+        let preimage = format!("{}/{}/{}/{}", auction_id, info.sender, amount, salt);
+        if Sha256::digest(preimage.as_bytes()).as_slice() != bid.commitment.as_slice() {
+            return Err(ContractError::InvalidReveal {});
+        }
+        let topped_up = may_pay(&info, &auction.reserve_price.denom)?;
+        bid.escrow += topped_up;
+        if bid.escrow < amount {
+            return Err(ContractError::InsufficientFunds {});
+        }
+        bid.revealed_amount = Some(amount);
+        *unrevealed_deposits -= *deposit;
+        auction.bid_count += 1;
+        let record = BidRecord {
+            bidder: info.sender.clone(),
+            amount,
+            height: env.block.height,
+            time: env.block.time,
+        };
+        self.bid_history
+            .save(deps.storage, (auction_id, auction.bid_count), &record)?;
+
+        let denom = auction.reserve_price.denom.clone();
+        self._credit_treasury(
+            deps.storage,
+            TreasuryAccount::AuctionEscrow,
+            &denom,
+            topped_up,
+        )?;
+        let mut res = Response::new()
+            .add_attribute("action", "reveal_bid")
+            .add_attribute("bidder", info.sender.clone())
+            .add_attribute("auction_id", auction_id.to_string());
+
+        let leads = amount >= auction.reserve_price.amount
+            && auction
+                .highest_bid
+                .as_ref()
+                .is_none_or(|highest| amount > highest.amount);
+        if leads {
+            // the previous leader is outbid, refund their whole escrow
+            if let Some(previous) = auction.highest_bid.take() {
+                let previous_bid = self
+                    .sealed_bids
+                    .load(deps.storage, (auction_id, &previous.bidder))?;
+                self._debit_treasury(
+                    deps.storage,
+                    TreasuryAccount::AuctionEscrow,
+                    &denom,
+                    previous_bid.escrow,
+                )?;
+                res = res.add_message(BankMsg::Send {
+                    to_address: previous.bidder.into_string(),
+                    amount: coins(previous_bid.escrow.u128(), &denom),
+                });
+            }
+            auction.highest_bid = Some(Bid {
+                bidder: info.sender.clone(),
+                amount,
+            });
+        } else {
+            self._debit_treasury(
+                deps.storage,
+                TreasuryAccount::AuctionEscrow,
+                &denom,
+                bid.escrow,
+            )?;
+            res = res.add_message(BankMsg::Send {
+                to_address: info.sender.to_string(),
+                amount: coins(bid.escrow.u128(), &denom),
+            });
+        }
+
+        self.sealed_bids
+            .save(deps.storage, (auction_id, &info.sender), &bid)?;
+        self.auctions.save(deps.storage, auction_id, &auction)?;
+
+        Ok(res.add_attribute("leading", leads.to_string()))
+    }
+
+    /// settles an ended auction with the settlement of its format
+    pub fn finalize(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        auction_id: u64,
+    ) -> Result<Response<C>, ContractError> {
+        let auction = self.auctions.load(deps.storage, auction_id)?;
+        match auction.format {
+            AuctionFormat::SealedBid { .. } => {
+                self.settle_sealed_auction(deps, env, info, auction_id)
+            }
+            AuctionFormat::English { .. } => self.settle_auction(deps, env, info, auction_id),
+        }
+    }
+
+    pub fn settle_sealed_auction(
+        &self,
+        mut deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        auction_id: u64,
+    ) -> Result<Response<C>, ContractError> {
+        let mut auction = self.auctions.load(deps.storage, auction_id)?;
+        let AuctionFormat::SealedBid {
+            reveal_end,
+            unrevealed_deposits,
+            ..
+        } = auction.format.clone()
+        else {
+            return Err(ContractError::WrongAuctionFormat {});
+        };
+        if env.block.time < reveal_end {
+            return Err(ContractError::AuctionNotEnded {});
+        }
+        if auction.settled {
+            return Err(ContractError::AuctionSettled {});
+        }
+        self.check_not_frozen(deps.storage, &auction.token_id)?;
+        auction.settled = true;
+        self.auctions.save(deps.storage, auction_id, &auction)?;
+        self._clear_expiry(deps.storage, &ExpiryKind::AuctionEnd { auction_id })?;
+
+        let denom = auction.reserve_price.denom.clone();
+        let mut res = Response::new()
+            .add_attribute("action", "settle_sealed_auction")
+            .add_attribute("sender", info.sender.clone())
+            .add_attribute("auction_id", auction_id.to_string())
+            .add_attribute("token_id", auction.token_id.clone());
+
+        // unrevealed bids forfeit their deposits to the seller
+        let mut seller_proceeds = unrevealed_deposits;
+        // everything still escrowed for the auction is paid out
+        let mut released = unrevealed_deposits;
+        let mut token = self
+            .tokens
+            .load(deps.storage, token_key(&auction.token_id)?)?;
+        match auction.highest_bid {
+            Some(winner) => {
+                let bid = self
+                    .sealed_bids
+                    .load(deps.storage, (auction_id, &winner.bidder))?;
+                released += bid.escrow;
+                let change = bid.escrow - winner.amount;
+                if !change.is_zero() {
+                    res = res.add_message(BankMsg::Send {
+                        to_address: winner.bidder.to_string(),
+                        amount: coins(change.u128(), &denom),
+                    });
+                }
+
+                let price = Coin {
+                    denom: denom.clone(),
+                    amount: winner.amount,
+                };
+                let mut proceeds = winner.amount;
+                let withholding = self.withholding_for(deps.storage, &winner.bidder, &price)?;
+                if let Some(withholding) = &withholding {
+                    proceeds -= withholding.amount;
+                    res = self.add_withholding(res, withholding, &denom);
+                }
+                let curator = self.featured_curator(deps.storage, &auction.token_id)?;
+                let curation = self.curation_fee_for(deps.storage, curator, &price, proceeds)?;
+                if let Some(curation) = &curation {
+                    proceeds -= curation.amount;
+                    res = self.add_curation_fee(deps.storage, res, curation, &denom)?;
+                }
+                let fee_override = auction.fee_override.clone().unwrap_or_default();
+                let (fees_res, proceeds, marketplace_fee) = self._pay_sale_fees(
+                    &mut deps,
+                    &env,
+                    &auction.token_id,
+                    &token,
+                    &price,
+                    !fee_override.waive_royalties,
+                    !fee_override.waive_marketplace_fee,
+                    true,
+                    proceeds,
+                    res,
+                )?;
+                res = fees_res;
+                seller_proceeds += proceeds;
+                let receipt = SaleReceipt {
+                    buyer: winner.bidder.clone(),
+                    price,
+                    withholding,
+                    proceeds_to: auction.beneficiary.clone(),
+                    curation,
+                    fee_override: auction.fee_override.clone(),
+                    marketplace_fee,
+                };
+
+                token.owner = winner.bidder.clone();
+                self._track_transfer(
+                    deps.storage,
+                    &env,
+                    &auction.token_id,
+                    &env.contract.address,
+                    &token.owner,
+                )?;
+                self._record_sale(deps.storage, &env, &auction.token_id, &receipt)?;
+
+                res = res
+                    .add_attribute("winner", winner.bidder)
+                    .add_attribute("price", winner.amount);
+            }
+            None => {
+                token.owner = auction.seller.clone();
+                self._track_transfer(
+                    deps.storage,
+                    &env,
+                    &auction.token_id,
+                    &env.contract.address,
+                    &token.owner,
+                )?;
+            }
+        }
+        self.tokens
+            .save(deps.storage, token_key(&auction.token_id)?, &token)?;
+        self._debit_treasury(
+            deps.storage,
+            TreasuryAccount::AuctionEscrow,
+            &denom,
+            released,
+        )?;
+
+        if !seller_proceeds.is_zero() {
+            let payee = auction.beneficiary.unwrap_or(auction.seller);
+            res = res.add_message(BankMsg::Send {
+                to_address: payee.into_string(),
+                amount: coins(seller_proceeds.u128(), &denom),
+            });
+        }
+        self._pay_keeper(deps.storage, &info.sender, res)
+    }
+
+    /// Seller or a listing-approved spender: call off an auction nobody has
+    /// bid on yet, returning the token to the seller
+    pub fn cancel_sealed_auction(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        auction_id: u64,
+    ) -> Result<Response<C>, ContractError> {
+        let auction = self.auctions.load(deps.storage, auction_id)?;
+        if auction.settled {
+            return Err(ContractError::AuctionSettled {});
+        }
+        if auction.seller != info.sender
+            && !self.has_listing_approval(deps.as_ref(), &env, &auction.token_id, &info.sender)?
+        {
+            return Err(ContractError::Unauthorized {});
+        }
+        let has_bids = auction.highest_bid.is_some()
+            || self
+                .sealed_bids
+                .prefix(auction_id)
+                .keys_raw(deps.storage, None, None, Order::Ascending)
+                .next()
+                .is_some();
+        if has_bids {
+            return Err(ContractError::AuctionHasBids {});
+        }
+        self.check_not_frozen(deps.storage, &auction.token_id)?;
+
+        self.auctions.remove(deps.storage, auction_id);
+        self._clear_expiry(deps.storage, &ExpiryKind::AuctionEnd { auction_id })?;
+        let mut token = self
+            .tokens
+            .load(deps.storage, token_key(&auction.token_id)?)?;
+        token.owner = auction.seller.clone();
+        self.tokens
+            .save(deps.storage, token_key(&auction.token_id)?, &token)?;
+        self._track_transfer(
+            deps.storage,
+            &env,
+            &auction.token_id,
+            &env.contract.address,
+            &token.owner,
+        )?;
+
+        Ok(Response::new()
+            .add_attribute("action", "cancel_sealed_auction")
+            .add_attribute("sender", info.sender)
+            .add_attribute("auction_id", auction_id.to_string())
+            .add_attribute("token_id", auction.token_id))
+    }
+
+    pub fn set_auction_beneficiary(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        auction_id: u64,
+        beneficiary: Option<String>,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(deps.as_ref(), &info, Role::Admin)?;
+
+        let mut auction = self.auctions.load(deps.storage, auction_id)?;
+        if auction.settled {
+            return Err(ContractError::AuctionSettled {});
+        }
+        auction.beneficiary = beneficiary
+            .map(|addr| self.resolve_addr(deps.as_ref(), &addr))
+            .transpose()?;
+        self.auctions.save(deps.storage, auction_id, &auction)?;
+
+        let payee = auction.beneficiary.unwrap_or(auction.seller);
+        Ok(Response::new()
+            .add_attribute("action", "set_auction_beneficiary")
+            .add_attribute("sender", info.sender)
+            .add_attribute("auction_id", auction_id.to_string())
+            .add_attribute("beneficiary", payee))
+    }
+
+    pub fn set_auction_fee_override(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        auction_id: u64,
+        fee_override: Option<SaleFeeOverride>,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(deps.as_ref(), &info, Role::Admin)?;
+
+        let mut auction = self.auctions.load(deps.storage, auction_id)?;
+        if auction.settled {
+            return Err(ContractError::AuctionSettled {});
+        }
+        auction.fee_override = fee_override;
+        self.auctions.save(deps.storage, auction_id, &auction)?;
+
+        let fee_override = auction.fee_override.unwrap_or_default();
+        Ok(Response::new()
+            .add_attribute("action", "set_auction_fee_override")
+            .add_attribute("sender", info.sender)
+            .add_attribute("auction_id", auction_id.to_string())
+            .add_attribute("waive_royalties", fee_override.waive_royalties.to_string())
+            .add_attribute(
+                "waive_marketplace_fee",
+                fee_override.waive_marketplace_fee.to_string(),
+            ))
+    }
+}
+
+// english auctions
+impl<'a, T, C> Cw721Contract<'a, T, C>
+where
+    T: Serialize + DeserializeOwned + Clone,
+    C: CustomMsg,
+{
+    pub fn create_auction(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        token_id: String,
+        reserve_price: Coin,
+        duration: u64,
+    ) -> Result<Response<C>, ContractError> {
+        if duration == 0 {
+            return Err(ContractError::InvalidDuration {});
+        }
+
+        let end = env.block.time.plus_seconds(duration);
+        let format = AuctionFormat::English { end };
+        let (auction_id, seller) =
+            self._open_auction(deps, &env, &info, &token_id, reserve_price, format, end)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "create_auction")
+            .add_attribute("seller", seller)
+            .add_attribute("token_id", token_id)
+            .add_attribute("auction_id", auction_id.to_string())
+            .add_attribute("end", end.seconds().to_string()))
+    }
+
+    pub fn place_bid(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        auction_id: u64,
+    ) -> Result<Response<C>, ContractError> {
+        self._place_bid(deps, env, info, auction_id, false)
+    }
+
+    pub fn place_max_bid(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        auction_id: u64,
+    ) -> Result<Response<C>, ContractError> {
+        self._place_bid(deps, env, info, auction_id, true)
+    }
+
+    /// bids the funds sent or, by proxy, up to them. A standing maximum
+    /// answers lower bids with the smallest amount above them and wins ties.
+    /// The leader's whole maximum stays escrowed until outbid or settled
+    fn _place_bid(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        auction_id: u64,
+        proxy: bool,
+    ) -> Result<Response<C>, ContractError> {
+        let mut auction = self.auctions.load(deps.storage, auction_id)?;
+        let AuctionFormat::English { end } = auction.format else {
+            return Err(ContractError::WrongAuctionFormat {});
+        };
+        if env.block.time >= end || info.sender == auction.seller {
+            return Err(ContractError::AuctionNotOpen {});
+        }
+        let denom = auction.reserve_price.denom.clone();
+        let sent = must_pay(&info, &denom)?;
+        let beats_highest = auction
+            .highest_bid
+            .as_ref()
+            .is_none_or(|highest| sent > highest.amount);
+        if sent < auction.reserve_price.amount || !beats_highest {
+            return Err(ContractError::BidTooLow {});
+        }
+        self._credit_treasury(deps.storage, TreasuryAccount::AuctionEscrow, &denom, sent)?;
+
+        let action = if proxy { "place_max_bid" } else { "place_bid" };
+        let mut res = Response::new()
+            .add_attribute("action", action)
+            .add_attribute("bidder", info.sender.clone())
+            .add_attribute("auction_id", auction_id.to_string());
+        if !proxy {
+            res = res.add_attribute("amount", sent);
+        }
+
+        let standing = self.proxy_bids.may_load(deps.storage, auction_id)?;
+        let new_max = proxy.then(|| ProxyBid {
+            bidder: info.sender.clone(),
+            max: sent,
+        });
+        // bids made, in order, and what is escrowed for a bid that no
+        // longer leads
+        let mut history = vec![];
+        let mut refund = None;
+        let (leader, max) = match auction.highest_bid.take() {
+            None => {
+                let amount = match proxy {
+                    true => auction.reserve_price.amount,
+                    false => sent,
+                };
+                history.push((info.sender.clone(), amount));
+                (
+                    Bid {
+                        bidder: info.sender.clone(),
+                        amount,
+                    },
+                    new_max,
+                )
+            }
+            // raising one's own bid only replaces its escrow
+            Some(leader) if leader.bidder == info.sender => {
+                let escrowed = standing.map_or(leader.amount, |standing| standing.max);
+                if sent <= escrowed {
+                    return Err(ContractError::BidTooLow {});
+                }
+                refund = Some((leader.bidder.clone(), escrowed));
+                let amount = match proxy {
+                    true => leader.amount,
+                    false => sent,
+                };
+                if !proxy {
+                    history.push((info.sender.clone(), amount));
+                }
+                (
+                    Bid {
+                        bidder: info.sender.clone(),
+                        amount,
+                    },
+                    new_max,
+                )
+            }
+            Some(leader) => {
+                let escrowed = standing
+                    .as_ref()
+                    .map_or(leader.amount, |standing| standing.max);
+                if sent <= escrowed {
+                    // only a standing maximum can hold the lead against a
+                    // higher bid
+                    if !proxy {
+                        history.push((info.sender.clone(), sent));
+                    }
+                    let amount = (sent + Uint128::one()).min(escrowed);
+                    history.push((leader.bidder.clone(), amount));
+                    refund = Some((info.sender.clone(), sent));
+                    (
+                        Bid {
+                            bidder: leader.bidder,
+                            amount,
+                        },
+                        standing,
+                    )
+                } else {
+                    let amount = match proxy {
+                        true => escrowed + Uint128::one(),
+                        false => sent,
+                    };
+                    history.push((info.sender.clone(), amount));
+                    refund = Some((leader.bidder, escrowed));
+                    (
+                        Bid {
+                            bidder: info.sender.clone(),
+                            amount,
+                        },
+                        new_max,
+                    )
+                }
+            }
+        };
+        for (bidder, amount) in history {
+            self._record_bid(
+                deps.storage,
+                &env,
+                auction_id,
+                &mut auction,
+                &bidder,
+                amount,
+            )?;
+        }
+        if let Some((bidder, amount)) = refund {
+            self._debit_treasury(deps.storage, TreasuryAccount::AuctionEscrow, &denom, amount)?;
+            res = res.add_message(BankMsg::Send {
+                to_address: bidder.into_string(),
+                amount: coins(amount.u128(), &denom),
+            });
+        }
+        match max {
+            Some(max) => self.proxy_bids.save(deps.storage, auction_id, &max)?,
+            None => self.proxy_bids.remove(deps.storage, auction_id),
+        }
+        res = res
+            .add_attribute("leader", leader.bidder.clone())
+            .add_attribute("highest_bid", leader.amount);
+        auction.highest_bid = Some(leader);
+
+        // a late bid gives the others time to answer it
+        let extended_end = env.block.time.plus_seconds(AUCTION_EXTENSION_WINDOW);
+        if extended_end > end {
+            auction.format = AuctionFormat::English { end: extended_end };
+            self._schedule_expiry(
+                deps.storage,
+                extended_end,
+                ExpiryKind::AuctionEnd { auction_id },
+            )?;
+            res = res.add_attribute("end", extended_end.seconds().to_string());
+        }
+        self.auctions.save(deps.storage, auction_id, &auction)?;
+
+        Ok(res)
+    }
+
+    /// adds a bid to the auction's public history
+    fn _record_bid(
+        &self,
+        storage: &mut dyn Storage,
+        env: &Env,
+        auction_id: u64,
+        auction: &mut Auction,
+        bidder: &Addr,
+        amount: Uint128,
+    ) -> StdResult<()> {
+        auction.bid_count += 1;
+        let record = BidRecord {
+            bidder: bidder.clone(),
+            amount,
+            height: env.block.height,
+            time: env.block.time,
+        };
+        self.bid_history
+            .save(storage, (auction_id, auction.bid_count), &record)
+    }
+
+    pub fn settle_auction(
+        &self,
+        mut deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        auction_id: u64,
+    ) -> Result<Response<C>, ContractError> {
+        let mut auction = self.auctions.load(deps.storage, auction_id)?;
+        let AuctionFormat::English { end } = auction.format else {
+            return Err(ContractError::WrongAuctionFormat {});
+        };
+        if env.block.time < end {
+            return Err(ContractError::AuctionNotEnded {});
+        }
+        if auction.settled {
+            return Err(ContractError::AuctionSettled {});
+        }
+        self.check_not_frozen(deps.storage, &auction.token_id)?;
+        auction.settled = true;
+        self.auctions.save(deps.storage, auction_id, &auction)?;
+        self._clear_expiry(deps.storage, &ExpiryKind::AuctionEnd { auction_id })?;
+
+        let denom = auction.reserve_price.denom.clone();
+        let mut res = Response::new()
+            .add_attribute("action", "settle_auction")
+            .add_attribute("sender", info.sender.clone())
+            .add_attribute("auction_id", auction_id.to_string())
+            .add_attribute("token_id", auction.token_id.clone());
+
+        let mut token = self
+            .tokens
+            .load(deps.storage, token_key(&auction.token_id)?)?;
+        match auction.highest_bid {
+            Some(winner) => {
+                // only the winning bid is still escrowed, all of its maximum
+                // when bid by proxy
+                let escrowed = match self.proxy_bids.may_load(deps.storage, auction_id)? {
+                    Some(standing) => {
+                        self.proxy_bids.remove(deps.storage, auction_id);
+                        res = res.add_attribute("max_bid", standing.max);
+                        standing.max
+                    }
+                    None => winner.amount,
+                };
+                self._debit_treasury(
+                    deps.storage,
+                    TreasuryAccount::AuctionEscrow,
+                    &denom,
+                    escrowed,
+                )?;
+                let change = escrowed - winner.amount;
+                if !change.is_zero() {
+                    res = res.add_message(BankMsg::Send {
+                        to_address: winner.bidder.to_string(),
+                        amount: coins(change.u128(), &denom),
+                    });
+                }
+                let price = Coin {
+                    denom: denom.clone(),
+                    amount: winner.amount,
+                };
+                let mut proceeds = winner.amount;
+                let withholding = self.withholding_for(deps.storage, &winner.bidder, &price)?;
+                if let Some(withholding) = &withholding {
+                    proceeds -= withholding.amount;
+                    res = self.add_withholding(res, withholding, &denom);
+                }
+                let curator = self.featured_curator(deps.storage, &auction.token_id)?;
+                let curation = self.curation_fee_for(deps.storage, curator, &price, proceeds)?;
+                if let Some(curation) = &curation {
+                    proceeds -= curation.amount;
+                    res = self.add_curation_fee(deps.storage, res, curation, &denom)?;
+                }
+
+                // settlement cannot be allowed to fail, so the fees are
+                // capped at what is left
+                let fee_override = auction.fee_override.clone().unwrap_or_default();
+                let (fees_res, proceeds, marketplace_fee) = self._pay_sale_fees(
+                    &mut deps,
+                    &env,
+                    &auction.token_id,
+                    &token,
+                    &price,
+                    !fee_override.waive_royalties,
+                    !fee_override.waive_marketplace_fee,
+                    true,
+                    proceeds,
+                    res,
+                )?;
+                res = fees_res;
+
+                let receipt = SaleReceipt {
+                    buyer: winner.bidder.clone(),
+                    price,
+                    withholding,
+                    proceeds_to: auction.beneficiary.clone(),
+                    curation,
+                    fee_override: auction.fee_override.clone(),
+                    marketplace_fee,
+                };
+                token.owner = winner.bidder.clone();
+                self._track_transfer(
+                    deps.storage,
+                    &env,
+                    &auction.token_id,
+                    &env.contract.address,
+                    &token.owner,
+                )?;
+                self._record_sale(deps.storage, &env, &auction.token_id, &receipt)?;
+
+                if !proceeds.is_zero() {
+                    let payee = auction.beneficiary.unwrap_or(auction.seller);
+                    res = res.add_message(BankMsg::Send {
+                        to_address: payee.into_string(),
+                        amount: coins(proceeds.u128(), &denom),
+                    });
+                }
+                res = res
+                    .add_attribute("winner", winner.bidder)
+                    .add_attribute("price", winner.amount);
+            }
+            None => {
+                token.owner = auction.seller.clone();
+                self._track_transfer(
+                    deps.storage,
+                    &env,
+                    &auction.token_id,
+                    &env.contract.address,
+                    &token.owner,
+                )?;
+            }
+        }
+        self.tokens
+            .save(deps.storage, token_key(&auction.token_id)?, &token)?;
+
+        self._pay_keeper(deps.storage, &info.sender, res)
+    }
+}
+
+// collection bids
+impl<'a, T, C> Cw721Contract<'a, T, C>
+where
+    T: Serialize + DeserializeOwned + Clone,
+    C: CustomMsg,
+{
+    pub fn place_collection_bid(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        price: Coin,
+        quantity: u64,
+    ) -> Result<Response<C>, ContractError> {
+        if price.amount.is_zero() || quantity == 0 {
+            return Err(ContractError::InvalidCollectionBid {});
+        }
+        // the whole quantity is escrowed up front
+        let escrow = price
+            .amount
+            .checked_mul(Uint128::from(quantity))
+            .map_err(StdError::from)?;
+        if must_pay(&info, &price.denom)? != escrow {
+            return Err(ContractError::IncorrectPayment {});
+        }
+
+        let bid = CollectionBid {
+            bidder: info.sender.clone(),
+            price,
+            remaining: quantity,
+            filled: 0,
+            created_at: env.block.time,
+        };
+        let bid_id = self
+            .collection_bid_count
+            .may_load(deps.storage)?
+            .unwrap_or_default()
+            + 1;
+        self.collection_bid_count.save(deps.storage, &bid_id)?;
+        self.collection_bids.save(deps.storage, bid_id, &bid)?;
+        self._credit_treasury(
+            deps.storage,
+            TreasuryAccount::CollectionBids,
+            &bid.price.denom,
+            escrow,
+        )?;
+
+        Ok(Response::new()
+            .add_attribute("action", "place_collection_bid")
+            .add_attribute("sender", info.sender)
+            .add_attribute("bid_id", bid_id.to_string())
+            .add_attribute("price", bid.price.to_string())
+            .add_attribute("quantity", quantity.to_string()))
+    }
+
+    /// Sells one token into a collection bid. The sender must be able to
+    /// transfer or list the token, the proceeds go to its owner
+    pub fn accept_collection_bid(
+        &self,
+        mut deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        bid_id: u64,
+        token_id: String,
+    ) -> Result<Response<C>, ContractError> {
+        let mut bid = self.collection_bids.load(deps.storage, bid_id)?;
+        let mut token = self.tokens.load(deps.storage, token_key(&token_id)?)?;
+        self.check_can_list(deps.as_ref(), &env, &info, &token_id, &token)?;
+        self.check_storage_fee(deps.storage, &env, &token_id)?;
+        self.check_price_floor(deps.storage, &env, &token_id, &bid.price)?;
+        let seller = token.owner.clone();
+        if seller == bid.bidder {
+            return Err(ContractError::Unauthorized {});
+        }
+        self._count_drop_resale(deps.storage, &env, &bid.bidder, &token_id)?;
+        token.owner = bid.bidder.clone();
+        self._clear_approvals(deps.storage, &token_id, &mut token)?;
+        self.tokens
+            .save(deps.storage, token_key(&token_id)?, &token)?;
+        self._track_transfer(deps.storage, &env, &token_id, &seller, &token.owner)?;
+
+        bid.remaining -= 1;
+        bid.filled += 1;
+        if bid.remaining == 0 {
+            self.collection_bids.remove(deps.storage, bid_id);
+        } else {
+            self.collection_bids.save(deps.storage, bid_id, &bid)?;
+        }
+
+        self._debit_treasury(
+            deps.storage,
+            TreasuryAccount::CollectionBids,
+            &bid.price.denom,
+            bid.price.amount,
+        )?;
+        let mut res = Response::new();
+        let mut proceeds = bid.price.amount;
+        let withholding = self.withholding_for(deps.storage, &bid.bidder, &bid.price)?;
+        if let Some(withholding) = &withholding {
+            proceeds -= withholding.amount;
+            res = self.add_withholding(res, withholding, &bid.price.denom);
+        }
+        let curator = self.featured_curator(deps.storage, &token_id)?;
+        let curation = self.curation_fee_for(deps.storage, curator, &bid.price, proceeds)?;
+        if let Some(curation) = &curation {
+            proceeds -= curation.amount;
+            res = self.add_curation_fee(deps.storage, res, curation, &bid.price.denom)?;
+        }
+        let (mut res, proceeds, marketplace_fee) = self._pay_sale_fees(
+            &mut deps, &env, &token_id, &token, &bid.price, true, true, false, proceeds, res,
+        )?;
+        let receipt = SaleReceipt {
+            buyer: bid.bidder.clone(),
+            price: bid.price.clone(),
+            withholding,
+            proceeds_to: None,
+            curation,
+            fee_override: None,
+            marketplace_fee,
+        };
+        self._record_sale(deps.storage, &env, &token_id, &receipt)?;
+        if !proceeds.is_zero() {
+            res = res.add_message(BankMsg::Send {
+                to_address: seller.to_string(),
+                amount: coins(proceeds.u128(), &bid.price.denom),
+            });
+        }
+
+        Ok(res
+            .add_attribute("action", "accept_collection_bid")
+            .add_attribute("sender", info.sender)
+            .add_attribute("bid_id", bid_id.to_string())
+            .add_attribute("token_id", token_id)
+            .add_attribute("seller", seller)
+            .add_attribute("buyer", bid.bidder)
+            .add_attribute("remaining", bid.remaining.to_string()))
+    }
+
+    pub fn cancel_collection_bid(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        bid_id: u64,
+    ) -> Result<Response<C>, ContractError> {
+        let bid = self.collection_bids.load(deps.storage, bid_id)?;
+        if bid.bidder != info.sender {
+            return Err(ContractError::Unauthorized {});
+        }
+        self.collection_bids.remove(deps.storage, bid_id);
+
+        // only the unfilled part is still escrowed
+        let refund = bid.price.amount * Uint128::from(bid.remaining);
+        self._debit_treasury(
+            deps.storage,
+            TreasuryAccount::CollectionBids,
+            &bid.price.denom,
+            refund,
+        )?;
+        Ok(Response::new()
+            .add_message(BankMsg::Send {
+                to_address: bid.bidder.to_string(),
+                amount: coins(refund.u128(), bid.price.denom),
+            })
+            .add_attribute("action", "cancel_collection_bid")
+            .add_attribute("sender", info.sender)
+            .add_attribute("bid_id", bid_id.to_string())
+            .add_attribute("refund", refund))
+    }
+}
+
+// fixed-price marketplace
+impl<'a, T, C> Cw721Contract<'a, T, C>
+where
+    T: Serialize + DeserializeOwned + Clone,
+    C: CustomMsg,
+{
+    pub fn list_for_sale(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        token_id: String,
+        price: Coin,
+        royalty_waiver: Option<u64>,
+    ) -> Result<Response<C>, ContractError> {
+        let token = self.tokens.load(deps.storage, token_key(&token_id)?)?;
+        self.check_can_list(deps.as_ref(), &env, &info, &token_id, &token)?;
+        if price.amount.is_zero() {
+            return Err(ContractError::InvalidListing {});
+        }
+        self.check_storage_fee(deps.storage, &env, &token_id)?;
+        self.check_price_floor(deps.storage, &env, &token_id, &price)?;
+        if let Some(waiver_id) = royalty_waiver {
+            if !self.waiver_applies(deps.storage, waiver_id, &token.owner, &token_id)? {
+                return Err(ContractError::RoyaltyWaiverNotApplicable {});
+            }
+        }
+
+        let first_refusal_until =
+            self.first_refusal_until(deps.storage, &env, &token_id, &token.owner)?;
+        let listing = Listing {
+            seller: token.owner,
+            price: price.clone(),
+            listed_at: env.block.time,
+            royalty_waiver,
+            fiat_price: None,
+            fee_override: None,
+            first_refusal_until,
+        };
+        self.listings.save(deps.storage, &token_id, &listing)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "list_for_sale")
+            .add_attribute("sender", info.sender)
+            .add_attribute("token_id", token_id)
+            .add_attribute("price", price.to_string()))
+    }
+
+    pub fn cancel_listing(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        token_id: String,
+    ) -> Result<Response<C>, ContractError> {
+        let listing = self.listings.load(deps.storage, &token_id)?;
+        if listing.seller != info.sender {
+            let token = self.tokens.load(deps.storage, token_key(&token_id)?)?;
+            self.check_can_list(deps.as_ref(), &env, &info, &token_id, &token)?;
+        }
+        self.listings.remove(deps.storage, &token_id);
+
+        Ok(Response::new()
+            .add_attribute("action", "cancel_listing")
+            .add_attribute("sender", info.sender)
+            .add_attribute("token_id", token_id))
+    }
+
+    pub fn set_listing_fiat_price(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        token_id: String,
+        fiat_price: Option<FiatPrice>,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(deps.as_ref(), &info, Role::PriceOracle)?;
+        if let Some(price) = &fiat_price {
+            let code = price.currency.as_bytes();
+            if code.len() != 3 || !code.iter().all(u8::is_ascii_uppercase) {
+                return Err(ContractError::InvalidCurrencyCode {});
+            }
+        }
+
+        let mut listing = self.listings.load(deps.storage, &token_id)?;
+        let display = fiat_price
+            .as_ref()
+            .map(|price| format!("{} {}", price.amount, price.currency))
+            .unwrap_or_default();
+        listing.fiat_price = fiat_price.map(|price| ListingFiatPrice {
+            price,
+            updated_at: env.block.time,
+        });
+        self.listings.save(deps.storage, &token_id, &listing)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "set_listing_fiat_price")
+            .add_attribute("sender", info.sender)
+            .add_attribute("token_id", token_id)
+            .add_attribute("fiat_price", display))
+    }
+
+    pub fn set_listing_fee_override(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        token_id: String,
+        fee_override: Option<SaleFeeOverride>,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(deps.as_ref(), &info, Role::Admin)?;
+
+        let mut listing = self.listings.load(deps.storage, &token_id)?;
+        listing.fee_override = fee_override;
+        self.listings.save(deps.storage, &token_id, &listing)?;
+
+        let fee_override = listing.fee_override.unwrap_or_default();
+        Ok(Response::new()
+            .add_attribute("action", "set_listing_fee_override")
+            .add_attribute("sender", info.sender)
+            .add_attribute("token_id", token_id)
+            .add_attribute("waive_royalties", fee_override.waive_royalties.to_string())
+            .add_attribute(
+                "waive_marketplace_fee",
+                fee_override.waive_marketplace_fee.to_string(),
+            ))
+    }
+
+    pub fn buy_token(
+        &self,
+        mut deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        token_id: String,
+    ) -> Result<Response<C>, ContractError> {
+        let listing = self.listings.load(deps.storage, &token_id)?;
+        let excess = take_payment(&info, &listing.price)?;
+        let seller = listing.seller.clone();
+        let price = listing.price.clone();
+
+        let mut res = Response::new();
+        if !excess.is_empty() {
+            res = res.add_message(BankMsg::Send {
+                to_address: info.sender.to_string(),
+                amount: excess,
+            });
+        }
+        let res = self._sell_listing(&mut deps, &env, &token_id, listing, &info.sender, res)?;
+
+        Ok(res
+            .add_attribute("action", "buy_token")
+            .add_attribute("sender", info.sender)
+            .add_attribute("token_id", token_id)
+            .add_attribute("seller", seller)
+            .add_attribute("price", price.to_string()))
+    }
+
+    pub fn buy_bundle(
+        &self,
+        mut deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        token_ids: Vec<String>,
+        companions: Vec<CompanionPurchase>,
+    ) -> Result<Response<C>, ContractError> {
+        check_batch_size(token_ids.len())?;
+        check_batch_size(token_ids.len() + companions.len())?;
+
+        let listings = token_ids
+            .iter()
+            .map(|token_id| self.listings.load(deps.storage, token_id))
+            .collect::<StdResult<Vec<_>>>()?;
+        let mut required: Vec<Coin> = vec![];
+        let prices = listings.iter().map(|listing| &listing.price);
+        for coin in prices.chain(companions.iter().flat_map(|c| &c.funds)) {
+            match required.iter_mut().find(|c| c.denom == coin.denom) {
+                Some(total) => total.amount += coin.amount,
+                None => required.push(coin.clone()),
+            }
+        }
+        let excess = take_payments(&info, &required)?;
+
+        let mut res = Response::new();
+        if !excess.is_empty() {
+            res = res.add_message(BankMsg::Send {
+                to_address: info.sender.to_string(),
+                amount: excess,
+            });
+        }
+        for (token_id, listing) in token_ids.iter().zip(listings) {
+            res = self._sell_listing(&mut deps, &env, token_id, listing, &info.sender, res)?;
+        }
+        for companion in companions {
+            let contract = deps.api.addr_validate(&companion.contract)?;
+            if contract == env.contract.address {
+                return Err(ContractError::Unauthorized {});
+            }
+            let msg = WasmMsg::Execute {
+                contract_addr: contract.to_string(),
+                msg: companion.msg,
+                funds: companion.funds,
+            };
+            let pending = PendingReply::BundleCompanion {
+                buyer: info.sender.clone(),
+                contract,
+            };
+            let id = self._expect_reply(deps.storage, &pending)?;
+            res = res.add_submessage(SubMsg::reply_always(msg, id));
+        }
+
+        Ok(res
+            .add_attribute("action", "buy_bundle")
+            .add_attribute("sender", info.sender)
+            .add_attribute("token_ids", token_ids.join(",")))
+    }
+
+    /// finishes a companion purchase of a bundle checkout. A failed one
+    /// fails the checkout, undoing the listings bought with it
+    pub fn _finish_companion_purchase(
+        &self,
+        buyer: Addr,
+        contract: Addr,
+        result: SubMsgResult,
+    ) -> Result<Response<C>, ContractError> {
+        if let SubMsgResult::Err(error) = result {
+            return Err(ContractError::CompanionPurchaseFailed {
+                contract: contract.into_string(),
+                error,
+            });
+        }
+        Ok(Response::new()
+            .add_attribute("action", "companion_purchase")
+            .add_attribute("buyer", buyer)
+            .add_attribute("contract", contract))
+    }
+
+    pub fn set_lender(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        lender: Option<String>,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(deps.as_ref(), &info, Role::Admin)?;
+        match &lender {
+            Some(lender) => {
+                let lender = deps.api.addr_validate(lender)?;
+                self.lender.save(deps.storage, &lender)?;
+            }
+            None => self.lender.remove(deps.storage),
+        }
+
+        Ok(Response::new()
+            .add_attribute("action", "set_lender")
+            .add_attribute("sender", info.sender)
+            .add_attribute("lender", lender.unwrap_or_else(|| "none".to_string())))
+    }
+
+    /// asks the lender to pay in the loan, the sale completes on its reply
+    pub fn buy_with_loan(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        token_id: String,
+        loan: Coin,
+    ) -> Result<Response<C>, ContractError> {
+        let lender = self
+            .lender
+            .may_load(deps.storage)?
+            .ok_or(ContractError::NoLender {})?;
+        self.check_send_allowed(deps.as_ref(), &lender)?;
+        let listing = self.listings.load(deps.storage, &token_id)?;
+        let price = listing.price;
+        if loan.denom != price.denom || loan.amount.is_zero() || loan.amount > price.amount {
+            return Err(ContractError::InvalidLoan {});
+        }
+        let down_payment = Coin {
+            denom: price.denom.clone(),
+            amount: price.amount - loan.amount,
+        };
+        let excess = take_payment(&info, &down_payment)?;
+
+        // the down payment is already in the balance
+        let balance = deps
+            .querier
+            .query_balance(&env.contract.address, &price.denom)?;
+        let open_loan = WasmMsg::Execute {
+            contract_addr: lender.to_string(),
+            msg: to_json_binary(&LenderExecuteMsg::OpenLoan {
+                borrower: info.sender.to_string(),
+                token_id: token_id.clone(),
+                amount: loan.clone(),
+            })?,
+            funds: vec![],
+        };
+        let pending = PendingReply::FinancedPurchase {
+            buyer: info.sender.clone(),
+            token_id: token_id.clone(),
+            price: price.clone(),
+            loan: loan.clone(),
+            funded: balance.amount + loan.amount,
+        };
+        let id = self._expect_reply(deps.storage, &pending)?;
+
+        let mut res = Response::new();
+        if !excess.is_empty() {
+            res = res.add_message(BankMsg::Send {
+                to_address: info.sender.to_string(),
+                amount: excess,
+            });
+        }
+        Ok(res
+            .add_submessage(SubMsg::reply_on_success(open_loan, id))
+            .add_attribute("action", "buy_with_loan")
+            .add_attribute("sender", info.sender)
+            .add_attribute("token_id", token_id)
+            .add_attribute("lender", lender)
+            .add_attribute("price", price.to_string())
+            .add_attribute("loan", loan.to_string()))
+    }
+
+    /// sells token_id to buyer once the lender paid in the loan, and sends
+    /// the token on to the lender as collateral
+    #[allow(clippy::too_many_arguments)]
+    pub fn _finish_financed_purchase(
+        &self,
+        mut deps: DepsMut,
+        env: Env,
+        buyer: Addr,
+        token_id: String,
+        price: Coin,
+        loan: Coin,
+        funded: Uint128,
+    ) -> Result<Response<C>, ContractError> {
+        // without the loan in the balance the sale would be paid out of
+        // other escrows
+        let balance = deps
+            .querier
+            .query_balance(&env.contract.address, &price.denom)?;
+        if balance.amount < funded {
+            return Err(ContractError::LoanNotFunded {});
+        }
+        // the lender may have been called back into the contract
+        let listing = self.listings.load(deps.storage, &token_id)?;
+        if listing.price != price {
+            return Err(ContractError::StaleListing {});
+        }
+        let lender = self.lender.load(deps.storage)?;
+        let seller = listing.seller.clone();
+        let res =
+            self._sell_listing(&mut deps, &env, &token_id, listing, &buyer, Response::new())?;
+
+        let info = MessageInfo {
+            sender: buyer.clone(),
+            funds: vec![],
+        };
+        self._transfer_nft(deps.branch(), &env, &info, lender.as_str(), &token_id)?;
+        let collateral = Cw721ReceiveMsg {
+            sender: buyer.to_string(),
+            token_id: token_id.clone(),
+            msg: to_json_binary(&LoanCollateralMsg {
+                borrower: buyer.to_string(),
+            })?,
+        };
+
+        Ok(res
+            .add_message(collateral.into_cosmos_msg(lender.clone())?)
+            .add_attribute("action", "financed_purchase")
+            .add_attribute("buyer", buyer)
+            .add_attribute("token_id", token_id)
+            .add_attribute("seller", seller)
+            .add_attribute("lender", lender)
+            .add_attribute("price", price.to_string())
+            .add_attribute("loan", loan.to_string()))
+    }
+
+    /// sells token_id to buyer, who paid the listing price, paying
+    /// withholding, curation, royalties and the marketplace fee out of it
+    /// and the rest to the seller
+    fn _sell_listing(
+        &self,
+        deps: &mut DepsMut,
+        env: &Env,
+        token_id: &str,
+        listing: Listing,
+        buyer: &Addr,
+        mut res: Response<C>,
+    ) -> Result<Response<C>, ContractError> {
+        let price = listing.price;
+        let seller = listing.seller;
+        if seller == *buyer {
+            return Err(ContractError::Unauthorized {});
+        }
+        if let Some(until) = listing.first_refusal_until {
+            let artist = self.token_artists.may_load(deps.storage, token_id)?;
+            if env.block.time < until && artist.as_ref() != Some(buyer) {
+                return Err(ContractError::FirstRefusalPending { until });
+            }
+        }
+        self.check_storage_fee(deps.storage, env, token_id)?;
+        self.check_price_floor(deps.storage, env, token_id, &price)?;
+        self._count_drop_resale(deps.storage, env, buyer, token_id)?;
+
+        // _track_transfer drops the listing
+        let mut token = self.tokens.load(deps.storage, token_key(token_id)?)?;
+        if token.owner != seller {
+            return Err(ContractError::StaleListing {});
+        }
+        self.check_not_frozen(deps.storage, token_id)?;
+        self.check_transferable(deps.storage, token_id)?;
+        token.owner = buyer.clone();
+        self._clear_approvals(deps.storage, token_id, &mut token)?;
+        self.tokens
+            .save(deps.storage, token_key(token_id)?, &token)?;
+        self._track_transfer(deps.storage, env, token_id, &seller, &token.owner)?;
+
+        let mut proceeds = price.amount;
+        let withholding = self.withholding_for(deps.storage, buyer, &price)?;
+        if let Some(withholding) = &withholding {
+            proceeds -= withholding.amount;
+            res = self.add_withholding(res, withholding, &price.denom);
+        }
+        let curator = self.featured_curator(deps.storage, token_id)?;
+        let curation = self.curation_fee_for(deps.storage, curator, &price, proceeds)?;
+        if let Some(curation) = &curation {
+            proceeds -= curation.amount;
+            res = self.add_curation_fee(deps.storage, res, curation, &price.denom)?;
+        }
+
+        let fee_override = listing.fee_override.clone().unwrap_or_default();
+        let waiver = match listing.royalty_waiver {
+            // the seller may have given it away or spent it since listing,
+            // and keeps it when the admin waived royalties anyway
+            Some(waiver_id)
+                if !fee_override.waive_royalties
+                    && self.waiver_applies(deps.storage, waiver_id, &seller, token_id)? =>
+            {
+                self._spend_royalty_waiver(deps.storage, waiver_id, &seller)?;
+                res = res.add_attribute("royalty_waiver", waiver_id.to_string());
+                Some(waiver_id)
+            }
+            _ => None,
+        };
+        let (mut res, proceeds, marketplace_fee) = self._pay_sale_fees(
+            deps,
+            env,
+            token_id,
+            &token,
+            &price,
+            waiver.is_none() && !fee_override.waive_royalties,
+            !fee_override.waive_marketplace_fee,
+            false,
+            proceeds,
+            res,
+        )?;
+
+        let receipt = SaleReceipt {
+            buyer: buyer.clone(),
+            price: price.clone(),
+            withholding,
+            proceeds_to: None,
+            curation,
+            fee_override: listing.fee_override,
+            marketplace_fee,
+        };
+        self._record_sale(deps.storage, env, token_id, &receipt)?;
+        if !proceeds.is_zero() {
+            res = res.add_message(BankMsg::Send {
+                to_address: seller.to_string(),
+                amount: coins(proceeds.u128(), &price.denom),
+            });
+        }
+        Ok(res)
+    }
+
+    /// pays the royalties and the marketplace fee of a sale at price (each
+    /// unless waived) out of proceeds, returns what is left for the seller
+    /// and the marketplace fee charged. Settlements that cannot be allowed
+    /// to fail pass capped, which cuts the fees down to what is left rather
+    /// than erroring
+    #[allow(clippy::too_many_arguments)]
+    fn _pay_sale_fees(
+        &self,
+        deps: &mut DepsMut,
+        env: &Env,
+        token_id: &str,
+        token: &TokenInfo<T>,
+        price: &Coin,
+        pay_royalties: bool,
+        pay_marketplace_fee: bool,
+        capped: bool,
+        mut proceeds: Uint128,
+        mut res: Response<C>,
+    ) -> Result<(Response<C>, Uint128, Option<Coin>), ContractError> {
+        // royalty and marketplace shares are of the full price
+        let mut payouts = vec![];
+        if pay_royalties {
+            for (recipient, amount) in
+                self.sale_royalties(deps.as_ref(), env, token_id, token, price.amount)?
+            {
+                payouts.push((recipient, amount, "royalty"));
+            }
+        }
+        let fee = match pay_marketplace_fee {
+            true => self.marketplace_fee.may_load(deps.storage)?,
+            false => None,
+        };
+        if let Some(fee) = fee {
+            let amount = price.amount.multiply_ratio(fee.share_bps, 10_000u128);
+            payouts.push((fee.recipient, amount, "marketplace_fee"));
+        }
+        let mut marketplace_fee = None;
+        for (recipient, amount, kind) in payouts {
+            let amount = match capped {
+                true => amount.min(proceeds),
+                false => amount,
+            };
+            if amount.is_zero() {
+                continue;
+            }
+            proceeds = proceeds
+                .checked_sub(amount)
+                .map_err(|_| ContractError::SaleFeesExceedPrice {})?;
+            let payment = Coin {
+                denom: price.denom.clone(),
+                amount,
+            };
+            res = match kind {
+                "royalty" => self._pay_royalty(deps.storage, &recipient, payment, res)?,
+                _ => {
+                    marketplace_fee = Some(payment.clone());
+                    res.add_message(BankMsg::Send {
+                        to_address: recipient.to_string(),
+                        amount: vec![payment],
+                    })
+                }
+            };
+            res = res.add_attribute(kind, format!("{}/{}", recipient, amount));
+        }
+        Ok((res, proceeds, marketplace_fee))
+    }
+
+    pub fn issue_royalty_waiver(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        holder: String,
+    ) -> Result<Response<C>, ContractError> {
+        if !self.artist_stats.has(deps.storage, &info.sender) {
+            return Err(ContractError::Unauthorized {});
+        }
+        let issued = self
+            .waivers_issued
+            .may_load(deps.storage, &info.sender)?
+            .unwrap_or_default();
+        if issued >= MAX_ROYALTY_WAIVERS {
+            return Err(ContractError::TooManyRoyaltyWaivers {
+                max: MAX_ROYALTY_WAIVERS,
+            });
+        }
+        self.waivers_issued
+            .save(deps.storage, &info.sender, &(issued + 1))?;
+
+        let holder = self.resolve_addr(deps.as_ref(), &holder)?;
+        let waiver_id = self
+            .royalty_waiver_count
+            .may_load(deps.storage)?
+            .unwrap_or_default()
+            + 1;
+        self.royalty_waiver_count.save(deps.storage, &waiver_id)?;
+        let waiver = RoyaltyWaiver {
+            artist: info.sender.clone(),
+            holder: holder.clone(),
+            serial: issued + 1,
+            issued_at: env.block.time,
+        };
+        self.royalty_waivers
+            .save(deps.storage, waiver_id, &waiver)?;
+        self.holder_waivers
+            .save(deps.storage, (&holder, waiver_id), &Empty {})?;
+
+        Ok(Response::new()
+            .add_attribute("action", "issue_royalty_waiver")
+            .add_attribute("artist", info.sender)
+            .add_attribute("holder", holder)
+            .add_attribute("waiver_id", waiver_id.to_string()))
+    }
+
+    pub fn transfer_royalty_waiver(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        waiver_id: u64,
+        recipient: String,
+    ) -> Result<Response<C>, ContractError> {
+        let mut waiver = self.royalty_waivers.load(deps.storage, waiver_id)?;
+        if waiver.holder != info.sender {
+            return Err(ContractError::Unauthorized {});
+        }
+        let recipient = self.resolve_addr(deps.as_ref(), &recipient)?;
+        self.holder_waivers
+            .remove(deps.storage, (&waiver.holder, waiver_id));
+        self.holder_waivers
+            .save(deps.storage, (&recipient, waiver_id), &Empty {})?;
+        waiver.holder = recipient.clone();
+        self.royalty_waivers
+            .save(deps.storage, waiver_id, &waiver)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "transfer_royalty_waiver")
+            .add_attribute("sender", info.sender)
+            .add_attribute("recipient", recipient)
+            .add_attribute("waiver_id", waiver_id.to_string()))
+    }
+
+    /// whether waiver_id is held by seller and from the artist of token_id
+    pub fn waiver_applies(
+        &self,
+        storage: &dyn Storage,
+        waiver_id: u64,
+        seller: &Addr,
+        token_id: &str,
+    ) -> StdResult<bool> {
+        let waiver = match self.royalty_waivers.may_load(storage, waiver_id)? {
+            Some(waiver) => waiver,
+            None => return Ok(false),
+        };
+        let artist = self.token_artists.may_load(storage, token_id)?;
+        Ok(waiver.holder == *seller && artist.as_ref() == Some(&waiver.artist))
+    }
+
+    fn _spend_royalty_waiver(
+        &self,
+        storage: &mut dyn Storage,
+        waiver_id: u64,
+        holder: &Addr,
+    ) -> StdResult<()> {
+        self.royalty_waivers.remove(storage, waiver_id);
+        self.holder_waivers.remove(storage, (holder, waiver_id));
+        Ok(())
+    }
+
+    pub fn set_marketplace_fee(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        fee: Option<MarketplaceFeeConfig>,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(deps.as_ref(), &info, Role::Admin)?;
+
+        match fee {
+            Some(fee) => {
+                if fee.share_bps == 0 || fee.share_bps > 10_000 {
+                    return Err(ContractError::InvalidMarketplaceFee {});
+                }
+                let fee = MarketplaceFee {
+                    recipient: self.resolve_addr(deps.as_ref(), &fee.recipient)?,
+                    share_bps: fee.share_bps,
+                };
+                self.marketplace_fee.save(deps.storage, &fee)?;
+            }
+            None => self.marketplace_fee.remove(deps.storage),
+        }
+
+        Ok(Response::new()
+            .add_attribute("action", "set_marketplace_fee")
+            .add_attribute("sender", info.sender))
+    }
+}
+
+// offers
+impl<'a, T, C> Cw721Contract<'a, T, C>
+where
+    T: Serialize + DeserializeOwned + Clone,
+    C: CustomMsg,
+{
+    pub fn make_offer(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        token_id: String,
+    ) -> Result<Response<C>, ContractError> {
+        let token = self.tokens.load(deps.storage, token_key(&token_id)?)?;
+        if token.owner == info.sender {
+            return Err(ContractError::Unauthorized {});
+        }
+        if self.offers.has(deps.storage, (&token_id, &info.sender)) {
+            return Err(ContractError::OfferExists {});
+        }
+        let price = one_coin(&info)?;
+
+        let offer = Offer {
+            price: price.clone(),
+            created_at: env.block.time,
+        };
+        self.offers
+            .save(deps.storage, (&token_id, &info.sender), &offer)?;
+        self.bidder_offers
+            .save(deps.storage, (&info.sender, &token_id), &Empty {})?;
+        self._credit_treasury(
+            deps.storage,
+            TreasuryAccount::Offers,
+            &price.denom,
+            price.amount,
+        )?;
+
+        Ok(Response::new()
+            .add_attribute("action", "make_offer")
+            .add_attribute("bidder", info.sender)
+            .add_attribute("token_id", token_id)
+            .add_attribute("price", price.to_string()))
+    }
+
+    pub fn withdraw_offer(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        token_id: String,
+    ) -> Result<Response<C>, ContractError> {
+        let offer = self.offers.load(deps.storage, (&token_id, &info.sender))?;
+        // an accepted offer is committed while the artist may match it
+        if let Some(refusal) = self.first_refusals.may_load(deps.storage, &token_id)? {
+            if refusal.bidder == info.sender {
+                return Err(ContractError::FirstRefusalPending {
+                    until: refusal.until,
+                });
+            }
+        }
+        self._remove_offer(deps.storage, &token_id, &info.sender, &offer)?;
+
+        Ok(Response::new()
+            .add_message(BankMsg::Send {
+                to_address: info.sender.to_string(),
+                amount: vec![offer.price.clone()],
+            })
+            .add_attribute("action", "withdraw_offer")
+            .add_attribute("bidder", info.sender)
+            .add_attribute("token_id", token_id)
+            .add_attribute("refund", offer.price.to_string()))
+    }
+
+    pub fn accept_offer(
+        &self,
+        mut deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        token_id: String,
+        bidder: String,
+    ) -> Result<Response<C>, ContractError> {
+        let bidder = self.resolve_addr(deps.as_ref(), &bidder)?;
+        let offer = self.offers.load(deps.storage, (&token_id, &bidder))?;
+        let price = offer.price.clone();
+        let mut token = self.tokens.load(deps.storage, token_key(&token_id)?)?;
+        self.check_can_list(deps.as_ref(), &env, &info, &token_id, &token)?;
+        self.check_storage_fee(deps.storage, &env, &token_id)?;
+        self.check_price_floor(deps.storage, &env, &token_id, &price)?;
+        let seller = token.owner.clone();
+        if seller == bidder {
+            return Err(ContractError::Unauthorized {});
+        }
+        // an artist bidding on their own work has nothing to match
+        let artist = self.token_artists.may_load(deps.storage, &token_id)?;
+        let refusal_until = match artist {
+            Some(artist) if artist == bidder => None,
+            _ => self.first_refusal_until(deps.storage, &env, &token_id, &seller)?,
+        };
+        if let Some(until) = refusal_until {
+            // the offer stays escrowed, and the token with it, until the
+            // artist matches it or the window closes
+            token.owner = env.contract.address.clone();
+            self._clear_approvals(deps.storage, &token_id, &mut token)?;
+            self.tokens
+                .save(deps.storage, token_key(&token_id)?, &token)?;
+            self._track_transfer(deps.storage, &env, &token_id, &seller, &token.owner)?;
+            let refusal = FirstRefusal {
+                seller: seller.clone(),
+                bidder: bidder.clone(),
+                price: price.clone(),
+                until,
+            };
+            self.first_refusals
+                .save(deps.storage, &token_id, &refusal)?;
+
+            return Ok(Response::new()
+                .add_attribute("action", "accept_offer")
+                .add_attribute("sender", info.sender)
+                .add_attribute("token_id", token_id)
+                .add_attribute("seller", seller)
+                .add_attribute("buyer", bidder)
+                .add_attribute("price", price.to_string())
+                .add_attribute("first_refusal_until", until.seconds().to_string()));
+        }
+        self._remove_offer(deps.storage, &token_id, &bidder, &offer)?;
+        let res =
+            self._complete_sale(&mut deps, &env, &token_id, token, &seller, &bidder, &price)?;
+
+        Ok(res
+            .add_attribute("action", "accept_offer")
+            .add_attribute("sender", info.sender)
+            .add_attribute("token_id", token_id)
+            .add_attribute("seller", seller)
+            .add_attribute("buyer", bidder)
+            .add_attribute("price", price.to_string()))
+    }
+
+    /// moves token_id to buyer, who paid price, paying withholding, curation,
+    /// royalties and the marketplace fee out of it and the rest to seller
+    #[allow(clippy::too_many_arguments)]
+    fn _complete_sale(
+        &self,
+        deps: &mut DepsMut,
+        env: &Env,
+        token_id: &str,
+        mut token: TokenInfo<T>,
+        seller: &Addr,
+        buyer: &Addr,
+        price: &Coin,
+    ) -> Result<Response<C>, ContractError> {
+        self._count_drop_resale(deps.storage, env, buyer, token_id)?;
+        let from = token.owner.clone();
+        token.owner = buyer.clone();
+        self._clear_approvals(deps.storage, token_id, &mut token)?;
+        self.tokens
+            .save(deps.storage, token_key(token_id)?, &token)?;
+        self._track_transfer(deps.storage, env, token_id, &from, &token.owner)?;
+
+        let mut res = Response::new();
+        let mut proceeds = price.amount;
+        let withholding = self.withholding_for(deps.storage, buyer, price)?;
+        if let Some(withholding) = &withholding {
+            proceeds -= withholding.amount;
+            res = self.add_withholding(res, withholding, &price.denom);
+        }
+        let curator = self.featured_curator(deps.storage, token_id)?;
+        let curation = self.curation_fee_for(deps.storage, curator, price, proceeds)?;
+        if let Some(curation) = &curation {
+            proceeds -= curation.amount;
+            res = self.add_curation_fee(deps.storage, res, curation, &price.denom)?;
+        }
+        let (mut res, proceeds, marketplace_fee) = self._pay_sale_fees(
+            deps, env, token_id, &token, price, true, true, false, proceeds, res,
+        )?;
+
+        let receipt = SaleReceipt {
+            buyer: buyer.clone(),
+            price: price.clone(),
+            withholding,
+            proceeds_to: None,
+            curation,
+            fee_override: None,
+            marketplace_fee,
+        };
+        self._record_sale(deps.storage, env, token_id, &receipt)?;
+        if !proceeds.is_zero() {
+            res = res.add_message(BankMsg::Send {
+                to_address: seller.to_string(),
+                amount: coins(proceeds.u128(), &price.denom),
+            });
+        }
+        Ok(res)
+    }
+
+    /// drops an offer and releases its escrow, the caller sends the funds on
+    fn _remove_offer(
+        &self,
+        storage: &mut dyn Storage,
+        token_id: &str,
+        bidder: &Addr,
+        offer: &Offer,
+    ) -> Result<(), ContractError> {
+        self.offers.remove(storage, (token_id, bidder));
+        self.bidder_offers.remove(storage, (bidder, token_id));
+        self._debit_treasury(
+            storage,
+            TreasuryAccount::Offers,
+            &offer.price.denom,
+            offer.price.amount,
+        )?;
+        Ok(())
+    }
+}
+
+// auto-buy budgets
+impl<'a, T, C> Cw721Contract<'a, T, C>
+where
+    T: Serialize + DeserializeOwned + Clone,
+    C: CustomMsg,
+{
+    pub fn fund_auto_buy(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        rules: AutoBuyRules,
+    ) -> Result<Response<C>, ContractError> {
+        let deposit = one_coin(&info)?;
+        let auto_buyer = match self.auto_buyers.may_load(deps.storage, &info.sender)? {
+            Some(mut auto_buyer) => {
+                if auto_buyer.budget.denom != deposit.denom {
+                    return Err(ContractError::WrongDenom {
+                        denom: auto_buyer.budget.denom,
+                    });
+                }
+                auto_buyer.budget.amount += deposit.amount;
+                if auto_buyer.rules != rules {
+                    auto_buyer.rules = rules;
+                    auto_buyer.since = env.block.time;
+                }
+                auto_buyer
+            }
+            None => AutoBuyer {
+                budget: deposit.clone(),
+                rules,
+                since: env.block.time,
+            },
+        };
+        self.auto_buyers
+            .save(deps.storage, &info.sender, &auto_buyer)?;
+        self._credit_treasury(
+            deps.storage,
+            TreasuryAccount::AutoBuy,
+            &deposit.denom,
+            deposit.amount,
+        )?;
+
+        Ok(Response::new()
+            .add_attribute("action", "fund_auto_buy")
+            .add_attribute("collector", info.sender)
+            .add_attribute("deposit", deposit.to_string())
+            .add_attribute("budget", auto_buyer.budget.to_string()))
+    }
+
+    pub fn withdraw_auto_buy(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+    ) -> Result<Response<C>, ContractError> {
+        let auto_buyer = self.auto_buyers.load(deps.storage, &info.sender)?;
+        self.auto_buyers.remove(deps.storage, &info.sender);
+        let budget = auto_buyer.budget;
+        self._debit_treasury(
+            deps.storage,
+            TreasuryAccount::AutoBuy,
+            &budget.denom,
+            budget.amount,
+        )?;
+
+        Ok(Response::new()
+            .add_message(BankMsg::Send {
+                to_address: info.sender.to_string(),
+                amount: vec![budget.clone()],
+            })
+            .add_attribute("action", "withdraw_auto_buy")
+            .add_attribute("collector", info.sender)
+            .add_attribute("refund", budget.to_string()))
+    }
+
+    pub fn auto_buy(
+        &self,
+        mut deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        collector: String,
+        token_id: String,
+    ) -> Result<Response<C>, ContractError> {
+        let collector = self.resolve_addr(deps.as_ref(), &collector)?;
+        let mut auto_buyer = self.auto_buyers.load(deps.storage, &collector)?;
+        let listing = self.listings.load(deps.storage, &token_id)?;
+        let price = listing.price.clone();
+        let seller = listing.seller.clone();
+        let token = self.tokens.load(deps.storage, token_key(&token_id)?)?;
+        let artwork = self.artwork(&token.extension)?;
+        if listing.listed_at < auto_buyer.since
+            || price.denom != auto_buyer.budget.denom
+            || price.amount > auto_buyer.rules.max_price
+            || !auto_buyer.rules.matches(artwork.as_ref())
+        {
+            return Err(ContractError::AutoBuyRulesNotMet {});
+        }
+        if price.amount > auto_buyer.budget.amount {
+            return Err(ContractError::AutoBuyBudgetExceeded {
+                balance: auto_buyer.budget.amount,
+            });
+        }
+
+        // an emptied budget goes with its rules
+        auto_buyer.budget.amount -= price.amount;
+        if auto_buyer.budget.amount.is_zero() {
+            self.auto_buyers.remove(deps.storage, &collector);
+        } else {
+            self.auto_buyers
+                .save(deps.storage, &collector, &auto_buyer)?;
+        }
+        self._debit_treasury(
+            deps.storage,
+            TreasuryAccount::AutoBuy,
+            &price.denom,
+            price.amount,
+        )?;
+        let res = self
+            ._sell_listing(
+                &mut deps,
+                &env,
+                &token_id,
+                listing,
+                &collector,
+                Response::new(),
+            )?
+            .add_attribute("action", "auto_buy")
+            .add_attribute("sender", info.sender.clone())
+            .add_attribute("collector", collector.clone())
+            .add_attribute("token_id", token_id)
+            .add_attribute("seller", seller)
+            .add_attribute("price", price.to_string())
+            .add_attribute("budget", auto_buyer.budget.to_string());
+        if info.sender == collector {
+            return Ok(res);
+        }
+        self._pay_keeper(deps.storage, &info.sender, res)
+    }
+}
+
+// trade-ins
+impl<'a, T, C> Cw721Contract<'a, T, C>
+where
+    T: Serialize + DeserializeOwned + Clone,
+    C: CustomMsg,
+{
+    pub fn open_trade_in(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        token_id: String,
+        top_up: Coin,
+        accepted_token_ids: Vec<String>,
+    ) -> Result<Response<C>, ContractError> {
+        if accepted_token_ids.is_empty() || accepted_token_ids.len() > MAX_TRADE_IN_TOKENS {
+            return Err(ContractError::InvalidTradeInTokens {
+                max: MAX_TRADE_IN_TOKENS,
+            });
+        }
+        let mut token = self.tokens.load(deps.storage, token_key(&token_id)?)?;
+        if token.owner != info.sender {
+            return Err(ContractError::Unauthorized {});
+        }
+        self.check_not_frozen(deps.storage, &token_id)?;
+        self.check_transferable(deps.storage, &token_id)?;
+        let artist = self.token_artists.may_load(deps.storage, &token_id)?;
+        if artist.as_ref() != Some(&info.sender)
+            && !self.has_role(deps.storage, &info.sender, Role::Treasurer)?
+        {
+            return Err(ContractError::Unauthorized {});
+        }
+        self.check_storage_fee(deps.storage, &env, &token_id)?;
+
+        // escrow the token in the contract
+        token.owner = env.contract.address.clone();
+        self._clear_approvals(deps.storage, &token_id, &mut token)?;
+        self.tokens
+            .save(deps.storage, token_key(&token_id)?, &token)?;
+        self._track_transfer(deps.storage, &env, &token_id, &info.sender, &token.owner)?;
+        let trade_in = TradeIn {
+            holder: info.sender.clone(),
+            top_up: top_up.clone(),
+            opened_at: env.block.time,
+            accepted_token_ids,
+        };
+        self.trade_ins.save(deps.storage, &token_id, &trade_in)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "open_trade_in")
+            .add_attribute("holder", info.sender)
+            .add_attribute("token_id", token_id)
+            .add_attribute("top_up", top_up.to_string()))
+    }
+
+    pub fn close_trade_in(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        token_id: String,
+    ) -> Result<Response<C>, ContractError> {
+        let trade_in = self.trade_ins.load(deps.storage, &token_id)?;
+        if trade_in.holder != info.sender {
+            return Err(ContractError::Unauthorized {});
+        }
+        self.check_not_frozen(deps.storage, &token_id)?;
+        self.trade_ins.remove(deps.storage, &token_id);
+
+        let mut token = self.tokens.load(deps.storage, token_key(&token_id)?)?;
+        token.owner = trade_in.holder;
+        self.tokens
+            .save(deps.storage, token_key(&token_id)?, &token)?;
+        self._track_transfer(
+            deps.storage,
+            &env,
+            &token_id,
+            &env.contract.address,
+            &token.owner,
+        )?;
+
+        Ok(Response::new()
+            .add_attribute("action", "close_trade_in")
+            .add_attribute("holder", info.sender)
+            .add_attribute("token_id", token_id))
+    }
+
+    /// Swaps offered_token_id and the top-up for the escrowed token_id. Both
+    /// tokens record the swap in their provenance
+    pub fn take_trade_in(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        token_id: String,
+        offered_token_id: String,
+    ) -> Result<Response<C>, ContractError> {
+        let trade_in = self.trade_ins.load(deps.storage, &token_id)?;
+        if !trade_in.accepted_token_ids.contains(&offered_token_id) {
+            return Err(ContractError::TradeInNotAccepted {});
+        }
+        // the offered token is checked by check_can_send
+        self.check_not_frozen(deps.storage, &token_id)?;
+        let excess = take_payment(&info, &trade_in.top_up)?;
+        let mut offered = self
+            .tokens
+            .load(deps.storage, token_key(&offered_token_id)?)?;
+        self.check_can_send(deps.as_ref(), &env, &info, &offered_token_id, &offered)?;
+        self.check_transferable(deps.storage, &offered_token_id)?;
+        self.check_storage_fee(deps.storage, &env, &offered_token_id)?;
+        let trader = offered.owner.clone();
+        if trader == trade_in.holder {
+            return Err(ContractError::Unauthorized {});
+        }
+        self.trade_ins.remove(deps.storage, &token_id);
+
+        offered.owner = trade_in.holder.clone();
+        self._clear_approvals(deps.storage, &offered_token_id, &mut offered)?;
+        self.tokens
+            .save(deps.storage, token_key(&offered_token_id)?, &offered)?;
+        self._track_transfer(
+            deps.storage,
+            &env,
+            &offered_token_id,
+            &trader,
+            &trade_in.holder,
+        )?;
+        let mut token = self.tokens.load(deps.storage, token_key(&token_id)?)?;
+        token.owner = trader.clone();
+        self.tokens
+            .save(deps.storage, token_key(&token_id)?, &token)?;
+        self._track_transfer(
+            deps.storage,
+            &env,
+            &token_id,
+            &env.contract.address,
+            &trader,
+        )?;
+
+        let event = format!("swap/{}/{}/{}", offered_token_id, token_id, trade_in.top_up);
+        self._append_provenance(deps.storage, &env, &token_id, event.clone())?;
+        self._append_provenance(deps.storage, &env, &offered_token_id, event)?;
+
+        let mut res = Response::new();
+        if !excess.is_empty() {
+            res = res.add_message(BankMsg::Send {
+                to_address: info.sender.to_string(),
+                amount: excess,
+            });
+        }
+        if !trade_in.top_up.amount.is_zero() {
+            res = res.add_message(BankMsg::Send {
+                to_address: trade_in.holder.to_string(),
+                amount: vec![trade_in.top_up.clone()],
+            });
+        }
+
+        Ok(res
+            .add_attribute("action", "trade_in")
+            .add_attribute("sender", info.sender)
+            .add_attribute("token_id", token_id)
+            .add_attribute("offered_token_id", offered_token_id)
+            .add_attribute("trader", trader)
+            .add_attribute("holder", trade_in.holder)
+            .add_attribute("top_up", trade_in.top_up.to_string()))
+    }
+}
+
+// tax withholding
+impl<'a, T, C> Cw721Contract<'a, T, C>
+where
+    T: Serialize + DeserializeOwned + Clone,
+    C: CustomMsg,
+{
+    pub fn set_withholding_rule(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        jurisdiction: String,
+        rate_bps: u16,
+        escrow: String,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(deps.as_ref(), &info, Role::Admin)?;
+        if rate_bps > 10_000 {
+            return Err(ContractError::InvalidRate {});
+        }
+
+        let rule = WithholdingRule {
+            rate_bps,
+            escrow: self.resolve_addr(deps.as_ref(), &escrow)?,
+        };
+        self.withholding_rules
+            .save(deps.storage, &jurisdiction, &rule)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "set_withholding_rule")
+            .add_attribute("jurisdiction", jurisdiction)
+            .add_attribute("rate_bps", rate_bps.to_string())
+            .add_attribute("escrow", escrow))
+    }
+
+    pub fn remove_withholding_rule(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        jurisdiction: String,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(deps.as_ref(), &info, Role::Admin)?;
+        self.withholding_rules.remove(deps.storage, &jurisdiction);
+
+        Ok(Response::new()
+            .add_attribute("action", "remove_withholding_rule")
+            .add_attribute("jurisdiction", jurisdiction))
+    }
+
+    pub fn set_account_jurisdiction(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        address: String,
+        jurisdiction: Option<String>,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(deps.as_ref(), &info, Role::Admin)?;
+
+        let addr = self.resolve_addr(deps.as_ref(), &address)?;
+        match &jurisdiction {
+            Some(jurisdiction) => {
+                self.account_jurisdictions
+                    .save(deps.storage, &addr, jurisdiction)?
+            }
+            None => self.account_jurisdictions.remove(deps.storage, &addr),
+        }
+
+        Ok(Response::new()
+            .add_attribute("action", "set_account_jurisdiction")
+            .add_attribute("address", address)
+            .add_attribute("jurisdiction", jurisdiction.unwrap_or_default()))
+    }
+
+    /// resolves the withholding due by buyer on payment, if their jurisdiction has a rule
+    pub fn withholding_for(
+        &self,
+        storage: &dyn Storage,
+        buyer: &Addr,
+        payment: &Coin,
+    ) -> StdResult<Option<Withholding>> {
+        let jurisdiction = match self.account_jurisdictions.may_load(storage, buyer)? {
+            Some(jurisdiction) => jurisdiction,
+            None => return Ok(None),
+        };
+        let rule = match self.withholding_rules.may_load(storage, &jurisdiction)? {
+            Some(rule) => rule,
+            None => return Ok(None),
+        };
+        Ok(Some(Withholding {
+            amount: withholding_amount(rule.rate_bps, payment.amount),
+            jurisdiction,
+            rate_bps: rule.rate_bps,
+            escrow: rule.escrow,
+        }))
+    }
+
+    /// sends the withheld amount to the escrow address
+    pub fn add_withholding(
+        &self,
+        res: Response<C>,
+        withholding: &Withholding,
+        denom: &str,
+    ) -> Response<C> {
+        if withholding.amount.is_zero() {
+            return res;
+        }
+        res.add_message(BankMsg::Send {
+            to_address: withholding.escrow.to_string(),
+            amount: coins(withholding.amount.u128(), denom),
+        })
+        .add_attribute("withholding_jurisdiction", withholding.jurisdiction.clone())
+        .add_attribute("withholding_amount", withholding.amount)
+    }
+}
+
+fn withholding_amount(rate_bps: u16, amount: Uint128) -> Uint128 {
+    amount.multiply_ratio(rate_bps, 10_000u128)
+}
+
+/// amount sent to the withholding escrow, which the contract no longer holds
+fn withheld(withholding: &Option<Withholding>) -> Uint128 {
+    withholding.as_ref().map(|w| w.amount).unwrap_or_default()
+}
+
+// treasury accounting
+impl<'a, T, C> Cw721Contract<'a, T, C>
+where
+    T: Serialize + DeserializeOwned + Clone,
+    C: CustomMsg,
+{
+    pub fn withdraw(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        amount: Option<Coin>,
+        recipient: Option<String>,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(deps.as_ref(), &info, Role::Treasurer)?;
+        let recipient = match recipient {
+            Some(recipient) => self.resolve_addr(deps.as_ref(), &recipient)?,
+            None => info.sender.clone(),
+        };
+        if self.withdrawal_guard.may_load(deps.storage)?.is_some() {
+            let active = self
+                .withdrawal_recipients
+                .may_load(deps.storage, &recipient)?
+                .is_some_and(|from| from <= env.block.time);
+            if !active {
+                return Err(ContractError::RecipientNotAllowed {});
+            }
+        }
+
+        let funds = match amount {
+            Some(amount) => {
+                let available = self.withdrawable(deps.as_ref(), &env, &amount.denom)?;
+                if amount.amount > available {
+                    return Err(ContractError::InsufficientWithdrawable { available });
+                }
+                if let Some(allowance) =
+                    self.withdrawal_allowance(deps.storage, env.block.time, &amount.denom)?
+                {
+                    if amount.amount > allowance {
+                        return Err(ContractError::WithdrawalCapExceeded {
+                            available: allowance,
+                        });
+                    }
+                }
+                vec![amount]
+            }
+            // everything withdrawable, up to the caps
+            None => deps
+                .querier
+                .query_all_balances(&env.contract.address)?
+                .into_iter()
+                .map(|coin| {
+                    let mut amount = self.withdrawable(deps.as_ref(), &env, &coin.denom)?;
+                    if let Some(allowance) =
+                        self.withdrawal_allowance(deps.storage, env.block.time, &coin.denom)?
+                    {
+                        amount = amount.min(allowance);
+                    }
+                    Ok(Coin {
+                        denom: coin.denom,
+                        amount,
+                    })
+                })
+                .collect::<StdResult<Vec<_>>>()?,
+        };
+        let funds: Vec<Coin> = funds.into_iter().filter(|c| !c.amount.is_zero()).collect();
+        if funds.is_empty() {
+            return Err(ContractError::NothingToWithdraw {});
+        }
+
+        for coin in &funds {
+            self._log_withdrawal(deps.storage, env.block.time, coin)?;
+            self._debit_treasury(
+                deps.storage,
+                TreasuryAccount::Proceeds,
+                &coin.denom,
+                coin.amount,
+            )?;
+            let withdrawn = self
+                .proceeds_withdrawn
+                .may_load(deps.storage, &coin.denom)?
+                .unwrap_or_default();
+            self.proceeds_withdrawn
+                .save(deps.storage, &coin.denom, &(withdrawn + coin.amount))?;
+        }
+
+        let sent = funds
+            .iter()
+            .map(|c| c.to_string())
+            .collect::<Vec<_>>()
+            .join(",");
+        Ok(Response::new()
+            .add_message(BankMsg::Send {
+                to_address: recipient.to_string(),
+                amount: funds,
+            })
+            .add_attribute("action", "withdraw")
+            .add_attribute("sender", info.sender)
+            .add_attribute("recipient", recipient)
+            .add_attribute("amount", sent))
+    }
+
+    pub fn update_withdrawal_recipients(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        add: Vec<String>,
+        remove: Vec<String>,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(deps.as_ref(), &info, Role::Admin)?;
+
+        self.withdrawal_guard.save(deps.storage, &Empty {})?;
+        let active_from = env.block.time.plus_seconds(WITHDRAWAL_POLICY_DELAY);
+        for recipient in &add {
+            let addr = self.resolve_addr(deps.as_ref(), recipient)?;
+            // re-adding an active recipient keeps it active
+            if !self.withdrawal_recipients.has(deps.storage, &addr) {
+                self.withdrawal_recipients
+                    .save(deps.storage, &addr, &active_from)?;
+            }
+        }
+        for recipient in &remove {
+            let addr = self.resolve_addr(deps.as_ref(), recipient)?;
+            self.withdrawal_recipients.remove(deps.storage, &addr);
+        }
+
+        Ok(Response::new()
+            .add_attribute("action", "update_withdrawal_recipients")
+            .add_attribute("sender", info.sender)
+            .add_attribute("added", add.len().to_string())
+            .add_attribute("removed", remove.len().to_string()))
+    }
+
+    pub fn set_withdrawal_cap(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        denom: String,
+        amount: Option<Uint128>,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(deps.as_ref(), &info, Role::Admin)?;
+
+        let current = self
+            .withdrawal_caps
+            .may_load(deps.storage, &denom)?
+            .and_then(|cap| cap.effective(env.block.time));
+        let tighter = match (amount, current) {
+            (Some(amount), Some(current)) => amount <= current,
+            (Some(_), None) => true,
+            (None, _) => current.is_none(),
+        };
+        let cap = if tighter {
+            WithdrawalCap {
+                amount,
+                pending: None,
+            }
+        } else {
+            WithdrawalCap {
+                amount: current,
+                pending: Some(PendingWithdrawalCap {
+                    amount,
+                    effective_at: env.block.time.plus_seconds(WITHDRAWAL_POLICY_DELAY),
+                }),
+            }
+        };
+        self.withdrawal_caps.save(deps.storage, &denom, &cap)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "set_withdrawal_cap")
+            .add_attribute("sender", info.sender)
+            .add_attribute("denom", denom)
+            .add_attribute("delayed", (!tighter).to_string()))
+    }
+
+    /// logs a withdrawal for the rolling cap, dropping entries that have left
+    /// the window
+    fn _log_withdrawal(
+        &self,
+        storage: &mut dyn Storage,
+        now: Timestamp,
+        coin: &Coin,
+    ) -> StdResult<()> {
+        let window_start = now.minus_seconds(WITHDRAWAL_WINDOW).nanos();
+        let expired = self
+            .withdrawals
+            .prefix(&coin.denom)
+            .keys(
+                storage,
+                None,
+                Some(Bound::inclusive(window_start)),
+                Order::Ascending,
+            )
+            .collect::<StdResult<Vec<_>>>()?;
+        for time in expired {
+            self.withdrawals.remove(storage, (&coin.denom, time));
+        }
+        self.withdrawals.update(
+            storage,
+            (&coin.denom, now.nanos()),
+            |logged| -> StdResult<_> { Ok(logged.unwrap_or_default() + coin.amount) },
+        )?;
+        Ok(())
+    }
+
+    /// records funds the contract now holds on behalf of `account`
+    pub fn _credit_treasury(
+        &self,
+        storage: &mut dyn Storage,
+        account: TreasuryAccount,
+        denom: &str,
+        amount: Uint128,
+    ) -> StdResult<()> {
+        if amount.is_zero() {
+            return Ok(());
+        }
+        let key = (account.key(), denom);
+        let balance = self
+            .treasury_ledger
+            .may_load(storage, key)?
+            .unwrap_or_default();
+        self.treasury_ledger.save(storage, key, &(balance + amount))
+    }
+
+    /// records funds of `account` leaving the contract. Saturates, as funds
+    /// escrowed before the ledger existed were never credited
+    pub fn _debit_treasury(
+        &self,
+        storage: &mut dyn Storage,
+        account: TreasuryAccount,
+        denom: &str,
+        amount: Uint128,
+    ) -> StdResult<()> {
+        let key = (account.key(), denom);
+        let balance = self
+            .treasury_ledger
+            .may_load(storage, key)?
+            .unwrap_or_default();
+        let balance = balance.saturating_sub(amount);
+        if balance.is_zero() {
+            self.treasury_ledger.remove(storage, key);
+            return Ok(());
+        }
+        self.treasury_ledger.save(storage, key, &balance)
+    }
+}
+
+impl<'a, T, C> Cw721Execute<T, C> for Cw721Contract<'a, T, C>
+where
+    T: Serialize + DeserializeOwned + Clone,
+    C: CustomMsg,
+{
+    type Err = ContractError;
+
+    fn transfer_nft(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        recipient: String,
+        token_id: String,
+    ) -> Result<Response<C>, ContractError> {
+        self._transfer_nft(deps, &env, &info, &recipient, &token_id)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "transfer_nft")
+            .add_attribute("sender", info.sender)
+            .add_attribute("recipient", recipient)
+            .add_attribute("token_id", token_id))
+    }
+
+    fn send_nft(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        contract: String,
+        token_id: String,
+        msg: Binary,
+    ) -> Result<Response<C>, ContractError> {
+        // a name is resolved once, so the allowlist check, the transfer and
+        // the receive hook all target the same contract
+        let contract = self.resolve_addr(deps.as_ref(), &contract)?;
+        self.check_send_allowed(deps.as_ref(), &contract)?;
+
+        // Transfer token
+        self._transfer_nft(deps, &env, &info, contract.as_str(), &token_id)?;
+
+        let send = Cw721ReceiveMsg {
+            sender: info.sender.to_string(),
+            token_id: token_id.clone(),
+            msg,
+        };
+
+        // Send message
+        Ok(Response::new()
+            .add_message(send.into_cosmos_msg(contract.clone())?)
+            .add_attribute("action", "send_nft")
+            .add_attribute("sender", info.sender)
+            .add_attribute("recipient", contract)
+            .add_attribute("token_id", token_id))
+    }
+
+    fn approve(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        spender: String,
+        token_id: String,
+        expires: Option<Expiration>,
+    ) -> Result<Response<C>, ContractError> {
+        self._update_approvals(deps, &env, &info, &spender, &token_id, true, expires)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "approve")
+            .add_attribute("sender", info.sender)
+            .add_attribute("spender", spender)
+            .add_attribute("token_id", token_id))
+    }
+
+    fn revoke(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        spender: String,
+        token_id: String,
+    ) -> Result<Response<C>, ContractError> {
+        self._update_approvals(deps, &env, &info, &spender, &token_id, false, None)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "revoke")
+            .add_attribute("sender", info.sender)
+            .add_attribute("spender", spender)
+            .add_attribute("token_id", token_id))
+    }
+
+    fn approve_all(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        operator: String,
+        expires: Option<Expiration>,
+    ) -> Result<Response<C>, ContractError> {
+        // reject expired data as invalid
+        let expires = expires.unwrap_or_default();
+        if expires.is_expired(&env.block) {
+            return Err(ContractError::Expired {});
+        }
+
+        // set the operator for us
+        let operator_addr = self.resolve_addr(deps.as_ref(), &operator)?;
+        self.operators
+            .save(deps.storage, (&info.sender, &operator_addr), &expires)?;
+        self.operator_grants
+            .save(deps.storage, (&operator_addr, &info.sender), &expires)?;
+        let expiry = ExpiryKind::Operator {
+            owner: info.sender.clone(),
+            operator: operator_addr,
+        };
+        match expires {
+            Expiration::AtTime(at) => self._schedule_expiry(deps.storage, at, expiry)?,
+            _ => self._clear_expiry(deps.storage, &expiry)?,
+        }
+
+        Ok(Response::new()
+            .add_attribute("action", "approve_all")
+            .add_attribute("sender", info.sender)
+            .add_attribute("operator", operator))
+    }
+
+    fn revoke_all(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        operator: String,
+    ) -> Result<Response<C>, ContractError> {
+        let operator_addr = self.resolve_addr(deps.as_ref(), &operator)?;
+        self.operators
+            .remove(deps.storage, (&info.sender, &operator_addr));
+        self.operator_grants
+            .remove(deps.storage, (&operator_addr, &info.sender));
+        let expiry = ExpiryKind::Operator {
+            owner: info.sender.clone(),
+            operator: operator_addr,
+        };
+        self._clear_expiry(deps.storage, &expiry)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "revoke_all")
+            .add_attribute("sender", info.sender)
+            .add_attribute("operator", operator))
+    }
+
+    fn burn(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        token_id: String,
+    ) -> Result<Response<C>, ContractError> {
+        let token = self.tokens.load(deps.storage, token_key(&token_id)?)?;
+        self.check_can_send(deps.as_ref(), &env, &info, &token_id, &token)?;
+        self.check_storage_fee(deps.storage, &env, &token_id)?;
+
+        self._remove_token(
+            deps.storage,
+            &env,
+            &token_id,
+            &token.owner,
+            RemovalKind::Burned,
+            &info.sender,
+        )?;
+
+        Ok(Response::new()
+            .add_attribute("action", "burn")
+            .add_attribute("sender", info.sender)
+            .add_attribute("token_id", token_id))
+    }
+}
+
+/// configuration messages that mint, move, approve or burn no tokens and pay
+/// out no funds. While the contract is paused the admin can still send these,
+/// anything else is rejected
+fn is_pausable_config<T>(msg: &ExecuteMsg<T>) -> bool {
+    matches!(
+        msg,
+        ExecuteMsg::SetMintConfig { .. }
+            | ExecuteMsg::SetMaxMintsPerAddress { .. }
+            | ExecuteMsg::SetDropLimit { .. }
+            | ExecuteMsg::ToggleMinting { .. }
+            | ExecuteMsg::SetUniqueContent { .. }
+            | ExecuteMsg::SetSizeLimits { .. }
+            | ExecuteMsg::ModerateProfile { .. }
+            | ExecuteMsg::UpdateSendAllowlist { .. }
+            | ExecuteMsg::SetInstallmentPlan { .. }
+            | ExecuteMsg::SetWithholdingRule { .. }
+            | ExecuteMsg::RemoveWithholdingRule { .. }
+            | ExecuteMsg::SetAccountJurisdiction { .. }
+            | ExecuteMsg::AddAttestor { .. }
+            | ExecuteMsg::RemoveAttestor { .. }
+            | ExecuteMsg::SetAuctionBeneficiary { .. }
+            | ExecuteMsg::SetAuctionFeeOverride { .. }
+            | ExecuteMsg::MigrateTokenKeys { .. }
+            | ExecuteMsg::SetBuyBack { .. }
+            | ExecuteMsg::UpdateVaultCollections { .. }
+            | ExecuteMsg::UpdatePartnerGalleries { .. }
+            | ExecuteMsg::SetVoucherLimit { .. }
+            | ExecuteMsg::SetMysteryMode { .. }
+            | ExecuteMsg::AddMysteryUris { .. }
+            | ExecuteMsg::Reveal { .. }
+            | ExecuteMsg::SetKeeperBounty { .. }
+            | ExecuteMsg::GrantRole { .. }
+            | ExecuteMsg::RevokeRole { .. }
+            | ExecuteMsg::SetNameResolver { .. }
+            | ExecuteMsg::SetMetadataResolver { .. }
+            | ExecuteMsg::SetPresale { .. }
+            | ExecuteMsg::SetAllowlistConfig { .. }
+            | ExecuteMsg::SetDutchAuction { .. }
+            | ExecuteMsg::SetMintPricePeg { .. }
+            | ExecuteMsg::AddToAllowlist { .. }
+            | ExecuteMsg::RemoveFromAllowlist { .. }
+            | ExecuteMsg::SetAllowlistMerkleRoot { .. }
+            | ExecuteMsg::SetCurator { .. }
+            | ExecuteMsg::SetCurationFee { .. }
+            | ExecuteMsg::SetReferral { .. }
+            | ExecuteMsg::SetTokenRoyalty { .. }
+            | ExecuteMsg::SetRoyaltySplit { .. }
+            | ExecuteMsg::ScheduleRoyaltySuccession { .. }
+            | ExecuteMsg::CancelRoyaltySuccession { .. }
+            | ExecuteMsg::SetRoyaltySwap { .. }
+            | ExecuteMsg::SetLender { .. }
+            | ExecuteMsg::SetListingFeeOverride { .. }
+            | ExecuteMsg::SetMarketplaceFee { .. }
+            | ExecuteMsg::FreezeToken { .. }
+            | ExecuteMsg::UnfreezeToken { .. }
+            | ExecuteMsg::AddVenue { .. }
+            | ExecuteMsg::RemoveVenue { .. }
+            | ExecuteMsg::StartExport { .. }
+            | ExecuteMsg::UpdateWithdrawalRecipients { .. }
+            | ExecuteMsg::SetWithdrawalCap { .. }
+            | ExecuteMsg::SetStorageFee { .. }
+            | ExecuteMsg::SetVaultCustody { .. }
+    )
+}
+
+/// the subsystem whose typed event msg emits, if any
+fn event_subsystem<T>(msg: &ExecuteMsg<T>) -> Option<EventSubsystem> {
+    match msg {
+        ExecuteMsg::ListForSale { .. }
+        | ExecuteMsg::CancelListing { .. }
+        | ExecuteMsg::SetListingFiatPrice { .. }
+        | ExecuteMsg::SetListingFeeOverride { .. }
+        | ExecuteMsg::BuyToken { .. }
+        | ExecuteMsg::BuyBundle { .. }
+        | ExecuteMsg::BuyWithLoan { .. }
+        | ExecuteMsg::SetLender { .. }
+        | ExecuteMsg::SetMarketplaceFee { .. }
+        | ExecuteMsg::ApproveListing { .. }
+        | ExecuteMsg::RevokeListing { .. }
+        | ExecuteMsg::MakeOffer { .. }
+        | ExecuteMsg::WithdrawOffer { .. }
+        | ExecuteMsg::AcceptOffer { .. }
+        | ExecuteMsg::SetFirstRefusal { .. }
+        | ExecuteMsg::MatchFirstRefusal { .. }
+        | ExecuteMsg::CompleteFirstRefusal { .. }
+        | ExecuteMsg::FundAutoBuy { .. }
+        | ExecuteMsg::WithdrawAutoBuy { .. }
+        | ExecuteMsg::AutoBuy { .. }
+        | ExecuteMsg::PlaceCollectionBid { .. }
+        | ExecuteMsg::AcceptCollectionBid { .. }
+        | ExecuteMsg::CancelCollectionBid { .. }
+        | ExecuteMsg::OpenTradeIn { .. }
+        | ExecuteMsg::CloseTradeIn { .. }
+        | ExecuteMsg::TradeIn { .. }
+        | ExecuteMsg::IssueRoyaltyWaiver { .. }
+        | ExecuteMsg::TransferRoyaltyWaiver { .. } => Some(EventSubsystem::Marketplace),
+        ExecuteMsg::CreateSealedAuction { .. }
+        | ExecuteMsg::CommitBid { .. }
+        | ExecuteMsg::RevealBid { .. }
+        | ExecuteMsg::SettleSealedAuction { .. }
+        | ExecuteMsg::CancelSealedAuction { .. }
+        | ExecuteMsg::Finalize { .. }
+        | ExecuteMsg::CreateAuction { .. }
+        | ExecuteMsg::PlaceBid { .. }
+        | ExecuteMsg::PlaceMaxBid { .. }
+        | ExecuteMsg::SettleAuction { .. }
+        | ExecuteMsg::SetAuctionBeneficiary { .. }
+        | ExecuteMsg::SetAuctionFeeOverride { .. } => Some(EventSubsystem::Auction),
+        ExecuteMsg::RecordPrivateSale { .. }
+        | ExecuteMsg::RevealPrivateSale { .. }
+        | ExecuteMsg::LogExhibition { .. }
+        | ExecuteMsg::IssueCertificate { .. }
+        | ExecuteMsg::Attest { .. }
+        | ExecuteMsg::RevokeAttestation { .. } => Some(EventSubsystem::Provenance),
+        _ => None,
+    }
+}
+
+/// royalty settings, part of the extended profile like the event subsystems
+fn is_royalty_config<T>(msg: &ExecuteMsg<T>) -> bool {
+    matches!(
+        msg,
+        ExecuteMsg::SetTokenRoyalty { .. }
+            | ExecuteMsg::SetRoyaltySplit { .. }
+            | ExecuteMsg::ScheduleRoyaltySuccession { .. }
+            | ExecuteMsg::CancelRoyaltySuccession { .. }
+            | ExecuteMsg::SetRoyaltySwap { .. }
+            | ExecuteMsg::SetRoyaltyPayoutDenom { .. }
+    )
+}
+
+/// mirrors res's attributes into a `<prefix>_<action>` event, leaving the
+/// generic wasm attributes in place for existing indexers
+fn add_subsystem_event<C>(res: Response<C>, subsystem: EventSubsystem) -> Response<C> {
+    let action = res
+        .attributes
+        .iter()
+        .find(|attr| attr.key == "action")
+        .map(|attr| attr.value.clone())
+        .unwrap_or_default();
+    let event = Event::new(format!("{}_{}", subsystem.prefix(), action))
+        .add_attribute("schema_version", EVENT_SCHEMA_VERSION.to_string())
+        .add_attributes(
+            res.attributes
+                .iter()
+                .filter(|attr| attr.key != "action")
+                .cloned(),
+        );
+    res.add_event(event)
+}
+
+/// returns an error unless a batch of len tokens is within MAX_BATCH_SIZE
+pub(crate) fn check_batch_size(len: usize) -> Result<(), ContractError> {
+    if len == 0 || len > MAX_BATCH_SIZE {
+        return Err(ContractError::InvalidBatchSize {
+            max: MAX_BATCH_SIZE,
+        });
+    }
+    Ok(())
+}
+
+// batch transfers
+impl<'a, T, C> Cw721Contract<'a, T, C>
+where
+    T: Serialize + DeserializeOwned + Clone,
+    C: CustomMsg,
+{
+    pub fn batch_transfer_nft(
+        &self,
+        mut deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        recipients: Vec<(String, String)>,
+    ) -> Result<Response<C>, ContractError> {
+        check_batch_size(recipients.len())?;
+
+        let count = recipients.len();
+        let mut res = Response::new();
+        for (recipient, token_id) in recipients {
+            self._transfer_nft(deps.branch(), &env, &info, &recipient, &token_id)?;
+            res = res.add_event(
+                Event::new("transfer_nft")
+                    .add_attribute("recipient", recipient)
+                    .add_attribute("token_id", token_id),
+            );
+        }
+
+        Ok(res
+            .add_attribute("action", "batch_transfer_nft")
+            .add_attribute("sender", info.sender)
+            .add_attribute("count", count.to_string()))
+    }
+
+    pub fn batch_burn(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        token_ids: Vec<String>,
+    ) -> Result<Response<C>, ContractError> {
+        check_batch_size(token_ids.len())?;
+
+        let count = token_ids.len();
+        let mut res = Response::new();
+        for token_id in token_ids {
+            let token = self.tokens.load(deps.storage, token_key(&token_id)?)?;
+            self.check_can_send(deps.as_ref(), &env, &info, &token_id, &token)?;
+            self.check_storage_fee(deps.storage, &env, &token_id)?;
+            self._remove_token(
+                deps.storage,
+                &env,
+                &token_id,
+                &token.owner,
+                RemovalKind::Burned,
+                &info.sender,
+            )?;
+            res = res.add_event(Event::new("burn").add_attribute("token_id", token_id));
+        }
+
+        Ok(res
+            .add_attribute("action", "batch_burn")
+            .add_attribute("sender", info.sender)
+            .add_attribute("count", count.to_string()))
+    }
+}
+
+// ics-721 transfers
+impl<'a, T, C> Cw721Contract<'a, T, C>
+where
+    T: Serialize + DeserializeOwned + Clone,
+    C: CustomMsg,
+{
+    #[allow(clippy::too_many_arguments)]
+    pub fn ibc_transfer(
+        &self,
+        mut deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        channel_id: String,
+        receiver: String,
+        token_ids: Vec<String>,
+        timeout_seconds: Option<u64>,
+    ) -> Result<Response<C>, ContractError> {
+        check_batch_size(token_ids.len())?;
+        let channel = self
+            .ibc_channels
+            .may_load(deps.storage, &channel_id)?
+            .ok_or_else(|| ContractError::UnknownChannel {
+                channel_id: channel_id.clone(),
+            })?;
+
+        // a packet carries a single class: this collection's own tokens, or
+        // vouchers going back over the channel they came through
+        let class_id = self.ibc_class_id(deps.storage, &env, &token_ids[0])?;
+        let prefix = format!("{}/{}/", channel.endpoint.port_id, channel_id);
+        if class_id != env.contract.address.as_str() && !class_id.starts_with(&prefix) {
+            return Err(ContractError::IbcClassMismatch {});
+        }
+
+        let mut remote_ids = vec![];
+        let mut token_uris = vec![];
+        let mut token_data = vec![];
+        for token_id in &token_ids {
+            if self.ibc_class_id(deps.storage, &env, token_id)? != class_id {
+                return Err(ContractError::IbcClassMismatch {});
+            }
+            let owner = self.tokens.load(deps.storage, token_key(token_id)?)?.owner;
+            let token = self._transfer_nft(
+                deps.branch(),
+                &env,
+                &info,
+                env.contract.address.as_str(),
+                token_id,
+            )?;
+            let escrow = IbcEscrow {
+                channel_id: channel_id.clone(),
+                owner,
+            };
+            self.ibc_escrows.save(deps.storage, token_id, &escrow)?;
+
+            let remote_id = match self.ibc_vouchers.may_load(deps.storage, token_id)? {
+                Some(voucher) => voucher.token_id,
+                None => token_id.clone(),
+            };
+            remote_ids.push(remote_id);
+            token_uris.push(token.token_uri.unwrap_or_default());
+            token_data.push(to_json_binary(&token.extension)?);
+        }
+
+        let packet = NonFungibleTokenPacketData {
+            class_id,
+            class_uri: None,
+            class_data: None,
+            token_ids: remote_ids,
+            token_uris: Some(token_uris),
+            token_data: Some(token_data),
+            sender: info.sender.to_string(),
+            receiver: receiver.clone(),
+            memo: None,
+        };
+        let timeout = env
+            .block
+            .time
+            .plus_seconds(timeout_seconds.unwrap_or(DEFAULT_IBC_TIMEOUT));
+        let msg = IbcMsg::SendPacket {
+            channel_id: channel_id.clone(),
+            data: to_json_binary(&packet)?,
+            timeout: IbcTimeout::with_timestamp(timeout),
+        };
+
+        Ok(Response::new()
+            .add_message(msg)
+            .add_attribute("action", "ibc_transfer")
+            .add_attribute("sender", info.sender)
+            .add_attribute("channel_id", channel_id)
+            .add_attribute("receiver", receiver)
+            .add_attribute("token_ids", token_ids.join(",")))
+    }
+}
+
+// operator grants
+impl<'a, T, C> Cw721Contract<'a, T, C>
+where
+    T: Serialize + DeserializeOwned + Clone,
+    C: CustomMsg,
+{
+    pub fn renounce_operator(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        owner: String,
+    ) -> Result<Response<C>, ContractError> {
+        let owner_addr = self.resolve_addr(deps.as_ref(), &owner)?;
+        self.operators
+            .remove(deps.storage, (&owner_addr, &info.sender));
+        self.operator_grants
+            .remove(deps.storage, (&info.sender, &owner_addr));
+        let expiry = ExpiryKind::Operator {
+            owner: owner_addr,
+            operator: info.sender.clone(),
+        };
+        self._clear_expiry(deps.storage, &expiry)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "renounce_operator")
+            .add_attribute("operator", info.sender)
+            .add_attribute("owner", owner))
+    }
+
+    /// RevokeAll, logging the reason if one is given
+    pub fn revoke_operator(
+        &self,
+        mut deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        operator: String,
+        reason: Option<RevocationReason>,
+    ) -> Result<Response<C>, ContractError> {
+        let operator_addr = self.resolve_addr(deps.as_ref(), &operator)?;
+        let res = self.revoke_all(deps.branch(), env.clone(), info.clone(), operator)?;
+        let reason = match reason {
+            Some(reason) => reason,
+            None => return Ok(res),
+        };
+        if let RevocationReason::Other { label } = &reason {
+            self.check_provenance_field(deps.storage, label)?;
+        }
+
+        let seq = self
+            .operator_revocation_count
+            .may_load(deps.storage)?
+            .unwrap_or_default()
+            + 1;
+        self.operator_revocation_count.save(deps.storage, &seq)?;
+        let res = res.add_attribute("reason", reason.code());
+        let revocation = OperatorRevocation {
+            owner: info.sender,
+            reason,
+            revoked_at: env.block.time,
+        };
+        self.operator_revocations
+            .save(deps.storage, (&operator_addr, seq), &revocation)?;
+        Ok(res)
+    }
+}
+
+// range approvals
+impl<'a, T, C> Cw721Contract<'a, T, C>
+where
+    T: Serialize + DeserializeOwned + Clone,
+    C: CustomMsg,
+{
+    /// Grants operator the sender's tokens with ids in `[first, last]`,
+    /// including ones the sender receives later
+    #[allow(clippy::too_many_arguments)]
+    pub fn approve_range(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        operator: String,
+        first: u64,
+        last: u64,
+        expires: Option<Expiration>,
+    ) -> Result<Response<C>, ContractError> {
+        if last < first {
+            return Err(ContractError::InvalidRange {});
+        }
+        let expires = expires.unwrap_or_default();
+        if expires.is_expired(&env.block) {
+            return Err(ContractError::Expired {});
+        }
+
+        let operator_addr = self.resolve_addr(deps.as_ref(), &operator)?;
+        let key = (&info.sender, &operator_addr, first);
+        if !self.range_approvals.has(deps.storage, key) {
+            let ranges = self
+                .range_approvals
+                .prefix((&info.sender, &operator_addr))
+                .keys(deps.storage, None, None, Order::Ascending)
+                .count();
+            if ranges >= MAX_RANGE_APPROVALS {
+                return Err(ContractError::TooManyRangeApprovals {
+                    max: MAX_RANGE_APPROVALS as u32,
+                });
+            }
+        }
+        self.range_approvals
+            .save(deps.storage, key, &RangeApproval { last, expires })?;
+        let expiry = ExpiryKind::RangeApproval {
+            owner: info.sender.clone(),
+            operator: operator_addr,
+            first,
+        };
+        match expires {
+            Expiration::AtTime(at) => self._schedule_expiry(deps.storage, at, expiry)?,
+            _ => self._clear_expiry(deps.storage, &expiry)?,
+        }
+
+        Ok(Response::new()
+            .add_attribute("action", "approve_range")
+            .add_attribute("sender", info.sender)
+            .add_attribute("operator", operator)
+            .add_attribute("first", first.to_string())
+            .add_attribute("last", last.to_string()))
+    }
+
+    pub fn revoke_range(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        operator: String,
+        first: u64,
+    ) -> Result<Response<C>, ContractError> {
+        let operator_addr = self.resolve_addr(deps.as_ref(), &operator)?;
+        self.range_approvals
+            .remove(deps.storage, (&info.sender, &operator_addr, first));
+        let expiry = ExpiryKind::RangeApproval {
+            owner: info.sender.clone(),
+            operator: operator_addr,
+            first,
+        };
+        self._clear_expiry(deps.storage, &expiry)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "revoke_range")
+            .add_attribute("sender", info.sender)
+            .add_attribute("operator", operator)
+            .add_attribute("first", first.to_string()))
+    }
+}
+
+// storage fees
+impl<'a, T, C> Cw721Contract<'a, T, C>
+where
+    T: Serialize + DeserializeOwned + Clone,
+    C: CustomMsg,
+{
+    pub fn set_storage_fee(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        schedule: Option<StorageFeeSchedule>,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(deps.as_ref(), &info, Role::Admin)?;
+        // accounts are accrued at the current schedule
+        let custodied = self
+            .storage_custody_count
+            .may_load(deps.storage)?
+            .unwrap_or_default();
+        if custodied > 0 {
+            return Err(ContractError::StorageFeeLocked {});
+        }
+
+        match schedule {
+            Some(schedule) => {
+                if schedule.fee.amount.is_zero() || schedule.period == 0 {
+                    return Err(ContractError::InvalidStorageFee {});
+                }
+                let fee = StorageFee {
+                    fee: schedule.fee,
+                    period: schedule.period,
+                    collector: self.resolve_addr(deps.as_ref(), &schedule.collector)?,
+                };
+                self.storage_fee.save(deps.storage, &fee)?;
+            }
+            None => self.storage_fee.remove(deps.storage),
+        }
+
+        Ok(Response::new()
+            .add_attribute("action", "set_storage_fee")
+            .add_attribute("sender", info.sender))
+    }
+
+    pub fn set_vault_custody(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        token_id: String,
+        custodied: bool,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(deps.as_ref(), &info, Role::Admin)?;
+        self.tokens.load(deps.storage, token_key(&token_id)?)?;
+        let count = self
+            .storage_custody_count
+            .may_load(deps.storage)?
+            .unwrap_or_default();
+
+        let account = self.accrue_storage_fee(deps.storage, env.block.time, &token_id)?;
+        match (account, custodied) {
+            (None, true) => {
+                if self.storage_fee.may_load(deps.storage)?.is_none() {
+                    return Err(ContractError::StorageFeeDisabled {});
+                }
+                let account = StorageAccount {
+                    custodied_since: env.block.time,
+                    accrued_to: env.block.time,
+                    owed: Uint128::zero(),
+                    paid: Uint128::zero(),
+                };
+                self.storage_accounts
+                    .save(deps.storage, &token_id, &account)?;
+                self.storage_custody_count
+                    .save(deps.storage, &(count + 1))?;
+            }
+            (Some(account), false) => {
+                if !account.owed.is_zero() {
+                    return Err(ContractError::StorageFeeOutstanding { owed: account.owed });
+                }
+                self.storage_accounts.remove(deps.storage, &token_id);
+                self.storage_custody_count
+                    .save(deps.storage, &(count - 1))?;
+            }
+            (None, false) => return Err(ContractError::NotCustodied {}),
+            (Some(_), true) => return Err(ContractError::AlreadyCustodied {}),
+        }
+
+        Ok(Response::new()
+            .add_attribute("action", "set_vault_custody")
+            .add_attribute("sender", info.sender)
+            .add_attribute("token_id", token_id)
+            .add_attribute("custodied", custodied.to_string()))
+    }
+
+    /// Anyone can pay, the owner is who is blocked until it is settled
+    pub fn pay_storage_fee(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        token_id: String,
+    ) -> Result<Response<C>, ContractError> {
+        let mut account = self
+            .accrue_storage_fee(deps.storage, env.block.time, &token_id)?
+            .ok_or(ContractError::NotCustodied {})?;
+        let schedule = self.storage_fee.load(deps.storage)?;
+        let amount = must_pay(&info, &schedule.fee.denom)?;
+        if amount > account.owed {
+            return Err(ContractError::StorageFeeOverpaid { owed: account.owed });
+        }
+
+        account.owed -= amount;
+        account.paid += amount;
+        self.storage_accounts
+            .save(deps.storage, &token_id, &account)?;
+
+        Ok(Response::new()
+            .add_message(BankMsg::Send {
+                to_address: schedule.collector.into_string(),
+                amount: coins(amount.u128(), schedule.fee.denom),
+            })
+            .add_attribute("action", "pay_storage_fee")
+            .add_attribute("sender", info.sender)
+            .add_attribute("token_id", token_id)
+            .add_attribute("amount", amount)
+            .add_attribute("owed", account.owed))
+    }
+
+    /// fails while the token has unpaid storage fees
+    pub fn check_storage_fee(
+        &self,
+        storage: &dyn Storage,
+        env: &Env,
+        token_id: &str,
+    ) -> Result<(), ContractError> {
+        match self.accrue_storage_fee(storage, env.block.time, token_id)? {
+            Some(account) if !account.owed.is_zero() => {
+                Err(ContractError::StorageFeeOutstanding { owed: account.owed })
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+// separable rights
+impl<'a, T, C> Cw721Contract<'a, T, C>
+where
+    T: Serialize + DeserializeOwned + Clone,
+    C: CustomMsg,
+{
+    /// Owner: hand a right of the token to holder. Only allowed while the
+    /// right is with the owner or was assigned as non-transferable
+    #[allow(clippy::too_many_arguments)]
+    pub fn assign_right(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        token_id: String,
+        right: TokenRight,
+        holder: String,
+        transferable: bool,
+    ) -> Result<Response<C>, ContractError> {
+        let token = self.tokens.load(deps.storage, token_key(&token_id)?)?;
+        if token.owner != info.sender {
+            return Err(ContractError::Unauthorized {});
+        }
+        let current = self
+            .token_rights
+            .may_load(deps.storage, (&token_id, right.key()))?;
+        if current.as_ref().is_some_and(|a| a.transferable) {
+            return Err(ContractError::RightAssigned {});
+        }
+
+        let holder = self.resolve_addr(deps.as_ref(), &holder)?;
+        let from = current.map(|a| a.holder).unwrap_or(token.owner);
+        let assignment = RightAssignment {
+            holder: holder.clone(),
+            transferable,
+            assigned_at: env.block.time,
+        };
+        self.token_rights
+            .save(deps.storage, (&token_id, right.key()), &assignment)?;
+        let event = format!("right/{}/{}/{}", right.key(), from, holder);
+        self._append_provenance(deps.storage, &env, &token_id, event)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "assign_right")
+            .add_attribute("sender", info.sender)
+            .add_attribute("token_id", token_id)
+            .add_attribute("right", right.key())
+            .add_attribute("holder", holder))
+    }
+
+    /// Holder of a transferable right: pass it on
+    pub fn transfer_right(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        token_id: String,
+        right: TokenRight,
+        recipient: String,
+    ) -> Result<Response<C>, ContractError> {
+        let mut assignment = self
+            .token_rights
+            .load(deps.storage, (&token_id, right.key()))?;
+        if assignment.holder != info.sender {
+            return Err(ContractError::Unauthorized {});
+        }
+        if !assignment.transferable {
+            return Err(ContractError::RightNotTransferable {});
+        }
+
+        let recipient = self.resolve_addr(deps.as_ref(), &recipient)?;
+        assignment.holder = recipient.clone();
+        assignment.assigned_at = env.block.time;
+        self.token_rights
+            .save(deps.storage, (&token_id, right.key()), &assignment)?;
+        let event = format!("right/{}/{}/{}", right.key(), info.sender, recipient);
+        self._append_provenance(deps.storage, &env, &token_id, event)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "transfer_right")
+            .add_attribute("sender", info.sender)
+            .add_attribute("token_id", token_id)
+            .add_attribute("right", right.key())
+            .add_attribute("recipient", recipient))
+    }
+
+    /// Holder: give a right back to the token's owner
+    pub fn release_right(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        token_id: String,
+        right: TokenRight,
+    ) -> Result<Response<C>, ContractError> {
+        let assignment = self
+            .token_rights
+            .load(deps.storage, (&token_id, right.key()))?;
+        if assignment.holder != info.sender {
+            return Err(ContractError::Unauthorized {});
+        }
+        let owner = self.tokens.load(deps.storage, token_key(&token_id)?)?.owner;
+        self.token_rights
+            .remove(deps.storage, (&token_id, right.key()));
+        let event = format!("right/{}/{}/{}", right.key(), info.sender, owner);
+        self._append_provenance(deps.storage, &env, &token_id, event)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "release_right")
+            .add_attribute("sender", info.sender)
+            .add_attribute("token_id", token_id)
+            .add_attribute("right", right.key()))
+    }
+}
+
+// listing approvals
+impl<'a, T, C> Cw721Contract<'a, T, C>
+where
+    T: Serialize + DeserializeOwned + Clone,
+    C: CustomMsg,
+{
+    pub fn approve_listing(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        spender: String,
+        token_id: String,
+        expires: Option<Expiration>,
+    ) -> Result<Response<C>, ContractError> {
+        let token = self.tokens.load(deps.storage, token_key(&token_id)?)?;
+        self.check_can_approve(deps.as_ref(), &env, &info, &token)?;
+        self.check_transferable(deps.storage, &token_id)?;
+        let expires = expires.unwrap_or_default();
+        if expires.is_expired(&env.block) {
+            return Err(ContractError::Expired {});
+        }
+
+        let spender_addr = self.resolve_addr(deps.as_ref(), &spender)?;
+        let approval = ListingApproval {
+            granted_by: info.sender.clone(),
+            expires,
+        };
+        self.listing_approvals
+            .save(deps.storage, (&token_id, &spender_addr), &approval)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "approve_listing")
+            .add_attribute("sender", info.sender)
+            .add_attribute("spender", spender_addr)
+            .add_attribute("token_id", token_id))
+    }
+
+    pub fn revoke_listing(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        spender: String,
+        token_id: String,
+    ) -> Result<Response<C>, ContractError> {
+        let spender_addr = self.resolve_addr(deps.as_ref(), &spender)?;
+        let approval = self
+            .listing_approvals
+            .load(deps.storage, (&token_id, &spender_addr))?;
+        // the grantor keeps control while the token is escrowed in an auction
+        if approval.granted_by != info.sender {
+            let token = self.tokens.load(deps.storage, token_key(&token_id)?)?;
+            self.check_can_approve(deps.as_ref(), &env, &info, &token)?;
+        }
+        self.listing_approvals
+            .remove(deps.storage, (&token_id, &spender_addr));
+
+        Ok(Response::new()
+            .add_attribute("action", "revoke_listing")
+            .add_attribute("sender", info.sender)
+            .add_attribute("spender", spender_addr)
+            .add_attribute("token_id", token_id))
+    }
+
+    pub fn has_listing_approval(
+        &self,
+        deps: Deps,
+        env: &Env,
+        token_id: &str,
+        spender: &Addr,
+    ) -> StdResult<bool> {
+        Ok(self
+            .listing_approvals
+            .may_load(deps.storage, (token_id, spender))?
+            .is_some_and(|approval| !approval.expires.is_expired(&env.block)))
+    }
+
+    /// returns an error unless the sender can send the token or holds a
+    /// listing approval for it
+    pub fn check_can_list(
+        &self,
+        deps: Deps,
+        env: &Env,
+        info: &MessageInfo,
+        token_id: &str,
+        token: &TokenInfo<T>,
+    ) -> Result<(), ContractError> {
+        self.check_not_frozen(deps.storage, token_id)?;
+        self.check_transferable(deps.storage, token_id)?;
+        if self.has_listing_approval(deps, env, token_id, &info.sender)? {
+            return Ok(());
+        }
+        self.check_can_send(deps, env, info, token_id, token)
+    }
+
+    /// drops the listing approvals of a token once it changes hands
+    pub fn _clear_listing_approvals(
+        &self,
+        storage: &mut dyn Storage,
+        token_id: &str,
+    ) -> StdResult<()> {
+        let spenders = self
+            .listing_approvals
+            .prefix(token_id)
+            .keys(storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()?;
+        for spender in spenders {
+            self.listing_approvals.remove(storage, (token_id, &spender));
+        }
+        Ok(())
+    }
+}
+
+// scheduled metadata
+impl<'a, T, C> Cw721Contract<'a, T, C>
+where
+    T: Serialize + DeserializeOwned + Clone,
+    C: CustomMsg,
+{
+    pub fn set_metadata_variants(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        token_id: String,
+        variants: Vec<MetadataVariant>,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(deps.as_ref(), &info, Role::Curator)?;
+        // variants can only be scheduled on existing tokens
+        self.tokens.load(deps.storage, token_key(&token_id)?)?;
+        self.check_metadata_mutable(deps.storage, &token_id)?;
+
+        if variants.len() > MAX_METADATA_VARIANTS {
+            return Err(ContractError::TooManyMetadataVariants {
+                max: MAX_METADATA_VARIANTS,
+            });
+        }
+        if variants.iter().any(|v| v.end <= v.start) {
+            return Err(ContractError::InvalidMetadataWindow {});
+        }
+        for variant in &variants {
+            self.check_uri(deps.storage, &variant.token_uri)?;
+        }
+
+        if variants.is_empty() {
+            self.metadata_variants.remove(deps.storage, &token_id);
+        } else {
+            self.metadata_variants
+                .save(deps.storage, &token_id, &variants)?;
+        }
+
+        Ok(Response::new()
+            .add_attribute("action", "set_metadata_variants")
+            .add_attribute("token_id", token_id)
+            .add_attribute("variants", variants.len().to_string()))
+    }
+
+    pub fn update_token_metadata(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        token_id: String,
+        token_uri: Option<String>,
+        extension: T,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(deps.as_ref(), &info, Role::Curator)?;
+        let key = token_key(&token_id)?;
+        let mut token = self.tokens.load(deps.storage, key)?;
+        self.check_metadata_mutable(deps.storage, &token_id)?;
+        if let Some(token_uri) = &token_uri {
+            self.check_uri(deps.storage, token_uri)?;
+        }
+        self.check_extension(deps.storage, &extension)?;
+
+        // the content index follows the new token_uri
+        let old_keys = content_keys(&token.token_uri, &None);
+        let new_keys = content_keys(&token_uri, &None);
+        if self.unique_content.may_load(deps.storage)?.unwrap_or(false) {
+            for key in &new_keys {
+                match self.content_index.may_load(deps.storage, key)? {
+                    Some(other) if other != token_id => {
+                        return Err(ContractError::DuplicateContent { token_id: other })
+                    }
+                    _ => {}
+                }
+            }
+        }
+        for key in old_keys {
+            if self.content_index.may_load(deps.storage, &key)?.as_deref() == Some(&token_id) {
+                self.content_index.remove(deps.storage, &key);
+            }
+        }
+        self._index_content(deps.storage, &token_id, new_keys, None)?;
+
+        token.token_uri = token_uri;
+        token.extension = extension;
+        self.tokens.save(deps.storage, key, &token)?;
+        let event = format!("metadata/{}", token.token_uri.clone().unwrap_or_default());
+        self._append_provenance(deps.storage, &env, &token_id, event)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "update_token_metadata")
+            .add_attribute("sender", info.sender)
+            .add_attribute("token_id", token_id)
+            .add_attribute("token_uri", token.token_uri.unwrap_or_default()))
+    }
+
+    pub fn freeze_token_metadata(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        token_id: String,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(deps.as_ref(), &info, Role::Curator)?;
+        self.tokens.load(deps.storage, token_key(&token_id)?)?;
+        self.check_metadata_mutable(deps.storage, &token_id)?;
+
+        self.metadata_frozen
+            .save(deps.storage, &token_id, &env.block.time)?;
+        self._append_provenance(deps.storage, &env, &token_id, "metadata_frozen".to_string())?;
+
+        Ok(Response::new()
+            .add_attribute("action", "freeze_token_metadata")
+            .add_attribute("sender", info.sender)
+            .add_attribute("token_id", token_id))
+    }
+
+    /// returns an error once token_id's metadata is frozen
+    pub fn check_metadata_mutable(
+        &self,
+        storage: &dyn Storage,
+        token_id: &str,
+    ) -> Result<(), ContractError> {
+        if self.metadata_frozen.has(storage, token_id) {
+            return Err(ContractError::MetadataFrozen {});
+        }
+        Ok(())
+    }
+}
+
+// first refusal
+impl<'a, T, C> Cw721Contract<'a, T, C>
+where
+    T: Serialize + DeserializeOwned + Clone,
+    C: CustomMsg,
+{
+    pub fn set_first_refusal(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        window: Option<u64>,
+    ) -> Result<Response<C>, ContractError> {
+        if !self.artist_stats.has(deps.storage, &info.sender) {
+            return Err(ContractError::Unauthorized {});
+        }
+        match window {
+            Some(window) => {
+                if window == 0 || window > MAX_FIRST_REFUSAL_WINDOW {
+                    return Err(ContractError::InvalidFirstRefusalWindow {
+                        max: MAX_FIRST_REFUSAL_WINDOW,
+                    });
+                }
+                self.first_refusal_windows
+                    .save(deps.storage, &info.sender, &window)?;
+            }
+            None => self
+                .first_refusal_windows
+                .remove(deps.storage, &info.sender),
+        }
+
+        Ok(Response::new()
+            .add_attribute("action", "set_first_refusal")
+            .add_attribute("artist", info.sender)
+            .add_attribute("window", window.unwrap_or_default().to_string()))
+    }
+
+    pub fn match_first_refusal(
+        &self,
+        mut deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        token_id: String,
+    ) -> Result<Response<C>, ContractError> {
+        let refusal = self.first_refusals.load(deps.storage, &token_id)?;
+        let artist = self.token_artists.may_load(deps.storage, &token_id)?;
+        if artist.as_ref() != Some(&info.sender) {
+            return Err(ContractError::Unauthorized {});
+        }
+        if env.block.time >= refusal.until {
+            return Err(ContractError::FirstRefusalLapsed {});
+        }
+        let excess = take_payment(&info, &refusal.price)?;
+        self.first_refusals.remove(deps.storage, &token_id);
+        let offer = self
+            .offers
+            .load(deps.storage, (&token_id, &refusal.bidder))?;
+        self._remove_offer(deps.storage, &token_id, &refusal.bidder, &offer)?;
+
+        let token = self.tokens.load(deps.storage, token_key(&token_id)?)?;
+        let mut res = self._complete_sale(
+            &mut deps,
+            &env,
+            &token_id,
+            token,
+            &refusal.seller,
+            &info.sender,
+            &refusal.price,
+        )?;
+        res = res.add_message(BankMsg::Send {
+            to_address: refusal.bidder.to_string(),
+            amount: vec![offer.price],
+        });
+        if !excess.is_empty() {
+            res = res.add_message(BankMsg::Send {
+                to_address: info.sender.to_string(),
+                amount: excess,
+            });
+        }
+
+        Ok(res
+            .add_attribute("action", "match_first_refusal")
+            .add_attribute("artist", info.sender)
+            .add_attribute("token_id", token_id)
+            .add_attribute("seller", refusal.seller)
+            .add_attribute("bidder", refusal.bidder)
+            .add_attribute("price", refusal.price.to_string()))
+    }
+
+    pub fn complete_first_refusal(
+        &self,
+        mut deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        token_id: String,
+    ) -> Result<Response<C>, ContractError> {
+        let refusal = self.first_refusals.load(deps.storage, &token_id)?;
+        if env.block.time < refusal.until {
+            return Err(ContractError::FirstRefusalPending {
+                until: refusal.until,
+            });
+        }
+        self.first_refusals.remove(deps.storage, &token_id);
+        let offer = self
+            .offers
+            .load(deps.storage, (&token_id, &refusal.bidder))?;
+        self._remove_offer(deps.storage, &token_id, &refusal.bidder, &offer)?;
+
+        let token = self.tokens.load(deps.storage, token_key(&token_id)?)?;
+        let res = self
+            ._complete_sale(
+                &mut deps,
+                &env,
+                &token_id,
+                token,
+                &refusal.seller,
+                &refusal.bidder,
+                &refusal.price,
+            )?
+            .add_attribute("action", "complete_first_refusal")
+            .add_attribute("sender", info.sender.clone())
+            .add_attribute("token_id", token_id)
+            .add_attribute("seller", refusal.seller.clone())
+            .add_attribute("buyer", refusal.bidder.clone())
+            .add_attribute("price", refusal.price.to_string());
+        if info.sender == refusal.seller || info.sender == refusal.bidder {
+            return Ok(res);
+        }
+        self._pay_keeper(deps.storage, &info.sender, res)
+    }
+
+    /// end of the window the artist of token_id has to match a resale by
+    /// seller, if they hold a right of first refusal over it
+    fn first_refusal_until(
+        &self,
+        storage: &dyn Storage,
+        env: &Env,
+        token_id: &str,
+        seller: &Addr,
+    ) -> StdResult<Option<Timestamp>> {
+        let artist = match self.token_artists.may_load(storage, token_id)? {
+            Some(artist) if artist != *seller => artist,
+            _ => return Ok(None),
+        };
+        let window = self.first_refusal_windows.may_load(storage, &artist)?;
+        Ok(window.map(|window| env.block.time.plus_seconds(window)))
+    }
+}
+
+// artist buy-back
+impl<'a, T, C> Cw721Contract<'a, T, C>
+where
+    T: Serialize + DeserializeOwned + Clone,
+    C: CustomMsg,
+{
+    pub fn set_buyback(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        program: Option<BuyBackProgram>,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(deps.as_ref(), &info, Role::Admin)?;
+
+        // the pool is kept in the program's denom
+        let pool = self.refund_pool.may_load(deps.storage)?.unwrap_or_default();
+        let current_denom = self.buyback.may_load(deps.storage)?.map(|p| p.price.denom);
+        let new_denom = program.as_ref().map(|p| p.price.denom.clone());
+        if !pool.balance.is_zero() && current_denom != new_denom {
+            return Err(ContractError::RefundPoolNotEmpty {});
+        }
+
+        match program {
+            Some(program) => {
+                if program.price.amount.is_zero() || program.end <= program.start {
+                    return Err(ContractError::InvalidBuyBack {});
+                }
+                self.buyback.save(deps.storage, &program)?;
+            }
+            None => self.buyback.remove(deps.storage),
+        }
+
+        Ok(Response::new()
+            .add_attribute("action", "set_buyback")
+            .add_attribute("sender", info.sender))
+    }
+
+    pub fn fund_refund_pool(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(deps.as_ref(), &info, Role::Admin)?;
+        let program = self
+            .buyback
+            .may_load(deps.storage)?
+            .ok_or(ContractError::BuyBackDisabled {})?;
+        let amount = must_pay(&info, &program.price.denom)?;
+
+        let mut pool = self.refund_pool.may_load(deps.storage)?.unwrap_or_default();
+        pool.balance += amount;
+        pool.funded += amount;
+        self.refund_pool.save(deps.storage, &pool)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "fund_refund_pool")
+            .add_attribute("sender", info.sender)
+            .add_attribute("amount", amount)
+            .add_attribute("balance", pool.balance))
+    }
+
+    pub fn withdraw_refund_pool(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        amount: Uint128,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(deps.as_ref(), &info, Role::Treasurer)?;
+        let program = self
+            .buyback
+            .may_load(deps.storage)?
+            .ok_or(ContractError::BuyBackDisabled {})?;
+        // holders are promised the pool until the window closes
+        if env.block.time < program.end {
+            return Err(ContractError::BuyBackRunning {});
+        }
+
+        let mut pool = self.refund_pool.may_load(deps.storage)?.unwrap_or_default();
+        if amount > pool.balance {
+            return Err(ContractError::RefundPoolInsufficient {
+                balance: pool.balance,
+            });
+        }
+        pool.balance -= amount;
+        pool.withdrawn += amount;
+        self.refund_pool.save(deps.storage, &pool)?;
+
+        Ok(Response::new()
+            .add_message(BankMsg::Send {
+                to_address: info.sender.to_string(),
+                amount: coins(amount.u128(), program.price.denom),
+            })
+            .add_attribute("action", "withdraw_refund_pool")
+            .add_attribute("sender", info.sender)
+            .add_attribute("amount", amount))
+    }
+
+    pub fn burn_for_refund(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        token_id: String,
+    ) -> Result<Response<C>, ContractError> {
+        let program = self
+            .buyback
+            .may_load(deps.storage)?
+            .ok_or(ContractError::BuyBackDisabled {})?;
+        if env.block.time < program.start || env.block.time >= program.end {
+            return Err(ContractError::BuyBackNotOpen {});
+        }
+
+        // the refund goes to the holder, so only the holder can claim it
+        let token = self.tokens.load(deps.storage, token_key(&token_id)?)?;
+        if token.owner != info.sender {
+            return Err(ContractError::Unauthorized {});
+        }
+
+        let mut pool = self.refund_pool.may_load(deps.storage)?.unwrap_or_default();
+        if pool.balance < program.price.amount {
+            return Err(ContractError::RefundPoolInsufficient {
+                balance: pool.balance,
+            });
+        }
+        pool.balance -= program.price.amount;
+        pool.refunded += program.price.amount;
+        self.refund_pool.save(deps.storage, &pool)?;
+
+        self._remove_token(
+            deps.storage,
+            &env,
+            &token_id,
+            &token.owner,
+            RemovalKind::Refunded,
+            &info.sender,
+        )?;
+        let refund = Refund {
+            holder: info.sender.clone(),
+            amount: program.price.clone(),
+            refunded_at: env.block.time,
+        };
+        self.refunds.save(deps.storage, &token_id, &refund)?;
+
+        Ok(Response::new()
+            .add_message(BankMsg::Send {
+                to_address: info.sender.to_string(),
+                amount: vec![program.price.clone()],
+            })
+            .add_attribute("action", "burn_for_refund")
+            .add_attribute("sender", info.sender)
+            .add_attribute("token_id", token_id)
+            .add_attribute("refund", program.price.to_string()))
+    }
+}
+
+// vault
+impl<'a, T, C> Cw721Contract<'a, T, C>
+where
+    T: Serialize + DeserializeOwned + Clone,
+    C: CustomMsg,
+{
+    pub fn update_vault_collections(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        add: Vec<String>,
+        remove: Vec<String>,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(deps.as_ref(), &info, Role::Admin)?;
+
+        for collection in &add {
+            let addr = deps.api.addr_validate(collection)?;
+            self.vault_collections
+                .save(deps.storage, &addr, &Empty {})?;
+        }
+        for collection in &remove {
+            let addr = deps.api.addr_validate(collection)?;
+            self.vault_collections.remove(deps.storage, &addr);
+        }
+
+        Ok(Response::new()
+            .add_attribute("action", "update_vault_collections")
+            .add_attribute("sender", info.sender)
+            .add_attribute("added", add.len().to_string())
+            .add_attribute("removed", remove.len().to_string()))
+    }
+
+    /// mints a receipt token to the depositor of an external token
+    pub fn receive_nft(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        msg: Cw721ReceiveMsg,
+    ) -> Result<Response<C>, ContractError> {
+        // the sender is the collection contract that transferred the token to us
+        let collection = info.sender;
+        if collection == env.contract.address
+            || !self.vault_collections.has(deps.storage, &collection)
+        {
+            return Err(ContractError::VaultCollectionNotAllowed {
+                collection: collection.into_string(),
+            });
+        }
+        if self
+            .vault_receipts
+            .has(deps.storage, (&collection, &msg.token_id))
+        {
+            return Err(ContractError::Claimed {});
+        }
+        let deposit_msg: VaultDepositMsg<T> = from_json(&msg.msg)?;
+        let depositor = deps.api.addr_validate(&msg.sender)?;
+        self.count_exempt_mints(deps.storage, 1)?;
+
+        let receipt_id = self._create_token(
+            deps.storage,
+            &env,
+            depositor.clone(),
+            &depositor,
+            None,
+            deposit_msg.extension,
+        )?;
+        let deposit = VaultDeposit {
+            collection: collection.clone(),
+            token_id: msg.token_id.clone(),
+            depositor: depositor.clone(),
+            deposited_at: env.block.time,
+        };
+        self.vault_deposits
+            .save(deps.storage, &receipt_id, &deposit)?;
+        self.vault_receipts
+            .save(deps.storage, (&collection, &msg.token_id), &receipt_id)?;
+        let outstanding = self
+            .vault_receipt_count
+            .may_load(deps.storage)?
+            .unwrap_or_default();
+        self.vault_receipt_count
+            .save(deps.storage, &(outstanding + 1))?;
+
+        Ok(Response::new()
+            .add_attribute("action", "vault_deposit")
+            .add_attribute("depositor", depositor)
+            .add_attribute("collection", collection)
+            .add_attribute("deposited_token_id", msg.token_id)
+            .add_attribute("token_id", receipt_id))
+    }
+
+    pub fn redeem_vault_receipt(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        token_id: String,
+    ) -> Result<Response<C>, ContractError> {
+        let deposit = self.vault_deposits.load(deps.storage, &token_id)?;
+        let token = self.tokens.load(deps.storage, token_key(&token_id)?)?;
+        self.check_can_send(deps.as_ref(), &env, &info, &token_id, &token)?;
+
+        self.vault_deposits.remove(deps.storage, &token_id);
+        self.vault_receipts
+            .remove(deps.storage, (&deposit.collection, &deposit.token_id));
+        let outstanding = self.vault_receipt_count.load(deps.storage)?;
+        self.vault_receipt_count
+            .save(deps.storage, &(outstanding - 1))?;
+        self._remove_token(
+            deps.storage,
+            &env,
+            &token_id,
+            &token.owner,
+            RemovalKind::Redeemed,
+            &info.sender,
+        )?;
+
+        let transfer = WasmMsg::Execute {
+            contract_addr: deposit.collection.to_string(),
+            msg: to_json_binary(&Cw721ExecuteMsg::TransferNft {
+                recipient: token.owner.to_string(),
+                token_id: deposit.token_id.clone(),
+            })?,
+            funds: vec![],
+        };
+
+        Ok(Response::new()
+            .add_message(transfer)
+            .add_attribute("action", "redeem_vault_receipt")
+            .add_attribute("sender", info.sender)
+            .add_attribute("owner", token.owner)
+            .add_attribute("token_id", token_id)
+            .add_attribute("collection", deposit.collection)
+            .add_attribute("deposited_token_id", deposit.token_id))
+    }
+
+    /// royalties owed on a sale of token_id at sale_price. A vault receipt
+    /// owes what the deposited token's origin collection asks through
+    /// cw2981, and this collection's own royalties only if its origin
+    /// answers none
+    pub fn sale_royalties(
+        &self,
+        deps: Deps,
+        env: &Env,
+        token_id: &str,
+        token: &TokenInfo<T>,
+        sale_price: Uint128,
+    ) -> StdResult<Vec<(Addr, Uint128)>> {
+        if let Some(deposit) = self.vault_deposits.may_load(deps.storage, token_id)? {
+            let royalty_info = Cw2981QueryMsg::RoyaltyInfo {
+                token_id: deposit.token_id,
+                sale_price,
+            };
+            let extension = Cw2981QueryMsg::Extension {
+                msg: Box::new(royalty_info.clone()),
+            };
+            // a collection that fails both queries has no cw2981 royalties
+            let origin: Option<RoyaltiesInfoResponse> = deps
+                .querier
+                .query_wasm_smart(&deposit.collection, &extension)
+                .or_else(|_| {
+                    deps.querier
+                        .query_wasm_smart(&deposit.collection, &royalty_info)
+                })
+                .ok();
+            let origin = origin
+                .filter(|info| !info.royalty_amount.is_zero())
+                .and_then(|info| {
+                    let address = deps.api.addr_validate(&info.address).ok()?;
+                    Some((address, info.royalty_amount.min(sale_price)))
+                });
+            if let Some(origin) = origin {
+                return Ok(vec![origin]);
+            }
+        }
+        Ok(self
+            .royalties_at(deps.storage, token_id, token, env.block.time)?
+            .into_iter()
+            .map(|royalty| {
+                let amount = sale_price.multiply_ratio(royalty.share_bps, 10_000u128);
+                (royalty.payment_address, amount)
+            })
+            .collect())
+    }
+}
+
+// gallery visit vouchers
+impl<'a, T, C> Cw721Contract<'a, T, C>
+where
+    T: Serialize + DeserializeOwned + Clone,
+    C: CustomMsg,
+{
+    pub fn update_partner_galleries(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        add: Vec<String>,
+        remove: Vec<String>,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(deps.as_ref(), &info, Role::Admin)?;
+
+        for gallery in &add {
+            let addr = self.resolve_addr(deps.as_ref(), gallery)?;
+            self.partner_galleries
+                .save(deps.storage, &addr, &Empty {})?;
+            self.permission_sweeps.remove(deps.storage, &addr);
+        }
+        for gallery in &remove {
+            let addr = self.resolve_addr(deps.as_ref(), gallery)?;
+            if !self.partner_galleries.has(deps.storage, &addr) {
+                continue;
+            }
+            self.partner_galleries.remove(deps.storage, &addr);
+            let sweep = PermissionSweep {
+                removed_at: env.block.time,
+                stage: SweepStage::OperatorGrants,
+            };
+            self.permission_sweeps.save(deps.storage, &addr, &sweep)?;
+            // the rest is left to SweepPermissions
+            self._sweep_permissions(deps.storage, &addr, DEFAULT_CLEANUP_BATCH as usize)?;
+        }
+
+        Ok(Response::new()
+            .add_attribute("action", "update_partner_galleries")
+            .add_attribute("sender", info.sender)
+            .add_attribute("added", add.len().to_string())
+            .add_attribute("removed", remove.len().to_string()))
+    }
+
+    /// The limit may be set below the vouchers already issued, which stops
+    /// further issuance without touching outstanding vouchers
+    pub fn set_voucher_limit(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        parent_token_id: String,
+        limit: u32,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(deps.as_ref(), &info, Role::Admin)?;
+        self.tokens
+            .load(deps.storage, token_key(&parent_token_id)?)?;
+        if self.vouchers.has(deps.storage, &parent_token_id) {
+            return Err(ContractError::VoucherParent {});
+        }
+
+        let mut issuance = self
+            .voucher_issuance
+            .may_load(deps.storage, &parent_token_id)?
+            .unwrap_or_default();
+        issuance.limit = limit;
+        self.voucher_issuance
+            .save(deps.storage, &parent_token_id, &issuance)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "set_voucher_limit")
+            .add_attribute("sender", info.sender)
+            .add_attribute("parent_token_id", parent_token_id)
+            .add_attribute("limit", limit.to_string()))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn issue_voucher(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        parent_token_id: String,
+        owner: String,
+        token_uri: Option<String>,
+        extension: T,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(deps.as_ref(), &info, Role::Minter)?;
+        self.tokens
+            .load(deps.storage, token_key(&parent_token_id)?)?;
+        let mut issuance = self
+            .voucher_issuance
+            .may_load(deps.storage, &parent_token_id)?
+            .unwrap_or_default();
+        if issuance.issued >= issuance.limit {
+            return Err(ContractError::VoucherLimitReached {
+                limit: issuance.limit,
+            });
+        }
+        issuance.issued += 1;
+        self.voucher_issuance
+            .save(deps.storage, &parent_token_id, &issuance)?;
+
+        let owner = self.resolve_addr(deps.as_ref(), &owner)?;
+        let token_id = self._create_token(
+            deps.storage,
+            &env,
+            owner.clone(),
+            &info.sender,
+            token_uri,
+            extension,
+        )?;
+        let voucher = Voucher {
+            parent: parent_token_id.clone(),
+            issued_at: env.block.time,
+        };
+        self.vouchers.save(deps.storage, &token_id, &voucher)?;
+        self.count_exempt_mints(deps.storage, 1)?;
+        let outstanding = self
+            .voucher_count
+            .may_load(deps.storage)?
+            .unwrap_or_default();
+        self.voucher_count.save(deps.storage, &(outstanding + 1))?;
+
+        Ok(Response::new()
+            .add_attribute("action", "issue_voucher")
+            .add_attribute("sender", info.sender)
+            .add_attribute("owner", owner)
+            .add_attribute("parent_token_id", parent_token_id)
+            .add_attribute("token_id", token_id)
+            .add_attribute("issued", issuance.issued.to_string()))
+    }
+
+    pub fn redeem_voucher(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        token_id: String,
+    ) -> Result<Response<C>, ContractError> {
+        if !self.partner_galleries.has(deps.storage, &info.sender) {
+            return Err(ContractError::NotPartnerGallery {});
+        }
+        let voucher = self
+            .vouchers
+            .may_load(deps.storage, &token_id)?
+            .ok_or(ContractError::NotVoucher {})?;
+        let token = self.tokens.load(deps.storage, token_key(&token_id)?)?;
+        self.check_can_send(deps.as_ref(), &env, &info, &token_id, &token)?;
+
+        let redemption = VoucherRedemption {
+            voucher_id: token_id.clone(),
+            holder: token.owner.clone(),
+            gallery: info.sender.clone(),
+            redeemed_at: env.block.time,
+        };
+        self.voucher_redemptions
+            .save(deps.storage, (&voucher.parent, &token_id), &redemption)?;
+        self.voucher_issuance.update(
+            deps.storage,
+            &voucher.parent,
+            |issuance| -> StdResult<_> {
+                let mut issuance: VoucherIssuance = issuance.unwrap_or_default();
+                issuance.redeemed += 1;
+                Ok(issuance)
+            },
+        )?;
+        self._remove_token(
+            deps.storage,
+            &env,
+            &token_id,
+            &token.owner,
+            RemovalKind::VoucherRedeemed {
+                gallery: info.sender.clone(),
+            },
+            &info.sender,
+        )?;
+
+        Ok(Response::new()
+            .add_attribute("action", "redeem_voucher")
+            .add_attribute("gallery", info.sender)
+            .add_attribute("holder", token.owner)
+            .add_attribute("token_id", token_id)
+            .add_attribute("parent_token_id", voucher.parent))
+    }
+}
+
+// featured tokens
+impl<'a, T, C> Cw721Contract<'a, T, C>
+where
+    T: Serialize + DeserializeOwned + Clone,
+    C: CustomMsg,
+{
+    pub fn set_curator(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        curator: Option<String>,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(deps.as_ref(), &info, Role::Admin)?;
+
+        match curator {
+            Some(curator) => {
+                let curator = self.resolve_addr(deps.as_ref(), &curator)?;
+                self.curator.save(deps.storage, &curator)?;
+            }
+            None => self.curator.remove(deps.storage),
+        }
+
+        Ok(Response::new()
+            .add_attribute("action", "set_curator")
+            .add_attribute("sender", info.sender))
+    }
+
+    pub fn set_featured(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        tokens: Vec<FeaturedToken>,
+    ) -> Result<Response<C>, ContractError> {
+        // the admin can always stand in for the curator
+        if self.curator.may_load(deps.storage)?.as_ref() != Some(&info.sender) {
+            self.assert_role(deps.as_ref(), &info, Role::Admin)?;
+        }
+
+        if tokens.len() > MAX_FEATURED {
+            return Err(ContractError::TooManyFeatured { max: MAX_FEATURED });
+        }
+        for (i, featured) in tokens.iter().enumerate() {
+            self.tokens
+                .load(deps.storage, token_key(&featured.token_id)?)?;
+            if tokens[..i].iter().any(|f| f.token_id == featured.token_id) {
+                return Err(ContractError::DuplicateFeatured {
+                    token_id: featured.token_id.clone(),
+                });
+            }
+            if featured.blurb.chars().count() > MAX_BLURB_LENGTH {
+                return Err(ContractError::BlurbTooLong {
+                    max: MAX_BLURB_LENGTH,
+                });
+            }
+        }
+        self.featured.save(deps.storage, &tokens)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "set_featured")
+            .add_attribute("sender", info.sender)
+            .add_attribute("featured", tokens.len().to_string()))
+    }
+}
+
+// curation fees
+impl<'a, T, C> Cw721Contract<'a, T, C>
+where
+    T: Serialize + DeserializeOwned + Clone,
+    C: CustomMsg,
+{
+    pub fn set_curation_fee(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        bps: u16,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(deps.as_ref(), &info, Role::Admin)?;
+        if bps > 10_000 {
+            return Err(ContractError::InvalidCurationFee {});
+        }
+        self.curation_fee_bps.save(deps.storage, &bps)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "set_curation_fee")
+            .add_attribute("sender", info.sender)
+            .add_attribute("bps", bps.to_string()))
+    }
+
+    pub fn set_referral(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        referral_id: String,
+        curator: Option<String>,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(deps.as_ref(), &info, Role::Admin)?;
+        if referral_id.is_empty() {
+            return Err(ContractError::InvalidReferral {});
+        }
+        self.check_provenance_field(deps.storage, &referral_id)?;
+
+        match curator {
+            Some(curator) => {
+                let curator = self.resolve_addr(deps.as_ref(), &curator)?;
+                self.referrals.save(deps.storage, &referral_id, &curator)?;
+            }
+            None => self.referrals.remove(deps.storage, &referral_id),
+        }
+
+        Ok(Response::new()
+            .add_attribute("action", "set_referral")
+            .add_attribute("sender", info.sender)
+            .add_attribute("referral_id", referral_id))
+    }
+
+    /// curator credited by a referral link id, erroring on unknown ids so a
+    /// mistyped link isn't silently dropped
+    pub fn referral_curator(
+        &self,
+        storage: &dyn Storage,
+        referral: &Option<String>,
+    ) -> Result<Option<Addr>, ContractError> {
+        match referral {
+            Some(referral_id) => match self.referrals.may_load(storage, referral_id)? {
+                Some(curator) => Ok(Some(curator)),
+                None => Err(ContractError::UnknownReferral {
+                    referral_id: referral_id.clone(),
+                }),
+            },
+            None => Ok(None),
+        }
+    }
+
+    /// the featured list's curator, when token_id is featured
+    pub fn featured_curator(
+        &self,
+        storage: &dyn Storage,
+        token_id: &str,
+    ) -> StdResult<Option<Addr>> {
+        let featured = self.featured.may_load(storage)?.unwrap_or_default();
+        if !featured.iter().any(|f| f.token_id == token_id) {
+            return Ok(None);
+        }
+        self.curator.may_load(storage)
+    }
+
+    /// Curation fee owed to curator on a sale at price, capped at what is left
+    /// of the proceeds after withholding
+    pub fn curation_fee_for(
+        &self,
+        storage: &dyn Storage,
+        curator: Option<Addr>,
+        price: &Coin,
+        available: Uint128,
+    ) -> StdResult<Option<CurationFee>> {
+        let curator = match curator {
+            Some(curator) => curator,
+            None => return Ok(None),
+        };
+        let bps = self.curation_fee_bps.may_load(storage)?.unwrap_or_default();
+        let amount = withholding_amount(bps, price.amount).min(available);
+        if amount.is_zero() {
+            return Ok(None);
+        }
+        Ok(Some(CurationFee { curator, amount }))
+    }
+
+    /// pays the curation fee to the curator and adds it to their earnings
+    pub fn add_curation_fee(
+        &self,
+        storage: &mut dyn Storage,
+        res: Response<C>,
+        curation: &CurationFee,
+        denom: &str,
+    ) -> StdResult<Response<C>> {
+        self.curator_earnings.update(
+            storage,
+            (&curation.curator, denom),
+            |earned| -> StdResult<_> { Ok(earned.unwrap_or_default() + curation.amount) },
+        )?;
+        Ok(res
+            .add_message(BankMsg::Send {
+                to_address: curation.curator.to_string(),
+                amount: coins(curation.amount.u128(), denom),
+            })
+            .add_attribute("curator", curation.curator.clone())
+            .add_attribute("curation_fee", curation.amount))
+    }
+}
+
+// royalties
+impl<'a, T, C> Cw721Contract<'a, T, C>
+where
+    T: Serialize + DeserializeOwned + Clone,
+    C: CustomMsg,
+{
+    pub fn set_token_royalty(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        token_id: String,
+        royalty: Option<RoyaltyConfig>,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(deps.as_ref(), &info, Role::Admin)?;
+        let mut token = self.tokens.load(deps.storage, token_key(&token_id)?)?;
+        token.royalty = royalty
+            .map(|royalty| self.check_royalty(deps.as_ref(), royalty))
+            .transpose()?;
+        self.tokens
+            .save(deps.storage, token_key(&token_id)?, &token)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "set_token_royalty")
+            .add_attribute("sender", info.sender)
+            .add_attribute("token_id", token_id))
+    }
+
+    pub fn set_royalty_split(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        token_id: String,
+        payees: Option<Vec<RoyaltyConfig>>,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(deps.as_ref(), &info, Role::Admin)?;
+        self.tokens.load(deps.storage, token_key(&token_id)?)?;
+        // successions already in effect would override the new split
+        self._prune_royalty_successions(deps.storage, &token_id, env.block.time)?;
+
+        let count = match payees {
+            Some(payees) => {
+                let split = self.check_royalty_split(deps.as_ref(), payees)?;
+                self.royalty_splits.save(deps.storage, &token_id, &split)?;
+                split.payees.len()
+            }
+            None => {
+                self.royalty_splits.remove(deps.storage, &token_id);
+                0
+            }
+        };
+
+        Ok(Response::new()
+            .add_attribute("action", "set_royalty_split")
+            .add_attribute("sender", info.sender)
+            .add_attribute("token_id", token_id)
+            .add_attribute("payees", count.to_string()))
+    }
+
+    pub fn schedule_royalty_succession(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        token_id: String,
+        effective_at: Timestamp,
+        payees: Vec<RoyaltyConfig>,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(deps.as_ref(), &info, Role::Admin)?;
+        self.tokens.load(deps.storage, token_key(&token_id)?)?;
+        if effective_at <= env.block.time {
+            return Err(ContractError::RoyaltySuccessionInPast {});
+        }
+        let split = self.check_royalty_split(deps.as_ref(), payees)?;
+
+        let mut successions = self
+            .royalty_successions
+            .may_load(deps.storage, &token_id)?
+            .unwrap_or_default();
+        successions.retain(|s| s.effective_at != effective_at);
+        // only the latest succession already in effect still matters
+        let in_effect = successions.partition_point(|s| s.effective_at <= env.block.time);
+        successions.drain(..in_effect.saturating_sub(1));
+        if successions.len() >= MAX_ROYALTY_SUCCESSIONS {
+            return Err(ContractError::TooManyRoyaltySuccessions {
+                max: MAX_ROYALTY_SUCCESSIONS,
+            });
+        }
+        let count = split.payees.len();
+        let at = successions.partition_point(|s| s.effective_at < effective_at);
+        successions.insert(
+            at,
+            RoyaltySuccession {
+                effective_at,
+                split,
+            },
+        );
+        self.royalty_successions
+            .save(deps.storage, &token_id, &successions)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "schedule_royalty_succession")
+            .add_attribute("sender", info.sender)
+            .add_attribute("token_id", token_id)
+            .add_attribute("effective_at", effective_at.to_string())
+            .add_attribute("payees", count.to_string()))
+    }
+
+    pub fn cancel_royalty_succession(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        token_id: String,
+        effective_at: Timestamp,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(deps.as_ref(), &info, Role::Admin)?;
+        if effective_at <= env.block.time {
+            return Err(ContractError::RoyaltySuccessionInPast {});
+        }
+
+        let mut successions = self
+            .royalty_successions
+            .may_load(deps.storage, &token_id)?
+            .unwrap_or_default();
+        let count = successions.len();
+        successions.retain(|s| s.effective_at != effective_at);
+        if successions.len() == count {
+            return Err(ContractError::NoRoyaltySuccession {});
+        }
+        if successions.is_empty() {
+            self.royalty_successions.remove(deps.storage, &token_id);
+        } else {
+            self.royalty_successions
+                .save(deps.storage, &token_id, &successions)?;
+        }
+
+        Ok(Response::new()
+            .add_attribute("action", "cancel_royalty_succession")
+            .add_attribute("sender", info.sender)
+            .add_attribute("token_id", token_id)
+            .add_attribute("effective_at", effective_at.to_string()))
+    }
+
+    pub fn set_royalty_swap(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        swap: Option<String>,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(deps.as_ref(), &info, Role::Admin)?;
+        match &swap {
+            Some(swap) => {
+                let swap = deps.api.addr_validate(swap)?;
+                self.royalty_swap.save(deps.storage, &swap)?;
+            }
+            None => self.royalty_swap.remove(deps.storage),
+        }
+
+        Ok(Response::new()
+            .add_attribute("action", "set_royalty_swap")
+            .add_attribute("sender", info.sender)
+            .add_attribute("swap", swap.unwrap_or_else(|| "none".to_string())))
+    }
+
+    pub fn set_royalty_payout_denom(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        denom: Option<String>,
+    ) -> Result<Response<C>, ContractError> {
+        match &denom {
+            Some(denom) => {
+                if denom.len() < 3 || denom.len() > 128 {
+                    return Err(ContractError::InvalidPayoutDenom {});
+                }
+                self.payout_denoms.save(deps.storage, &info.sender, denom)?;
+            }
+            None => self.payout_denoms.remove(deps.storage, &info.sender),
+        }
+
+        Ok(Response::new()
+            .add_attribute("action", "set_royalty_payout_denom")
+            .add_attribute("sender", info.sender)
+            .add_attribute("denom", denom.unwrap_or_else(|| "none".to_string())))
+    }
+
+    pub fn claim_accrued_royalties(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+    ) -> Result<Response<C>, ContractError> {
+        let accrued = self
+            .accrued_royalties
+            .prefix(&info.sender)
+            .range(deps.storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()?;
+        if accrued.is_empty() {
+            return Err(ContractError::NothingToWithdraw {});
+        }
+        let mut amount = vec![];
+        for (denom, accrued) in accrued {
+            self.accrued_royalties
+                .remove(deps.storage, (&info.sender, &denom));
+            self._debit_treasury(
+                deps.storage,
+                TreasuryAccount::AccruedRoyalties,
+                &denom,
+                accrued,
+            )?;
+            amount.push(Coin {
+                denom,
+                amount: accrued,
+            });
+        }
+
+        let claimed = amount
+            .iter()
+            .map(|c| c.to_string())
+            .collect::<Vec<_>>()
+            .join(",");
+        Ok(Response::new()
+            .add_message(BankMsg::Send {
+                to_address: info.sender.to_string(),
+                amount,
+            })
+            .add_attribute("action", "claim_accrued_royalties")
+            .add_attribute("sender", info.sender)
+            .add_attribute("amount", claimed))
+    }
+
+    /// pays a royalty to recipient, through the swap contract when they
+    /// asked to be paid in another denom
+    pub fn _pay_royalty(
+        &self,
+        storage: &mut dyn Storage,
+        recipient: &Addr,
+        payment: Coin,
+        res: Response<C>,
+    ) -> StdResult<Response<C>> {
+        let ask_denom = self
+            .payout_denoms
+            .may_load(storage, recipient)?
+            .filter(|denom| *denom != payment.denom);
+        let swap = self.royalty_swap.may_load(storage)?;
+        let (ask_denom, swap) = match (ask_denom, swap) {
+            (Some(ask_denom), Some(swap)) => (ask_denom, swap),
+            _ => {
+                return Ok(res.add_message(BankMsg::Send {
+                    to_address: recipient.to_string(),
+                    amount: vec![payment],
+                }))
+            }
+        };
+        let msg = WasmMsg::Execute {
+            contract_addr: swap.into_string(),
+            msg: to_json_binary(&SwapExecuteMsg::Swap {
+                ask_denom,
+                recipient: recipient.to_string(),
+            })?,
+            funds: vec![payment.clone()],
+        };
+        let pending = PendingReply::RoyaltySwap {
+            recipient: recipient.clone(),
+            payment,
+        };
+        let id = self._expect_reply(storage, &pending)?;
+        Ok(res.add_submessage(SubMsg::reply_always(msg, id)))
+    }
+
+    /// finishes a royalty swap: a failed one accrues the royalty to its
+    /// recipient in the sale's denom
+    pub fn _finish_royalty_swap(
+        &self,
+        storage: &mut dyn Storage,
+        recipient: Addr,
+        payment: Coin,
+        result: SubMsgResult,
+    ) -> StdResult<Response<C>> {
+        let res = Response::new()
+            .add_attribute("action", "royalty_swap")
+            .add_attribute("recipient", recipient.to_string())
+            .add_attribute("payment", payment.to_string());
+        if let SubMsgResult::Err(err) = result {
+            let key = (&recipient, payment.denom.as_str());
+            let accrued = self
+                .accrued_royalties
+                .may_load(storage, key)?
+                .unwrap_or_default();
+            self.accrued_royalties
+                .save(storage, key, &(accrued + payment.amount))?;
+            self._credit_treasury(
+                storage,
+                TreasuryAccount::AccruedRoyalties,
+                &payment.denom,
+                payment.amount,
+            )?;
+            return Ok(res
+                .add_attribute("accrued", "true")
+                .add_attribute("error", err));
+        }
+        Ok(res.add_attribute("accrued", "false"))
+    }
+
+    /// drops successions already in effect at `now`
+    fn _prune_royalty_successions(
+        &self,
+        storage: &mut dyn Storage,
+        token_id: &str,
+        now: Timestamp,
+    ) -> StdResult<()> {
+        let mut successions = match self.royalty_successions.may_load(storage, token_id)? {
+            Some(successions) => successions,
+            None => return Ok(()),
+        };
+        successions.retain(|s| s.effective_at > now);
+        if successions.is_empty() {
+            self.royalty_successions.remove(storage, token_id);
+            return Ok(());
+        }
+        self.royalty_successions
+            .save(storage, token_id, &successions)
+    }
+
+    pub fn check_royalty_split(
+        &self,
+        deps: Deps,
+        payees: Vec<RoyaltyConfig>,
+    ) -> Result<RoyaltySplit, ContractError> {
+        if payees.is_empty() {
+            return Err(ContractError::InvalidRoyalty {});
+        }
+        if payees.len() > MAX_ROYALTY_PAYEES {
+            return Err(ContractError::TooManyRoyaltyPayees {
+                max: MAX_ROYALTY_PAYEES,
+            });
+        }
+        let mut split = RoyaltySplit { payees: vec![] };
+        let mut total = 0u32;
+        for payee in payees {
+            let payee = self.check_royalty(deps, payee)?;
+            if payee.share_bps == 0
+                || split
+                    .payees
+                    .iter()
+                    .any(|p| p.payment_address == payee.payment_address)
+            {
+                return Err(ContractError::InvalidRoyalty {});
+            }
+            total += payee.share_bps as u32;
+            split.payees.push(payee);
+        }
+        if total > 10_000 {
+            return Err(ContractError::RoyaltySplitExceeded {});
+        }
+        Ok(split)
+    }
+
+    pub fn check_royalty(
+        &self,
+        deps: Deps,
+        royalty: RoyaltyConfig,
+    ) -> Result<Royalty, ContractError> {
+        if royalty.share_bps > 10_000 {
+            return Err(ContractError::InvalidRoyalty {});
+        }
+        Ok(Royalty {
+            payment_address: self.resolve_addr(deps, &royalty.payment_address)?,
+            share_bps: royalty.share_bps,
+        })
+    }
+}
+
+/// amount paid out to the curator of a sale
+fn curated(curation: &Option<CurationFee>) -> Uint128 {
+    curation.as_ref().map(|c| c.amount).unwrap_or_default()
+}
+
+// artists of record
+impl<'a, T, C> Cw721Contract<'a, T, C>
+where
+    T: Serialize + DeserializeOwned + Clone,
+    C: CustomMsg,
+{
+    pub fn set_artist_of_record(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        token_id: String,
+        artist: Option<String>,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(deps.as_ref(), &info, Role::Curator)?;
+        let key = token_key(&token_id)?;
+        self.tokens.load(deps.storage, key)?;
+
+        self._clear_artist(deps.storage, &token_id)?;
+        if let Some(artist) = &artist {
+            let artist = self.resolve_addr(deps.as_ref(), artist)?;
+            self._set_artist(deps.storage, &token_id, &artist)?;
+        }
+
+        Ok(Response::new()
+            .add_attribute("action", "set_artist_of_record")
+            .add_attribute("sender", info.sender)
+            .add_attribute("token_id", token_id)
+            .add_attribute("artist", artist.unwrap_or_default()))
+    }
+
+    /// Artist: set or clear the resale floor of the sender's works
+    pub fn set_price_floor(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        floor: Option<PriceFloor>,
+    ) -> Result<Response<C>, ContractError> {
+        match floor {
+            Some(floor) => {
+                if floor.price.amount.is_zero() || floor.until <= env.block.time {
+                    return Err(ContractError::InvalidPriceFloor {});
+                }
+                self.price_floors.save(deps.storage, &info.sender, &floor)?;
+            }
+            None => self.price_floors.remove(deps.storage, &info.sender),
+        }
+
+        Ok(Response::new()
+            .add_attribute("action", "set_price_floor")
+            .add_attribute("artist", info.sender))
+    }
+
+    /// fails if `price` undercuts the resale floor set by the token's artist.
+    /// Primary sales are never held to it
+    pub fn check_price_floor(
+        &self,
+        storage: &dyn Storage,
+        env: &Env,
+        token_id: &str,
+        price: &Coin,
+    ) -> Result<(), ContractError> {
+        if !self.sale_receipts.has(storage, token_id) {
+            return Ok(());
+        }
+        if let Some(floor) = self.price_floor(storage, &env.block, token_id)? {
+            if price.denom != floor.price.denom || price.amount < floor.price.amount {
+                return Err(ContractError::PriceBelowFloor { floor: floor.price });
+            }
+        }
+        Ok(())
+    }
+
+    /// makes artist the token's artist of record, which must have none
+    pub fn _set_artist(
+        &self,
+        storage: &mut dyn Storage,
+        token_id: &str,
+        artist: &Addr,
+    ) -> StdResult<()> {
+        self.token_artists.save(storage, token_id, artist)?;
+        self.artist_tokens
+            .save(storage, (artist, token_key(token_id)?), &Empty {})?;
+        let mut stats = self
+            .artist_stats
+            .may_load(storage, artist)?
+            .unwrap_or_default();
+        stats.works += 1;
+        self.artist_stats.save(storage, artist, &stats)
+    }
+
+    /// removes the token from its artist's works, if it has an artist of record
+    pub fn _clear_artist(&self, storage: &mut dyn Storage, token_id: &str) -> StdResult<()> {
+        if let Some(artist) = self.token_artists.may_load(storage, token_id)? {
+            self.token_artists.remove(storage, token_id);
+            self.artist_tokens
+                .remove(storage, (&artist, token_key(token_id)?));
+            let mut stats = self.artist_stats.load(storage, &artist)?;
+            stats.works -= 1;
+            self.artist_stats.save(storage, &artist, &stats)?;
+        }
+        Ok(())
+    }
+}
+
+// artist keys
+impl<'a, T, C> Cw721Contract<'a, T, C>
+where
+    T: Serialize + DeserializeOwned + Clone,
+    C: CustomMsg,
+{
+    pub fn register_artist_key(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        pubkey: Binary,
+    ) -> Result<Response<C>, ContractError> {
+        check_pubkey(&pubkey)?;
+        if self.artist_key_count.has(deps.storage, &info.sender) {
+            return Err(ContractError::ArtistKeyExists {});
+        }
+
+        let key = ArtistKey {
+            pubkey,
+            activated_at: env.block.time,
+            retired_at: None,
+            continuity_proof: None,
+        };
+        self.artist_keys
+            .save(deps.storage, (&info.sender, 0), &key)?;
+        self.artist_key_count.save(deps.storage, &info.sender, &1)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "register_artist_key")
+            .add_attribute("artist", info.sender)
+            .add_attribute("version", "0"))
+    }
+
+    pub fn rotate_artist_key(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        pubkey: Binary,
+        signature: Binary,
+    ) -> Result<Response<C>, ContractError> {
+        check_pubkey(&pubkey)?;
+        let version = self
+            .artist_key_count
+            .may_load(deps.storage, &info.sender)?
+            .ok_or(ContractError::NoArtistKey {})?;
+        let mut current = self
+            .artist_keys
+            .load(deps.storage, (&info.sender, version - 1))?;
+
+        let doc = KeyRotationSignDoc {
+            contract: env.contract.address.to_string(),
+            artist: info.sender.to_string(),
+            version,
+            pubkey: pubkey.clone(),
+        };
+        let hash = Sha256::digest(to_json_vec(&doc)?);
+        let valid = deps
+            .api
+            .secp256k1_verify(&hash, &signature, &current.pubkey)
+            .map_err(StdError::from)?;
+        if !valid {
+            return Err(ContractError::InvalidSignature {});
+        }
+
+        current.retired_at = Some(env.block.time);
+        self.artist_keys
+            .save(deps.storage, (&info.sender, version - 1), &current)?;
+        let key = ArtistKey {
+            pubkey,
+            activated_at: env.block.time,
+            retired_at: None,
+            continuity_proof: Some(signature),
+        };
+        self.artist_keys
+            .save(deps.storage, (&info.sender, version), &key)?;
+        self.artist_key_count
+            .save(deps.storage, &info.sender, &(version + 1))?;
+
+        Ok(Response::new()
+            .add_attribute("action", "rotate_artist_key")
+            .add_attribute("artist", info.sender)
+            .add_attribute("version", version.to_string()))
+    }
+
+    pub fn issue_certificate(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        token_id: String,
+        signature: Binary,
+    ) -> Result<Response<C>, ContractError> {
+        self.tokens.load(deps.storage, token_key(&token_id)?)?;
+        if self.token_artists.may_load(deps.storage, &token_id)? != Some(info.sender.clone()) {
+            return Err(ContractError::Unauthorized {});
+        }
+        let version = self
+            .artist_key_count
+            .may_load(deps.storage, &info.sender)?
+            .ok_or(ContractError::NoArtistKey {})?
+            - 1;
+        let key = self
+            .artist_keys
+            .load(deps.storage, (&info.sender, version))?;
+
+        let doc = CertificateSignDoc {
+            contract: env.contract.address.to_string(),
+            token_id: token_id.clone(),
+            artist: info.sender.to_string(),
+        };
+        let hash = Sha256::digest(to_json_vec(&doc)?);
+        let valid = deps
+            .api
+            .secp256k1_verify(&hash, &signature, &key.pubkey)
+            .map_err(StdError::from)?;
+        if !valid {
+            return Err(ContractError::InvalidSignature {});
+        }
+
+        // reissuing under a newer key replaces the certificate
+        let certificate = Certificate {
+            artist: info.sender.clone(),
+            key_version: version,
+            signature,
+            issued_at: env.block.time,
+        };
+        self.certificates
+            .save(deps.storage, &token_id, &certificate)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "issue_certificate")
+            .add_attribute("artist", info.sender)
+            .add_attribute("token_id", token_id)
+            .add_attribute("version", version.to_string()))
+    }
+}
+
+// lazy mints
+impl<'a, T, C> Cw721Contract<'a, T, C>
+where
+    T: Serialize + DeserializeOwned + Clone,
+    C: CustomMsg,
+{
+    pub fn lazy_mint(
+        &self,
+        mut deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        voucher: LazyMintVoucher<T>,
+        signature: Binary,
+    ) -> Result<Response<C>, ContractError> {
+        // only artists the curator has onboarded can offer works
+        let artist = self.resolve_addr(deps.as_ref(), &voucher.artist)?;
+        if !self.artist_stats.has(deps.storage, &artist) {
+            return Err(ContractError::Unauthorized {});
+        }
+        if self
+            .lazy_mint_nonces
+            .has(deps.storage, (&artist, voucher.nonce))
+        {
+            return Err(ContractError::VoucherRedeemed {});
+        }
+        if voucher.expires <= env.block.time {
+            return Err(ContractError::VoucherExpired {});
+        }
+        if voucher.artist_share_bps > 10_000 {
+            return Err(ContractError::InvalidArtistShare {});
+        }
+        let version = self
+            .artist_key_count
+            .may_load(deps.storage, &artist)?
+            .ok_or(ContractError::NoArtistKey {})?
+            - 1;
+        let key = self.artist_keys.load(deps.storage, (&artist, version))?;
+
+        let doc = LazyMintSignDoc {
+            contract: env.contract.address.to_string(),
+            voucher,
+        };
+        let hash = Sha256::digest(to_json_vec(&doc)?);
+        let valid = deps
+            .api
+            .secp256k1_verify(&hash, &signature, &key.pubkey)
+            .map_err(StdError::from)?;
+        if !valid {
+            return Err(ContractError::InvalidSignature {});
+        }
+        let voucher = doc.voucher;
+        self.lazy_mint_nonces
+            .save(deps.storage, (&artist, voucher.nonce), &Empty {})?;
+
+        self.check_uri(deps.storage, &voucher.token_uri)?;
+        let royalty = match voucher.royalty {
+            Some(_) if self.is_minimal(deps.storage)? => {
+                return Err(ContractError::ExtendedProfileRequired {})
+            }
+            Some(royalty) => Some(self.check_royalty(deps.as_ref(), royalty)?),
+            None => None,
+        };
+        self.check_mint_available(deps.storage)?;
+        self._count_wallet_mint(deps.storage, &info.sender)?;
+        self._count_drop_acquisition(deps.storage, &info.sender)?;
+        let excess = take_payment(&info, &voucher.price)?;
+
+        let mut res = Response::new();
+        if !excess.is_empty() {
+            res = res.add_message(BankMsg::Send {
+                to_address: info.sender.to_string(),
+                amount: excess,
+            });
+        }
+        let msg = MintMsg {
+            owner: info.sender.to_string(),
+            token_uri: Some(voucher.token_uri),
+            referral: None,
+            content_hash: None,
+            extension: voucher.extension,
+            transferable: true,
+        };
+        let (mut res, token_id, owner, proceeds) =
+            self._sell_mint(deps.branch(), &env, &info, msg, &voucher.price, res)?;
+        let artist_share = proceeds.multiply_ratio(voucher.artist_share_bps, 10_000u128);
+        if !artist_share.is_zero() {
+            self._debit_treasury(
+                deps.storage,
+                TreasuryAccount::Proceeds,
+                &voucher.price.denom,
+                artist_share,
+            )?;
+            res = res
+                .add_message(BankMsg::Send {
+                    to_address: artist.to_string(),
+                    amount: coins(artist_share.u128(), &voucher.price.denom),
+                })
+                .add_attribute("artist_share", artist_share);
+        }
+        if royalty.is_some() {
+            let key = token_key(&token_id)?;
+            let mut token = self.tokens.load(deps.storage, key)?;
+            token.royalty = royalty;
+            self.tokens.save(deps.storage, key, &token)?;
+        }
+        self._set_artist(deps.storage, &token_id, &artist)?;
+        if voucher.price.amount.is_zero() {
+            res = res.add_attribute("complimentary", "true");
+        }
+
+        Ok(res
+            .add_attribute("action", "lazy_mint")
+            .add_attribute("artist", artist)
+            .add_attribute("owner", owner)
+            .add_attribute("nonce", voucher.nonce.to_string())
+            .add_attribute("token_id", token_id))
+    }
+}
+
+// gifts
+impl<'a, T, C> Cw721Contract<'a, T, C>
+where
+    T: Serialize + DeserializeOwned + Clone,
+    C: CustomMsg,
+{
+    #[allow(clippy::too_many_arguments)]
+    pub fn send_gift(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        token_id: String,
+        recipient: String,
+        message: Option<GiftMessage>,
+        deadline: Option<Timestamp>,
+    ) -> Result<Response<C>, ContractError> {
+        let recipient = self.resolve_addr(deps.as_ref(), &recipient)?;
+        if let Some(message) = &message {
+            self.check_provenance_field(deps.storage, &message.body)?;
+        }
+        if deadline.is_some_and(|d| d <= env.block.time) {
+            return Err(ContractError::GiftExpired {});
+        }
+
+        // hold the token in the contract until it is claimed or returned
+        let mut token = self.tokens.load(deps.storage, token_key(&token_id)?)?;
+        self.check_can_send(deps.as_ref(), &env, &info, &token_id, &token)?;
+        self.check_transferable(deps.storage, &token_id)?;
+        self.check_storage_fee(deps.storage, &env, &token_id)?;
+        let sender = token.owner.clone();
+        token.owner = env.contract.address.clone();
+        self._clear_approvals(deps.storage, &token_id, &mut token)?;
+        self._track_transfer(deps.storage, &env, &token_id, &sender, &token.owner)?;
+        // no marketplace can act on a gifted or conditionally sent token
+        self._clear_listing_approvals(deps.storage, &token_id)?;
+        self.tokens
+            .save(deps.storage, token_key(&token_id)?, &token)?;
+
+        let gift = Gift {
+            sender: sender.clone(),
+            recipient: recipient.clone(),
+            message,
+            deadline,
+            sent_at: env.block.time,
+        };
+        self.gifts.save(deps.storage, &token_id, &gift)?;
+        if let Some(deadline) = deadline {
+            self._schedule_expiry(deps.storage, deadline, gift_expiry(&token_id))?;
+        }
+
+        Ok(Response::new()
+            .add_attribute("action", "send_gift")
+            .add_attribute("sender", sender)
+            .add_attribute("recipient", recipient)
+            .add_attribute("token_id", token_id))
+    }
+
+    pub fn claim_gift(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        token_id: String,
+    ) -> Result<Response<C>, ContractError> {
+        let gift = self.gifts.load(deps.storage, &token_id)?;
+        if gift.recipient != info.sender {
+            return Err(ContractError::Unauthorized {});
+        }
+        if gift.deadline.is_some_and(|d| d <= env.block.time) {
+            return Err(ContractError::GiftExpired {});
+        }
+        self.check_not_frozen(deps.storage, &token_id)?;
+
+        let mut token = self.tokens.load(deps.storage, token_key(&token_id)?)?;
+        token.owner = gift.recipient.clone();
+        self.update_holding(deps.storage, &env.contract.address, env.block.time, false)?;
+        self.update_holding(deps.storage, &token.owner, env.block.time, true)?;
+        self.tokens
+            .save(deps.storage, token_key(&token_id)?, &token)?;
+        self.gifts.remove(deps.storage, &token_id)?;
+        self._clear_expiry(deps.storage, &gift_expiry(&token_id))?;
+        // a gift is recorded as such in provenance, not as a transfer or sale
+        let event = format!("gift/{}/{}", gift.sender, gift.recipient);
+        self._append_provenance(deps.storage, &env, &token_id, event)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "claim_gift")
+            .add_attribute("sender", gift.sender)
+            .add_attribute("recipient", gift.recipient)
+            .add_attribute("token_id", token_id))
+    }
+
+    pub fn return_gift(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        token_id: String,
+    ) -> Result<Response<C>, ContractError> {
+        let gift = self.gifts.load(deps.storage, &token_id)?;
+        let expired = gift.deadline.is_some_and(|d| d <= env.block.time);
+        if gift.sender != info.sender && !expired {
+            return Err(ContractError::Unauthorized {});
+        }
+        self.check_not_frozen(deps.storage, &token_id)?;
+
+        let mut token = self.tokens.load(deps.storage, token_key(&token_id)?)?;
+        token.owner = gift.sender.clone();
+        self.update_holding(deps.storage, &env.contract.address, env.block.time, false)?;
+        self.update_holding(deps.storage, &token.owner, env.block.time, true)?;
+        self.tokens
+            .save(deps.storage, token_key(&token_id)?, &token)?;
+        self.gifts.remove(deps.storage, &token_id)?;
+        self._clear_expiry(deps.storage, &gift_expiry(&token_id))?;
+
+        let res = Response::new()
+            .add_attribute("action", "return_gift")
+            .add_attribute("sender", info.sender.clone())
+            .add_attribute("owner", gift.sender.clone())
+            .add_attribute("token_id", token_id);
+        // only returning an expired gift on someone else's behalf is maintenance
+        if gift.sender == info.sender {
+            return Ok(res);
+        }
+        self._pay_keeper(deps.storage, &info.sender, res)
+    }
+}
+
+// conditional transfers
+impl<'a, T, C> Cw721Contract<'a, T, C>
+where
+    T: Serialize + DeserializeOwned + Clone,
+    C: CustomMsg,
+{
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_conditional_transfer(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        token_id: String,
+        recipient: String,
+        condition: TransferCondition,
+        expires: Timestamp,
+    ) -> Result<Response<C>, ContractError> {
+        let recipient = self.resolve_addr(deps.as_ref(), &recipient)?;
+        match &condition {
+            TransferCondition::PriceAtLeast {
+                oracle,
+                base,
+                quote,
+                ..
+            }
+            | TransferCondition::PriceAtMost {
+                oracle,
+                base,
+                quote,
+                ..
+            } => {
+                deps.api.addr_validate(oracle)?;
+                self.check_provenance_field(deps.storage, base)?;
+                self.check_provenance_field(deps.storage, quote)?;
+            }
+            TransferCondition::Attested { kind } => {
+                if let AttestationKind::Other { label } = kind {
+                    self.check_provenance_field(deps.storage, label)?;
+                }
+            }
+        }
+        if expires <= env.block.time {
+            return Err(ContractError::ConditionalTransferExpired {});
+        }
+
+        // hold the token in the contract until it is delivered or returned
+        let mut token = self.tokens.load(deps.storage, token_key(&token_id)?)?;
+        self.check_can_send(deps.as_ref(), &env, &info, &token_id, &token)?;
+        self.check_transferable(deps.storage, &token_id)?;
+        self.check_storage_fee(deps.storage, &env, &token_id)?;
+        let sender = token.owner.clone();
+        token.owner = env.contract.address.clone();
+        self._clear_approvals(deps.storage, &token_id, &mut token)?;
+        self._track_transfer(deps.storage, &env, &token_id, &sender, &token.owner)?;
+        // no marketplace can act on a gifted or conditionally sent token
+        self._clear_listing_approvals(deps.storage, &token_id)?;
+        self.tokens
+            .save(deps.storage, token_key(&token_id)?, &token)?;
+
+        let transfer = ConditionalTransfer {
+            sender: sender.clone(),
+            recipient: recipient.clone(),
+            condition,
+            attestations_from: self
+                .attestation_count
+                .may_load(deps.storage, &token_id)?
+                .unwrap_or_default(),
+            created_at: env.block.time,
+            expires,
+        };
+        self.conditional_transfers
+            .save(deps.storage, &token_id, &transfer)?;
+        self._schedule_expiry(deps.storage, expires, conditional_expiry(&token_id))?;
+
+        Ok(Response::new()
+            .add_attribute("action", "create_conditional_transfer")
+            .add_attribute("sender", sender)
+            .add_attribute("recipient", recipient)
+            .add_attribute("token_id", token_id))
+    }
+
+    pub fn execute_conditional_transfer(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        token_id: String,
+    ) -> Result<Response<C>, ContractError> {
+        let transfer = self.conditional_transfers.load(deps.storage, &token_id)?;
+        if transfer.expires <= env.block.time {
+            return Err(ContractError::ConditionalTransferExpired {});
+        }
+        if !self.condition_met(deps.as_ref(), &token_id, &transfer)? {
+            return Err(ContractError::ConditionNotMet {});
+        }
+        self.check_not_frozen(deps.storage, &token_id)?;
+
+        let mut token = self.tokens.load(deps.storage, token_key(&token_id)?)?;
+        token.owner = transfer.recipient.clone();
+        self.update_holding(deps.storage, &env.contract.address, env.block.time, false)?;
+        self.update_holding(deps.storage, &token.owner, env.block.time, true)?;
+        self._clear_listing_approvals(deps.storage, &token_id)?;
+        self.tokens
+            .save(deps.storage, token_key(&token_id)?, &token)?;
+        self.conditional_transfers.remove(deps.storage, &token_id);
+        self._clear_expiry(deps.storage, &conditional_expiry(&token_id))?;
+        let event = format!("transfer/{}/{}", transfer.sender, transfer.recipient);
+        self._append_provenance(deps.storage, &env, &token_id, event)?;
+
+        let res = Response::new()
+            .add_attribute("action", "execute_conditional_transfer")
+            .add_attribute("sender", info.sender.clone())
+            .add_attribute("owner", transfer.recipient.clone())
+            .add_attribute("token_id", token_id);
+        if info.sender == transfer.sender || info.sender == transfer.recipient {
+            return Ok(res);
+        }
+        self._pay_keeper(deps.storage, &info.sender, res)
+    }
+
+    pub fn cancel_conditional_transfer(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        token_id: String,
+    ) -> Result<Response<C>, ContractError> {
+        let transfer = self.conditional_transfers.load(deps.storage, &token_id)?;
+        let expired = transfer.expires <= env.block.time;
+        if transfer.sender != info.sender && !expired {
+            return Err(ContractError::Unauthorized {});
+        }
+        self.check_not_frozen(deps.storage, &token_id)?;
+
+        let mut token = self.tokens.load(deps.storage, token_key(&token_id)?)?;
+        token.owner = transfer.sender.clone();
+        self.update_holding(deps.storage, &env.contract.address, env.block.time, false)?;
+        self.update_holding(deps.storage, &token.owner, env.block.time, true)?;
+        self.tokens
+            .save(deps.storage, token_key(&token_id)?, &token)?;
+        self.conditional_transfers.remove(deps.storage, &token_id);
+        self._clear_expiry(deps.storage, &conditional_expiry(&token_id))?;
+
+        let res = Response::new()
+            .add_attribute("action", "cancel_conditional_transfer")
+            .add_attribute("sender", info.sender.clone())
+            .add_attribute("owner", transfer.sender.clone())
+            .add_attribute("token_id", token_id);
+        if info.sender == transfer.sender {
+            return Ok(res);
+        }
+        self._pay_keeper(deps.storage, &info.sender, res)
+    }
+}
+
+fn conditional_expiry(token_id: &str) -> ExpiryKind {
+    ExpiryKind::ConditionalTransfer {
+        token_id: token_id.to_string(),
+    }
+}
+
+// series
+impl<'a, T, C> Cw721Contract<'a, T, C>
+where
+    T: Serialize + DeserializeOwned + Clone,
+    C: CustomMsg,
+{
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_series(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        name: String,
+        max_editions: u64,
+        price: Coin,
+        base_uri: String,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(deps.as_ref(), &info, Role::Minter)?;
+        if name.trim().is_empty() || max_editions == 0 {
+            return Err(ContractError::InvalidSeries {});
+        }
+        self.check_uri(deps.storage, &base_uri)?;
+
+        let series_id = self
+            .series_count
+            .may_load(deps.storage)?
+            .unwrap_or_default()
+            + 1;
+        self.series_count.save(deps.storage, &series_id)?;
+        let series = Series {
+            name,
+            max_editions,
+            price,
+            base_uri,
+            minted: 0,
+            created_at: env.block.time,
+        };
+        self.series.save(deps.storage, series_id, &series)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "create_series")
+            .add_attribute("sender", info.sender)
+            .add_attribute("series_id", series_id.to_string())
+            .add_attribute("max_editions", max_editions.to_string()))
+    }
+
+    pub fn mint_series(
+        &self,
+        mut deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        series_id: u64,
+        mut msg: MintMsg<T>,
+    ) -> Result<Response<C>, ContractError> {
+        let mut series = self.series.load(deps.storage, series_id)?;
+        if series.minted >= series.max_editions {
+            return Err(ContractError::SeriesSoldOut { series_id });
+        }
+        if msg.token_uri.is_some() {
+            return Err(ContractError::InvalidSeries {});
+        }
+        self.check_mint_available(deps.storage)?;
+        self._count_wallet_mint(deps.storage, &info.sender)?;
+        self._count_drop_acquisition(deps.storage, &info.sender)?;
+        let excess = take_payment(&info, &series.price)?;
+
+        series.minted += 1;
+        let edition = series.minted;
+        self.series.save(deps.storage, series_id, &series)?;
+        msg.token_uri = Some(format!(
+            "{}/{}.json",
+            series.base_uri.trim_end_matches('/'),
+            edition
+        ));
+
+        let mut res = Response::new();
+        if !excess.is_empty() {
+            res = res.add_message(BankMsg::Send {
+                to_address: info.sender.to_string(),
+                amount: excess,
+            });
+        }
+        let (mut res, token_id, owner, _) =
+            self._sell_mint(deps.branch(), &env, &info, msg, &series.price, res)?;
+        self.token_series
+            .save(deps.storage, &token_id, &series_id)?;
+        if series.price.amount.is_zero() {
+            res = res.add_attribute("complimentary", "true");
+        }
+
+        Ok(res
+            .add_attribute("action", "mint_series")
+            .add_attribute("minter", info.sender)
+            .add_attribute("owner", owner)
+            .add_attribute("series_id", series_id.to_string())
+            .add_attribute("edition", edition.to_string())
+            .add_attribute("token_id", token_id))
+    }
+}
+
+// mystery mints
+impl<'a, T, C> Cw721Contract<'a, T, C>
+where
+    T: Serialize + DeserializeOwned + Clone,
+    C: CustomMsg,
+{
+    pub fn set_mystery_mode(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        sealed_uri: Option<String>,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(deps.as_ref(), &info, Role::Admin)?;
+
+        match &sealed_uri {
+            Some(sealed_uri) => {
+                self.check_uri(deps.storage, sealed_uri)?;
+                self.mystery_sealed_uri.save(deps.storage, sealed_uri)?;
+            }
+            None => self.mystery_sealed_uri.remove(deps.storage),
+        }
+
+        Ok(Response::new()
+            .add_attribute("action", "set_mystery_mode")
+            .add_attribute("sender", info.sender)
+            .add_attribute("enabled", sealed_uri.is_some().to_string()))
+    }
+
+    pub fn add_mystery_uris(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        uris: Vec<String>,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(deps.as_ref(), &info, Role::Admin)?;
+
+        let mut size = self
+            .mystery_pool_size
+            .may_load(deps.storage)?
+            .unwrap_or_default();
+        for uri in &uris {
+            self.check_uri(deps.storage, uri)?;
+            self.mystery_pool.save(deps.storage, size, uri)?;
+            size += 1;
+        }
+        self.mystery_pool_size.save(deps.storage, &size)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "add_mystery_uris")
+            .add_attribute("sender", info.sender)
+            .add_attribute("added", uris.len().to_string())
+            .add_attribute("remaining", size.to_string()))
+    }
+
+    pub fn reveal_token(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        token_id: String,
+    ) -> Result<Response<C>, ContractError> {
+        let mut token = self.tokens.load(deps.storage, token_key(&token_id)?)?;
+        if token.owner != info.sender {
+            return Err(ContractError::Unauthorized {});
+        }
+        if !self.sealed_tokens.has(deps.storage, &token_id) {
+            return Err(ContractError::NotSealed {});
+        }
+
+        // draw from the pool and move its last entry into the gap
+        let size = self
+            .mystery_pool_size
+            .may_load(deps.storage)?
+            .unwrap_or_default();
+        if size == 0 {
+            return Err(ContractError::MysteryPoolEmpty {});
+        }
+        let index = reveal_index(&env, &token_id, size);
+        let uri = self.mystery_pool.load(deps.storage, index)?;
+        let last = self.mystery_pool.load(deps.storage, size - 1)?;
+        self.mystery_pool.save(deps.storage, index, &last)?;
+        self.mystery_pool.remove(deps.storage, size - 1);
+        self.mystery_pool_size.save(deps.storage, &(size - 1))?;
+
+        token.token_uri = Some(uri.clone());
+        self.tokens
+            .save(deps.storage, token_key(&token_id)?, &token)?;
+        self.sealed_tokens.remove(deps.storage, &token_id);
+        self._append_provenance(deps.storage, &env, &token_id, format!("reveal/{}", uri))?;
+
+        Ok(Response::new()
+            .add_attribute("action", "reveal_token")
+            .add_attribute("owner", info.sender)
+            .add_attribute("token_id", token_id)
+            .add_attribute("token_uri", uri))
+    }
+
+    pub fn reveal(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        base_uri: String,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(deps.as_ref(), &info, Role::Admin)?;
+        let mut drop = self
+            .blind_drop
+            .may_load(deps.storage)?
+            .ok_or(ContractError::NoBlindDrop {})?;
+        if drop.base_uri.is_some() {
+            return Err(ContractError::AlreadyRevealed {});
+        }
+        self.check_uri(deps.storage, &base_uri)?;
+        if Sha256::digest(base_uri.as_bytes()).as_slice() != drop.metadata_commitment.as_slice() {
+            return Err(ContractError::MetadataCommitmentMismatch {});
+        }
+
+        drop.base_uri = Some(base_uri.clone());
+        drop.revealed_at = Some(env.block.time);
+        self.blind_drop.save(deps.storage, &drop)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "reveal")
+            .add_attribute("sender", info.sender)
+            .add_attribute("base_uri", base_uri))
+    }
+
+    /// seals a freshly minted token while mystery mode is on
+    pub fn _seal_if_mystery(&self, storage: &mut dyn Storage, token_id: &str) -> StdResult<()> {
+        if let Some(sealed_uri) = self.mystery_sealed_uri.may_load(storage)? {
+            self.sealed_tokens.save(storage, token_id, &sealed_uri)?;
+        }
+        Ok(())
+    }
+}
+
+fn installment_expiry(token_id: &str) -> ExpiryKind {
+    ExpiryKind::InstallmentDue {
+        token_id: token_id.to_string(),
+    }
+}
+
+fn gift_expiry(token_id: &str) -> ExpiryKind {
+    ExpiryKind::GiftDeadline {
+        token_id: token_id.to_string(),
+    }
+}
+
+/// picks an index below `size` from block data and `salt`. Validators can bias
+/// it, which is acceptable when every outcome is bought at the same price
+fn reveal_index(env: &Env, salt: &str, size: u64) -> u64 {
+    let tx_index = env
+        .transaction
+        .as_ref()
+        .map(|t| t.index)
+        .unwrap_or_default();
+    let seed = format!(
+        "{}/{}/{}/{}",
+        env.block.height,
+        env.block.time.nanos(),
+        tx_index,
+        salt
+    );
+    let hash = Sha256::digest(seed.as_bytes());
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&hash[..8]);
+    u64::from_be_bytes(bytes) % size
+}
+
+/// picks an index below `size` from a revealed secret seed and `salt`
+fn seeded_index(seed: &str, salt: &str, size: u64) -> u64 {
+    let hash = Sha256::digest(format!("{}/{}", seed, salt).as_bytes());
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&hash[..8]);
+    u64::from_be_bytes(bytes) % size
+}
+
+// edition pre-sales
+impl<'a, T, C> Cw721Contract<'a, T, C>
+where
+    T: Serialize + DeserializeOwned + Clone,
+    C: CustomMsg,
+{
+    pub fn set_presale(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        config: Option<PresaleConfig>,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(deps.as_ref(), &info, Role::Admin)?;
+
+        // commitments are escrowed until drawn
+        let current = self.presale.may_load(deps.storage)?;
+        if let Some(current) = &current {
+            if current.pending > 0 {
+                return Err(ContractError::PresaleRunning {});
+            }
+            let expiry = ExpiryKind::PresaleEnd {
+                presale_id: current.id,
+            };
+            self._clear_expiry(deps.storage, &expiry)?;
+        }
+
+        match config {
+            Some(config) => {
+                if config.editions == 0
+                    || config.end <= config.start
+                    || config.seed_commitment.len() != 32
+                {
+                    return Err(ContractError::InvalidPresale {});
+                }
+                let presale = Presale {
+                    id: current.map(|p| p.id).unwrap_or_default() + 1,
+                    price: self.mint_price.load(deps.storage)?,
+                    config,
+                    pending: 0,
+                    committed: 0,
+                    allocated: 0,
+                    refunded: 0,
+                };
+                self.presale.save(deps.storage, &presale)?;
+                self._schedule_expiry(
+                    deps.storage,
+                    presale.config.end,
+                    ExpiryKind::PresaleEnd {
+                        presale_id: presale.id,
+                    },
+                )?;
+            }
+            None => self.presale.remove(deps.storage),
+        }
+
+        Ok(Response::new()
+            .add_attribute("action", "set_presale")
+            .add_attribute("sender", info.sender))
+    }
+
+    pub fn commit_presale(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        msg: MintMsg<T>,
+    ) -> Result<Response<C>, ContractError> {
+        let mut presale = self
+            .presale
+            .may_load(deps.storage)?
+            .ok_or(ContractError::PresaleDisabled {})?;
+        if env.block.time < presale.config.start || env.block.time >= presale.config.end {
+            return Err(ContractError::PresaleNotOpen {});
+        }
+        if self
+            .presale_outcomes
+            .has(deps.storage, (presale.id, &info.sender))
+        {
+            return Err(ContractError::AlreadyCommitted {});
+        }
+        if presale.pending >= MAX_PRESALE_COMMITMENTS {
+            return Err(ContractError::PresaleFull {
+                max: MAX_PRESALE_COMMITMENTS,
+            });
+        }
+        if must_pay(&info, &presale.price.denom)? != presale.price.amount {
+            return Err(ContractError::IncorrectPayment {});
+        }
+
+        self.check_token_uri(deps.as_ref(), &info, &msg.token_uri)?;
+        self.check_transferable_flag(deps.as_ref(), &info, msg.transferable)?;
+        self.check_unique_content(deps.storage, &msg.token_uri, &msg.content_hash)?;
+        let owner = self.resolve_addr(deps.as_ref(), &msg.owner)?;
+        self.check_extension(deps.storage, &msg.extension)?;
+        let entry = PresaleEntry {
+            buyer: info.sender.clone(),
+            owner,
+            token_uri: msg.token_uri,
+            content_hash: msg.content_hash,
+            referrer: self.referral_curator(deps.storage, &msg.referral)?,
+            extension: msg.extension,
+            transferable: msg.transferable,
+        };
+        self.presale_entries
+            .save(deps.storage, presale.pending, &entry)?;
+        self.presale_outcomes.save(
+            deps.storage,
+            (presale.id, &info.sender),
+            &PresaleOutcome::Pending,
+        )?;
+        presale.pending += 1;
+        presale.committed += 1;
+        self.presale.save(deps.storage, &presale)?;
+        self._credit_treasury(
+            deps.storage,
+            TreasuryAccount::Presale,
+            &presale.price.denom,
+            presale.price.amount,
+        )?;
+
+        Ok(Response::new()
+            .add_attribute("action", "commit_presale")
+            .add_attribute("sender", info.sender)
+            .add_attribute("presale_id", presale.id.to_string())
+            .add_attribute("committed", presale.committed.to_string()))
+    }
+
+    /// Draws every commitment once the window has closed. Winners are picked
+    /// with the admin's seed and minted their edition until the editions or
+    /// the collection supply run out, the rest are refunded. Drawing it all
+    /// in one admin call leaves nobody a partial draw to retry or revert
+    pub fn draw_presale(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        seed: Option<String>,
+    ) -> Result<Response<C>, ContractError> {
+        let mut presale = self
+            .presale
+            .may_load(deps.storage)?
+            .ok_or(ContractError::PresaleDisabled {})?;
+        if env.block.time < presale.config.end {
+            return Err(ContractError::PresaleRunning {});
+        }
+        if presale.pending == 0 {
+            return Err(ContractError::PresaleDrawn {});
+        }
+        match &seed {
+            Some(seed) => {
+                self.assert_role(deps.as_ref(), &info, Role::Admin)?;
+                let digest = Sha256::digest(seed.as_bytes());
+                if digest.as_slice() != presale.config.seed_commitment.as_slice() {
+                    return Err(ContractError::PresaleSeedMismatch {});
+                }
+            }
+            // the admin never drew, every commitment is refunded
+            None => {
+                if env.block.time < presale.config.end.plus_seconds(PRESALE_DRAW_WINDOW) {
+                    return Err(ContractError::Unauthorized {});
+                }
+            }
+        }
+
+        let price = presale.price.clone();
+        let mut res = Response::new();
+        let mut allocated = vec![];
+        while presale.pending > 0 {
+            let sold_out = seed.is_none()
+                || presale.allocated >= presale.config.editions
+                || self.remaining_mints(deps.storage)? == 0;
+
+            // swap-remove the drawn entry to keep the pending entries dense
+            let last = presale.pending - 1;
+            let index = match &seed {
+                Some(seed) if !sold_out => {
+                    let salt = format!("presale/{}/{}", presale.id, presale.committed - last);
+                    seeded_index(seed, &salt, presale.pending)
+                }
+                _ => last,
+            };
+            let entry = self.presale_entries.load(deps.storage, index)?;
+            if index != last {
+                let moved = self.presale_entries.load(deps.storage, last)?;
+                self.presale_entries.save(deps.storage, index, &moved)?;
+            }
+            self.presale_entries.remove(deps.storage, last);
+            presale.pending -= 1;
+            self._debit_treasury(
+                deps.storage,
+                TreasuryAccount::Presale,
+                &price.denom,
+                price.amount,
+            )?;
+
+            let outcome = if sold_out {
+                presale.refunded += 1;
+                res = res.add_message(BankMsg::Send {
+                    to_address: entry.buyer.to_string(),
+                    amount: vec![price.clone()],
+                });
+                PresaleOutcome::Refunded
+            } else {
+                presale.allocated += 1;
+                let content = content_keys(&entry.token_uri, &entry.content_hash);
+                let token_id = self._create_token(
+                    deps.storage,
+                    &env,
+                    entry.owner,
+                    &entry.buyer,
+                    entry.token_uri,
+                    entry.extension,
+                )?;
+                // checked at commit; a clash between two commitments must not
+                // block the draw, so the later one is just left out of the index
+                self._index_content(deps.storage, &token_id, content, entry.content_hash)?;
+                self._seal_if_mystery(deps.storage, &token_id)?;
+                self._set_transferable(deps.storage, &token_id, entry.transferable)?;
+                self._record_drop_mint(deps.storage, &env, &token_id)?;
+                let withholding = self.withholding_for(deps.storage, &entry.buyer, &price)?;
+                if let Some(withholding) = &withholding {
+                    res = self.add_withholding(res, withholding, &price.denom);
+                }
+                let curation = self.curation_fee_for(
+                    deps.storage,
+                    entry.referrer,
+                    &price,
+                    price.amount - withheld(&withholding),
+                )?;
+                if let Some(curation) = &curation {
+                    res = self.add_curation_fee(deps.storage, res, curation, &price.denom)?;
+                }
+                self._credit_treasury(
+                    deps.storage,
+                    TreasuryAccount::Proceeds,
+                    &price.denom,
+                    price.amount - withheld(&withholding) - curated(&curation),
+                )?;
+                let receipt = SaleReceipt {
+                    buyer: entry.buyer.clone(),
+                    price: price.clone(),
+                    withholding,
+                    proceeds_to: None,
+                    curation,
+                    fee_override: None,
+                    marketplace_fee: None,
+                };
+                self._record_sale(deps.storage, &env, &token_id, &receipt)?;
+                allocated.push(token_id.clone());
+                PresaleOutcome::Allocated { token_id }
+            };
+            self.presale_outcomes
+                .save(deps.storage, (presale.id, &entry.buyer), &outcome)?;
+        }
+        self.presale.save(deps.storage, &presale)?;
+        if presale.pending == 0 {
+            let expiry = ExpiryKind::PresaleEnd {
+                presale_id: presale.id,
+            };
+            self._clear_expiry(deps.storage, &expiry)?;
+        }
+
+        let res = res
+            .add_attribute("action", "draw_presale")
+            .add_attribute("sender", info.sender.clone())
+            .add_attribute("presale_id", presale.id.to_string())
+            .add_attribute("allocated", allocated.join(","));
+        // only refunding a pre-sale the admin never drew is maintenance
+        if seed.is_some() {
+            return Ok(res);
+        }
+        self._pay_keeper(deps.storage, &info.sender, res)
+    }
+}
+
+// allowlist phase
+impl<'a, T, C> Cw721Contract<'a, T, C>
+where
+    T: Serialize + DeserializeOwned + Clone,
+    C: CustomMsg,
+{
+    pub fn set_dutch_auction(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        config: Option<DutchAuctionConfig>,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(deps.as_ref(), &info, Role::Admin)?;
+
+        match &config {
+            Some(config) => {
+                if config.start_price.denom != config.end_price.denom
+                    || config.start_price.amount <= config.end_price.amount
+                    || config.decay_interval == 0
+                    || self.mint_price_peg.may_load(deps.storage)?.is_some()
+                {
+                    return Err(ContractError::InvalidDutchAuction {});
+                }
+                self.dutch_auction.save(deps.storage, config)?;
+            }
+            None => self.dutch_auction.remove(deps.storage),
+        }
+
+        Ok(Response::new()
+            .add_attribute("action", "set_dutch_auction")
+            .add_attribute("sender", info.sender)
+            .add_attribute("enabled", config.is_some().to_string()))
+    }
+
+    pub fn set_mint_price_peg(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        peg: Option<MintPricePeg>,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(deps.as_ref(), &info, Role::Admin)?;
+
+        match &peg {
+            Some(peg) => {
+                deps.api.addr_validate(&peg.oracle)?;
+                if peg.quote.is_empty()
+                    || peg.price.is_zero()
+                    || peg.max_staleness == 0
+                    || self.dutch_auction.may_load(deps.storage)?.is_some()
+                {
+                    return Err(ContractError::InvalidMintPricePeg {});
+                }
+                self.mint_price_peg.save(deps.storage, peg)?;
+            }
+            None => self.mint_price_peg.remove(deps.storage),
+        }
+
+        Ok(Response::new()
+            .add_attribute("action", "set_mint_price_peg")
+            .add_attribute("sender", info.sender)
+            .add_attribute("enabled", peg.is_some().to_string()))
+    }
+
+    pub fn set_allowlist_config(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        config: Option<AllowlistConfig>,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(deps.as_ref(), &info, Role::Admin)?;
+
+        match &config {
+            Some(config) => {
+                if config.per_address_limit == 0 || config.end <= env.block.time {
+                    return Err(ContractError::InvalidAllowlistConfig {});
+                }
+                self.allowlist_config.save(deps.storage, config)?;
+            }
+            None => self.allowlist_config.remove(deps.storage),
+        }
+
+        Ok(Response::new()
+            .add_attribute("action", "set_allowlist_config")
+            .add_attribute("sender", info.sender)
+            .add_attribute("enabled", config.is_some().to_string()))
+    }
+
+    pub fn add_to_allowlist(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        addresses: Vec<String>,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(deps.as_ref(), &info, Role::Admin)?;
+
+        for address in &addresses {
+            let addr = self.resolve_addr(deps.as_ref(), address)?;
+            // re-adding keeps what was already minted
+            if !self.allowlist.has(deps.storage, &addr) {
+                self.allowlist
+                    .save(deps.storage, &addr, &AllowlistEntry::default())?;
+            }
+        }
+
+        Ok(Response::new()
+            .add_attribute("action", "add_to_allowlist")
+            .add_attribute("sender", info.sender)
+            .add_attribute("added", addresses.len().to_string()))
+    }
+
+    pub fn remove_from_allowlist(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        addresses: Vec<String>,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(deps.as_ref(), &info, Role::Admin)?;
+
+        for address in &addresses {
+            let addr = self.resolve_addr(deps.as_ref(), address)?;
+            self.allowlist.remove(deps.storage, &addr);
+        }
+
+        Ok(Response::new()
+            .add_attribute("action", "remove_from_allowlist")
+            .add_attribute("sender", info.sender)
+            .add_attribute("removed", addresses.len().to_string()))
+    }
+
+    pub fn set_allowlist_merkle_root(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        root: Option<HexBinary>,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(deps.as_ref(), &info, Role::Admin)?;
+
+        match &root {
+            Some(root) => {
+                if root.len() != 32 {
+                    return Err(ContractError::InvalidMerkleRoot {});
+                }
+                self.allowlist_merkle_root.save(deps.storage, root)?;
+            }
+            None => self.allowlist_merkle_root.remove(deps.storage),
+        }
+
+        Ok(Response::new()
+            .add_attribute("action", "set_allowlist_merkle_root")
+            .add_attribute("sender", info.sender)
+            .add_attribute("root", root.map(|r| r.to_hex()).unwrap_or_default()))
+    }
+
+    /// counts a mint by minter against its allowlist allowance. Minters not
+    /// on the allowlist get on it with a proof against the Merkle root
+    pub fn _use_allowlist(
+        &self,
+        storage: &mut dyn Storage,
+        minter: &Addr,
+        phase: &AllowlistConfig,
+        proof: Option<&[HexBinary]>,
+    ) -> Result<(), ContractError> {
+        let mut entry = match self.allowlist.may_load(storage, minter)? {
+            Some(entry) => entry,
+            None => {
+                let root = self.allowlist_merkle_root.may_load(storage)?;
+                match (root, proof) {
+                    (Some(root), Some(proof)) => {
+                        if !verify_merkle_proof(&root, minter, proof) {
+                            return Err(ContractError::InvalidMerkleProof {});
+                        }
+                        AllowlistEntry::default()
+                    }
+                    _ => return Err(ContractError::NotAllowlisted {}),
+                }
+            }
+        };
+        if entry.minted >= phase.per_address_limit {
+            return Err(ContractError::AllowlistLimitReached {
+                limit: phase.per_address_limit,
+            });
+        }
+        entry.minted += 1;
+        self.allowlist.save(storage, minter, &entry)?;
+        Ok(())
+    }
+}
+
+/// checks that proof links the leaf sha256(address) to root, hashing each
+/// pair in sorted order
+fn verify_merkle_proof(root: &HexBinary, address: &Addr, proof: &[HexBinary]) -> bool {
+    let mut hash: [u8; 32] = Sha256::digest(address.as_bytes()).into();
+    for node in proof {
+        let node: [u8; 32] = match node.as_slice().try_into() {
+            Ok(node) => node,
+            Err(_) => return false,
+        };
+        let (first, second) = if hash <= node {
+            (hash, node)
+        } else {
+            (node, hash)
+        };
+        hash = Sha256::new()
+            .chain_update(first)
+            .chain_update(second)
+            .finalize()
+            .into();
+    }
+    hash.as_slice() == root.as_slice()
+}
+
+// private sales
+impl<'a, T, C> Cw721Contract<'a, T, C>
+where
+    T: Serialize + DeserializeOwned + Clone,
+    C: CustomMsg,
+{
+    pub fn record_private_sale(
+        &self,
+        mut deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        token_id: String,
+        buyer: String,
+        price_commitment: HexBinary,
+    ) -> Result<Response<C>, ContractError> {
+        if price_commitment.len() != 32 {
+            return Err(ContractError::InvalidPriceCommitment {});
+        }
+        let seller = self.tokens.load(deps.storage, token_key(&token_id)?)?.owner;
+        let token = self._transfer_nft(deps.branch(), &env, &info, &buyer, &token_id)?;
+
+        let sale_id = self
+            .private_sale_count
+            .may_load(deps.storage)?
+            .unwrap_or_default()
+            + 1;
+        self.private_sale_count.save(deps.storage, &sale_id)?;
+        let sale = PrivateSale {
+            seller: seller.clone(),
+            buyer: token.owner.clone(),
+            price_commitment: price_commitment.clone(),
+            sold_at: env.block.time,
+            revealed: None,
+        };
+        self.private_sales
+            .save(deps.storage, (&token_id, sale_id), &sale)?;
+        let event = format!(
+            "private_sale/{}/{}/{}/{}",
+            sale_id, seller, token.owner, price_commitment
+        );
+        self._append_provenance(deps.storage, &env, &token_id, event)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "record_private_sale")
+            .add_attribute("sender", info.sender)
+            .add_attribute("seller", seller)
+            .add_attribute("buyer", token.owner)
+            .add_attribute("token_id", token_id)
+            .add_attribute("sale_id", sale_id.to_string()))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn reveal_private_sale(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        token_id: String,
+        sale_id: u64,
+        price: Coin,
+        salt: HexBinary,
+    ) -> Result<Response<C>, ContractError> {
+        let mut sale = self
+            .private_sales
+            .load(deps.storage, (&token_id, sale_id))?;
+        if info.sender != sale.seller && info.sender != sale.buyer {
+            return Err(ContractError::Unauthorized {});
+        }
+        if sale.revealed.is_some() {
+            return Err(ContractError::PriceAlreadyRevealed {});
+        }
+        if price_commitment(&price, &salt) != sale.price_commitment {
+            return Err(ContractError::PriceCommitmentMismatch {});
+        }
+
+        sale.revealed = Some(RevealedPrice {
+            price: price.clone(),
+            revealed_by: info.sender.clone(),
+            revealed_at: env.block.time,
+        });
+        self.private_sales
+            .save(deps.storage, (&token_id, sale_id), &sale)?;
+        let event = format!("private_sale_revealed/{}/{}", sale_id, price);
+        self._append_provenance(deps.storage, &env, &token_id, event)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "reveal_private_sale")
+            .add_attribute("sender", info.sender)
+            .add_attribute("token_id", token_id)
+            .add_attribute("sale_id", sale_id.to_string())
+            .add_attribute("price", price.to_string()))
+    }
+}
+
+/// sha256(salt ++ "{amount}{denom}")
+fn price_commitment(price: &Coin, salt: &[u8]) -> HexBinary {
+    let hash = Sha256::new()
+        .chain_update(salt)
+        .chain_update(price.to_string().as_bytes())
+        .finalize();
+    HexBinary::from(hash.as_slice())
+}
+
+// redactions
+impl<'a, T, C> Cw721Contract<'a, T, C>
+where
+    T: Serialize + DeserializeOwned + Clone,
+    C: CustomMsg,
+{
+    pub fn set_redactions(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        token_id: String,
+        requested: Vec<RedactedField>,
+    ) -> Result<Response<C>, ContractError> {
+        let token = self.tokens.load(deps.storage, token_key(&token_id)?)?;
+        if token.owner != info.sender {
+            return Err(ContractError::Unauthorized {});
+        }
+
+        let mut fields: Vec<RedactedField> = Vec::new();
+        for field in requested {
+            if !fields.contains(&field) {
+                fields.push(field);
+            }
+        }
+        if fields.is_empty() {
+            self.redactions.remove(deps.storage, &token_id);
+        } else {
+            self.redactions.save(deps.storage, &token_id, &fields)?;
+        }
+
+        Ok(Response::new()
+            .add_attribute("action", "set_redactions")
+            .add_attribute("sender", info.sender)
+            .add_attribute("token_id", token_id)
+            .add_attribute("fields", fields.len().to_string()))
+    }
+}
+
+// emergency export
+impl<'a, T, C> Cw721Contract<'a, T, C>
+where
+    T: Serialize + DeserializeOwned + Clone,
+    C: CustomMsg,
+{
+    pub fn start_export(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(deps.as_ref(), &info, Role::Admin)?;
+
+        let export = ExportMode {
+            started_at: env.block.time,
+            started_by: info.sender.clone(),
+        };
+        self.export_mode.save(deps.storage, &export)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "start_export")
+            .add_attribute("sender", info.sender))
+    }
+
+    /// Unfreezes the contract, e.g. when a migration is called off
+    pub fn end_export(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(deps.as_ref(), &info, Role::Admin)?;
+        if self.export_mode.may_load(deps.storage)?.is_none() {
+            return Err(ContractError::NotExporting {});
+        }
+        self.export_mode.remove(deps.storage);
+
+        Ok(Response::new()
+            .add_attribute("action", "end_export")
+            .add_attribute("sender", info.sender))
+    }
+}
+
+// token aliases
+impl<'a, T, C> Cw721Contract<'a, T, C>
+where
+    T: Serialize + DeserializeOwned + Clone,
+    C: CustomMsg,
+{
+    pub fn set_token_alias(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        token_id: String,
+        alias: Option<String>,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(deps.as_ref(), &info, Role::Minter)?;
+        self.tokens.load(deps.storage, token_key(&token_id)?)?;
+        if let Some(alias) = &alias {
+            check_token_alias(alias)?;
+            match self.token_aliases.may_load(deps.storage, alias)? {
+                Some(aliased) if aliased == token_id => {}
+                Some(_) => return Err(ContractError::AliasTaken {}),
+                None => {}
+            }
+        }
+
+        self._clear_alias(deps.storage, &token_id)?;
+        if let Some(alias) = &alias {
+            self.token_aliases.save(deps.storage, alias, &token_id)?;
+            self.alias_by_token.save(deps.storage, &token_id, alias)?;
+        }
+
+        Ok(Response::new()
+            .add_attribute("action", "set_token_alias")
+            .add_attribute("sender", info.sender)
+            .add_attribute("token_id", token_id)
+            .add_attribute("alias", alias.unwrap_or_default()))
+    }
+
+    pub fn _clear_alias(&self, storage: &mut dyn Storage, token_id: &str) -> StdResult<()> {
+        if let Some(alias) = self.alias_by_token.may_load(storage, token_id)? {
+            self.token_aliases.remove(storage, &alias);
+            self.alias_by_token.remove(storage, token_id);
+        }
+        Ok(())
+    }
+}
+
+/// aliases are lowercase words joined by single hyphens, and never plain
+/// numbers so they cannot shadow a token id
+fn check_token_alias(alias: &str) -> Result<(), ContractError> {
+    let valid = !alias.is_empty()
+        && alias.len() <= MAX_ALIAS_LENGTH
+        && alias.split('-').all(|part| {
+            !part.is_empty()
+                && part
+                    .bytes()
+                    .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit())
+        })
+        && alias.parse::<u64>().is_err();
+    if !valid {
+        return Err(ContractError::InvalidTokenAlias {
+            max: MAX_ALIAS_LENGTH,
+        });
+    }
+    Ok(())
+}
+
+// token freezes
+impl<'a, T, C> Cw721Contract<'a, T, C>
+where
+    T: Serialize + DeserializeOwned + Clone,
+    C: CustomMsg,
+{
+    pub fn freeze_token(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        token_id: String,
+        reason: String,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(deps.as_ref(), &info, Role::Admin)?;
+        self.check_provenance_field(deps.storage, &reason)?;
+        // only existing tokens can be frozen
+        self.tokens.load(deps.storage, token_key(&token_id)?)?;
+
+        let frozen = FrozenToken {
+            reason: reason.clone(),
+            frozen_by: info.sender.clone(),
+            frozen_at: env.block.time,
+        };
+        self.frozen_tokens.save(deps.storage, &token_id, &frozen)?;
+        self._append_provenance(deps.storage, &env, &token_id, format!("freeze/{}", reason))?;
+
+        Ok(Response::new()
+            .add_attribute("action", "freeze_token")
+            .add_attribute("sender", info.sender)
+            .add_attribute("token_id", token_id)
+            .add_attribute("reason", reason))
+    }
+
+    pub fn unfreeze_token(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        token_id: String,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(deps.as_ref(), &info, Role::Admin)?;
+        if !self.frozen_tokens.has(deps.storage, &token_id) {
+            return Err(ContractError::TokenNotFrozen {});
+        }
+        self.frozen_tokens.remove(deps.storage, &token_id);
+        self._append_provenance(deps.storage, &env, &token_id, "unfreeze".to_string())?;
+
+        Ok(Response::new()
+            .add_attribute("action", "unfreeze_token")
+            .add_attribute("sender", info.sender)
+            .add_attribute("token_id", token_id))
+    }
+
+    pub fn seize_token(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        token_id: String,
+        custody_address: String,
+        reason: String,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(deps.as_ref(), &info, Role::Admin)?;
+        self.check_provenance_field(deps.storage, &reason)?;
+        if !self.frozen_tokens.has(deps.storage, &token_id) {
+            return Err(ContractError::TokenNotFrozen {});
+        }
+        let custody = self.resolve_addr(deps.as_ref(), &custody_address)?;
+        let mut token = self.tokens.load(deps.storage, token_key(&token_id)?)?;
+        let mut res = Response::new();
+        if token.owner == env.contract.address {
+            res = self._call_off_escrow(deps.storage, &token_id, res)?;
+        }
+
+        let previous_owner = token.owner.clone();
+        token.owner = custody.clone();
+        self._clear_approvals(deps.storage, &token_id, &mut token)?;
+        self.tokens
+            .save(deps.storage, token_key(&token_id)?, &token)?;
+        self._track_transfer(deps.storage, &env, &token_id, &previous_owner, &custody)?;
+        self._append_provenance(deps.storage, &env, &token_id, format!("seize/{}", reason))?;
+
+        Ok(res
+            .add_attribute("action", "seize_token")
+            .add_attribute("sender", info.sender)
+            .add_attribute("token_id", token_id)
+            .add_attribute("from", previous_owner)
+            .add_attribute("custody_address", custody)
+            .add_attribute("reason", reason))
+    }
+
+    /// drops the gift, conditional transfer, trade-in or reservation holding
+    /// token_id, refunding a reservation's buyer. Auctions hold bids and
+    /// bridges owe the token to another chain, their tokens stay escrowed
+    fn _call_off_escrow(
+        &self,
+        storage: &mut dyn Storage,
+        token_id: &str,
+        res: Response<C>,
+    ) -> Result<Response<C>, ContractError> {
+        if self.gifts.may_load(storage, token_id)?.is_some() {
+            self.gifts.remove(storage, token_id)?;
+            self._clear_expiry(storage, &gift_expiry(token_id))?;
+            return Ok(res.add_attribute("called_off", "gift"));
+        }
+        if self.conditional_transfers.has(storage, token_id) {
+            self.conditional_transfers.remove(storage, token_id);
+            self._clear_expiry(storage, &conditional_expiry(token_id))?;
+            return Ok(res.add_attribute("called_off", "conditional_transfer"));
+        }
+        if self.trade_ins.has(storage, token_id) {
+            self.trade_ins.remove(storage, token_id);
+            return Ok(res.add_attribute("called_off", "trade_in"));
+        }
+        if let Some(reservation) = self.reservations.may_load(storage, token_id)? {
+            let res = self._refund_reservation(storage, token_id, reservation, res)?;
+            return Ok(res.add_attribute("called_off", "reservation"));
+        }
+        Err(ContractError::TokenEscrowed {})
+    }
+
+    /// returns an error if the admin froze token_id
+    pub fn check_not_frozen(
+        &self,
+        storage: &dyn Storage,
+        token_id: &str,
+    ) -> Result<(), ContractError> {
+        if self.frozen_tokens.has(storage, token_id) {
+            return Err(ContractError::TokenFrozen {
+                token_id: token_id.to_string(),
+            });
+        }
+        Ok(())
+    }
+}
+
+// pin registry
+impl<'a, T, C> Cw721Contract<'a, T, C>
+where
+    T: Serialize + DeserializeOwned + Clone,
+    C: CustomMsg,
+{
+    #[allow(clippy::too_many_arguments)]
+    pub fn record_pin(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        token_id: String,
+        provider: String,
+        receipt_hash: String,
+        expires_at: Timestamp,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(deps.as_ref(), &info, Role::Custodian)?;
+        self.check_provenance_field(deps.storage, &provider)?;
+        self.check_provenance_field(deps.storage, &receipt_hash)?;
+        if provider.is_empty() || receipt_hash.is_empty() || expires_at <= env.block.time {
+            return Err(ContractError::InvalidPin {});
+        }
+        // only existing tokens can be pinned
+        self.tokens.load(deps.storage, token_key(&token_id)?)?;
+
+        if let Some(previous) = self.pins.may_load(deps.storage, (&token_id, &provider))? {
+            self.pin_expiries.remove(
+                deps.storage,
+                (previous.expires_at.seconds(), &token_id, &provider),
+            );
+        }
+        let pin = PinRecord {
+            receipt_hash,
+            expires_at,
+            status: PinStatus::Pinned,
+            updated_by: info.sender.clone(),
+            updated_at: env.block.time,
+        };
+        self.pins.save(deps.storage, (&token_id, &provider), &pin)?;
+        self.pin_expiries.save(
+            deps.storage,
+            (expires_at.seconds(), &token_id, &provider),
+            &Empty {},
+        )?;
+
+        Ok(Response::new()
+            .add_attribute("action", "record_pin")
+            .add_attribute("sender", info.sender)
+            .add_attribute("token_id", token_id)
+            .add_attribute("provider", provider)
+            .add_attribute("expires_at", expires_at.seconds().to_string()))
+    }
+
+    pub fn set_pin_status(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        token_id: String,
+        provider: String,
+        status: PinStatus,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(deps.as_ref(), &info, Role::Custodian)?;
+        let mut pin = self.pins.load(deps.storage, (&token_id, &provider))?;
+
+        // unpinned media no longer needs watching
+        let expiry = (
+            pin.expires_at.seconds(),
+            token_id.as_str(),
+            provider.as_str(),
+        );
+        if status == PinStatus::Unpinned {
+            self.pin_expiries.remove(deps.storage, expiry);
+        } else {
+            self.pin_expiries.save(deps.storage, expiry, &Empty {})?;
+        }
+        pin.status = status;
+        pin.updated_by = info.sender.clone();
+        pin.updated_at = env.block.time;
+        self.pins.save(deps.storage, (&token_id, &provider), &pin)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "set_pin_status")
+            .add_attribute("sender", info.sender)
+            .add_attribute("token_id", token_id)
+            .add_attribute("provider", provider)
+            .add_attribute("status", format!("{:?}", pin.status).to_lowercase()))
+    }
+}
+
+// provenance links
+impl<'a, T, C> Cw721Contract<'a, T, C>
+where
+    T: Serialize + DeserializeOwned + Clone,
+    C: CustomMsg,
+{
+    #[allow(clippy::too_many_arguments)]
+    pub fn propose_link(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        token_id: String,
+        contract: String,
+        linked_token_id: String,
+        kind: LinkKind,
+    ) -> Result<Response<C>, ContractError> {
+        self.check_link_owner(deps.as_ref(), &info, &token_id)?;
+        let contract = deps.api.addr_validate(&contract)?;
+        if contract == env.contract.address && linked_token_id == token_id {
+            return Err(ContractError::InvalidLink {});
+        }
+        let key = (token_id.as_str(), &contract, linked_token_id.as_str());
+        if self.token_links.has(deps.storage, key) {
+            return Err(ContractError::LinkExists {});
+        }
+        let link = TokenLink {
+            kind,
+            proposed: true,
+            created_at: env.block.time,
+            confirmed_at: None,
+        };
+        self.token_links.save(deps.storage, key, &link)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "propose_link")
+            .add_attribute("token_id", token_id)
+            .add_attribute("contract", contract)
+            .add_attribute("linked_token_id", linked_token_id))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn accept_link(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        token_id: String,
+        contract: String,
+        linked_token_id: String,
+        kind: LinkKind,
+    ) -> Result<Response<C>, ContractError> {
+        self.check_link_owner(deps.as_ref(), &info, &token_id)?;
+        let contract = deps.api.addr_validate(&contract)?;
+        let key = (token_id.as_str(), &contract, linked_token_id.as_str());
+        if self.token_links.has(deps.storage, key) {
+            return Err(ContractError::LinkExists {});
+        }
+        let link = TokenLink {
+            kind,
+            proposed: false,
+            created_at: env.block.time,
+            confirmed_at: Some(env.block.time),
+        };
+        self.token_links.save(deps.storage, key, &link)?;
+        self._append_provenance(
+            deps.storage,
+            &env,
+            &token_id,
+            format!("link/{}/{}", contract, linked_token_id),
+        )?;
+
+        // the proposing collection rejects the callback, and so this accept,
+        // unless the proposal is there
+        let callback = LinkCallback::Confirm {
+            token_id: linked_token_id.clone(),
+            linked_token_id: token_id.clone(),
+            kind,
+        };
+        Ok(Response::new()
+            .add_message(link_callback::<T>(&contract, callback)?)
+            .add_attribute("action", "accept_link")
+            .add_attribute("token_id", token_id)
+            .add_attribute("contract", contract)
+            .add_attribute("linked_token_id", linked_token_id))
+    }
+
+    pub fn remove_link(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        token_id: String,
+        contract: String,
+        linked_token_id: String,
+    ) -> Result<Response<C>, ContractError> {
+        self.check_link_owner(deps.as_ref(), &info, &token_id)?;
+        let contract = deps.api.addr_validate(&contract)?;
+        let key = (token_id.as_str(), &contract, linked_token_id.as_str());
+        let link = self.token_links.load(deps.storage, key)?;
+        self.token_links.remove(deps.storage, key);
+
+        let mut res = Response::new();
+        if link.confirmed_at.is_some() {
+            let callback = LinkCallback::Remove {
+                token_id: linked_token_id.clone(),
+                linked_token_id: token_id.clone(),
+            };
+            res = res.add_message(link_callback::<T>(&contract, callback)?);
+        }
+        Ok(res
+            .add_attribute("action", "remove_link")
+            .add_attribute("token_id", token_id)
+            .add_attribute("contract", contract)
+            .add_attribute("linked_token_id", linked_token_id))
+    }
+
+    pub fn receive_link(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        msg: LinkCallback,
+    ) -> Result<Response<C>, ContractError> {
+        // the sender is the collection holding the other side of the link
+        let contract = info.sender;
+        match msg {
+            LinkCallback::Confirm {
+                token_id,
+                linked_token_id,
+                kind,
+            } => {
+                let key = (token_id.as_str(), &contract, linked_token_id.as_str());
+                let mut link = match self.token_links.may_load(deps.storage, key)? {
+                    Some(link) if link.proposed && link.confirmed_at.is_none() => link,
+                    _ => return Err(ContractError::LinkNotProposed {}),
+                };
+                if link.kind != kind {
+                    return Err(ContractError::LinkNotProposed {});
+                }
+                link.confirmed_at = Some(env.block.time);
+                self.token_links.save(deps.storage, key, &link)?;
+                self._append_provenance(
+                    deps.storage,
+                    &env,
+                    &token_id,
+                    format!("link/{}/{}", contract, linked_token_id),
+                )?;
+
+                Ok(Response::new()
+                    .add_attribute("action", "confirm_link")
+                    .add_attribute("token_id", token_id)
+                    .add_attribute("contract", contract)
+                    .add_attribute("linked_token_id", linked_token_id))
+            }
+            LinkCallback::Remove {
+                token_id,
+                linked_token_id,
+            } => {
+                // already gone here if this side was burned, which must not
+                // block the other side
+                let key = (token_id.as_str(), &contract, linked_token_id.as_str());
+                self.token_links.remove(deps.storage, key);
+
+                Ok(Response::new()
+                    .add_attribute("action", "unlink")
+                    .add_attribute("token_id", token_id)
+                    .add_attribute("contract", contract)
+                    .add_attribute("linked_token_id", linked_token_id))
+            }
+        }
+    }
+
+    /// only the owner of token_id can link it
+    fn check_link_owner(
+        &self,
+        deps: Deps,
+        info: &MessageInfo,
+        token_id: &str,
+    ) -> Result<(), ContractError> {
+        let token = self.tokens.load(deps.storage, token_key(token_id)?)?;
+        if token.owner != info.sender {
+            return Err(ContractError::Unauthorized {});
+        }
+        Ok(())
+    }
+}
+
+/// message handing `callback` to the linked collection `contract`
+fn link_callback<T: Serialize>(contract: &Addr, callback: LinkCallback) -> StdResult<WasmMsg> {
+    Ok(WasmMsg::Execute {
+        contract_addr: contract.to_string(),
+        msg: to_json_binary(&ExecuteMsg::<T>::ReceiveLink(callback))?,
+        funds: vec![],
+    })
+}
+
+// token key migration
+impl<'a, T, C> Cw721Contract<'a, T, C>
+where
+    T: Serialize + DeserializeOwned + Clone,
+    C: CustomMsg,
+{
+    pub fn migrate_token_keys(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        limit: Option<u32>,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(deps.as_ref(), &info, Role::Admin)?;
+
+        let limit = limit
+            .unwrap_or(DEFAULT_MIGRATION_BATCH)
+            .min(MAX_MIGRATION_BATCH) as usize;
+        let batch = self
+            .legacy_tokens
+            .range(deps.storage, None, None, Order::Ascending)
+            .take(limit)
+            .collect::<StdResult<Vec<_>>>()?;
+
+        for (token_id, token) in &batch {
+            self.legacy_tokens.remove(deps.storage, token_id)?;
+            self.tokens
+                .save(deps.storage, token_key(token_id)?, token)?;
+        }
+        let remaining = self
+            .legacy_tokens
+            .keys_raw(deps.storage, None, None, Order::Ascending)
+            .next()
+            .is_some();
+
+        Ok(Response::new()
+            .add_attribute("action", "migrate_token_keys")
+            .add_attribute("migrated", batch.len().to_string())
+            .add_attribute("complete", (!remaining).to_string()))
+    }
+}
+
+// contract migration
+impl<'a, T, C> Cw721Contract<'a, T, C>
+where
+    T: Serialize + DeserializeOwned + Clone,
+    C: CustomMsg,
+{
+    pub fn migrate(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        msg: MigrateMsg,
+    ) -> Result<Response<C>, ContractError> {
+        let stored = get_contract_version(deps.storage)?;
+        if stored.contract != CONTRACT_NAME {
+            return Err(ContractError::CannotMigrate {
+                previous_contract: stored.contract,
+            });
+        }
+        let from = parse_version(&stored.version)?;
+        if from > parse_version(CONTRACT_VERSION)? {
+            return Err(ContractError::CannotMigrateVersion {
+                previous_version: stored.version,
+            });
+        }
+
+        let mut res = Response::new()
+            .add_attribute("action", "migrate")
+            .add_attribute("from_version", &stored.version)
+            .add_attribute("to_version", CONTRACT_VERSION);
+
+        // each step upgrades state written by versions before the one it is
+        // keyed by, in version order
+        if from < (0, 2, 0) {
+            let backfilled = self._backfill_token_uris(deps.storage)?;
+            let converted = self._convert_mint_price(deps.storage, msg.legacy_price_denom)?;
+            res = res
+                .add_attribute("backfilled_token_uris", backfilled.to_string())
+                .add_attribute("converted_mint_price", converted.to_string());
+        }
+
+        set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+        Ok(res)
+    }
+
+    /// gives tokens minted without a token_uri the collection's, as
+    /// `_create_token` does for new mints. Returns how many were updated
+    fn _backfill_token_uris(&self, storage: &mut dyn Storage) -> Result<usize, ContractError> {
+        let token_uri = match self.token_uri.load(storage)? {
+            Some(token_uri) => token_uri,
+            None => return Ok(0),
+        };
+
+        let tokens = self
+            .tokens
+            .range(storage, None, None, Order::Ascending)
+            .filter(|item| !matches!(item, Ok((_, token)) if token.token_uri.is_some()))
+            .collect::<StdResult<Vec<_>>>()?;
+        for (key, mut token) in tokens.iter().cloned() {
+            token.token_uri = Some(token_uri.clone());
+            self.tokens.save(storage, key, &token)?;
+        }
+        let legacy = self
+            .legacy_tokens
+            .range(storage, None, None, Order::Ascending)
+            .filter(|item| !matches!(item, Ok((_, token)) if token.token_uri.is_some()))
+            .collect::<StdResult<Vec<_>>>()?;
+        for (token_id, mut token) in legacy.iter().cloned() {
+            token.token_uri = Some(token_uri.clone());
+            self.legacy_tokens.save(storage, &token_id, &token)?;
+        }
+
+        Ok(tokens.len() + legacy.len())
+    }
+
+    /// rewrites a mint price stored as a bare amount as a coin of `denom`.
+    /// Returns whether it had to
+    fn _convert_mint_price(
+        &self,
+        storage: &mut dyn Storage,
+        denom: Option<String>,
+    ) -> Result<bool, ContractError> {
+        if self.mint_price.load(storage).is_ok() {
+            return Ok(false);
+        }
+        let amount = self.legacy_mint_price.load(storage)?;
+        let denom = denom.ok_or(ContractError::LegacyPriceDenomRequired {})?;
+        self.mint_price.save(storage, &Coin { denom, amount })?;
+        Ok(true)
+    }
+}
+
+/// (major, minor, patch) of a semver string, ignoring any pre-release or build
+/// suffix
+fn parse_version(version: &str) -> StdResult<(u64, u64, u64)> {
+    let core = version.split(['-', '+']).next().unwrap_or_default();
+    let parts = core
+        .split('.')
+        .map(|part| part.parse::<u64>())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| StdError::generic_err(format!("Invalid version {}", version)))?;
+    match parts[..] {
+        [major, minor, patch] => Ok((major, minor, patch)),
+        _ => Err(StdError::generic_err(format!(
+            "Invalid version {}",
+            version
+        ))),
+    }
+}
+
+// keepers
+impl<'a, T, C> Cw721Contract<'a, T, C>
+where
+    T: Serialize + DeserializeOwned + Clone,
+    C: CustomMsg,
+{
+    pub fn set_keeper_bounty(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        bounty: Option<Coin>,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(deps.as_ref(), &info, Role::Admin)?;
+
+        // the treasury is kept in the bounty's denom
+        let treasury = self
+            .keeper_treasury
+            .may_load(deps.storage)?
+            .unwrap_or_default();
+        let current_denom = self.keeper_bounty.may_load(deps.storage)?.map(|b| b.denom);
+        let new_denom = bounty.as_ref().map(|b| b.denom.clone());
+        if !treasury.balance.is_zero() && current_denom != new_denom {
+            return Err(ContractError::KeeperTreasuryNotEmpty {});
+        }
+
+        match bounty {
+            Some(bounty) => {
+                if bounty.amount.is_zero() {
+                    return Err(ContractError::InvalidKeeperBounty {});
+                }
+                self.keeper_bounty.save(deps.storage, &bounty)?;
+            }
+            None => self.keeper_bounty.remove(deps.storage),
+        }
+
+        Ok(Response::new()
+            .add_attribute("action", "set_keeper_bounty")
+            .add_attribute("sender", info.sender))
+    }
+
+    pub fn fund_keeper_treasury(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+    ) -> Result<Response<C>, ContractError> {
+        let bounty = self
+            .keeper_bounty
+            .may_load(deps.storage)?
+            .ok_or(ContractError::KeeperBountyDisabled {})?;
+        let amount = must_pay(&info, &bounty.denom)?;
+
+        let mut treasury = self
+            .keeper_treasury
+            .may_load(deps.storage)?
+            .unwrap_or_default();
+        treasury.balance += amount;
+        treasury.funded += amount;
+        self.keeper_treasury.save(deps.storage, &treasury)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "fund_keeper_treasury")
+            .add_attribute("sender", info.sender)
+            .add_attribute("amount", amount)
+            .add_attribute("balance", treasury.balance))
+    }
+
+    pub fn withdraw_keeper_treasury(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        amount: Uint128,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(deps.as_ref(), &info, Role::Treasurer)?;
+        let bounty = self
+            .keeper_bounty
+            .may_load(deps.storage)?
+            .ok_or(ContractError::KeeperBountyDisabled {})?;
+
+        let mut treasury = self
+            .keeper_treasury
+            .may_load(deps.storage)?
+            .unwrap_or_default();
+        if amount > treasury.balance {
+            return Err(ContractError::KeeperTreasuryInsufficient {
+                balance: treasury.balance,
+            });
+        }
+        treasury.balance -= amount;
+        treasury.withdrawn += amount;
+        self.keeper_treasury.save(deps.storage, &treasury)?;
 
         Ok(Response::new()
-            .add_attribute("action", "revoke")
+            .add_message(BankMsg::Send {
+                to_address: info.sender.to_string(),
+                amount: coins(amount.u128(), bounty.denom),
+            })
+            .add_attribute("action", "withdraw_keeper_treasury")
             .add_attribute("sender", info.sender)
-            .add_attribute("spender", spender)
-            .add_attribute("token_id", token_id))
+            .add_attribute("amount", amount))
     }
 
-    fn approve_all(
+    /// Drops approvals and operator grants that have expired, oldest first
+    pub fn clean_expired(
         &self,
         deps: DepsMut,
         env: Env,
         info: MessageInfo,
-        operator: String,
-        expires: Option<Expiration>,
+        limit: Option<u32>,
     ) -> Result<Response<C>, ContractError> {
-        // reject expired data as invalid
-        let expires = expires.unwrap_or_default();
-        if expires.is_expired(&env.block) {
-            return Err(ContractError::Expired {});
-        }
+        let limit = limit
+            .unwrap_or(DEFAULT_CLEANUP_BATCH)
+            .min(MAX_CLEANUP_BATCH) as usize;
+        let end = Some(Bound::inclusive((env.block.time.nanos(), u64::MAX)));
+        let batch = self
+            .expiries
+            .range(deps.storage, None, end, Order::Ascending)
+            .filter(|item| {
+                matches!(
+                    item,
+                    Err(_)
+                        | Ok((
+                            _,
+                            ExpiryKind::Approval { .. }
+                                | ExpiryKind::Operator { .. }
+                                | ExpiryKind::RangeApproval { .. }
+                        ))
+                )
+            })
+            .take(limit)
+            .collect::<StdResult<Vec<_>>>()?;
 
-        // set the operator for us
-        let operator_addr = deps.api.addr_validate(&operator)?;
-        self.operators
-            .save(deps.storage, (&info.sender, &operator_addr), &expires)?;
+        for (_, kind) in &batch {
+            match kind {
+                ExpiryKind::Approval { token_id, spender } => {
+                    let key = token_key(token_id)?;
+                    if let Some(mut token) = self.tokens.may_load(deps.storage, key)? {
+                        token.approvals.retain(|apr| apr.spender != *spender);
+                        self.tokens.save(deps.storage, key, &token)?;
+                    }
+                }
+                ExpiryKind::Operator { owner, operator } => {
+                    self.operators.remove(deps.storage, (owner, operator));
+                    self.operator_grants.remove(deps.storage, (operator, owner));
+                }
+                ExpiryKind::RangeApproval {
+                    owner,
+                    operator,
+                    first,
+                } => {
+                    self.range_approvals
+                        .remove(deps.storage, (owner, operator, *first));
+                }
+                _ => {}
+            }
+            self._clear_expiry(deps.storage, kind)?;
+        }
 
-        Ok(Response::new()
-            .add_attribute("action", "approve_all")
-            .add_attribute("sender", info.sender)
-            .add_attribute("operator", operator))
+        let res = Response::new()
+            .add_attribute("action", "clean_expired")
+            .add_attribute("sender", info.sender.clone())
+            .add_attribute("cleaned", batch.len().to_string());
+        if batch.is_empty() {
+            return Ok(res);
+        }
+        self._pay_keeper(deps.storage, &info.sender, res)
     }
 
-    fn revoke_all(
+    pub fn sweep_permissions(
         &self,
         deps: DepsMut,
         _env: Env,
         info: MessageInfo,
-        operator: String,
+        address: String,
+        limit: Option<u32>,
     ) -> Result<Response<C>, ContractError> {
-        let operator_addr = deps.api.addr_validate(&operator)?;
-        self.operators
-            .remove(deps.storage, (&info.sender, &operator_addr));
+        let limit = limit
+            .unwrap_or(DEFAULT_CLEANUP_BATCH)
+            .min(MAX_CLEANUP_BATCH) as usize;
+        let addr = self.resolve_addr(deps.as_ref(), &address)?;
+        let visited = self._sweep_permissions(deps.storage, &addr, limit)?;
 
-        Ok(Response::new()
-            .add_attribute("action", "revoke_all")
-            .add_attribute("sender", info.sender)
-            .add_attribute("operator", operator))
+        let res = Response::new()
+            .add_attribute("action", "sweep_permissions")
+            .add_attribute("sender", info.sender.clone())
+            .add_attribute("address", addr)
+            .add_attribute("visited", visited.to_string());
+        if visited == 0 {
+            return Ok(res);
+        }
+        self._pay_keeper(deps.storage, &info.sender, res)
     }
 
-    fn burn(
+    /// revokes the operator grants, range approvals, approvals and listing
+    /// approvals held by addr, visiting at most `limit` entries. Returns the
+    /// number visited and drops the sweep once it is complete
+    pub fn _sweep_permissions(
         &self,
-        deps: DepsMut,
-        env: Env,
-        info: MessageInfo,
-        token_id: String,
+        storage: &mut dyn Storage,
+        addr: &Addr,
+        limit: usize,
+    ) -> StdResult<usize> {
+        let mut sweep = match self.permission_sweeps.may_load(storage, addr)? {
+            Some(sweep) => sweep,
+            None => return Ok(0),
+        };
+        let mut visited = 0;
+        while visited < limit {
+            let budget = limit - visited;
+            match sweep.stage.clone() {
+                SweepStage::OperatorGrants => {
+                    let owners = self
+                        .operator_grants
+                        .prefix(addr)
+                        .keys(storage, None, None, Order::Ascending)
+                        .take(budget)
+                        .collect::<StdResult<Vec<_>>>()?;
+                    visited += owners.len();
+                    for owner in owners.iter() {
+                        self.operators.remove(storage, (owner, addr));
+                        self.operator_grants.remove(storage, (addr, owner));
+                        let expiry = ExpiryKind::Operator {
+                            owner: owner.clone(),
+                            operator: addr.clone(),
+                        };
+                        self._clear_expiry(storage, &expiry)?;
+                    }
+                    if owners.len() < budget {
+                        sweep.stage = SweepStage::RangeApprovals { after: None };
+                    }
+                }
+                SweepStage::RangeApprovals { after } => {
+                    let start = after.as_ref().map(|(owner, operator, first)| {
+                        Bound::exclusive((owner, operator, *first))
+                    });
+                    let keys = self
+                        .range_approvals
+                        .keys(storage, start, None, Order::Ascending)
+                        .take(budget)
+                        .collect::<StdResult<Vec<_>>>()?;
+                    visited += keys.len();
+                    for (owner, operator, first) in keys.iter() {
+                        if operator != addr {
+                            continue;
+                        }
+                        self.range_approvals
+                            .remove(storage, (owner, operator, *first));
+                        let expiry = ExpiryKind::RangeApproval {
+                            owner: owner.clone(),
+                            operator: operator.clone(),
+                            first: *first,
+                        };
+                        self._clear_expiry(storage, &expiry)?;
+                    }
+                    sweep.stage = match keys.last() {
+                        Some(last) if keys.len() == budget => SweepStage::RangeApprovals {
+                            after: Some(last.clone()),
+                        },
+                        _ => SweepStage::Tokens { after: None },
+                    };
+                }
+                SweepStage::Tokens { after } => {
+                    let tokens = self
+                        .tokens
+                        .range(storage, after.map(Bound::exclusive), None, Order::Ascending)
+                        .take(budget)
+                        .collect::<StdResult<Vec<_>>>()?;
+                    visited += tokens.len();
+                    for (key, mut token) in tokens.iter().cloned() {
+                        let token_id = key.to_string();
+                        if token.approvals.iter().any(|apr| apr.spender == *addr) {
+                            token.approvals.retain(|apr| apr.spender != *addr);
+                            self.tokens.save(storage, key, &token)?;
+                            let expiry = ExpiryKind::Approval {
+                                token_id: token_id.clone(),
+                                spender: addr.clone(),
+                            };
+                            self._clear_expiry(storage, &expiry)?;
+                        }
+                        self.listing_approvals.remove(storage, (&token_id, addr));
+                    }
+                    match tokens.last() {
+                        Some((last, _)) if tokens.len() == budget => {
+                            sweep.stage = SweepStage::Tokens { after: Some(*last) };
+                        }
+                        _ => {
+                            self.permission_sweeps.remove(storage, addr);
+                            return Ok(visited);
+                        }
+                    }
+                }
+            }
+        }
+        self.permission_sweeps.save(storage, addr, &sweep)?;
+        Ok(visited)
+    }
+
+    /// pays the keeper bounty to `keeper` for a successful maintenance call.
+    /// Maintenance still goes through when the treasury has run dry
+    pub fn _pay_keeper(
+        &self,
+        storage: &mut dyn Storage,
+        keeper: &Addr,
+        res: Response<C>,
     ) -> Result<Response<C>, ContractError> {
-        let token = self.tokens.load(deps.storage, &token_id)?;
-        self.check_can_send(deps.as_ref(), &env, &info, &token)?;
+        let bounty = match self.keeper_bounty.may_load(storage)? {
+            Some(bounty) => bounty,
+            None => return Ok(res),
+        };
+        let mut treasury = self.keeper_treasury.may_load(storage)?.unwrap_or_default();
+        if treasury.balance < bounty.amount {
+            return Ok(res);
+        }
+        treasury.balance -= bounty.amount;
+        treasury.paid += bounty.amount;
+        self.keeper_treasury.save(storage, &treasury)?;
 
-        self.tokens.remove(deps.storage, &token_id)?;
-        self.update_token_count(deps.storage, false)?;
+        Ok(res
+            .add_attribute("keeper_bounty", bounty.amount)
+            .add_message(BankMsg::Send {
+                to_address: keeper.to_string(),
+                amount: vec![bounty],
+            }))
+    }
+}
 
-        Ok(Response::new()
-            .add_attribute("action", "burn")
-            .add_attribute("sender", info.sender)
-            .add_attribute("token_id", token_id))
+/// accepts compressed or uncompressed secp256k1 keys
+fn check_pubkey(pubkey: &Binary) -> Result<(), ContractError> {
+    if pubkey.len() != 33 && pubkey.len() != 65 {
+        return Err(ContractError::InvalidPubkey {});
     }
+    Ok(())
 }
 
 // helpers
@@ -258,16 +9896,281 @@ where
         recipient: &str,
         token_id: &str,
     ) -> Result<TokenInfo<T>, ContractError> {
-        let mut token = self.tokens.load(deps.storage, token_id)?;
+        let mut token = self.tokens.load(deps.storage, token_key(token_id)?)?;
         // ensure we have permissions
-        self.check_can_send(deps.as_ref(), env, info, &token)?;
+        self.check_can_send(deps.as_ref(), env, info, token_id, &token)?;
+        self.check_transferable(deps.storage, token_id)?;
+        self.check_storage_fee(deps.storage, env, token_id)?;
         // set owner and remove existing approvals
-        token.owner = deps.api.addr_validate(recipient)?;
-        token.approvals = vec![];
-        self.tokens.save(deps.storage, token_id, &token)?;
+        let previous_owner = token.owner;
+        token.owner = self.resolve_addr(deps.as_ref(), recipient)?;
+        self._clear_approvals(deps.storage, token_id, &mut token)?;
+        self.tokens
+            .save(deps.storage, token_key(token_id)?, &token)?;
+        self._track_transfer(deps.storage, env, token_id, &previous_owner, &token.owner)?;
         Ok(token)
     }
 
+    /// removes a token from the collection and records its tombstone
+    pub fn _remove_token(
+        &self,
+        storage: &mut dyn Storage,
+        env: &Env,
+        token_id: &str,
+        owner: &Addr,
+        kind: RemovalKind,
+        removed_by: &Addr,
+    ) -> Result<(), ContractError> {
+        // burning a receipt would strand the deposited token in the vault
+        if self.vault_deposits.has(storage, token_id) {
+            return Err(ContractError::VaultReceipt {});
+        }
+        self.check_not_frozen(storage, token_id)?;
+        let mut token = self.tokens.load(storage, token_key(token_id)?)?;
+        self._clear_approvals(storage, token_id, &mut token)?;
+        self._clear_listing_approvals(storage, token_id)?;
+        for right in [TokenRight::Physical, TokenRight::Display] {
+            self.token_rights.remove(storage, (token_id, right.key()));
+        }
+        if self.storage_accounts.has(storage, token_id) {
+            self.storage_accounts.remove(storage, token_id);
+            let count = self.storage_custody_count.load(storage)?;
+            self.storage_custody_count.save(storage, &(count - 1))?;
+        }
+        self.tokens.remove(storage, token_key(token_id)?)?;
+        self.update_token_count(storage, false)?;
+        self.update_holding(storage, owner, env.block.time, false)?;
+        self.metadata_variants.remove(storage, token_id);
+        self.metadata_frozen.remove(storage, token_id);
+        let content_hash = self.content_hashes.may_load(storage, token_id)?;
+        self.content_hashes.remove(storage, token_id);
+        for key in content_keys(&token.token_uri, &content_hash) {
+            if self.content_index.may_load(storage, &key)?.as_deref() == Some(token_id) {
+                self.content_index.remove(storage, &key);
+            }
+        }
+        self.redactions.remove(storage, token_id);
+        self.listings.remove(storage, token_id);
+        self.drop_mints.remove(storage, token_id);
+        let pins = self
+            .pins
+            .prefix(token_id)
+            .range(storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()?;
+        for (provider, pin) in pins {
+            self.pins.remove(storage, (token_id, &provider));
+            self.pin_expiries
+                .remove(storage, (pin.expires_at.seconds(), token_id, &provider));
+        }
+        let links = self
+            .token_links
+            .sub_prefix(token_id)
+            .keys(storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()?;
+        for (contract, linked_token_id) in links {
+            self.token_links
+                .remove(storage, (token_id, &contract, &linked_token_id));
+        }
+        self.sealed_tokens.remove(storage, token_id);
+        self.non_transferable.remove(storage, token_id);
+        self._clear_alias(storage, token_id)?;
+        self.token_series.remove(storage, token_id);
+        self._clear_artist(storage, token_id)?;
+        self.certificates.remove(storage, token_id);
+        self.royalty_splits.remove(storage, token_id);
+        self.royalty_successions.remove(storage, token_id);
+        if self.vouchers.has(storage, token_id) {
+            self.vouchers.remove(storage, token_id);
+            let count = self.voucher_count.load(storage)?;
+            self.voucher_count.save(storage, &(count - 1))?;
+        }
+        if let Some(voucher) = self.ibc_vouchers.may_load(storage, token_id)? {
+            self.ibc_vouchers.remove(storage, token_id);
+            self.ibc_voucher_ids
+                .remove(storage, (&voucher.class_id, &voucher.token_id));
+            let count = self.ibc_voucher_count.load(storage)?;
+            self.ibc_voucher_count.save(storage, &(count - 1))?;
+        }
+        if let Some(mut featured) = self.featured.may_load(storage)? {
+            featured.retain(|f| f.token_id != token_id);
+            self.featured.save(storage, &featured)?;
+        }
+
+        let event = match &kind {
+            RemovalKind::Burned => "burn".to_string(),
+            RemovalKind::Refunded => "refund".to_string(),
+            RemovalKind::Redeemed => "redeem".to_string(),
+            RemovalKind::VoucherRedeemed { gallery } => format!("visit/{}", gallery),
+            RemovalKind::Destroyed { evidence_hash, .. } => format!("destroy/{}", evidence_hash),
+            RemovalKind::Bridged { channel_id } => format!("bridge/{}", channel_id),
+        };
+        self._append_provenance(storage, env, token_id, event)?;
+
+        let tombstone = Tombstone {
+            kind,
+            owner: owner.clone(),
+            removed_by: removed_by.clone(),
+            removed_at: env.block.time,
+        };
+        self.tombstones.save(storage, token_id, &tombstone)?;
+        Ok(())
+    }
+
+    /// drops all approvals of a token along with their expiry ledger entries
+    pub fn _clear_approvals(
+        &self,
+        storage: &mut dyn Storage,
+        token_id: &str,
+        token: &mut TokenInfo<T>,
+    ) -> StdResult<()> {
+        for approval in token.approvals.drain(..) {
+            let expiry = ExpiryKind::Approval {
+                token_id: token_id.to_string(),
+                spender: approval.spender,
+            };
+            self._clear_expiry(storage, &expiry)?;
+        }
+        Ok(())
+    }
+
+    /// adds `kind` to the expiry ledger at `at`, replacing its previous entry
+    pub fn _schedule_expiry(
+        &self,
+        storage: &mut dyn Storage,
+        at: Timestamp,
+        kind: ExpiryKind,
+    ) -> StdResult<()> {
+        self._clear_expiry(storage, &kind)?;
+        let seq = self.expiry_count.may_load(storage)?.unwrap_or_default() + 1;
+        self.expiry_count.save(storage, &seq)?;
+        self.expiries.save(storage, (at.nanos(), seq), &kind)?;
+        self.expiry_keys
+            .save(storage, &kind.key(), &(at.nanos(), seq))
+    }
+
+    pub fn _clear_expiry(&self, storage: &mut dyn Storage, kind: &ExpiryKind) -> StdResult<()> {
+        let key = kind.key();
+        if let Some(entry) = self.expiry_keys.may_load(storage, &key)? {
+            self.expiries.remove(storage, entry);
+            self.expiry_keys.remove(storage, &key);
+        }
+        Ok(())
+    }
+
+    /// records a change of owner in holding scores and the token's provenance
+    pub fn _track_transfer(
+        &self,
+        storage: &mut dyn Storage,
+        env: &Env,
+        token_id: &str,
+        from: &Addr,
+        to: &Addr,
+    ) -> StdResult<()> {
+        self.update_holding(storage, from, env.block.time, false)?;
+        self.update_holding(storage, to, env.block.time, true)?;
+        self.listings.remove(storage, token_id);
+        // escrow keeps them, so a marketplace can still cancel its auction
+        if *to != env.contract.address {
+            self._clear_listing_approvals(storage, token_id)?;
+        }
+        let event = ProvenanceEvent::Transfer {
+            from: from.clone(),
+            to: to.clone(),
+        };
+        self._log_provenance(storage, env, token_id, event)
+    }
+
+    /// stores the receipt of a token's latest sale and adds it to its provenance
+    pub fn _record_sale(
+        &self,
+        storage: &mut dyn Storage,
+        env: &Env,
+        token_id: &str,
+        receipt: &SaleReceipt,
+    ) -> StdResult<()> {
+        if self.is_minimal(storage)? {
+            return Ok(());
+        }
+        // the first sale of a token is its primary sale
+        let secondary = self.sale_receipts.has(storage, token_id);
+        self.sale_receipts.save(storage, token_id, receipt)?;
+        let seq = self.sales_count.may_load(storage)?.unwrap_or_default() + 1;
+        self.sales_count.save(storage, &seq)?;
+        let sale = Sale {
+            token_id: token_id.to_string(),
+            receipt: receipt.clone(),
+            secondary,
+            sold_at: env.block.time,
+        };
+        self.sales_feed.save(storage, seq, &sale)?;
+        if let Some(artist) = self.token_artists.may_load(storage, token_id)? {
+            let mut stats = self.artist_stats.load(storage, &artist)?;
+            stats.sales += 1;
+            match stats
+                .volume
+                .iter_mut()
+                .find(|c| c.denom == receipt.price.denom)
+            {
+                Some(volume) => volume.amount += receipt.price.amount,
+                None => stats.volume.push(receipt.price.clone()),
+            }
+            self.artist_stats.save(storage, &artist, &stats)?;
+        }
+        let event = ProvenanceEvent::Sale {
+            buyer: receipt.buyer.clone(),
+            price: receipt.price.clone(),
+        };
+        self._log_provenance(storage, env, token_id, event)
+    }
+
+    /// logs `event` under the token's provenance, see `_log_provenance`
+    pub fn _append_provenance(
+        &self,
+        storage: &mut dyn Storage,
+        env: &Env,
+        token_id: &str,
+        event: String,
+    ) -> StdResult<()> {
+        self._log_provenance(storage, env, token_id, ProvenanceEvent::Other { event })
+    }
+
+    /// appends `event` to the token's provenance log and chains it into its
+    /// digest: `hash = sha256(previous hash ++ "{entry}/{block height}/{event}")`
+    pub fn _log_provenance(
+        &self,
+        storage: &mut dyn Storage,
+        env: &Env,
+        token_id: &str,
+        event: ProvenanceEvent,
+    ) -> StdResult<()> {
+        if self.is_minimal(storage)? {
+            return Ok(());
+        }
+        let mut digest = self
+            .provenance_digests
+            .may_load(storage, token_id)?
+            .unwrap_or_default();
+        let entry = format!(
+            "{}/{}/{}",
+            digest.entries,
+            env.block.height,
+            event.digest_event()
+        );
+        let mut hasher = Sha256::new();
+        hasher.update(digest.hash.as_slice());
+        hasher.update(entry.as_bytes());
+        digest.hash = HexBinary::from(hasher.finalize().as_slice());
+        let logged = ProvenanceEntry {
+            event,
+            height: env.block.height,
+            recorded_at: env.block.time,
+        };
+        self.provenance_log
+            .save(storage, (token_id, digest.entries), &logged)?;
+        digest.entries += 1;
+        self.provenance_digests.save(storage, token_id, &digest)
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub fn _update_approvals(
         &self,
@@ -280,25 +10183,30 @@ where
         add: bool,
         expires: Option<Expiration>,
     ) -> Result<TokenInfo<T>, ContractError> {
-        let mut token = self.tokens.load(deps.storage, token_id)?;
+        let mut token = self.tokens.load(deps.storage, token_key(token_id)?)?;
         // ensure we have permissions
         self.check_can_approve(deps.as_ref(), env, info, &token)?;
 
         // update the approval list (remove any for the same spender before adding)
-        let spender_addr = deps.api.addr_validate(spender)?;
-        token.approvals = token
-            .approvals
-            .into_iter()
-            .filter(|apr| apr.spender != spender_addr)
-            .collect();
+        let spender_addr = self.resolve_addr(deps.as_ref(), spender)?;
+        token.approvals.retain(|apr| apr.spender != spender_addr);
+        let expiry = ExpiryKind::Approval {
+            token_id: token_id.to_string(),
+            spender: spender_addr.clone(),
+        };
+        self._clear_expiry(deps.storage, &expiry)?;
 
         // only difference between approve and revoke
         if add {
+            self.check_transferable(deps.storage, token_id)?;
             // reject expired data as invalid
             let expires = expires.unwrap_or_default();
             if expires.is_expired(&env.block) {
                 return Err(ContractError::Expired {});
             }
+            if let Expiration::AtTime(at) = expires {
+                self._schedule_expiry(deps.storage, at, expiry)?;
+            }
             let approval = Approval {
                 spender: spender_addr,
                 expires,
@@ -306,7 +10214,8 @@ where
             token.approvals.push(approval);
         }
 
-        self.tokens.save(deps.storage, token_id, &token)?;
+        self.tokens
+            .save(deps.storage, token_key(token_id)?, &token)?;
 
         Ok(token)
     }
@@ -345,8 +10254,10 @@ where
         deps: Deps,
         env: &Env,
         info: &MessageInfo,
+        token_id: &str,
         token: &TokenInfo<T>,
     ) -> Result<(), ContractError> {
+        self.check_not_frozen(deps.storage, token_id)?;
         // owner can send
         if token.owner == info.sender {
             return Ok(());
@@ -365,15 +10276,351 @@ where
         let op = self
             .operators
             .may_load(deps.storage, (&token.owner, &info.sender))?;
-        match op {
-            Some(ex) => {
-                if ex.is_expired(&env.block) {
-                    Err(ContractError::Unauthorized {})
-                } else {
-                    Ok(())
-                }
+        if op.is_some_and(|ex| !ex.is_expired(&env.block)) {
+            return Ok(());
+        }
+
+        // so can a range operator whose range covers the token. Ranges are
+        // keyed by their first id, so only those starting at or below it are
+        // read, and each pair has at most MAX_RANGE_APPROVALS of them
+        let key = token_key(token_id)?;
+        let covered = self
+            .range_approvals
+            .prefix((&token.owner, &info.sender))
+            .range(
+                deps.storage,
+                None,
+                Some(Bound::inclusive(key)),
+                Order::Ascending,
+            )
+            .collect::<StdResult<Vec<_>>>()?
+            .into_iter()
+            .any(|(_, range)| range.last >= key && !range.expires.is_expired(&env.block));
+        if covered {
+            return Ok(());
+        }
+        Err(ContractError::Unauthorized {})
+    }
+
+    /// only the minter role can mint soulbound tokens
+    pub fn check_transferable_flag(
+        &self,
+        deps: Deps,
+        info: &MessageInfo,
+        transferable: bool,
+    ) -> Result<(), ContractError> {
+        if !transferable {
+            self.assert_role(deps, info, Role::Minter)?;
+        }
+        Ok(())
+    }
+
+    pub fn _set_transferable(
+        &self,
+        storage: &mut dyn Storage,
+        token_id: &str,
+        transferable: bool,
+    ) -> StdResult<()> {
+        if !transferable {
+            self.non_transferable.save(storage, token_id, &Empty {})?;
+        }
+        Ok(())
+    }
+
+    /// returns an error if token_id is soulbound
+    pub fn check_transferable(
+        &self,
+        storage: &dyn Storage,
+        token_id: &str,
+    ) -> Result<(), ContractError> {
+        if self.non_transferable.has(storage, token_id) {
+            return Err(ContractError::NonTransferable {
+                token_id: token_id.to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    /// only the minter can give a token its own token_uri
+    pub fn check_token_uri(
+        &self,
+        deps: Deps,
+        info: &MessageInfo,
+        token_uri: &Option<String>,
+    ) -> Result<(), ContractError> {
+        if let Some(token_uri) = token_uri {
+            self.assert_role(deps, info, Role::Minter)?;
+            self.check_uri(deps.storage, token_uri)?;
+        }
+        Ok(())
+    }
+
+    /// with unique content enforced, returns an error if another token has
+    /// the token_uri or content hash
+    pub fn check_unique_content(
+        &self,
+        storage: &dyn Storage,
+        token_uri: &Option<String>,
+        content_hash: &Option<HexBinary>,
+    ) -> Result<(), ContractError> {
+        if !self.unique_content.may_load(storage)?.unwrap_or(false) {
+            return Ok(());
+        }
+        for key in content_keys(token_uri, content_hash) {
+            if let Some(token_id) = self.content_index.may_load(storage, &key)? {
+                return Err(ContractError::DuplicateContent { token_id });
             }
-            None => Err(ContractError::Unauthorized {}),
         }
+        Ok(())
+    }
+
+    /// indexes a new token's content keys, keeping any earlier token's entry
+    pub fn _index_content(
+        &self,
+        storage: &mut dyn Storage,
+        token_id: &str,
+        keys: Vec<String>,
+        content_hash: Option<HexBinary>,
+    ) -> StdResult<()> {
+        if let Some(content_hash) = content_hash {
+            self.content_hashes.save(storage, token_id, &content_hash)?;
+        }
+        for key in keys {
+            if !self.content_index.has(storage, &key) {
+                self.content_index
+                    .save(storage, &key, &token_id.to_string())?;
+            }
+        }
+        Ok(())
+    }
+
+    /// returns an error if minting is disabled or the collection is sold out
+    pub fn check_mint_available(&self, storage: &dyn Storage) -> Result<(), ContractError> {
+        if !self.mint_allowed.load(storage)? {
+            return Err(ContractError::MintingDisabled {});
+        }
+
+        if self.remaining_mints(storage)? == 0 {
+            return Err(ContractError::MaxMintsReached {});
+        }
+        Ok(())
+    }
+
+    /// counts a mint paid for by minter, erroring once it reaches the
+    /// per-wallet limit
+    pub fn _count_wallet_mint(
+        &self,
+        storage: &mut dyn Storage,
+        minter: &Addr,
+    ) -> Result<(), ContractError> {
+        let minted = self
+            .mints_per_address
+            .may_load(storage, minter)?
+            .unwrap_or_default();
+        if let Some(limit) = self.max_mints_per_address.may_load(storage)? {
+            if minted >= limit {
+                return Err(ContractError::WalletMintLimitReached { limit });
+            }
+        }
+        self.mints_per_address
+            .save(storage, minter, &(minted + 1))?;
+        Ok(())
+    }
+
+    /// counts a token acquired by addr toward the cap of the drop in progress
+    pub fn _count_drop_acquisition(
+        &self,
+        storage: &mut dyn Storage,
+        addr: &Addr,
+    ) -> Result<(), ContractError> {
+        if let Some((drop, acquired)) = self.drop_acquired(storage, addr)? {
+            if acquired >= drop.max_per_address {
+                return Err(ContractError::DropLimitReached {
+                    limit: drop.max_per_address,
+                });
+            }
+            self.drop_acquisitions
+                .save(storage, (drop.drop_id, addr), &(acquired + 1))?;
+        }
+        Ok(())
+    }
+
+    /// counts a purchase of token_id toward the buyer's cap if the token was
+    /// minted in the drop in progress within its resale window
+    pub fn _count_drop_resale(
+        &self,
+        storage: &mut dyn Storage,
+        env: &Env,
+        buyer: &Addr,
+        token_id: &str,
+    ) -> Result<(), ContractError> {
+        let (drop, mint) = match (
+            self.drop_limit.may_load(storage)?,
+            self.drop_mints.may_load(storage, token_id)?,
+        ) {
+            (Some(drop), Some(mint)) if mint.drop_id == drop.drop_id => (drop, mint),
+            _ => return Ok(()),
+        };
+        if env.block.time < mint.minted_at.plus_seconds(drop.resale_window) {
+            self._count_drop_acquisition(storage, buyer)?;
+        }
+        Ok(())
+    }
+
+    /// notes the drop a newly minted token belongs to
+    pub fn _record_drop_mint(
+        &self,
+        storage: &mut dyn Storage,
+        env: &Env,
+        token_id: &str,
+    ) -> StdResult<()> {
+        if let Some(drop) = self.drop_limit.may_load(storage)? {
+            let mint = DropMint {
+                drop_id: drop.drop_id,
+                minted_at: env.block.time,
+            };
+            self.drop_mints.save(storage, token_id, &mint)?;
+        }
+        Ok(())
+    }
+
+    pub fn check_extension(
+        &self,
+        storage: &dyn Storage,
+        extension: &T,
+    ) -> Result<(), ContractError> {
+        let max_extension_bytes = self.load_size_limits(storage)?.max_extension_bytes;
+        if to_json_vec(extension)?.len() > max_extension_bytes as usize {
+            return Err(ContractError::ExtensionTooLarge {
+                max: max_extension_bytes,
+            });
+        }
+        if let Some(artwork) = self.artwork(extension)? {
+            self.check_artwork(storage, &artwork)?;
+        }
+        Ok(())
+    }
+
+    pub fn check_artwork(
+        &self,
+        storage: &dyn Storage,
+        artwork: &ArtworkExtension,
+    ) -> Result<(), ContractError> {
+        if artwork.artist_name.trim().is_empty() || artwork.title.trim().is_empty() {
+            return Err(ContractError::InvalidArtwork {});
+        }
+        if artwork
+            .edition
+            .as_ref()
+            .is_some_and(|e| e.number == 0 || e.number > e.size)
+        {
+            return Err(ContractError::InvalidArtwork {});
+        }
+        let fields = [
+            Some(&artwork.artist_name),
+            Some(&artwork.title),
+            artwork.medium.as_ref(),
+            artwork.dimensions.as_ref(),
+            artwork.provenance_notes.as_ref(),
+        ];
+        for field in fields.into_iter().flatten() {
+            self.check_provenance_field(storage, field)?;
+        }
+        Ok(())
+    }
+
+    pub fn save_size_limits(
+        &self,
+        storage: &mut dyn Storage,
+        limits: &SizeLimits,
+    ) -> Result<(), ContractError> {
+        if limits.max_uri_length == 0
+            || limits.max_extension_bytes == 0
+            || limits.max_provenance_entry_length == 0
+        {
+            return Err(ContractError::InvalidSizeLimits {});
+        }
+        self.size_limits.save(storage, limits)?;
+        Ok(())
+    }
+
+    pub fn check_uri(&self, storage: &dyn Storage, uri: &str) -> Result<(), ContractError> {
+        let max = self.load_size_limits(storage)?.max_uri_length;
+        if uri.len() > max as usize {
+            return Err(ContractError::UriTooLong { max });
+        }
+        Ok(())
+    }
+
+    pub fn check_provenance_field(
+        &self,
+        storage: &dyn Storage,
+        field: &str,
+    ) -> Result<(), ContractError> {
+        let max = self.load_size_limits(storage)?.max_provenance_entry_length;
+        if field.len() > max as usize {
+            return Err(ContractError::ProvenanceEntryTooLong { max });
+        }
+        Ok(())
+    }
+
+    /// stores a new token for owner under the next token id and returns the id
+    pub fn _create_token(
+        &self,
+        storage: &mut dyn Storage,
+        env: &Env,
+        owner: Addr,
+        payer: &Addr,
+        token_uri: Option<String>,
+        extension: T,
+    ) -> Result<String, ContractError> {
+        self.check_extension(storage, &extension)?;
+        self.update_holding(storage, &owner, env.block.time, true)?;
+        let event = ProvenanceEvent::Mint {
+            owner: owner.clone(),
+            payer: payer.clone(),
+        };
+        let token_uri = match token_uri {
+            Some(token_uri) => Some(token_uri),
+            None => self.token_uri.load(storage)?,
+        };
+        let token = TokenInfo {
+            owner,
+            approvals: vec![],
+            token_uri,
+            extension,
+            royalty: None,
+        };
+        let token_id = self.next_token_id(storage)?;
+        self.update_token_count(storage, true)?;
+        self.tokens.update(storage, token_id, |old| match old {
+            Some(_) => Err(ContractError::Claimed {}),
+            None => Ok(token),
+        })?;
+        let token_id = token_id.to_string();
+        self._log_provenance(storage, env, &token_id, event)?;
+        Ok(token_id)
+    }
+
+    /// returns an error unless the sender is the configured minter
+    pub fn assert_minter(&self, deps: Deps, info: &MessageInfo) -> Result<(), ContractError> {
+        let minter = self.minter.load(deps.storage)?;
+        if info.sender != minter {
+            return Err(ContractError::Unauthorized {});
+        }
+        Ok(())
+    }
+
+    /// returns an error unless the sender holds role, or is the minter
+    pub fn assert_role(
+        &self,
+        deps: Deps,
+        info: &MessageInfo,
+        role: Role,
+    ) -> Result<(), ContractError> {
+        if !self.has_role(deps.storage, &info.sender, role)? {
+            return Err(ContractError::Unauthorized {});
+        }
+        Ok(())
     }
 }
@@ -0,0 +1,337 @@
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use cosmwasm_std::{
+    from_json, to_json_binary, Addr, Binary, DepsMut, Env, Ibc3ChannelOpenResponse,
+    IbcBasicResponse, IbcChannelCloseMsg, IbcChannelConnectMsg, IbcChannelOpenMsg,
+    IbcChannelOpenResponse, IbcOrder, IbcPacket, IbcPacketAckMsg, IbcPacketReceiveMsg,
+    IbcPacketTimeoutMsg, IbcReceiveResponse, StdResult, Storage,
+};
+
+use cw721::CustomMsg;
+
+use crate::error::ContractError;
+use crate::execute::check_batch_size;
+use crate::msg::{Ics721Ack, NonFungibleTokenPacketData};
+use crate::state::{token_key, Cw721Contract, IbcVoucher, RemovalKind};
+
+pub const IBC_VERSION: &str = "ics721-1";
+
+// channel handshake
+impl<'a, T, C> Cw721Contract<'a, T, C>
+where
+    T: Serialize + DeserializeOwned + Clone,
+    C: CustomMsg,
+{
+    pub fn ibc_channel_open(
+        &self,
+        _deps: DepsMut,
+        _env: Env,
+        msg: IbcChannelOpenMsg,
+    ) -> Result<IbcChannelOpenResponse, ContractError> {
+        let channel = msg.channel();
+        if channel.order != IbcOrder::Unordered {
+            return Err(ContractError::OnlyUnorderedChannel {});
+        }
+        for version in [Some(channel.version.as_str()), msg.counterparty_version()]
+            .into_iter()
+            .flatten()
+        {
+            if version != IBC_VERSION {
+                return Err(ContractError::InvalidIbcVersion {
+                    expected: IBC_VERSION.to_string(),
+                    version: version.to_string(),
+                });
+            }
+        }
+        Ok(Some(Ibc3ChannelOpenResponse {
+            version: IBC_VERSION.to_string(),
+        }))
+    }
+
+    pub fn ibc_channel_connect(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        msg: IbcChannelConnectMsg,
+    ) -> Result<IbcBasicResponse<C>, ContractError> {
+        let channel = msg.channel();
+        self.ibc_channels
+            .save(deps.storage, &channel.endpoint.channel_id, channel)?;
+
+        Ok(IbcBasicResponse::new()
+            .add_attribute("action", "ibc_channel_connect")
+            .add_attribute("channel_id", &channel.endpoint.channel_id))
+    }
+
+    pub fn ibc_channel_close(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        msg: IbcChannelCloseMsg,
+    ) -> Result<IbcBasicResponse<C>, ContractError> {
+        // tokens escrowed for the channel stay with the contract, their
+        // counterparts being stuck on the other chain
+        let channel = msg.channel();
+        self.ibc_channels
+            .remove(deps.storage, &channel.endpoint.channel_id);
+
+        Ok(IbcBasicResponse::new()
+            .add_attribute("action", "ibc_channel_close")
+            .add_attribute("channel_id", &channel.endpoint.channel_id))
+    }
+}
+
+// packets
+impl<'a, T, C> Cw721Contract<'a, T, C>
+where
+    T: Serialize + DeserializeOwned + Clone,
+    C: CustomMsg,
+{
+    pub fn ibc_packet_receive(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        msg: IbcPacketReceiveMsg,
+    ) -> Result<IbcReceiveResponse<C>, ContractError> {
+        // failures are acknowledged rather than returned, so the sending
+        // chain gives the tokens back
+        match self._receive_packet(deps, &env, &msg.packet) {
+            Ok(res) => Ok(res.set_ack(to_json_binary(&Ics721Ack::Result(Binary::from([1u8])))?)),
+            Err(err) => Ok(IbcReceiveResponse::new()
+                .set_ack(to_json_binary(&Ics721Ack::Error(err.to_string()))?)
+                .add_attribute("action", "ibc_receive")
+                .add_attribute("error", err.to_string())),
+        }
+    }
+
+    pub fn ibc_packet_ack(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        msg: IbcPacketAckMsg,
+    ) -> Result<IbcBasicResponse<C>, ContractError> {
+        let packet = msg.original_packet;
+        let data: NonFungibleTokenPacketData = from_json(&packet.data)?;
+        let token_ids = self.local_token_ids(deps.storage, &env, &data)?;
+
+        match from_json(&msg.acknowledgement.data)? {
+            Ics721Ack::Result(_) => {
+                // this collection's own tokens stay escrowed until they come
+                // back, vouchers go away with the token they stood for
+                for token_id in &token_ids {
+                    if self.ibc_vouchers.has(deps.storage, token_id) {
+                        let escrow = self.ibc_escrows.load(deps.storage, token_id)?;
+                        self.ibc_escrows.remove(deps.storage, token_id);
+                        self._remove_token(
+                            deps.storage,
+                            &env,
+                            token_id,
+                            &env.contract.address,
+                            RemovalKind::Bridged {
+                                channel_id: packet.src.channel_id.clone(),
+                            },
+                            &escrow.owner,
+                        )?;
+                    }
+                }
+                Ok(IbcBasicResponse::new()
+                    .add_attribute("action", "ibc_transfer_complete")
+                    .add_attribute("token_ids", token_ids.join(",")))
+            }
+            Ics721Ack::Error(err) => {
+                self._refund_transfer(deps.storage, &env, &token_ids)?;
+                Ok(IbcBasicResponse::new()
+                    .add_attribute("action", "ibc_transfer_refund")
+                    .add_attribute("token_ids", token_ids.join(","))
+                    .add_attribute("error", err))
+            }
+        }
+    }
+
+    pub fn ibc_packet_timeout(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        msg: IbcPacketTimeoutMsg,
+    ) -> Result<IbcBasicResponse<C>, ContractError> {
+        let data: NonFungibleTokenPacketData = from_json(&msg.packet.data)?;
+        let token_ids = self.local_token_ids(deps.storage, &env, &data)?;
+        self._refund_transfer(deps.storage, &env, &token_ids)?;
+
+        Ok(IbcBasicResponse::new()
+            .add_attribute("action", "ibc_transfer_refund")
+            .add_attribute("token_ids", token_ids.join(","))
+            .add_attribute("error", "timeout"))
+    }
+
+    /// releases returning tokens or mints vouchers for the packet's tokens.
+    /// Everything that can fail is checked before state changes, as the
+    /// caller acknowledges errors instead of reverting
+    fn _receive_packet(
+        &self,
+        deps: DepsMut,
+        env: &Env,
+        packet: &IbcPacket,
+    ) -> Result<IbcReceiveResponse<C>, ContractError> {
+        if self.export_mode.may_load(deps.storage)?.is_some() {
+            return Err(ContractError::Frozen {});
+        }
+        if self.paused.may_load(deps.storage)?.is_some() {
+            return Err(ContractError::ContractPaused {});
+        }
+        let data: NonFungibleTokenPacketData = from_json(&packet.data)?;
+        check_batch_size(data.token_ids.len())?;
+        let receiver = deps.api.addr_validate(&data.receiver)?;
+
+        // the sending chain prefixed its own port and channel to the class
+        // when the tokens first left here
+        let prefix = format!("{}/{}/", packet.src.port_id, packet.src.channel_id);
+        if let Some(class_id) = data.class_id.strip_prefix(&prefix) {
+            if class_id != env.contract.address.as_str() {
+                return Err(ContractError::UnknownClass {
+                    class_id: class_id.to_string(),
+                });
+            }
+            for token_id in &data.token_ids {
+                match self.ibc_escrows.may_load(deps.storage, token_id)? {
+                    Some(escrow) if escrow.channel_id == packet.dest.channel_id => {}
+                    _ => {
+                        return Err(ContractError::NotEscrowed {
+                            token_id: token_id.clone(),
+                        })
+                    }
+                }
+            }
+            for token_id in &data.token_ids {
+                self.ibc_escrows.remove(deps.storage, token_id);
+                self._release_escrow(deps.storage, env, token_id, &receiver)?;
+            }
+        } else {
+            let class_id = format!(
+                "{}/{}/{}",
+                packet.dest.port_id, packet.dest.channel_id, data.class_id
+            );
+            let mut mints = vec![];
+            for (i, token_id) in data.token_ids.iter().enumerate() {
+                if data.token_ids[..i].contains(token_id)
+                    || self
+                        .ibc_voucher_ids
+                        .has(deps.storage, (&class_id, token_id))
+                {
+                    return Err(ContractError::IbcTokenExists {
+                        token_id: token_id.clone(),
+                    });
+                }
+                let token_uri = data
+                    .token_uris
+                    .as_ref()
+                    .and_then(|uris| uris.get(i))
+                    .filter(|uri| !uri.is_empty())
+                    .cloned();
+                if let Some(token_uri) = &token_uri {
+                    self.check_uri(deps.storage, token_uri)?;
+                }
+                let extension: T = match data.token_data.as_ref().and_then(|data| data.get(i)) {
+                    Some(token_data) => from_json(token_data)?,
+                    None => from_json(b"null")?,
+                };
+                self.check_extension(deps.storage, &extension)?;
+                mints.push((token_id, token_uri, extension));
+            }
+            self.count_exempt_mints(deps.storage, mints.len() as u64)?;
+            for (remote_id, token_uri, extension) in mints {
+                let token_id = self._create_token(
+                    deps.storage,
+                    env,
+                    receiver.clone(),
+                    &receiver,
+                    token_uri,
+                    extension,
+                )?;
+                let voucher = IbcVoucher {
+                    class_id: class_id.clone(),
+                    token_id: remote_id.clone(),
+                };
+                self.ibc_vouchers.save(deps.storage, &token_id, &voucher)?;
+                self.ibc_voucher_ids
+                    .save(deps.storage, (&class_id, remote_id), &token_id)?;
+            }
+            let count = self
+                .ibc_voucher_count
+                .may_load(deps.storage)?
+                .unwrap_or_default();
+            self.ibc_voucher_count
+                .save(deps.storage, &(count + data.token_ids.len() as u64))?;
+        }
+
+        Ok(IbcReceiveResponse::new()
+            .add_attribute("action", "ibc_receive")
+            .add_attribute("class_id", data.class_id)
+            .add_attribute("receiver", receiver)
+            .add_attribute("token_ids", data.token_ids.join(",")))
+    }
+
+    /// hands escrowed tokens back to whoever sent them
+    fn _refund_transfer(
+        &self,
+        storage: &mut dyn Storage,
+        env: &Env,
+        token_ids: &[String],
+    ) -> Result<(), ContractError> {
+        for token_id in token_ids {
+            let escrow = self.ibc_escrows.load(storage, token_id)?;
+            self.ibc_escrows.remove(storage, token_id);
+            self._release_escrow(storage, env, token_id, &escrow.owner)?;
+        }
+        Ok(())
+    }
+
+    fn _release_escrow(
+        &self,
+        storage: &mut dyn Storage,
+        env: &Env,
+        token_id: &str,
+        owner: &Addr,
+    ) -> Result<(), ContractError> {
+        let mut token = self.tokens.load(storage, token_key(token_id)?)?;
+        let previous_owner = token.owner;
+        token.owner = owner.clone();
+        self.tokens.save(storage, token_key(token_id)?, &token)?;
+        self._track_transfer(storage, env, token_id, &previous_owner, owner)?;
+        Ok(())
+    }
+
+    /// ids in this collection of the tokens of a packet it sent
+    fn local_token_ids(
+        &self,
+        storage: &dyn Storage,
+        env: &Env,
+        data: &NonFungibleTokenPacketData,
+    ) -> StdResult<Vec<String>> {
+        if data.class_id == env.contract.address.as_str() {
+            return Ok(data.token_ids.clone());
+        }
+        data.token_ids
+            .iter()
+            .map(|token_id| {
+                self.ibc_voucher_ids
+                    .load(storage, (&data.class_id, token_id))
+            })
+            .collect()
+    }
+
+    /// ICS-721 class of token_id: this collection's address for its own
+    /// tokens, the class trace for vouchers
+    pub fn ibc_class_id(
+        &self,
+        storage: &dyn Storage,
+        env: &Env,
+        token_id: &str,
+    ) -> StdResult<String> {
+        Ok(match self.ibc_vouchers.may_load(storage, token_id)? {
+            Some(voucher) => voucher.class_id,
+            None => env.contract.address.to_string(),
+        })
+    }
+}
@@ -1,18 +1,133 @@
 mod error;
 mod execute;
 pub mod helpers;
+mod ibc;
 pub mod msg;
 mod query;
+mod reply;
 pub mod state;
 
+#[cfg(test)]
+mod contract_tests;
+
 pub use crate::error::ContractError;
-pub use crate::msg::{ExecuteMsg, InstantiateMsg, MintMsg, MinterResponse, QueryMsg};
+pub use crate::msg::{
+    ArtworkExtension, ExecuteMsg, InstantiateMsg, MigrateMsg, MintMsg, MinterResponse, QueryMsg,
+};
 pub use crate::state::Cw721Contract;
 use cosmwasm_std::Empty;
 
-pub type Extension = Option<Empty>;
+pub type Extension = Option<ArtworkExtension>;
 
 #[cfg(not(feature = "library"))]
 pub mod entry {
-   // add code as instructed in the lesson
+    use super::*;
+
+    use cosmwasm_std::entry_point;
+    use cosmwasm_std::{
+        Binary, Deps, DepsMut, Env, IbcBasicResponse, IbcChannelCloseMsg, IbcChannelConnectMsg,
+        IbcChannelOpenMsg, IbcChannelOpenResponse, IbcPacketAckMsg, IbcPacketReceiveMsg,
+        IbcPacketTimeoutMsg, IbcReceiveResponse, MessageInfo, Reply, Response, StdResult,
+    };
+
+    // This makes a conscious choice on the various generics used by the contract
+    #[entry_point]
+    pub fn instantiate(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        msg: InstantiateMsg,
+    ) -> Result<Response, ContractError> {
+        let tract = Cw721Contract::<Extension, Empty>::default();
+        tract.instantiate(deps, env, info, msg)
+    }
+
+    #[entry_point]
+    pub fn execute(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        msg: ExecuteMsg<Extension>,
+    ) -> Result<Response, ContractError> {
+        let tract = Cw721Contract::<Extension, Empty>::default();
+        tract.execute(deps, env, info, msg)
+    }
+
+    #[entry_point]
+    pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+        let tract = Cw721Contract::<Extension, Empty>::default();
+        tract.query(deps, env, msg)
+    }
+
+    #[entry_point]
+    pub fn migrate(deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+        let tract = Cw721Contract::<Extension, Empty>::default();
+        tract.migrate(deps, env, msg)
+    }
+
+    #[entry_point]
+    pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
+        let tract = Cw721Contract::<Extension, Empty>::default();
+        tract.reply(deps, env, msg)
+    }
+
+    #[entry_point]
+    pub fn ibc_channel_open(
+        deps: DepsMut,
+        env: Env,
+        msg: IbcChannelOpenMsg,
+    ) -> Result<IbcChannelOpenResponse, ContractError> {
+        let tract = Cw721Contract::<Extension, Empty>::default();
+        tract.ibc_channel_open(deps, env, msg)
+    }
+
+    #[entry_point]
+    pub fn ibc_channel_connect(
+        deps: DepsMut,
+        env: Env,
+        msg: IbcChannelConnectMsg,
+    ) -> Result<IbcBasicResponse, ContractError> {
+        let tract = Cw721Contract::<Extension, Empty>::default();
+        tract.ibc_channel_connect(deps, env, msg)
+    }
+
+    #[entry_point]
+    pub fn ibc_channel_close(
+        deps: DepsMut,
+        env: Env,
+        msg: IbcChannelCloseMsg,
+    ) -> Result<IbcBasicResponse, ContractError> {
+        let tract = Cw721Contract::<Extension, Empty>::default();
+        tract.ibc_channel_close(deps, env, msg)
+    }
+
+    #[entry_point]
+    pub fn ibc_packet_receive(
+        deps: DepsMut,
+        env: Env,
+        msg: IbcPacketReceiveMsg,
+    ) -> Result<IbcReceiveResponse, ContractError> {
+        let tract = Cw721Contract::<Extension, Empty>::default();
+        tract.ibc_packet_receive(deps, env, msg)
+    }
+
+    #[entry_point]
+    pub fn ibc_packet_ack(
+        deps: DepsMut,
+        env: Env,
+        msg: IbcPacketAckMsg,
+    ) -> Result<IbcBasicResponse, ContractError> {
+        let tract = Cw721Contract::<Extension, Empty>::default();
+        tract.ibc_packet_ack(deps, env, msg)
+    }
+
+    #[entry_point]
+    pub fn ibc_packet_timeout(
+        deps: DepsMut,
+        env: Env,
+        msg: IbcPacketTimeoutMsg,
+    ) -> Result<IbcBasicResponse, ContractError> {
+        let tract = Cw721Contract::<Extension, Empty>::default();
+        tract.ibc_packet_timeout(deps, env, msg)
+    }
 }
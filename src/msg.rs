@@ -1,23 +1,136 @@
+use cosmwasm_std::Binary;
+use cosmwasm_std::{Addr, Coin, Decimal, HexBinary, IbcChannel, Timestamp, Uint128};
+use cw721::{Cw721ReceiveMsg, Expiration};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use cosmwasm_std::Coin;
-use cosmwasm_std::Binary;
-use cw721::Expiration;
+
+use crate::state::{
+    Approval, ArtistKey, ArtistStats, Attestation, Attestor, Auction, AutoBuyRules, AutoBuyer,
+    BidRecord, BlindDrop, Certificate, CollectionBid, ConditionalTransfer, DropLimit, EventWork,
+    Exhibition, ExhibitionEvent, ExpiryKind, ExportMode, FirstRefusal, FrozenToken, Gift,
+    IbcEscrow, IbcVoucher, KeeperTreasury, Listing, MarketplaceFee, Offer, OperatorRevocation,
+    PermissionSweep, PinRecord, PinStatus, Presale, PresaleOutcome, PrivateSale, ProvenanceDigest,
+    ProvenanceEntry, Refund, RefundPool, RoyaltySplit, RoyaltySuccession, RoyaltyWaiver, Sale,
+    SaleFeeOverride, SaleReceipt, SealedBid, Series, StorageAccount, StorageFee, TokenLink,
+    Tombstone, TradeIn, VaultDeposit, Venue, Voucher, VoucherIssuance, VoucherRedemption,
+    WithdrawalCap, Withholding, WithholdingRule,
+};
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct InstantiateMsg {
     pub name: String,
     pub symbol: String,
     pub minter: String,
+    pub max_mints: u64,
+    pub mint_price: Coin,
+    pub token_uri: Option<String>,
+    /// Defaults to `SizeLimits::default()`
+    pub size_limits: Option<SizeLimits>,
+    /// Default resale royalty of the collection's tokens
+    #[serde(default)]
+    pub royalty: Option<RoyaltyConfig>,
+    /// Serve every token from a placeholder until the admin's `Reveal`
+    #[serde(default)]
+    pub blind_drop: Option<BlindDropConfig>,
+    /// Defaults to the extended fine-art profile
+    #[serde(default)]
+    pub profile: DeploymentProfile,
+}
+
+/// Feature set of a deployment, fixed at instantiation
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DeploymentProfile {
+    /// Plain cw721: no marketplace, auctions, provenance or royalties, and no
+    /// sale receipts or provenance stored on transfers
+    Minimal,
+    /// The full fine-art platform
+    #[default]
+    Extended,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BlindDropConfig {
+    pub placeholder_uri: String,
+    /// sha256 of the `base_uri` the drop will be revealed with. With a
+    /// content-addressed base, e.g. `ipfs://<cid>`, this commits to the whole
+    /// metadata set
+    pub metadata_commitment: HexBinary,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MigrateMsg {
+    /// Denom of a mint price stored by an earlier version as a bare amount.
+    /// Required only when migrating such a collection
+    #[serde(default)]
+    pub legacy_price_denom: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct DropLimitConfig {
+    pub max_per_address: u64,
+    /// Seconds after a token's mint during which buying it counts as well
+    pub resale_window: u64,
+}
 
-    // add code as instructed in the lesson
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MarketplaceFeeConfig {
+    pub recipient: String,
+    /// Share of the sale price, in basis points
+    pub share_bps: u16,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RoyaltyConfig {
+    pub payment_address: String,
+    /// Share of the sale price, in basis points
+    pub share_bps: u16,
+}
+
+/// Upper bounds on user supplied metadata, against state-bloat griefing
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SizeLimits {
+    /// Bytes of any stored URI
+    pub max_uri_length: u32,
+    /// Bytes of a token extension, serialized as JSON
+    pub max_extension_bytes: u32,
+    /// Bytes of free-text provenance fields (evidence, attestation hashes and labels)
+    pub max_provenance_entry_length: u32,
+}
 
+impl Default for SizeLimits {
+    fn default() -> Self {
+        SizeLimits {
+            max_uri_length: 512,
+            max_extension_bytes: 4096,
+            max_provenance_entry_length: 256,
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg<T> {
-    TransferNft { recipient: String, token_id: String },
+    TransferNft {
+        recipient: String,
+        token_id: String,
+    },
+    /// Transfer up to 100 tokens, each given as `(recipient, token_id)`. Every
+    /// token is checked as in TransferNft and the batch fails as a whole
+    BatchTransferNft {
+        recipients: Vec<(String, String)>,
+    },
+    /// Send up to 100 tokens to `receiver` on another chain over an ICS-721
+    /// channel. This collection's own tokens stay in escrow until they come
+    /// back; tokens received from another chain can only go back over the
+    /// channel they came through
+    IbcTransfer {
+        channel_id: String,
+        receiver: String,
+        token_ids: Vec<String>,
+        /// Defaults to ten minutes
+        timeout_seconds: Option<u64>,
+    },
     SendNft {
         contract: String,
         token_id: String,
@@ -28,70 +141,3227 @@ pub enum ExecuteMsg<T> {
         token_id: String,
         expires: Option<Expiration>,
     },
-    Revoke { spender: String, token_id: String },
+    Revoke {
+        spender: String,
+        token_id: String,
+    },
     ApproveAll {
         operator: String,
         expires: Option<Expiration>,
     },
-    RevokeAll { operator: String },
-    Burn{ token_id: String},
+    /// Allow operator to send any of the sender's tokens with ids in
+    /// `[first, last]`, e.g. a series held by one custodian
+    ApproveRange {
+        operator: String,
+        first: u64,
+        last: u64,
+        expires: Option<Expiration>,
+    },
+    /// Remove the range approval starting at `first`
+    RevokeRange {
+        operator: String,
+        first: u64,
+    },
+    /// `reason` is optional; when given it is emitted and kept in the
+    /// operator's revocation log
+    RevokeAll {
+        operator: String,
+        reason: Option<RevocationReason>,
+    },
+    Burn {
+        token_id: String,
+    },
+    /// Burn up to 100 tokens, each checked as in Burn
+    BatchBurn {
+        token_ids: Vec<String>,
+    },
+    Mint(MintMsg<T>),
+    /// Minter role: mint a series in one transaction, paying the mint price for
+    /// each token
+    BatchMint {
+        mints: Vec<MintMsg<T>>,
+    },
+    SetMintConfig {
+        max_mints: u64,
+        /// A zero amount makes minting free, funds sent along are refunded
+        mint_price: Coin,
+    },
+    /// Admin: cap how many tokens a single wallet can mint, or lift the cap
+    /// with `None`
+    SetMaxMintsPerAddress {
+        limit: Option<u64>,
+    },
+    /// Admin: start a drop capping what each address acquires, or end it
+    /// with `None`. Mints and purchases of the drop's tokens within
+    /// `resale_window` of their mint count toward the cap together, so
+    /// flipping to fresh wallets does not get around it
+    SetDropLimit {
+        limit: Option<DropLimitConfig>,
+    },
+    ToggleMinting {},
+    /// Admin: freeze the contract during an incident. Tokens and funds stay
+    /// put and only the admin can still change configuration
+    Pause {},
+    /// Admin: lift a pause
+    Unpause {},
+    /// Admin: reject mints whose own token_uri or content hash is already
+    /// used by a token of the collection
+    SetUniqueContent {
+        enabled: bool,
+    },
+    /// Admin: change the metadata size limits
+    SetSizeLimits {
+        limits: SizeLimits,
+    },
 
-    // add code as instructed in the lesson
-}
+    /// Register or update the sender's public display name and profile URI
+    RegisterProfile {
+        display_name: String,
+        profile_uri: Option<String>,
+    },
+    /// Remove the sender's profile
+    ClearProfile {},
+    /// Admin moderation: suspend (hide) or reinstate an address' profile
+    ModerateProfile {
+        address: String,
+        suspended: bool,
+    },
 
-// add code as instructed in the lesson
+    /// Admin: enable or disable the `SendNft` contract allowlist and edit its entries
+    UpdateSendAllowlist {
+        enabled: Option<bool>,
+        add: Vec<String>,
+        remove: Vec<String>,
+    },
 
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
-#[serde(rename_all = "snake_case")]
-pub enum QueryMsg {
-    OwnerOf {
+    /// Admin: configure (or disable with `None`) installment purchases of the primary mint
+    SetInstallmentPlan {
+        plan: Option<InstallmentPlan>,
+    },
+    /// Reserve a token by paying the down payment; the token is held by the contract
+    /// until all installments are paid
+    ReserveMint(MintMsg<T>),
+    /// Pay (at least) the next installment of a reservation
+    PayInstallment {
+        token_id: String,
+    },
+    /// Forfeit a reservation whose installment is overdue past its grace
+    /// period. The token goes to the minter and payments made so far are
+    /// split per the plan's forfeiture policy. Anyone can call it; callers
+    /// other than the minter earn the keeper bounty
+    ForfeitReservation {
         token_id: String,
-        include_expired: Option<bool>,
     },
 
-    Approval {
+    /// Admin: set the withholding rate and escrow address for a jurisdiction
+    SetWithholdingRule {
+        jurisdiction: String,
+        rate_bps: u16,
+        escrow: String,
+    },
+    /// Admin: remove the withholding rule of a jurisdiction
+    RemoveWithholdingRule {
+        jurisdiction: String,
+    },
+    /// Admin: assign (or clear) the tax jurisdiction of a buyer
+    SetAccountJurisdiction {
+        address: String,
+        jurisdiction: Option<String>,
+    },
+
+    /// Custodian role: record that the physical artwork was destroyed and
+    /// remove its token. Unlike `Burn`, this does not require ownership of
+    /// the token. Escrowed tokens cannot be destroyed, except a reserved one,
+    /// whose buyer is refunded what they paid
+    Destroy {
         token_id: String,
-        spender: String,
-        include_expired: Option<bool>,
+        /// Hash of the evidence of destruction (report, photos)
+        evidence_hash: String,
+        /// Custodian attestation of the destruction (statement or URI)
+        attestation: String,
     },
 
-    Approvals {
+    /// Admin: approve an expert (authenticator, lab) to attest to tokens
+    AddAttestor {
+        address: String,
+        name: String,
+    },
+    /// Admin: withdraw an attestor's approval, existing attestations are kept
+    RemoveAttestor {
+        address: String,
+    },
+    /// Approved attestor: attach an attestation to a token
+    Attest {
         token_id: String,
-        include_expired: Option<bool>,
+        kind: AttestationKind,
+        /// Location of the full report
+        uri: String,
+        /// Hash of the report contents
+        content_hash: Option<String>,
+    },
+    /// Revoke an attestation. Allowed for the attestor who issued it and the admin
+    RevokeAttestation {
+        token_id: String,
+        index: u64,
     },
 
-    AllOperators {
+    /// Put a token up for a sealed-bid (commit-reveal) auction, escrowing it in the contract
+    CreateSealedAuction {
+        token_id: String,
+        reserve_price: Coin,
+        /// Seconds during which bids can be committed
+        commit_duration: u64,
+        /// Seconds after the commit phase during which bids can be revealed
+        reveal_duration: u64,
+        /// Deposit sent with every commitment, forfeited if the bid is not revealed
+        deposit: Uint128,
+    },
+    /// Commit a hidden bid, sending exactly the auction deposit.
+    /// The commitment is `sha256("{auction_id}/{bidder}/{amount}/{salt}")`
+    CommitBid {
+        auction_id: u64,
+        commitment: HexBinary,
+    },
+    /// Reveal a committed bid, sending whatever the amount exceeds the deposit by
+    RevealBid {
+        auction_id: u64,
+        amount: Uint128,
+        salt: String,
+    },
+    /// Seller or listing-approved spender: cancel an auction without bids
+    CancelSealedAuction {
+        auction_id: u64,
+    },
+    /// Settle an auction after its reveal phase. Callable by anyone
+    SettleSealedAuction {
+        auction_id: u64,
+    },
+    /// Settle an ended auction of any format, or return the token to the
+    /// seller if nobody won it. Callable by anyone, so a missing seller
+    /// cannot lock the artwork or the bids
+    Finalize {
+        auction_id: u64,
+    },
+    /// Admin: pay the proceeds of an unsettled auction to `beneficiary`
+    /// instead of the seller (e.g. a charity auction), or restore the seller
+    /// with `None`. Recorded in the sale receipt
+    SetAuctionBeneficiary {
+        auction_id: u64,
+        beneficiary: Option<String>,
+    },
+    /// Admin: waive royalties and/or the marketplace fee on an unsettled
+    /// auction, or restore them with `None`. Recorded in the sale receipt
+    SetAuctionFeeOverride {
+        auction_id: u64,
+        fee_override: Option<SaleFeeOverride>,
+    },
+    /// Put a token up for an English (open ascending-bid) auction, escrowing
+    /// it in the contract. A bid in the last 10 minutes extends the auction
+    /// to 10 minutes after that bid
+    CreateAuction {
+        token_id: String,
+        reserve_price: Coin,
+        /// Seconds during which bids can be placed
+        duration: u64,
+    },
+    /// Bid the funds sent, which must reach the reserve price and beat the
+    /// highest bid. The outbid bidder is refunded
+    PlaceBid {
+        auction_id: u64,
+    },
+    /// Bid by proxy up to the funds sent: the contract bids the least needed
+    /// to lead and answers later bids up to that maximum, winning ties. The
+    /// maximum is escrowed and only shown once the auction is settled, the
+    /// part not needed is refunded then
+    PlaceMaxBid {
+        auction_id: u64,
+    },
+    /// Settle an English auction after it ended, paying royalties and the
+    /// marketplace fee out of the winning bid. Callable by anyone
+    SettleAuction {
+        auction_id: u64,
+    },
+
+    /// Operator: give up an ApproveAll grant received from owner
+    RenounceOperator {
         owner: String,
-        include_expired: Option<bool>,
-        start_after: Option<String>,
+    },
+
+    /// Admin: move up to `limit` tokens stored under string keys by earlier
+    /// versions to numeric keys. Repeat until no tokens remain to migrate
+    MigrateTokenKeys {
         limit: Option<u32>,
     },
 
-    NumTokens {},
+    /// Owner: hide the fields from public queries, replacing any earlier
+    /// choice. An empty list lifts all redactions
+    SetRedactions {
+        token_id: String,
+        fields: Vec<RedactedField>,
+    },
 
-    ContractInfo {},
+    /// Owner: propose linking token_id to linked_token_id of `contract`, a
+    /// collection accepting `ReceiveLink`. The link holds once the linked
+    /// token's owner accepts it there
+    ProposeLink {
+        token_id: String,
+        contract: String,
+        linked_token_id: String,
+        kind: LinkKind,
+    },
+    /// Owner: accept the link linked_token_id of `contract` proposed to
+    /// token_id, confirming it in both collections
+    AcceptLink {
+        token_id: String,
+        contract: String,
+        linked_token_id: String,
+        kind: LinkKind,
+    },
+    /// Owner: remove a link or proposal of token_id, from both collections
+    /// once confirmed
+    RemoveLink {
+        token_id: String,
+        contract: String,
+        linked_token_id: String,
+    },
+    /// Sent by a linked collection to confirm or remove a link on this side
+    ReceiveLink(LinkCallback),
 
-    NftInfo {
+    /// Custodian role: record (or renew) a token's pin with a pinning
+    /// provider, marking it pinned
+    RecordPin {
+        token_id: String,
+        provider: String,
+        /// Hash of the provider's pin receipt
+        receipt_hash: String,
+        expires_at: Timestamp,
+    },
+    /// Custodian role: update the status of a recorded pin
+    SetPinStatus {
         token_id: String,
+        provider: String,
+        status: PinStatus,
     },
 
-    AllNftInfo {
+    /// Curator role: replace the scheduled metadata variants of a token. An empty
+    /// list reverts the token to its base token_uri
+    SetMetadataVariants {
+        token_id: String,
+        variants: Vec<MetadataVariant>,
+    },
+    /// Curator role: replace the token_uri and extension of a token, unless
+    /// its metadata is frozen
+    UpdateTokenMetadata {
+        token_id: String,
+        token_uri: Option<String>,
+        extension: T,
+    },
+    /// Curator role: make a token's metadata and variants permanently
+    /// immutable, once curation is finalized
+    FreezeTokenMetadata {
         token_id: String,
-        include_expired: Option<bool>,
     },
 
-    Tokens {
+    /// Admin: configure or remove the buy-back program
+    SetBuyBack {
+        program: Option<BuyBackProgram>,
+    },
+    /// Admin: add the sent funds to the refund pool
+    FundRefundPool {},
+    /// Treasurer: take funds out of the refund pool while no buy-back is running
+    WithdrawRefundPool {
+        amount: Uint128,
+    },
+    /// Burn a token you own for the buy-back price, paid from the refund pool
+    BurnForRefund {
+        token_id: String,
+    },
+
+    /// Deposit an external token into the vault. Only accepted from vault
+    /// collections; `msg` is a `VaultDepositMsg` and the depositor receives a
+    /// receipt token of this collection. Sales of the receipt pay the origin
+    /// collection's cw2981 royalty when it has one
+    ReceiveNft(Cw721ReceiveMsg),
+    /// Admin: edit the collections accepted by the vault
+    UpdateVaultCollections {
+        add: Vec<String>,
+        remove: Vec<String>,
+    },
+    /// Burn a vault receipt and return the deposited token to the receipt owner
+    RedeemVaultReceipt {
+        token_id: String,
+    },
+
+    /// Admin: edit the galleries that redeem visit vouchers. A removed
+    /// gallery loses the operator grants and approvals it holds, revoked in
+    /// batches by SweepPermissions
+    UpdatePartnerGalleries {
+        add: Vec<String>,
+        remove: Vec<String>,
+    },
+    /// Admin: cap the vouchers ever issued for an artwork
+    SetVoucherLimit {
+        parent_token_id: String,
+        limit: u32,
+    },
+    /// Minter role: mint a visit voucher for an artwork to owner
+    IssueVoucher {
+        parent_token_id: String,
         owner: String,
-        start_after: Option<String>,
+        token_uri: Option<String>,
+        extension: T,
+    },
+    /// Partner gallery able to send the voucher (e.g. approved by its holder
+    /// at the door): burn it and record the visit
+    RedeemVoucher {
+        token_id: String,
+    },
+
+    /// Curator role: record (or clear with `None`) the artist of a token
+    SetArtistOfRecord {
+        token_id: String,
+        artist: Option<String>,
+    },
+
+    /// Artist: register a first authenticity signing key
+    RegisterArtistKey {
+        pubkey: Binary,
+    },
+    /// Artist: replace the signing key. `signature` is the current key's
+    /// signature over the sha256 of the JSON `KeyRotationSignDoc`, so
+    /// certificates signed by older keys can still be traced to the new one
+    RotateArtistKey {
+        pubkey: Binary,
+        signature: Binary,
+    },
+    /// Artist of record: certify a token. `signature` is the artist's current
+    /// key's signature over the sha256 of the JSON `CertificateSignDoc`
+    IssueCertificate {
+        token_id: String,
+        signature: Binary,
+    },
+    /// Mint a token an artist offered off-chain, to the sender at the
+    /// voucher's price. `signature` is the artist's current key's signature
+    /// over the sha256 of the JSON `LazyMintSignDoc`. The artist becomes the
+    /// token's artist of record and is paid the voucher's share of the price
+    LazyMint {
+        voucher: LazyMintVoucher<T>,
+        signature: Binary,
+    },
+
+    /// Artist: set (or clear with `None`) a minimum resale price for the
+    /// sender's works. Auctions and collection bid sales of
+    /// already-sold works below it are rejected
+    SetPriceFloor {
+        floor: Option<PriceFloor>,
+    },
+
+    /// Hold a token for recipient to claim, optionally until a deadline
+    SendGift {
+        token_id: String,
+        recipient: String,
+        message: Option<GiftMessage>,
+        deadline: Option<Timestamp>,
+    },
+    /// Recipient: accept a gift
+    ClaimGift {
+        token_id: String,
+    },
+    /// Return an unclaimed gift to its sender. The sender can do this at any
+    /// time, anyone else once the deadline has passed
+    ReturnGift {
+        token_id: String,
+    },
+
+    /// Hold a token in the contract until `condition` is met, then let anyone
+    /// deliver it to recipient before `expires`
+    CreateConditionalTransfer {
+        token_id: String,
+        recipient: String,
+        condition: TransferCondition,
+        expires: Timestamp,
+    },
+    /// Deliver a conditional transfer whose condition is met. Callable by anyone
+    ExecuteConditionalTransfer {
+        token_id: String,
+    },
+    /// Return a conditional transfer to its sender. The sender can do this at
+    /// any time, anyone else once it has expired
+    CancelConditionalTransfer {
+        token_id: String,
+    },
+
+    /// Transfer a token sold off-chain to buyer, recording the sale in its
+    /// provenance with only a commitment to the price,
+    /// `sha256(salt ++ "{amount}{denom}")`
+    RecordPrivateSale {
+        token_id: String,
+        buyer: String,
+        price_commitment: HexBinary,
+    },
+    /// Seller or buyer: disclose the price of a private sale
+    RevealPrivateSale {
+        token_id: String,
+        sale_id: u64,
+        price: Coin,
+        salt: HexBinary,
+    },
+
+    /// Admin: mint new tokens sealed behind `sealed_uri`, or stop with `None`.
+    /// Tokens already sealed stay sealed until revealed
+    SetMysteryMode {
+        sealed_uri: Option<String>,
+    },
+    /// Admin: add token URIs to the pool sealed tokens are revealed from
+    AddMysteryUris {
+        uris: Vec<String>,
+    },
+    /// Owner: open a sealed token, assigning it a random URI from the pool
+    RevealToken {
+        token_id: String,
+    },
+    /// Admin: reveal a blind drop, serving every token from
+    /// `{base_uri}/{token_id}.json`. base_uri must hash to the commitment
+    /// made at instantiation
+    Reveal {
+        base_uri: String,
+    },
+
+    /// Admin: set (or remove with `None`) the bounty paid to whoever runs a
+    /// maintenance call: CleanExpired, SweepPermissions, SettleSealedAuction, SettleAuction or Finalize, DrawPresale without a seed,
+    /// ForfeitReservation by someone other than the minter, ReturnGift on an
+    /// expired gift sent by someone else, or Execute/CancelConditionalTransfer
+    /// by someone other than its parties
+    SetKeeperBounty {
+        bounty: Option<Coin>,
+    },
+    /// Add the sent funds to the keeper treasury
+    FundKeeperTreasury {},
+    /// Treasurer: take funds out of the keeper treasury
+    WithdrawKeeperTreasury {
+        amount: Uint128,
+    },
+    /// Drop up to `limit` expired approvals and operator grants
+    CleanExpired {
         limit: Option<u32>,
     },
-    
-    AllTokens {
-        start_after: Option<String>,
+    /// Continue revoking the permissions of a removed gallery, visiting up
+    /// to `limit` grants or tokens
+    SweepPermissions {
+        address: String,
         limit: Option<u32>,
     },
 
-    // add code as instructed in the lesson
-}
+    /// Minter: offer the minter role to new_minter, who takes it over with
+    /// AcceptMinter. Replaces any earlier offer; offering it to the current
+    /// minter withdraws the offer
+    TransferMinter {
+        new_minter: String,
+    },
+    /// Pending minter: take over the minter role
+    AcceptMinter {},
+    /// Minter: delegate role to address
+    GrantRole {
+        address: String,
+        role: Role,
+    },
+    /// Minter: take role back from address
+    RevokeRole {
+        address: String,
+        role: Role,
+    },
+
+    /// Admin: set (or remove with `None`) the name-service contract used to
+    /// resolve names like `alice.mantra` given wherever an address is expected
+    SetNameResolver {
+        resolver: Option<String>,
+    },
+
+    /// Admin: set (or remove with `None`) the contract `NftInfo` and
+    /// `AllNftInfo` ask for each token's extension, so metadata logic can
+    /// change without migrating this contract
+    SetMetadataResolver {
+        resolver: Option<String>,
+    },
+
+    /// Admin: set up a pre-sale at the current mint price, or remove it with
+    /// `None`. Not allowed while commitments are left to draw
+    SetPresale {
+        config: Option<PresaleConfig>,
+    },
+    /// Escrow the mint price for a chance at one edition of the pre-sale. One
+    /// commitment per address
+    CommitPresale(MintMsg<T>),
+    /// Admin, after the window closes: allocate every commitment by a lottery
+    /// drawn with the seed committed to in the config, minting the winners'
+    /// editions and refunding the others. If the admin has not drawn a week
+    /// after the window closed, anyone can call it without a seed to refund
+    /// every commitment
+    DrawPresale {
+        seed: Option<String>,
+    },
+
+    /// Admin: open Mint to allowlisted addresses only, at the phase's price,
+    /// until its end. Removed with `None`
+    SetAllowlistConfig {
+        config: Option<AllowlistConfig>,
+    },
+    /// Admin: sell public mints at a price declining over time instead of
+    /// the mint price, or go back to the mint price with `None`
+    SetDutchAuction {
+        config: Option<DutchAuctionConfig>,
+    },
+    /// Admin: peg the public mint price to a reference unit through an
+    /// oracle, or go back to the mint price with `None`. Cannot be combined
+    /// with a Dutch auction
+    SetMintPricePeg {
+        peg: Option<MintPricePeg>,
+    },
+    /// Admin
+    AddToAllowlist {
+        addresses: Vec<String>,
+    },
+    /// Admin
+    RemoveFromAllowlist {
+        addresses: Vec<String>,
+    },
+    /// Admin: allowlist the addresses of a Merkle tree, or stop with `None`.
+    /// Leaves are sha256 of the address, pairs are hashed in sorted order.
+    /// Addresses are added to the allowlist on their first MintWithProof
+    SetAllowlistMerkleRoot {
+        root: Option<HexBinary>,
+    },
+    /// Mint during the allowlist phase as a member of the Merkle allowlist
+    MintWithProof {
+        mint: MintMsg<T>,
+        proof: Vec<HexBinary>,
+    },
+
+    /// Minter role: add a distinct work sold in up to max_editions editions
+    /// at `price`, edition n getting the URI `{base_uri}/{n}.json`
+    CreateSeries {
+        name: String,
+        max_editions: u64,
+        price: Coin,
+        base_uri: String,
+    },
+    /// Mint the next edition of a series at its price. The series sets the
+    /// token_uri, so `mint` must not
+    MintSeries {
+        series_id: u64,
+        mint: MintMsg<T>,
+    },
+
+    /// Admin: appoint (or remove with `None`) the curator of the featured list
+    SetCurator {
+        curator: Option<String>,
+    },
+    /// Curator or admin: replace the featured list, in display order
+    SetFeatured {
+        tokens: Vec<FeaturedToken>,
+    },
+    /// Admin: share of curated sales paid to the curator, in basis points. A
+    /// sale is curated when minted through a referral link or when the token
+    /// is on the featured list
+    SetCurationFee {
+        bps: u16,
+    },
+    /// Admin: register (or remove with `None`) a referral link id crediting
+    /// `curator`
+    SetReferral {
+        referral_id: String,
+        curator: Option<String>,
+    },
+
+    /// Admin: give a token its own royalty, or fall back to the collection
+    /// default with `None`
+    SetTokenRoyalty {
+        token_id: String,
+        royalty: Option<RoyaltyConfig>,
+    },
+    /// Admin: share a token's royalty among several payees, taking precedence
+    /// over its royalty, or remove the split with `None`
+    SetRoyaltySplit {
+        token_id: String,
+        payees: Option<Vec<RoyaltyConfig>>,
+    },
+    /// Admin: replace a token's royalty split with payees from effective_at
+    /// on, so sales after that date pay the new beneficiaries. Replaces any
+    /// succession scheduled for the same time
+    ScheduleRoyaltySuccession {
+        token_id: String,
+        effective_at: Timestamp,
+        payees: Vec<RoyaltyConfig>,
+    },
+    /// Admin: drop a succession that has not taken effect yet
+    CancelRoyaltySuccession {
+        token_id: String,
+        effective_at: Timestamp,
+    },
+    /// Admin: set (or remove with `None`) the contract royalties are swapped
+    /// through into their recipient's payout denom. It must answer
+    /// `SwapExecuteMsg::Swap`
+    SetRoyaltySwap {
+        swap: Option<String>,
+    },
+    /// Royalty recipient: be paid royalties in denom, or in the sale's denom
+    /// again with `None`. Royalties whose swap fails accrue in the sale's
+    /// denom for ClaimAccruedRoyalties
+    SetRoyaltyPayoutDenom {
+        denom: Option<String>,
+    },
+    /// Royalty recipient: take the royalties accrued to the sender
+    ClaimAccruedRoyalties {},
+
+    /// Bid `price` per token for up to `quantity` tokens of the collection,
+    /// escrowing `price * quantity`
+    PlaceCollectionBid {
+        price: Coin,
+        quantity: u64,
+    },
+    /// Sell one token into a collection bid. Bids can be filled by several
+    /// sellers over time until exhausted
+    AcceptCollectionBid {
+        bid_id: u64,
+        token_id: String,
+    },
+    /// Bidder: withdraw a bid and the escrow of its unfilled quantity
+    CancelCollectionBid {
+        bid_id: u64,
+    },
+
+    /// Offer a token for sale at a fixed price, replacing any earlier listing
+    /// of it. The token stays with its owner until bought; the listing lapses
+    /// once it changes hands
+    ListForSale {
+        token_id: String,
+        price: Coin,
+        /// Royalty waiver held by the owner, from the token's artist, to
+        /// spend on this sale
+        #[serde(default)]
+        royalty_waiver: Option<u64>,
+    },
+    CancelListing {
+        token_id: String,
+    },
+    /// Price oracle role: set (or clear with `None`) the informational fiat
+    /// equivalent of a listing's price. Relisting clears it
+    SetListingFiatPrice {
+        token_id: String,
+        fiat_price: Option<FiatPrice>,
+    },
+    /// Admin: waive royalties and/or the marketplace fee on a listing, or
+    /// restore them with `None`. Recorded in the sale receipt
+    SetListingFeeOverride {
+        token_id: String,
+        fee_override: Option<SaleFeeOverride>,
+    },
+    /// Buy a listed token at its price. The seller gets the price less
+    /// royalties, the marketplace fee, withholding and curation fees
+    BuyToken {
+        token_id: String,
+    },
+    /// Buy every listed token_id and, in the same checkout, the companion
+    /// items sold by other contracts. The funds must cover the listings and
+    /// each companion's funds, the excess is refunded. If any companion
+    /// purchase fails the whole checkout is undone
+    BuyBundle {
+        token_ids: Vec<String>,
+        companions: Vec<CompanionPurchase>,
+    },
+    /// Buy a listed token with `loan` of its price lent by the lender, which
+    /// holds the token as collateral. The funds pay the rest of the price
+    BuyWithLoan {
+        token_id: String,
+        loan: Coin,
+    },
+    /// Admin: set (or remove with `None`) the lender financing BuyWithLoan.
+    /// It must answer `LenderExecuteMsg::OpenLoan`
+    SetLender {
+        lender: Option<String>,
+    },
+    /// Admin: set (or remove with `None`) the fee taken from marketplace sales
+    SetMarketplaceFee {
+        fee: Option<MarketplaceFeeConfig>,
+    },
+    /// Artist of record: issue a pass waiving the royalties of one sale of
+    /// any of your works. At most 100 per artist
+    IssueRoyaltyWaiver {
+        holder: String,
+    },
+    /// Holder: give a royalty waiver to someone else
+    TransferRoyaltyWaiver {
+        waiver_id: u64,
+        recipient: String,
+    },
+
+    /// Offer the funds sent (one coin) for a token, listed or not. They stay
+    /// escrowed until the offer is accepted or withdrawn. One offer per
+    /// token and bidder
+    MakeOffer {
+        token_id: String,
+    },
+    /// Bidder: withdraw an offer and get the funds back
+    WithdrawOffer {
+        token_id: String,
+    },
+    /// Owner or listing-approved spender: sell the token to bidder at their
+    /// offer. The owner gets it less royalties, the marketplace fee,
+    /// withholding and curation fees
+    AcceptOffer {
+        token_id: String,
+        bidder: String,
+    },
+    /// Artist: take (or give up with `None`) a right of first refusal on
+    /// resales of the sender's works. For `window` seconds after a listing
+    /// only the artist can buy it, and an accepted offer is held in escrow
+    /// for as long for the artist to match
+    SetFirstRefusal {
+        window: Option<u64>,
+    },
+    /// Artist: buy a token held for first refusal at the accepted offer's
+    /// price, sending exactly that. The offer is refunded
+    MatchFirstRefusal {
+        token_id: String,
+    },
+    /// Complete the sale to the bidder of a first refusal the artist let
+    /// lapse. Callable by anyone
+    CompleteFirstRefusal {
+        token_id: String,
+    },
+    /// Deposit the funds sent towards the sender's auto-buy budget and set
+    /// its rules. Topping up must be in the budget's denom; changing the
+    /// rules only lets listings made from now on match them
+    FundAutoBuy {
+        rules: AutoBuyRules,
+    },
+    /// Refund the sender's whole auto-buy budget and drop its rules
+    WithdrawAutoBuy {},
+    /// Buy token_id for collector out of their auto-buy budget, if its
+    /// listing matches their rules. Callable by anyone
+    AutoBuy {
+        collector: String,
+        token_id: String,
+    },
+
+    /// Artist of record or treasurer holding token_id: escrow it in the
+    /// contract for an owner to take in exchange for one of
+    /// `accepted_token_ids` plus `top_up`
+    OpenTradeIn {
+        token_id: String,
+        top_up: Coin,
+        accepted_token_ids: Vec<String>,
+    },
+    /// Holder: call off a trade-in, getting the token back
+    CloseTradeIn {
+        token_id: String,
+    },
+    /// Swap offered_token_id, which the sender must be able to send and the
+    /// trade-in must accept, and the top-up sent for the escrowed token_id
+    TradeIn {
+        token_id: String,
+        offered_token_id: String,
+    },
 
-// add code as instructed in the lesson
+    /// Minter: give a token a unique human-readable alias, e.g.
+    /// "nocturne-no-3", or clear it with `None`. Any token id in other
+    /// messages and queries can then be given as the alias
+    SetTokenAlias {
+        token_id: String,
+        alias: Option<String>,
+    },
+
+    /// Admin: freeze a token for compliance or a dispute. It cannot be
+    /// transferred, sent, sold, burned or released from escrow until unfrozen
+    FreezeToken {
+        token_id: String,
+        reason: String,
+    },
+    /// Admin
+    UnfreezeToken {
+        token_id: String,
+    },
+    /// Admin: move a frozen token to a custody address, e.g. under a court
+    /// order. It stays frozen. A gift, conditional transfer, trade-in or
+    /// reservation holding the token is called off, refunding the
+    /// reservation's buyer. Tokens in auctions or bridges cannot be seized
+    SeizeToken {
+        token_id: String,
+        custody_address: String,
+        reason: String,
+    },
+
+    /// Admin: approve a venue (or update its name and key) to vouch for
+    /// exhibitions
+    AddVenue {
+        address: String,
+        name: String,
+        /// secp256k1 public key the venue signs with
+        pubkey: Binary,
+    },
+    /// Admin: withdraw a venue's approval, logged exhibitions are kept
+    RemoveVenue {
+        address: String,
+    },
+
+    /// Admin: freeze the contract for an emergency migration. Every execute
+    /// but EndExport fails until it ends, and the Export queries open up
+    StartExport {},
+
+    /// Treasurer: send collected funds to recipient (default: the sender). Without
+    /// `amount` everything withdrawable in every denom is sent. Funds escrowed
+    /// for bids, pre-sales and pools cannot be withdrawn
+    Withdraw {
+        amount: Option<Coin>,
+        recipient: Option<String>,
+    },
+    /// Admin: edit the recipients Withdraw may pay. Once used, Withdraw only
+    /// pays allowlisted recipients. Removals apply at once, added recipients
+    /// can be paid after a day, so a compromised admin key cannot add its own
+    /// address and drain the treasury right away
+    UpdateWithdrawalRecipients {
+        add: Vec<String>,
+        remove: Vec<String>,
+    },
+    /// Admin: cap what Withdraw sends in denom over any 24 hours, or lift the
+    /// cap with `None`. A tighter cap applies at once, a looser one after a day
+    SetWithdrawalCap {
+        denom: String,
+        amount: Option<Uint128>,
+    },
+
+    /// Admin: set (or remove with `None`) the storage fee for vault-custodied
+    /// works. Cannot be changed while any token is in custody
+    SetStorageFee {
+        schedule: Option<StorageFeeSchedule>,
+    },
+    /// Admin: mark a token as held in the vault, or release it once its
+    /// storage fees are paid. Tokens with unpaid fees cannot be transferred
+    SetVaultCustody {
+        token_id: String,
+        custodied: bool,
+    },
+    /// Pay towards a token's unpaid storage fees
+    PayStorageFee {
+        token_id: String,
+    },
+
+    /// Owner: split a right off the token and give it to holder. Rights stay
+    /// where they are when the token itself is transferred
+    AssignRight {
+        token_id: String,
+        right: TokenRight,
+        holder: String,
+        /// Let the holder pass the right on with TransferRight
+        transferable: bool,
+    },
+    /// Holder of a transferable right: pass it on
+    TransferRight {
+        token_id: String,
+        right: TokenRight,
+        recipient: String,
+    },
+    /// Holder: return a right to the token's owner
+    ReleaseRight {
+        token_id: String,
+        right: TokenRight,
+    },
+
+    /// Let spender (e.g. a marketplace) put a token up for sale without being
+    /// able to transfer it: it can create and cancel auctions for the token and
+    /// sell it into collection bids, the token only moving on settlement.
+    /// Cleared when the token changes hands
+    ApproveListing {
+        spender: String,
+        token_id: String,
+        expires: Option<Expiration>,
+    },
+    RevokeListing {
+        spender: String,
+        token_id: String,
+    },
+    /// Admin: unfreeze the contract
+    EndExport {},
+    /// Register a group exhibition, curated by the sender
+    RegisterEvent {
+        details: ExhibitionDetails,
+    },
+    /// Curator or owner: confirm a work's inclusion in an event. `collection`
+    /// defaults to this contract. Once both have confirmed, works of this
+    /// collection gain an exhibition provenance entry
+    ConfirmEventWork {
+        event_id: u64,
+        collection: Option<String>,
+        token_id: String,
+    },
+    /// Owner: add an exhibition of a token to its provenance. `signature` is
+    /// the venue's signature over the sha256 of the JSON `ExhibitionSignDoc`
+    LogExhibition {
+        token_id: String,
+        venue: String,
+        exhibition: ExhibitionDetails,
+        signature: Binary,
+    },
+}
+
+impl<T> ExecuteMsg<T> {
+    /// ids of this collection's tokens named in the message, which may be
+    /// given as aliases
+    pub fn token_ids_mut(&mut self) -> Vec<&mut String> {
+        match self {
+            ExecuteMsg::TransferNft { token_id, .. }
+            | ExecuteMsg::SendNft { token_id, .. }
+            | ExecuteMsg::Approve { token_id, .. }
+            | ExecuteMsg::Revoke { token_id, .. }
+            | ExecuteMsg::Burn { token_id, .. }
+            | ExecuteMsg::PayInstallment { token_id, .. }
+            | ExecuteMsg::ForfeitReservation { token_id, .. }
+            | ExecuteMsg::Destroy { token_id, .. }
+            | ExecuteMsg::Attest { token_id, .. }
+            | ExecuteMsg::RevokeAttestation { token_id, .. }
+            | ExecuteMsg::CreateSealedAuction { token_id, .. }
+            | ExecuteMsg::CreateAuction { token_id, .. }
+            | ExecuteMsg::SetRedactions { token_id, .. }
+            | ExecuteMsg::ProposeLink { token_id, .. }
+            | ExecuteMsg::AcceptLink { token_id, .. }
+            | ExecuteMsg::RemoveLink { token_id, .. }
+            | ExecuteMsg::RecordPin { token_id, .. }
+            | ExecuteMsg::SetPinStatus { token_id, .. }
+            | ExecuteMsg::SetMetadataVariants { token_id, .. }
+            | ExecuteMsg::UpdateTokenMetadata { token_id, .. }
+            | ExecuteMsg::FreezeTokenMetadata { token_id, .. }
+            | ExecuteMsg::BurnForRefund { token_id, .. }
+            | ExecuteMsg::RedeemVaultReceipt { token_id, .. }
+            | ExecuteMsg::RedeemVoucher { token_id, .. }
+            | ExecuteMsg::SetArtistOfRecord { token_id, .. }
+            | ExecuteMsg::IssueCertificate { token_id, .. }
+            | ExecuteMsg::SendGift { token_id, .. }
+            | ExecuteMsg::ClaimGift { token_id, .. }
+            | ExecuteMsg::ReturnGift { token_id, .. }
+            | ExecuteMsg::CreateConditionalTransfer { token_id, .. }
+            | ExecuteMsg::ExecuteConditionalTransfer { token_id, .. }
+            | ExecuteMsg::CancelConditionalTransfer { token_id, .. }
+            | ExecuteMsg::RecordPrivateSale { token_id, .. }
+            | ExecuteMsg::RevealPrivateSale { token_id, .. }
+            | ExecuteMsg::RevealToken { token_id, .. }
+            | ExecuteMsg::SetTokenRoyalty { token_id, .. }
+            | ExecuteMsg::SetRoyaltySplit { token_id, .. }
+            | ExecuteMsg::ScheduleRoyaltySuccession { token_id, .. }
+            | ExecuteMsg::CancelRoyaltySuccession { token_id, .. }
+            | ExecuteMsg::AcceptCollectionBid { token_id, .. }
+            | ExecuteMsg::ListForSale { token_id, .. }
+            | ExecuteMsg::CancelListing { token_id, .. }
+            | ExecuteMsg::SetListingFeeOverride { token_id, .. }
+            | ExecuteMsg::SetListingFiatPrice { token_id, .. }
+            | ExecuteMsg::BuyToken { token_id, .. }
+            | ExecuteMsg::BuyWithLoan { token_id, .. }
+            | ExecuteMsg::MakeOffer { token_id, .. }
+            | ExecuteMsg::WithdrawOffer { token_id, .. }
+            | ExecuteMsg::AcceptOffer { token_id, .. }
+            | ExecuteMsg::CloseTradeIn { token_id, .. }
+            | ExecuteMsg::MatchFirstRefusal { token_id, .. }
+            | ExecuteMsg::CompleteFirstRefusal { token_id, .. }
+            | ExecuteMsg::AutoBuy { token_id, .. }
+            | ExecuteMsg::FreezeToken { token_id, .. }
+            | ExecuteMsg::UnfreezeToken { token_id, .. }
+            | ExecuteMsg::SeizeToken { token_id, .. }
+            | ExecuteMsg::SetVaultCustody { token_id, .. }
+            | ExecuteMsg::PayStorageFee { token_id, .. }
+            | ExecuteMsg::AssignRight { token_id, .. }
+            | ExecuteMsg::TransferRight { token_id, .. }
+            | ExecuteMsg::ReleaseRight { token_id, .. }
+            | ExecuteMsg::ApproveListing { token_id, .. }
+            | ExecuteMsg::RevokeListing { token_id, .. }
+            | ExecuteMsg::ConfirmEventWork { token_id, .. }
+            | ExecuteMsg::LogExhibition { token_id, .. } => vec![token_id],
+            ExecuteMsg::TradeIn {
+                token_id,
+                offered_token_id,
+            } => vec![token_id, offered_token_id],
+            ExecuteMsg::OpenTradeIn {
+                token_id,
+                accepted_token_ids,
+                ..
+            } => std::iter::once(token_id)
+                .chain(accepted_token_ids.iter_mut())
+                .collect(),
+            ExecuteMsg::SetVoucherLimit {
+                parent_token_id, ..
+            }
+            | ExecuteMsg::IssueVoucher {
+                parent_token_id, ..
+            } => vec![parent_token_id],
+            ExecuteMsg::IbcTransfer { token_ids, .. }
+            | ExecuteMsg::BatchBurn { token_ids, .. }
+            | ExecuteMsg::BuyBundle { token_ids, .. } => token_ids.iter_mut().collect(),
+            ExecuteMsg::BatchTransferNft { recipients } => recipients
+                .iter_mut()
+                .map(|(_, token_id)| token_id)
+                .collect(),
+            ExecuteMsg::SetFeatured { tokens } => {
+                tokens.iter_mut().map(|token| &mut token.token_id).collect()
+            }
+            _ => vec![],
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct ExhibitionDetails {
+    pub title: String,
+    pub start: Timestamp,
+    pub end: Timestamp,
+}
+
+/// Document a venue signs to vouch for an exhibition
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct ExhibitionSignDoc {
+    /// This contract's address
+    pub contract: String,
+    pub token_id: String,
+    /// The venue's address
+    pub venue: String,
+    pub exhibition: ExhibitionDetails,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct PriceFloor {
+    pub price: Coin,
+    /// The floor lapses at this time
+    pub until: Timestamp,
+}
+
+/// Document an artist's current key signs to hand over to a new key
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct KeyRotationSignDoc {
+    /// This contract's address
+    pub contract: String,
+    pub artist: String,
+    /// Version the new key will have
+    pub version: u32,
+    pub pubkey: Binary,
+}
+
+/// Terms of a lazy mint, signed by the artist off-chain
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct LazyMintVoucher<T> {
+    /// An artist of this collection with a registered key
+    pub artist: String,
+    pub token_uri: String,
+    pub price: Coin,
+    /// Royalty of the minted token, otherwise the collection default applies
+    pub royalty: Option<RoyaltyConfig>,
+    /// Any number, each can be redeemed once per artist
+    pub nonce: u64,
+    /// Share of the proceeds, after withholding and curation fees, paid to
+    /// the artist in basis points. The rest goes to the collection
+    pub artist_share_bps: u16,
+    /// The voucher cannot be redeemed from then on
+    pub expires: Timestamp,
+    pub extension: T,
+}
+
+/// Document an artist signs to offer a lazy mint
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct LazyMintSignDoc<T> {
+    /// This contract's address
+    pub contract: String,
+    pub voucher: LazyMintVoucher<T>,
+}
+
+/// Document an artist signs to certify a token
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct CertificateSignDoc {
+    /// This contract's address
+    pub contract: String,
+    pub token_id: String,
+    pub artist: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct FeaturedToken {
+    pub token_id: String,
+    pub blurb: String,
+}
+
+/// Commitments are accepted during `[start, end)`, editions are allocated after
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct PresaleConfig {
+    pub editions: u64,
+    pub start: Timestamp,
+    pub end: Timestamp,
+    /// sha256 of the secret seed the admin draws the lottery with. Nobody
+    /// else can predict the draw, and the admin cannot change the seed once
+    /// commitments are in
+    #[serde(default)]
+    pub seed_commitment: HexBinary,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct AllowlistConfig {
+    /// Mint price during the phase
+    pub price: Coin,
+    /// Tokens each allowlisted address may mint during the phase
+    pub per_address_limit: u32,
+    /// Minting opens to everyone at the mint price from this time on
+    pub end: Timestamp,
+}
+
+/// Public mint price falling linearly from `start_price` to `end_price`
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct DutchAuctionConfig {
+    pub start_price: Coin,
+    /// Floor the price stays at once the decline is over
+    pub end_price: Coin,
+    /// The price starts declining at this time, until then it is `start_price`
+    pub start_time: Timestamp,
+    /// Seconds the price takes to fall to `end_price`
+    pub decay_interval: u64,
+}
+
+/// Public mint price set in a reference unit, e.g. USD, and charged in the
+/// fallback price's denom at the oracle's rate at mint time
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct MintPricePeg {
+    /// Answers `OracleQueryMsg::Price` with the price of one unit of the
+    /// fallback denom in `quote`
+    pub oracle: String,
+    /// Reference unit, e.g. "USD"
+    pub quote: String,
+    /// Mint price in `quote`
+    pub price: Decimal,
+    /// Oldest oracle price still used, in seconds
+    pub max_staleness: u64,
+    /// Charged when the oracle price is stale or cannot be read
+    pub fallback_price: Coin,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum TransferCondition {
+    /// The oracle's price of base in quote is at least `price`
+    PriceAtLeast {
+        oracle: String,
+        base: String,
+        quote: String,
+        price: Decimal,
+    },
+    /// The oracle's price of base in quote is at most `price`
+    PriceAtMost {
+        oracle: String,
+        base: String,
+        quote: String,
+        price: Decimal,
+    },
+    /// An active attestor attests the token with `kind` after the transfer
+    /// was created
+    Attested { kind: AttestationKind },
+}
+
+/// Amount in a fiat currency, for display only
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct FiatPrice {
+    pub amount: Decimal,
+    /// ISO 4217 code, e.g. "USD"
+    pub currency: String,
+}
+
+/// Query sent to price oracles of conditional transfers
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum OracleQueryMsg {
+    Price { base: String, quote: String },
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct OraclePriceResponse {
+    pub price: Decimal,
+    /// When the price was last updated, required by mint price pegs
+    #[serde(default)]
+    pub updated_at: Option<Timestamp>,
+}
+
+/// Query sent to the name resolver, following the ICNS resolver interface
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum ResolverQueryMsg {
+    AddressByIcns { icns: String },
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct ResolverAddressResponse {
+    pub bech32_address: String,
+}
+
+/// Query sent to the metadata resolver for the extension `NftInfo` reports,
+/// given the token's stored metadata
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum MetadataResolverQueryMsg<T> {
+    Extension {
+        token_id: String,
+        token_uri: Option<String>,
+        extension: T,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct ResolverExtensionResponse<T> {
+    pub extension: T,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct GiftMessage {
+    pub body: String,
+    /// Set when `body` is ciphertext encrypted off-chain for the recipient
+    pub encrypted: bool,
+}
+
+/// Payload of a vault deposit
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct VaultDepositMsg<T> {
+    /// Extension of the receipt token
+    pub extension: T,
+}
+
+/// Artist buy-back: during `[start, end)` holders can burn tokens for `price`
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct BuyBackProgram {
+    pub price: Coin,
+    pub start: Timestamp,
+    pub end: Timestamp,
+}
+
+/// A token_uri served by NftInfo while `start <= block time < end`. When
+/// windows overlap the first matching variant wins
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct MetadataVariant {
+    pub token_uri: String,
+    pub start: Timestamp,
+    pub end: Timestamp,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct StorageFeeSchedule {
+    /// Charged once per full period in custody
+    pub fee: Coin,
+    /// Seconds
+    pub period: u64,
+    /// Receives storage fee payments
+    pub collector: String,
+}
+
+/// Subsystem the contract holds funds for
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum TreasuryAccount {
+    /// Primary sale revenue and late fees, net of withholding
+    Proceeds,
+    /// Sealed-bid deposits and bids
+    AuctionEscrow,
+    /// Unfilled collection bids
+    CollectionBids,
+    /// Open offers on tokens
+    Offers,
+    /// Undrawn pre-sale commitments
+    Presale,
+    /// Unspent auto-buy budgets
+    AutoBuy,
+    /// Installments paid on tokens not yet delivered
+    Reservations,
+    /// Royalties whose swap into the recipient's payout denom failed
+    AccruedRoyalties,
+    RefundPool,
+    KeeperTreasury,
+}
+
+impl TreasuryAccount {
+    /// accounts kept in the treasury ledger
+    pub const LEDGER: [TreasuryAccount; 8] = [
+        TreasuryAccount::Proceeds,
+        TreasuryAccount::AuctionEscrow,
+        TreasuryAccount::CollectionBids,
+        TreasuryAccount::Offers,
+        TreasuryAccount::Presale,
+        TreasuryAccount::AutoBuy,
+        TreasuryAccount::Reservations,
+        TreasuryAccount::AccruedRoyalties,
+    ];
+
+    pub fn key(&self) -> &'static str {
+        match self {
+            TreasuryAccount::Proceeds => "proceeds",
+            TreasuryAccount::AuctionEscrow => "auction_escrow",
+            TreasuryAccount::CollectionBids => "collection_bids",
+            TreasuryAccount::Offers => "offers",
+            TreasuryAccount::Presale => "presale",
+            TreasuryAccount::AutoBuy => "auto_buy",
+            TreasuryAccount::Reservations => "reservations",
+            TreasuryAccount::AccruedRoyalties => "accrued_royalties",
+            TreasuryAccount::RefundPool => "refund_pool",
+            TreasuryAccount::KeeperTreasury => "keeper_treasury",
+        }
+    }
+}
+
+/// Token details the owner can hide from public queries. The data stays in
+/// storage and in the provenance digest, so whoever it is disclosed to can
+/// check it against the digest
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum RedactedField {
+    /// `provenance_notes` of an artwork extension, e.g. storage locations
+    ProvenanceNotes,
+    /// The token's exhibition log
+    Exhibitions,
+    /// Buyers and sellers in the token's sale receipt, sales feed entries,
+    /// private sales and provenance log
+    PriorOwners,
+}
+
+/// ICS-721 packet data, as sent over NFT transfer channels
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct NonFungibleTokenPacketData {
+    /// This collection's address for its own tokens, the class trace for
+    /// tokens of other chains
+    pub class_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub class_uri: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub class_data: Option<Binary>,
+    pub token_ids: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token_uris: Option<Vec<String>>,
+    /// json of each token's extension
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token_data: Option<Vec<Binary>>,
+    pub sender: String,
+    pub receiver: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum Ics721Ack {
+    Result(Binary),
+    Error(String),
+}
+
+/// How the proposing token of a link relates to the token it links to
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum LinkKind {
+    /// A study or sketch for the linked work
+    Study,
+    /// A print or reproduction of the linked work
+    Print,
+    /// A companion piece, e.g. a panel of the same series
+    Companion,
+}
+
+/// cw2981 royalty query, as asked of the origin collection of a vault
+/// deposit. cw2981-royalties answers it wrapped in `extension`
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw2981QueryMsg {
+    RoyaltyInfo {
+        token_id: String,
+        sale_price: Uint128,
+    },
+    Extension {
+        msg: Box<Cw2981QueryMsg>,
+    },
+}
+
+/// Companion item of a bundle checkout: `msg` is executed on `contract`
+/// with `funds`, which pay for the item
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct CompanionPurchase {
+    pub contract: String,
+    pub msg: Binary,
+    pub funds: Vec<Coin>,
+}
+
+/// Message the lender is sent to finance a BuyWithLoan. It pays amount to
+/// this contract or fails, and is sent the token as collateral next
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum LenderExecuteMsg {
+    OpenLoan {
+        borrower: String,
+        token_id: String,
+        amount: Coin,
+    },
+}
+
+/// `msg` of the Cw721ReceiveMsg sending the lender a financed token
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct LoanCollateralMsg {
+    pub borrower: String,
+}
+
+/// Message the royalty swap contract is sent with the royalty as funds.
+/// It pays the funds, swapped to ask_denom, to recipient or fails
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum SwapExecuteMsg {
+    Swap {
+        ask_denom: String,
+        recipient: String,
+    },
+}
+
+/// Handshake between linked collections. `token_id` is the receiving
+/// collection's token, `linked_token_id` the sender's
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum LinkCallback {
+    /// The linked token's owner accepted the link proposed by token_id
+    Confirm {
+        token_id: String,
+        linked_token_id: String,
+        kind: LinkKind,
+    },
+    /// The link was removed on the sender's side
+    Remove {
+        token_id: String,
+        linked_token_id: String,
+    },
+}
+
+/// Authority the minter can delegate, so a gallery can split operations
+/// between keys. The minter itself holds every role
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum Role {
+    /// Configuration, the messages documented as "Admin"
+    Admin,
+    /// BatchMint, IssueVoucher and minting with a custom token_uri
+    Minter,
+    /// Metadata updates: SetMetadataVariants and SetArtistOfRecord
+    Curator,
+    /// Withdraw, WithdrawRefundPool and WithdrawKeeperTreasury
+    Treasurer,
+    /// Pin registry (RecordPin and SetPinStatus) and Destroy
+    Custodian,
+    /// SetListingFiatPrice
+    PriceOracle,
+}
+
+impl Role {
+    pub fn key(&self) -> &'static str {
+        match self {
+            Role::Admin => "admin",
+            Role::Minter => "minter",
+            Role::Curator => "curator",
+            Role::Treasurer => "treasurer",
+            Role::Custodian => "custodian",
+            Role::PriceOracle => "price_oracle",
+        }
+    }
+}
+
+/// Rights in the work that can be held apart from the token itself
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum TokenRight {
+    /// Possession of the physical artwork
+    Physical,
+    /// Right to display the digital work
+    Display,
+}
+
+impl TokenRight {
+    pub fn key(&self) -> &'static str {
+        match self {
+            TokenRight::Physical => "physical",
+            TokenRight::Display => "display",
+        }
+    }
+}
+
+/// Why an owner revoked an operator, so custodians can coordinate during
+/// marketplace incidents
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum RevocationReason {
+    /// The operator or its keys are believed to be compromised
+    Compromise,
+    /// The operator contract is being retired
+    ContractSunset,
+    Other {
+        label: String,
+    },
+}
+
+impl RevocationReason {
+    pub fn code(&self) -> &str {
+        match self {
+            RevocationReason::Compromise => "compromise",
+            RevocationReason::ContractSunset => "contract_sunset",
+            RevocationReason::Other { label } => label,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum AttestationKind {
+    Authentication,
+    PigmentAnalysis,
+    ProvenanceResearch,
+    ConditionReport,
+    Other { label: String },
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct InstallmentPlan {
+    /// Amount paid up front, in the mint price denom
+    pub down_payment: Uint128,
+    /// Number of equal installments covering the rest of the mint price
+    pub installments: u32,
+    /// Seconds between installment due dates
+    pub interval: u64,
+    /// Seconds after a due date during which the reservation cannot be
+    /// forfeited yet
+    #[serde(default)]
+    pub grace_period: u64,
+    /// Charged on top of any installment paid after its due date
+    #[serde(default)]
+    pub late_fee: Uint128,
+    /// Share of the net payments refunded to the buyer on forfeiture, in
+    /// basis points. The seller keeps the rest
+    #[serde(default)]
+    pub forfeit_refund_bps: u16,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct MintMsg<T> {
+    /// The owner of the newly minted NFT. The sender pays, so patrons can mint
+    /// straight into an institution's wallet; both are recorded in the mint's
+    /// provenance event
+    pub owner: String,
+    /// Minter only: metadata of this token. Defaults to the collection's
+    /// token_uri
+    #[serde(default)]
+    pub token_uri: Option<String>,
+    /// Referral link id the sale came through, its curator is paid the
+    /// curation fee
+    #[serde(default)]
+    pub referral: Option<String>,
+    /// Hash of the artwork's content, e.g. of the master image file. With
+    /// unique content enforced, no two tokens may share one
+    #[serde(default)]
+    pub content_hash: Option<HexBinary>,
+    /// Any custom extension used by this contract
+    pub extension: T,
+    /// Minter only: `false` makes the token soulbound, e.g. a certificate of
+    /// authenticity. It can then never be transferred, sold or approved,
+    /// only burned
+    #[serde(default = "transferable_by_default")]
+    pub transferable: bool,
+}
+
+fn transferable_by_default() -> bool {
+    true
+}
+
+/// cw721's `NftInfoResponse`, plus whether the token can change hands
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct TokenNftInfoResponse<T> {
+    pub token_uri: Option<String>,
+    pub extension: T,
+    pub transferable: bool,
+    /// Whether the curator has made the metadata immutable
+    pub metadata_frozen: bool,
+    /// Series the token is an edition of
+    pub series_id: Option<u64>,
+}
+
+/// On-chain description of an artwork, for use as the token extension so
+/// marketplaces can display the basics without fetching the token_uri
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct ArtworkExtension {
+    pub artist_name: String,
+    pub title: String,
+    /// Year of creation, negative for BCE
+    pub year: Option<i32>,
+    pub medium: Option<String>,
+    /// Free-form, e.g. "77 x 53 cm"
+    pub dimensions: Option<String>,
+    pub edition: Option<Edition>,
+    pub provenance_notes: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct Edition {
+    /// 1-based number within the edition
+    pub number: u32,
+    pub size: u32,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    OwnerOf {
+        token_id: String,
+        include_expired: Option<bool>,
+    },
+
+    Approval {
+        token_id: String,
+        spender: String,
+        include_expired: Option<bool>,
+    },
+
+    Approvals {
+        token_id: String,
+        include_expired: Option<bool>,
+    },
+
+    /// The storage fee schedule for vault-custodied works
+    StorageFee {},
+    /// Storage fee account of a token, with fees accrued up to now
+    StorageAccount {
+        token_id: String,
+    },
+
+    /// Current holders of a token's separable rights
+    TokenRights {
+        token_id: String,
+    },
+
+    /// Sales across the collection, newest first. `start_before` is the `seq`
+    /// of the last sale of the previous page
+    SalesFeed {
+        secondary_only: Option<bool>,
+        start_before: Option<u64>,
+        limit: Option<u32>,
+    },
+
+    /// Listing approvals of a token
+    ListingApprovals {
+        token_id: String,
+        include_expired: Option<bool>,
+    },
+
+    AllOperators {
+        owner: String,
+        include_expired: Option<bool>,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    NumTokens {},
+
+    ContractInfo {},
+
+    NftInfo {
+        token_id: String,
+    },
+    /// The token's extension as an `ArtworkExtension`, if it is one
+    ArtworkInfo {
+        token_id: String,
+    },
+    /// Fields of the token hidden by its owner
+    Redactions {
+        token_id: String,
+    },
+
+    AllNftInfo {
+        token_id: String,
+        include_expired: Option<bool>,
+    },
+
+    Tokens {
+        owner: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    AllTokens {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    Minter {},
+    /// Whether address holds role, directly or as the minter
+    HasRole {
+        address: String,
+        role: Role,
+    },
+    /// Address the minter role has been offered to, if any
+    PendingMinter {},
+    SizeLimits {},
+
+    NftDetails {},
+    /// Token minted with the token_uri or content hash, if any
+    TokenByContent {
+        token_uri: Option<String>,
+        content_hash: Option<HexBinary>,
+    },
+    /// Tokens minted by the address and how many more it may mint
+    MintedByAddress {
+        address: String,
+    },
+    /// The drop in progress, if any
+    DropLimit {},
+    /// What address acquired in the drop in progress
+    DropAcquisitions {
+        address: String,
+    },
+
+    /// Public profile of an address, if registered and not suspended
+    Profile {
+        address: String,
+    },
+
+    /// Whether `SendNft` targets are restricted and which contracts are allowed
+    SendAllowlist {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    InstallmentPlan {},
+
+    /// Installment reservation for a token
+    Reservation {
+        token_id: String,
+    },
+
+    /// Installment reservations made by a buyer
+    ReservationsByBuyer {
+        buyer: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    WithholdingRule {
+        jurisdiction: String,
+    },
+
+    AccountJurisdiction {
+        address: String,
+    },
+
+    /// Settlement receipt of a token's latest sale
+    SaleReceipt {
+        token_id: String,
+    },
+
+    /// Record kept for a burned or destroyed token
+    Tombstone {
+        token_id: String,
+    },
+
+    Attestor {
+        address: String,
+    },
+
+    Attestors {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    /// Attestations attached to a token, including revoked ones
+    Attestations {
+        token_id: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+
+    /// Things awaiting action from an address, for notification panels
+    Inbox {
+        address: String,
+    },
+
+    Auction {
+        auction_id: u64,
+    },
+
+    Auctions {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+
+    SealedBid {
+        auction_id: u64,
+        bidder: String,
+    },
+    /// An auction's bids in the order they became known, sealed bids once
+    /// revealed
+    BidHistory {
+        auction_id: u64,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+
+    /// List the owners that granted ApproveAll to an operator
+    OperatorGrants {
+        operator: String,
+        include_expired: Option<bool>,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    /// Export mode and the schema version of the Export queries
+    ExportStatus {},
+    /// Version and prefixes of the typed subsystem events, so indexers can
+    /// subscribe to e.g. `wasm-marketplace_buy_token` and detect upgrades
+    EventSchema {},
+    /// Whether the contract is paused, and since when
+    Paused {},
+    /// The profile the contract was instantiated with
+    DeploymentProfile {},
+    /// Export mode only: every token with its approvals, artist, seal and
+    /// provenance digest, in id order. Auctions, collection bids and other
+    /// per-token records have their own paginated queries
+    ExportTokens {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Export mode only: every ApproveAll grant, by (owner, operator)
+    ExportOperators {
+        start_after: Option<(String, String)>,
+        limit: Option<u32>,
+    },
+
+    /// Revocations of an operator that gave a reason, oldest first
+    OperatorRevocations {
+        operator: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+
+    /// All scheduled metadata variants of a token, active or not
+    MetadataVariants {
+        token_id: String,
+    },
+
+    /// Time-weighted holding score of an address, for loyalty programs
+    HoldingScore {
+        address: String,
+    },
+
+    /// The buy-back program and refund pool balance
+    BuyBack {},
+
+    Refund {
+        token_id: String,
+    },
+
+    /// Digest of a token's provenance, for stamping on printed certificates
+    ProvenanceCertificate {
+        token_id: String,
+    },
+    /// The events behind a token's provenance digest, oldest first. Works
+    /// for removed tokens too
+    Provenance {
+        token_id: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+
+    /// The external token held in the vault for a receipt token
+    VaultDeposit {
+        token_id: String,
+    },
+
+    VaultCollections {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    PartnerGalleries {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Progress revoking a removed gallery's permissions, if still running
+    PermissionSweep {
+        address: String,
+    },
+    /// The artwork an outstanding visit voucher was issued for
+    Voucher {
+        token_id: String,
+    },
+    /// Voucher limit and counts of an artwork
+    VoucherIssuance {
+        parent_token_id: String,
+    },
+    /// Redeemed vouchers of an artwork, by voucher id
+    VoucherRedemptions {
+        parent_token_id: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    /// Tokens whose artist of record is `artist`, in id order
+    TokensByArtist {
+        artist: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    ArtistStats {
+        artist: String,
+    },
+    /// The resale floor in force for a token, if any
+    PriceFloor {
+        token_id: String,
+    },
+    /// An artist's signing keys, oldest first
+    ArtistKeys {
+        artist: String,
+        start_after: Option<u32>,
+        limit: Option<u32>,
+    },
+    /// A token's authenticity certificate and the key that signed it
+    Certificate {
+        token_id: String,
+    },
+    /// Whether an artist's lazy mint voucher nonce has been redeemed
+    LazyMintNonce {
+        artist: String,
+        nonce: u64,
+    },
+
+    Gift {
+        token_id: String,
+    },
+    /// Pending conditional transfer of a token, and whether it can execute now
+    ConditionalTransfer {
+        token_id: String,
+    },
+    /// Private sales of a token, oldest first
+    PrivateSales {
+        token_id: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+
+    RevealStatus {
+        token_id: String,
+    },
+
+    MysteryPool {},
+
+    SeriesInfo {
+        series_id: u64,
+    },
+    /// Series by id
+    AllSeries {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// The blind drop's placeholder, commitment and reveal, if any
+    BlindDrop {},
+
+    /// Expiry ledger entries due at or before `timestamp`, oldest first.
+    /// `start_after` is the `(at, seq)` of the last entry of the previous page
+    DueBefore {
+        timestamp: Timestamp,
+        start_after: Option<(u64, u64)>,
+        limit: Option<u32>,
+    },
+
+    /// Ranges an owner has approved operator for
+    RangeApprovals {
+        owner: String,
+        operator: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+
+    /// Collected primary-sale proceeds and what the admin can withdraw
+    CollectedFunds {},
+    /// Allowlisted withdrawal recipients, by address
+    WithdrawalRecipients {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    WithdrawalCap {
+        denom: String,
+    },
+    /// Funds attributed to each subsystem, against the contract's actual
+    /// balances
+    TreasuryReconciliation {},
+
+    /// The keeper bounty and treasury balance
+    KeeperTreasury {},
+
+    /// The configured name resolver, if any
+    NameResolver {},
+    /// The configured metadata resolver, if any
+    MetadataResolver {},
+    /// The address `address` stands for, resolving names through the name
+    /// resolver
+    ResolveAddress {
+        address: String,
+    },
+
+    /// What minting `quantity` tokens (at most 30) would cost `buyer` right
+    /// now. Each Mint pays the mint price, or the allowlist price during the
+    /// allowlist phase, exactly once
+    SimulateMint {
+        quantity: u32,
+        buyer: Option<String>,
+    },
+
+    /// The current pre-sale, if any
+    Presale {},
+    /// How address fared in the current pre-sale
+    PresaleCommitment {
+        address: String,
+    },
+
+    /// Members of the Merkle allowlist only show up once they minted
+    IsAllowlisted {
+        address: String,
+    },
+    AllowlistConfig {},
+    /// Price Mint charges right now: the allowlist phase's, the Dutch
+    /// auction's or the mint price
+    CurrentMintPrice {},
+
+    /// Featured tokens in display order. `start_after` is a position in the list
+    Featured {
+        start_after: Option<u32>,
+        limit: Option<u32>,
+    },
+    Curator {},
+    CurationFee {},
+    /// Curator credited by a referral link id
+    Referral {
+        referral_id: String,
+    },
+    /// Curation fees a curator has earned, per denom
+    CuratorEarnings {
+        curator: String,
+    },
+
+    /// cw2981: royalty owed on a sale of token_id at sale_price, in the sale's
+    /// denom. For a vault receipt, what the origin collection asks
+    RoyaltyInfo {
+        token_id: String,
+        sale_price: Uint128,
+    },
+    /// cw2981: whether the contract implements royalties
+    CheckRoyalties {},
+    /// Royalty split in effect now and the successions still to come
+    RoyaltySplit {
+        token_id: String,
+    },
+    /// The payout denom of a royalty recipient and its accrued royalties
+    RoyaltyPayout {
+        address: String,
+    },
+
+    /// Open collection bid, removed once exhausted or cancelled
+    CollectionBid {
+        bid_id: u64,
+    },
+    CollectionBids {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    Listing {
+        token_id: String,
+    },
+    AllListings {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    MarketplaceFee {},
+    /// Lender financing BuyWithLoan, if any
+    Lender {},
+    /// Open offers on a token, by bidder
+    OffersByToken {
+        token_id: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Open offers of a bidder, by token id
+    OffersByBidder {
+        bidder: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    TradeIn {
+        token_id: String,
+    },
+    /// The first refusal window of a token's artist, and an accepted offer
+    /// held for the artist to match
+    FirstRefusal {
+        token_id: String,
+    },
+    /// A collector's auto-buy budget and rules
+    AutoBuyer {
+        collector: String,
+    },
+    /// The token id an alias stands for
+    ResolveAlias {
+        alias: String,
+    },
+    /// Whether a token is frozen, and why
+    FrozenStatus {
+        token_id: String,
+    },
+    /// Open trade-ins, by token id
+    TradeIns {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    RoyaltyWaiver {
+        waiver_id: u64,
+    },
+    /// Royalty waivers held by `holder`
+    RoyaltyWaivers {
+        holder: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+
+    Event {
+        event_id: u64,
+    },
+    /// Works of an event, by collection then token id
+    EventWorks {
+        event_id: u64,
+        start_after: Option<(String, String)>,
+        limit: Option<u32>,
+    },
+    Venue {
+        address: String,
+    },
+    /// Exhibitions of a token, in the order logged
+    Exhibitions {
+        token_id: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Open ICS-721 channels
+    IbcChannels {},
+    /// Where a token stands with respect to other chains
+    IbcToken {
+        token_id: String,
+    },
+    /// Links and pending proposals of a token, by (contract, linked token id)
+    TokenLinks {
+        token_id: String,
+        start_after: Option<(String, String)>,
+        limit: Option<u32>,
+    },
+    /// Pins of a token, by provider
+    Pins {
+        token_id: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Pins not marked unpinned that expire within `within` seconds or already
+    /// expired, soonest first. Paginate with the last pin's
+    /// (expires_at seconds, token id, provider)
+    ExpiringPins {
+        within: u64,
+        start_after: Option<(u64, String, String)>,
+        limit: Option<u32>,
+    },
+}
+
+impl QueryMsg {
+    /// ids of this collection's tokens named in the query, which may be
+    /// given as aliases
+    pub fn token_ids_mut(&mut self) -> Vec<&mut String> {
+        match self {
+            QueryMsg::OwnerOf { token_id, .. }
+            | QueryMsg::Approval { token_id, .. }
+            | QueryMsg::Approvals { token_id, .. }
+            | QueryMsg::StorageAccount { token_id, .. }
+            | QueryMsg::TokenRights { token_id, .. }
+            | QueryMsg::ListingApprovals { token_id, .. }
+            | QueryMsg::NftInfo { token_id, .. }
+            | QueryMsg::ArtworkInfo { token_id, .. }
+            | QueryMsg::Redactions { token_id, .. }
+            | QueryMsg::AllNftInfo { token_id, .. }
+            | QueryMsg::Reservation { token_id, .. }
+            | QueryMsg::SaleReceipt { token_id, .. }
+            | QueryMsg::Tombstone { token_id, .. }
+            | QueryMsg::Attestations { token_id, .. }
+            | QueryMsg::MetadataVariants { token_id, .. }
+            | QueryMsg::Refund { token_id, .. }
+            | QueryMsg::ProvenanceCertificate { token_id, .. }
+            | QueryMsg::Provenance { token_id, .. }
+            | QueryMsg::VaultDeposit { token_id, .. }
+            | QueryMsg::Voucher { token_id, .. }
+            | QueryMsg::PriceFloor { token_id, .. }
+            | QueryMsg::Certificate { token_id, .. }
+            | QueryMsg::Gift { token_id, .. }
+            | QueryMsg::ConditionalTransfer { token_id, .. }
+            | QueryMsg::PrivateSales { token_id, .. }
+            | QueryMsg::RevealStatus { token_id, .. }
+            | QueryMsg::RoyaltyInfo { token_id, .. }
+            | QueryMsg::RoyaltySplit { token_id, .. }
+            | QueryMsg::Listing { token_id, .. }
+            | QueryMsg::OffersByToken { token_id, .. }
+            | QueryMsg::TradeIn { token_id, .. }
+            | QueryMsg::FirstRefusal { token_id, .. }
+            | QueryMsg::FrozenStatus { token_id, .. }
+            | QueryMsg::Exhibitions { token_id, .. }
+            | QueryMsg::IbcToken { token_id, .. }
+            | QueryMsg::TokenLinks { token_id, .. }
+            | QueryMsg::Pins { token_id, .. } => vec![token_id],
+            QueryMsg::VoucherIssuance {
+                parent_token_id, ..
+            }
+            | QueryMsg::VoucherRedemptions {
+                parent_token_id, ..
+            } => vec![parent_token_id],
+            _ => vec![],
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct MinterResponse {
+    pub minter: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct HasRoleResponse {
+    pub has_role: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct PendingMinterResponse {
+    pub pending_minter: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct NftDetailsResponse {
+    pub mint_price: Coin,
+    pub max_mints: u64,
+    pub max_mints_per_address: Option<u64>,
+    pub token_uri: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct TokenByContentResponse {
+    pub token_id: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct PausedResponse {
+    pub paused: bool,
+    pub since: Option<Timestamp>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct DropLimitResponse {
+    pub limit: Option<DropLimit>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct DropAcquisitionsResponse {
+    pub address: String,
+    /// `None` when no drop is in progress
+    pub drop_id: Option<u64>,
+    pub acquired: u64,
+    /// Acquisitions left under the drop's cap, `None` when no drop is in
+    /// progress
+    pub remaining: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct MintedByAddressResponse {
+    pub address: String,
+    pub minted: u64,
+    /// Mints left under the per-wallet limit, `None` when unlimited
+    pub remaining: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct ProfileResponse {
+    pub address: String,
+    pub display_name: Option<String>,
+    pub profile_uri: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct SendAllowlistResponse {
+    pub enabled: bool,
+    pub contracts: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct InstallmentPlanResponse {
+    pub plan: Option<InstallmentPlan>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct ReservationResponse {
+    pub token_id: String,
+    pub buyer: String,
+    pub recipient: String,
+    pub price: Coin,
+    pub paid: Uint128,
+    pub installment_amount: Uint128,
+    pub interval: u64,
+    pub next_due: Timestamp,
+    /// The reservation can be forfeited after this time
+    pub grace_until: Timestamp,
+    /// Due on top of the next installment if paid now
+    pub late_fee: Uint128,
+    pub late_fees_paid: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct ReservationsResponse {
+    pub reservations: Vec<ReservationResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct WithholdingRuleResponse {
+    pub jurisdiction: String,
+    pub rule: Option<WithholdingRule>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct AccountJurisdictionResponse {
+    pub address: String,
+    pub jurisdiction: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct SaleReceiptResponse {
+    pub token_id: String,
+    pub receipt: SaleReceipt,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct TombstoneResponse {
+    pub token_id: String,
+    pub tombstone: Tombstone,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct AttestorResponse {
+    pub address: String,
+    pub attestor: Attestor,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct AttestorsResponse {
+    pub attestors: Vec<AttestorResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct AttestationResponse {
+    pub index: u64,
+    pub attestation: Attestation,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct AttestationsResponse {
+    pub attestations: Vec<AttestationResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct InboxResponse {
+    /// Installment reservations the address has to keep paying, soonest due first
+    pub installments_due: Vec<ReservationResponse>,
+    /// Gifts waiting to be claimed by the address
+    pub gifts_to_claim: Vec<GiftResponse>,
+    /// Open offers on tokens the address owns
+    #[serde(default)]
+    pub offers_received: Vec<OfferResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct AuctionResponse {
+    pub auction_id: u64,
+    pub auction: Auction,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct AuctionsResponse {
+    pub auctions: Vec<AuctionResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct BidHistoryEntry {
+    pub seq: u64,
+    pub bid: BidRecord,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct BidHistoryResponse {
+    pub auction_id: u64,
+    pub bids: Vec<BidHistoryEntry>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct SealedBidResponse {
+    pub auction_id: u64,
+    pub bidder: String,
+    pub bid: SealedBid,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct OperatorGrant {
+    pub owner: String,
+    pub expires: Expiration,
+    /// False once the owner no longer holds any token, so the grant is stale
+    pub owner_holds_tokens: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct OperatorGrantsResponse {
+    pub grants: Vec<OperatorGrant>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct MetadataVariantsResponse {
+    pub variants: Vec<MetadataVariant>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct HoldingScoreResponse {
+    /// Tokens currently held
+    pub held: u64,
+    /// Token-seconds held, accrued up to the queried block
+    pub score: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct BuyBackResponse {
+    pub program: Option<BuyBackProgram>,
+    pub pool: RefundPool,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct RefundResponse {
+    pub token_id: String,
+    pub refund: Refund,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct ProvenanceCertificateResponse {
+    pub token_id: String,
+    pub hash: HexBinary,
+    /// Number of events chained into `hash`
+    pub entries: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct ProvenanceLogEntry {
+    /// Position in the digest chain, from 0
+    pub seq: u64,
+    pub entry: ProvenanceEntry,
+    /// Display name of the holder after a mint, transfer or sale
+    pub holder_display_name: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct ProvenanceResponse {
+    pub token_id: String,
+    pub entries: Vec<ProvenanceLogEntry>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct VaultDepositResponse {
+    /// The receipt token
+    pub token_id: String,
+    pub deposit: VaultDeposit,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct VaultCollectionsResponse {
+    pub collections: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct PartnerGalleriesResponse {
+    pub galleries: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct VoucherResponse {
+    pub token_id: String,
+    pub voucher: Voucher,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct VoucherIssuanceResponse {
+    pub parent_token_id: String,
+    pub issuance: VoucherIssuance,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct VoucherRedemptionsResponse {
+    pub redemptions: Vec<VoucherRedemption>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct SizeLimitsResponse {
+    pub limits: SizeLimits,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct ArtistStatsResponse {
+    pub artist: String,
+    pub stats: ArtistStats,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct GiftResponse {
+    pub token_id: String,
+    pub gift: Gift,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct ConditionalTransferResponse {
+    pub token_id: String,
+    pub transfer: ConditionalTransfer,
+    pub condition_met: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct PrivateSaleResponse {
+    pub sale_id: u64,
+    pub sale: PrivateSale,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct PrivateSalesResponse {
+    pub sales: Vec<PrivateSaleResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct RevealStatusResponse {
+    pub token_id: String,
+    pub sealed: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct DeploymentProfileResponse {
+    pub profile: DeploymentProfile,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct SeriesResponse {
+    pub series_id: u64,
+    pub series: Series,
+    /// Editions left to mint
+    pub remaining: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct AllSeriesResponse {
+    pub series: Vec<SeriesResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct BlindDropResponse {
+    pub blind_drop: Option<BlindDrop>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct MysteryPoolResponse {
+    /// Set while new mints start sealed
+    pub sealed_uri: Option<String>,
+    /// URIs left to reveal
+    pub remaining: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct DueEntry {
+    /// Expiry time in nanoseconds
+    pub at: u64,
+    pub seq: u64,
+    pub kind: ExpiryKind,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct DueBeforeResponse {
+    pub entries: Vec<DueEntry>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct KeeperTreasuryResponse {
+    pub bounty: Option<Coin>,
+    pub treasury: KeeperTreasury,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct NameResolverResponse {
+    pub resolver: Option<Addr>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct MetadataResolverResponse {
+    pub resolver: Option<Addr>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct ResolveAddressResponse {
+    pub address: Addr,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct SimulateMintResponse {
+    pub minting_allowed: bool,
+    /// How many of the requested tokens can still be minted
+    pub quantity: u32,
+    /// Funds to attach to each Mint
+    pub funds_per_mint: Coin,
+    pub total: Coin,
+    /// Withheld from each mint payment, if buyer is subject to withholding
+    pub withholding_per_mint: Option<Withholding>,
+    /// Ids the tokens would get if nobody else mints first
+    pub token_ids: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct PresaleResponse {
+    pub presale: Option<Presale>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct PresaleCommitmentResponse {
+    /// `None` when address has not committed
+    pub outcome: Option<PresaleOutcome>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct IsAllowlistedResponse {
+    pub allowlisted: bool,
+    /// Tokens minted during the allowlist phase
+    pub minted: u32,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct CurrentMintPriceResponse {
+    pub price: Coin,
+    pub dutch_auction: Option<DutchAuctionConfig>,
+    pub peg: Option<MintPricePeg>,
+    /// Whether the peg's fallback price is charged, the oracle price being
+    /// stale or unreadable
+    pub peg_fallback: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct AllowlistConfigResponse {
+    pub config: Option<AllowlistConfig>,
+    pub merkle_root: Option<HexBinary>,
+    /// Whether minting is currently limited to the allowlist
+    pub active: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct FeaturedEntry {
+    /// Position in the featured list, starting at 0
+    pub position: u32,
+    pub token_id: String,
+    pub blurb: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct FeaturedResponse {
+    pub tokens: Vec<FeaturedEntry>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct CuratorResponse {
+    pub curator: Option<Addr>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct CurationFeeResponse {
+    pub bps: u16,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct ReferralResponse {
+    pub curator: Option<Addr>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct CuratorEarningsResponse {
+    pub earnings: Vec<Coin>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct RoyaltiesInfoResponse {
+    /// Empty when no royalty is owed. With a split, the first payee, who
+    /// receives the whole royalty from marketplaces unaware of `payees`
+    pub address: String,
+    pub royalty_amount: Uint128,
+    /// What each payee is owed, adding up to `royalty_amount`
+    #[serde(default)]
+    pub payees: Vec<RoyaltyPayee>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct RoyaltyPayee {
+    pub address: String,
+    pub amount: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct RoyaltySplitResponse {
+    pub token_id: String,
+    pub split: Option<RoyaltySplit>,
+    pub upcoming: Vec<RoyaltySuccession>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct RoyaltyPayoutResponse {
+    pub address: String,
+    /// Denom the recipient asked to be paid in, if any
+    pub denom: Option<String>,
+    /// Contract royalties are swapped through, if any
+    pub swap: Option<Addr>,
+    /// Royalties whose swap failed, claimable as paid
+    pub accrued: Vec<Coin>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct CheckRoyaltiesResponse {
+    pub royalty_payments: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct CollectionBidResponse {
+    pub bid_id: u64,
+    pub bid: CollectionBid,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct CollectionBidsResponse {
+    pub bids: Vec<CollectionBidResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct ListingResponse {
+    pub token_id: String,
+    pub listing: Listing,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct ListingsResponse {
+    pub listings: Vec<ListingResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct OfferResponse {
+    pub token_id: String,
+    pub bidder: Addr,
+    pub offer: Offer,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct OffersResponse {
+    pub offers: Vec<OfferResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct TokenAliasResponse {
+    pub alias: String,
+    pub token_id: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct FrozenStatusResponse {
+    pub frozen: Option<FrozenToken>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct AutoBuyerResponse {
+    pub auto_buyer: Option<AutoBuyer>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct FirstRefusalResponse {
+    pub token_id: String,
+    /// Seconds the artist has to match, `None` without a right of first refusal
+    pub window: Option<u64>,
+    pub pending: Option<FirstRefusal>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct TradeInResponse {
+    pub token_id: String,
+    pub trade_in: TradeIn,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct TradeInsResponse {
+    pub trade_ins: Vec<TradeInResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct RoyaltyWaiverResponse {
+    pub waiver_id: u64,
+    pub waiver: RoyaltyWaiver,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct RoyaltyWaiversResponse {
+    pub waivers: Vec<RoyaltyWaiverResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct MarketplaceFeeResponse {
+    pub fee: Option<MarketplaceFee>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct LenderResponse {
+    pub lender: Option<Addr>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct VenueResponse {
+    pub address: String,
+    pub venue: Venue,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct ExhibitionResponse {
+    pub index: u64,
+    pub exhibition: Exhibition,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct ExhibitionsResponse {
+    pub exhibitions: Vec<ExhibitionResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct OperatorRevocationResponse {
+    pub seq: u64,
+    pub revocation: OperatorRevocation,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct OperatorRevocationsResponse {
+    pub revocations: Vec<OperatorRevocationResponse>,
+}
+
+/// Bumped whenever an Export response changes shape
+pub const EXPORT_SCHEMA_VERSION: u32 = 1;
+
+/// Bumped whenever the typed subsystem events change shape
+pub const EVENT_SCHEMA_VERSION: u32 = 1;
+
+/// Subsystems whose handlers also emit a typed `wasm-<prefix>_<action>`
+/// event, carrying the handler's attributes and the event schema version
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum EventSubsystem {
+    /// Listings, offers, collection bids, trade-ins and royalty waivers
+    Marketplace,
+    /// Sealed-bid and English auctions
+    Auction,
+    /// Private sales, exhibitions, certificates and attestations
+    Provenance,
+}
+
+impl EventSubsystem {
+    pub const ALL: [EventSubsystem; 3] = [
+        EventSubsystem::Marketplace,
+        EventSubsystem::Auction,
+        EventSubsystem::Provenance,
+    ];
+
+    pub fn prefix(self) -> &'static str {
+        match self {
+            EventSubsystem::Marketplace => "marketplace",
+            EventSubsystem::Auction => "auction",
+            EventSubsystem::Provenance => "provenance",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct EventSubsystemInfo {
+    pub subsystem: EventSubsystem,
+    pub prefix: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct EventSchemaResponse {
+    pub version: u32,
+    pub subsystems: Vec<EventSubsystemInfo>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct ExportStatusResponse {
+    pub export: Option<ExportMode>,
+    pub schema_version: u32,
+    pub token_count: u64,
+    /// Ids handed out so far, the next mint gets `minted + 1`
+    pub minted: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct ExportedToken<T> {
+    pub token_id: String,
+    pub owner: Addr,
+    pub approvals: Vec<Approval>,
+    pub token_uri: Option<String>,
+    pub extension: T,
+    pub artist: Option<Addr>,
+    /// Placeholder URI while the token is sealed
+    pub sealed_uri: Option<String>,
+    pub provenance: Option<ProvenanceDigest>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct ExportTokensResponse<T> {
+    pub tokens: Vec<ExportedToken<T>>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct ExportedOperator {
+    pub owner: Addr,
+    pub operator: Addr,
+    pub expires: Expiration,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct ExportOperatorsResponse {
+    pub operators: Vec<ExportedOperator>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct ListingApprovalsResponse {
+    pub approvals: Vec<Approval>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct SalesFeedEntry {
+    pub seq: u64,
+    pub sale: Sale,
+    pub buyer_display_name: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct SalesFeedResponse {
+    pub sales: Vec<SalesFeedEntry>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct RightHolder {
+    pub right: TokenRight,
+    pub holder: Addr,
+    /// False while the right is with the token's owner
+    pub split: bool,
+    pub transferable: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct TokenRightsResponse {
+    pub owner: Addr,
+    pub rights: Vec<RightHolder>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct StorageFeeResponse {
+    pub schedule: Option<StorageFee>,
+    /// Tokens currently in vault custody
+    pub custodied: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct StorageAccountResponse {
+    /// `None` if the token is not in vault custody
+    pub account: Option<StorageAccount>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct ArtistKeyResponse {
+    pub version: u32,
+    pub key: ArtistKey,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct LazyMintNonceResponse {
+    pub used: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct ArtistKeysResponse {
+    pub keys: Vec<ArtistKeyResponse>,
+    /// Version of the current key, `None` if the artist has not registered one
+    pub current: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct CertificateResponse {
+    pub certificate: Certificate,
+    pub key: ArtistKey,
+    /// The artist's current key version. Keys between `key_version` and it
+    /// each carry a continuity proof from the one before
+    pub current_version: u32,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct EventResponse {
+    pub event_id: u64,
+    pub event: ExhibitionEvent,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct EventWorkResponse {
+    pub collection: Addr,
+    pub token_id: String,
+    pub work: EventWork,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct EventWorksResponse {
+    pub works: Vec<EventWorkResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct RangeApprovalResponse {
+    pub first: u64,
+    pub last: u64,
+    pub expires: Expiration,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct RangeApprovalsResponse {
+    pub ranges: Vec<RangeApprovalResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct PriceFloorResponse {
+    pub floor: Option<PriceFloor>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct TreasuryEntry {
+    pub account: TreasuryAccount,
+    pub denom: String,
+    pub amount: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct DenomReconciliation {
+    pub denom: String,
+    /// Bank balance of the contract
+    pub held: Uint128,
+    /// Sum of the subsystem balances
+    pub accounted: Uint128,
+    /// Held but attributed to no subsystem
+    pub unaccounted: Uint128,
+    /// Attributed but not held
+    pub shortfall: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct TreasuryReconciliationResponse {
+    pub accounts: Vec<TreasuryEntry>,
+    pub denoms: Vec<DenomReconciliation>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct IbcChannelsResponse {
+    pub channels: Vec<IbcChannel>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct IbcTokenResponse {
+    pub token_id: String,
+    /// Set while this collection's token is on another chain, or while any
+    /// token is in flight
+    pub escrow: Option<IbcEscrow>,
+    /// Set if the token stands for one received from another chain
+    pub voucher: Option<IbcVoucher>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct TokenLinkResponse {
+    pub contract: Addr,
+    pub linked_token_id: String,
+    pub link: TokenLink,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct TokenLinksResponse {
+    pub token_id: String,
+    pub links: Vec<TokenLinkResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct PinResponse {
+    pub token_id: String,
+    pub provider: String,
+    pub pin: PinRecord,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct PinsResponse {
+    pub pins: Vec<PinResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct RedactionsResponse {
+    pub token_id: String,
+    pub fields: Vec<RedactedField>,
+    /// sha256 of the hidden provenance notes, so they can be verified once
+    /// disclosed
+    pub provenance_notes_hash: Option<HexBinary>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct ArtworkInfoResponse {
+    pub token_id: String,
+    pub artwork: Option<ArtworkExtension>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct CollectedFunds {
+    pub denom: String,
+    /// Proceeds in the treasury ledger
+    pub proceeds: Uint128,
+    /// Held and owed to no other subsystem
+    pub withdrawable: Uint128,
+    pub withdrawn: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct WithdrawalRecipient {
+    pub address: String,
+    /// Time from which Withdraw may pay the recipient
+    pub active_from: Timestamp,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct WithdrawalRecipientsResponse {
+    /// Whether Withdraw is limited to the recipients
+    pub guarded: bool,
+    pub recipients: Vec<WithdrawalRecipient>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct WithdrawalCapResponse {
+    pub cap: Option<WithdrawalCap>,
+    /// Withdrawn in the last 24 hours
+    pub withdrawn: Uint128,
+    /// What can still be withdrawn under the cap, `None` when uncapped
+    pub allowance: Option<Uint128>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct PermissionSweepResponse {
+    pub sweep: Option<PermissionSweep>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct CollectedFundsResponse {
+    pub funds: Vec<CollectedFunds>,
+}
@@ -2,7 +2,8 @@ use serde::de::DeserializeOwned;
 use serde::Serialize;
 
 use cosmwasm_std::{
-    to_json_binary, Addr, Binary, BlockInfo, Deps, Env, Order, StdError, StdResult,
+    to_json_binary, Addr, Binary, BlockInfo, Coin, Deps, Env, HexBinary, Order, StdError,
+    StdResult, Timestamp, Uint128,
 };
 
 use cw721::{
@@ -12,30 +13,78 @@ use cw721::{
 };
 use cw_storage_plus::Bound;
 use cw_utils::maybe_addr;
+use sha2::{Digest, Sha256};
 
-use crate::msg::{MinterResponse, NftDetailsResponse, QueryMsg};
-use crate::state::{Approval, Cw721Contract, TokenInfo};
+use crate::msg::{
+    AccountJurisdictionResponse, AllSeriesResponse, AllowlistConfigResponse, ArtistKeyResponse,
+    ArtistKeysResponse, ArtistStatsResponse, ArtworkInfoResponse, AttestationResponse,
+    AttestationsResponse, AttestorResponse, AttestorsResponse, AuctionResponse, AuctionsResponse,
+    AutoBuyerResponse, BidHistoryEntry, BidHistoryResponse, BlindDropResponse, BuyBackResponse,
+    CertificateResponse, CheckRoyaltiesResponse, CollectedFunds, CollectedFundsResponse,
+    CollectionBidResponse, CollectionBidsResponse, ConditionalTransferResponse,
+    CurationFeeResponse, CuratorEarningsResponse, CuratorResponse, CurrentMintPriceResponse,
+    DenomReconciliation, DeploymentProfileResponse, DropAcquisitionsResponse, DropLimitResponse,
+    DueBeforeResponse, DueEntry, EventResponse, EventSchemaResponse, EventSubsystem,
+    EventSubsystemInfo, EventWorkResponse, EventWorksResponse, ExhibitionResponse,
+    ExhibitionsResponse, ExportOperatorsResponse, ExportStatusResponse, ExportTokensResponse,
+    ExportedOperator, ExportedToken, FeaturedEntry, FeaturedResponse, FirstRefusalResponse,
+    FrozenStatusResponse, GiftResponse, HasRoleResponse, HoldingScoreResponse, IbcChannelsResponse,
+    IbcTokenResponse, InboxResponse, InstallmentPlanResponse, IsAllowlistedResponse,
+    KeeperTreasuryResponse, LazyMintNonceResponse, LenderResponse, ListingApprovalsResponse,
+    ListingResponse, ListingsResponse, MarketplaceFeeResponse, MetadataResolverResponse,
+    MetadataVariantsResponse, MintedByAddressResponse, MinterResponse, MysteryPoolResponse,
+    NameResolverResponse, NftDetailsResponse, OfferResponse, OffersResponse, OperatorGrant,
+    OperatorGrantsResponse, OperatorRevocationResponse, OperatorRevocationsResponse,
+    PartnerGalleriesResponse, PausedResponse, PendingMinterResponse, PermissionSweepResponse,
+    PinResponse, PinsResponse, PresaleCommitmentResponse, PresaleResponse, PriceFloorResponse,
+    PrivateSaleResponse, PrivateSalesResponse, ProfileResponse, ProvenanceCertificateResponse,
+    ProvenanceLogEntry, ProvenanceResponse, QueryMsg, RangeApprovalResponse,
+    RangeApprovalsResponse, RedactedField, RedactionsResponse, ReferralResponse, RefundResponse,
+    ReservationResponse, ReservationsResponse, ResolveAddressResponse, RevealStatusResponse,
+    RightHolder, Role, RoyaltiesInfoResponse, RoyaltyPayee, RoyaltyPayoutResponse,
+    RoyaltySplitResponse, RoyaltyWaiverResponse, RoyaltyWaiversResponse, SaleReceiptResponse,
+    SalesFeedEntry, SalesFeedResponse, SealedBidResponse, SendAllowlistResponse, SeriesResponse,
+    SimulateMintResponse, SizeLimitsResponse, StorageAccountResponse, StorageFeeResponse,
+    TokenAliasResponse, TokenByContentResponse, TokenLinkResponse, TokenLinksResponse,
+    TokenNftInfoResponse, TokenRight, TokenRightsResponse, TombstoneResponse, TradeInResponse,
+    TradeInsResponse, TreasuryAccount, TreasuryReconciliationResponse, VaultCollectionsResponse,
+    VaultDepositResponse, VenueResponse, VoucherIssuanceResponse, VoucherRedemptionsResponse,
+    VoucherResponse, WithdrawalCapResponse, WithdrawalRecipient, WithdrawalRecipientsResponse,
+    WithholdingRuleResponse, EVENT_SCHEMA_VERSION, EXPORT_SCHEMA_VERSION,
+};
+use crate::state::{
+    content_keys, token_key, Approval, Cw721Contract, Reservation, Series, TokenInfo,
+    WITHDRAWAL_WINDOW,
+};
 
 const DEFAULT_LIMIT: u32 = 10;
 const MAX_LIMIT: u32 = 30;
 
+const DEFAULT_EXPORT_LIMIT: u32 = 30;
+const MAX_EXPORT_LIMIT: u32 = 100;
+
 impl<'a, T, C> Cw721Query<T> for Cw721Contract<'a, T, C>
 where
     T: Serialize + DeserializeOwned + Clone,
     C: CustomMsg,
 {
     fn contract_info(&self, deps: Deps) -> StdResult<ContractInfoResponse> {
-        // TODO: Load contract info from storage and return it
+        self.contract_info.load(deps.storage)
     }
 
     fn num_tokens(&self, deps: Deps) -> StdResult<NumTokensResponse> {
-        // TODO: Get the token count from storage
-        // TODO: Return the count wrapped in a NumTokensResponse
+        let count = self.token_count(deps.storage)?;
+        Ok(NumTokensResponse { count })
     }
 
     fn nft_info(&self, deps: Deps, token_id: String) -> StdResult<NftInfoResponse<T>> {
-        // TODO: Load the token info from storage
-        // TODO: Return the token URI and extension data in an NftInfoResponse
+        let info = self.tokens.load(deps.storage, token_key(&token_id)?)?;
+        let extension =
+            self.resolved_extension(deps, &token_id, &info.token_uri, info.extension)?;
+        Ok(NftInfoResponse {
+            token_uri: info.token_uri,
+            extension: self.public_extension(deps.storage, &token_id, extension)?,
+        })
     }
 
     fn owner_of(
@@ -45,7 +94,7 @@ where
         token_id: String,
         include_expired: bool,
     ) -> StdResult<OwnerOfResponse> {
-        let info = self.tokens.load(deps.storage, &token_id)?;
+        let info = self.tokens.load(deps.storage, token_key(&token_id)?)?;
         Ok(OwnerOfResponse {
             owner: info.owner.to_string(),
             approvals: humanize_approvals(&env.block, &info, include_expired),
@@ -65,7 +114,7 @@ where
         let start_addr = maybe_addr(deps.api, start_after)?;
         let start = start_addr.as_ref().map(Bound::exclusive);
 
-        let owner_addr = deps.api.addr_validate(&owner)?;
+        let owner_addr = self.resolve_addr(deps, &owner)?;
         let res: StdResult<Vec<_>> = self
             .operators
             .prefix(&owner_addr)
@@ -87,7 +136,7 @@ where
         spender: String,
         include_expired: bool,
     ) -> StdResult<ApprovalResponse> {
-        let token = self.tokens.load(deps.storage, &token_id)?;
+        let token = self.tokens.load(deps.storage, token_key(&token_id)?)?;
 
         if token.owner == spender {
             let approval = cw721::Approval {
@@ -123,7 +172,7 @@ where
         token_id: String,
         include_expired: bool,
     ) -> StdResult<ApprovalsResponse> {
-        let token = self.tokens.load(deps.storage, &token_id)?;
+        let token = self.tokens.load(deps.storage, token_key(&token_id)?)?;
         let approvals: Vec<_> = token
             .approvals
             .into_iter()
@@ -145,9 +194,12 @@ where
         limit: Option<u32>,
     ) -> StdResult<TokensResponse> {
         let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
-        let start = start_after.map(|s| Bound::ExclusiveRaw(s.into()));
+        let start = start_after
+            .map(|s| token_key(&s))
+            .transpose()?
+            .map(Bound::exclusive);
 
-        let owner_addr = deps.api.addr_validate(&owner)?;
+        let owner_addr = self.resolve_addr(deps, &owner)?;
         let tokens: Vec<String> = self
             .tokens
             .idx
@@ -155,7 +207,7 @@ where
             .prefix(owner_addr)
             .keys(deps.storage, start, None, Order::Ascending)
             .take(limit)
-            .map(|x| x.map(|addr| addr.to_string()))
+            .map(|x| x.map(|id| id.to_string()))
             .collect::<StdResult<Vec<_>>>()?;
 
         Ok(TokensResponse { tokens })
@@ -168,13 +220,16 @@ where
         limit: Option<u32>,
     ) -> StdResult<TokensResponse> {
         let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
-        let start = start_after.map(|s| Bound::ExclusiveRaw(s.into()));
+        let start = start_after
+            .map(|s| token_key(&s))
+            .transpose()?
+            .map(Bound::exclusive);
 
         let tokens: StdResult<Vec<String>> = self
             .tokens
-            .range(deps.storage, start, None, Order::Ascending)
+            .keys(deps.storage, start, None, Order::Ascending)
             .take(limit)
-            .map(|item| item.map(|(k, _)| k))
+            .map(|item| item.map(|k| k.to_string()))
             .collect();
 
         Ok(TokensResponse { tokens: tokens? })
@@ -187,15 +242,23 @@ where
         token_id: String,
         include_expired: bool,
     ) -> StdResult<AllNftInfoResponse<T>> {
-        let info = self.tokens.load(deps.storage, &token_id)?;
+        let info = self.tokens.load(deps.storage, token_key(&token_id)?)?;
+        let token_uri = self.active_token_uri(
+            deps.storage,
+            &token_id,
+            info.token_uri.clone(),
+            env.block.time,
+        )?;
+        let extension =
+            self.resolved_extension(deps, &token_id, &token_uri, info.extension.clone())?;
         Ok(AllNftInfoResponse {
             access: OwnerOfResponse {
                 owner: info.owner.to_string(),
                 approvals: humanize_approvals(&env.block, &info, include_expired),
             },
             info: NftInfoResponse {
-                token_uri: info.token_uri,
-                extension: info.extension,
+                token_uri,
+                extension: self.public_extension(deps.storage, &token_id, extension)?,
             },
         })
     }
@@ -213,94 +276,2456 @@ where
         })
     }
 
-    pub fn nft_details(&self, deps: Deps) -> StdResult<NftDetailsResponse> {
-        // TODO: Load mint price, max mints, and token URI from storage
-        // TODO: Return these details in an NftDetailsResponse
+    pub fn holds_role(
+        &self,
+        deps: Deps,
+        address: String,
+        role: Role,
+    ) -> StdResult<HasRoleResponse> {
+        let addr = self.resolve_addr(deps, &address)?;
+        Ok(HasRoleResponse {
+            has_role: self.has_role(deps.storage, &addr, role)?,
+        })
     }
 
-    pub fn query(&self, deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
-        match msg {
-            QueryMsg::ContractInfo {} => to_json_binary(&self.contract_info(deps)?),
-            QueryMsg::NftInfo { token_id } => to_json_binary(&self.nft_info(deps, token_id)?),
+    pub fn pending_minter(&self, deps: Deps) -> StdResult<PendingMinterResponse> {
+        Ok(PendingMinterResponse {
+            pending_minter: self
+                .pending_minter
+                .may_load(deps.storage)?
+                .map(Addr::into_string),
+        })
+    }
 
-            // TODO: Add cases for different query types
-            // Hint: Think about the four queries we mentioned
-            QueryMsg::OwnerOf {
-                token_id,
-                include_expired,
-            } => to_json_binary(&self.owner_of(
-                deps,
-                env,
-                token_id,
-                include_expired.unwrap_or(false),
-            )?),
-            QueryMsg::AllOperators {
-                owner,
-                include_expired,
-                start_after,
-                limit,
-            } => to_json_binary(&self.operators(
-                deps,
-                env,
-                owner,
-                include_expired.unwrap_or(false),
-                start_after,
-                limit,
-            )?),
-            QueryMsg::Tokens {
-                owner,
-                start_after,
-                limit,
-            } => to_json_binary(&self.tokens(deps, owner, start_after, limit)?),
-            QueryMsg::AllTokens { start_after, limit } => {
-                to_json_binary(&self.all_tokens(deps, start_after, limit)?)
+    pub fn nft_details(&self, deps: Deps) -> StdResult<NftDetailsResponse> {
+        Ok(NftDetailsResponse {
+            mint_price: self.mint_price.load(deps.storage)?,
+            max_mints: self.max_mints.load(deps.storage)?,
+            max_mints_per_address: self.max_mints_per_address.may_load(deps.storage)?,
+            token_uri: self.token_uri.load(deps.storage)?,
+        })
+    }
+
+    pub fn token_by_content(
+        &self,
+        deps: Deps,
+        token_uri: Option<String>,
+        content_hash: Option<HexBinary>,
+    ) -> StdResult<TokenByContentResponse> {
+        let mut token_id = None;
+        for key in content_keys(&token_uri, &content_hash) {
+            token_id = self.content_index.may_load(deps.storage, &key)?;
+            if token_id.is_some() {
+                break;
             }
-            QueryMsg::Approval {
-                token_id,
-                spender,
-                include_expired,
-            } => to_json_binary(&self.approval(
-                deps,
-                env,
-                token_id,
-                spender,
-                include_expired.unwrap_or(false),
-            )?),
-            QueryMsg::Approvals {
-                token_id,
-                include_expired,
-            } => to_json_binary(&self.approvals(
-                deps,
-                env,
-                token_id,
-                include_expired.unwrap_or(false),
-            )?),
         }
+        Ok(TokenByContentResponse { token_id })
     }
-}
 
-fn parse_approval(item: StdResult<(Addr, Expiration)>) -> StdResult<cw721::Approval> {
-    item.map(|(spender, expires)| cw721::Approval {
-        spender: spender.to_string(),
-        expires,
-    })
-}
+    pub fn paused(&self, deps: Deps) -> StdResult<PausedResponse> {
+        let since = self.paused.may_load(deps.storage)?;
+        Ok(PausedResponse {
+            paused: since.is_some(),
+            since,
+        })
+    }
 
-fn humanize_approvals<T>(
-    block: &BlockInfo,
-    info: &TokenInfo<T>,
-    include_expired: bool,
-) -> Vec<cw721::Approval> {
-    info.approvals
-        .iter()
-        .filter(|apr| include_expired || !apr.is_expired(block))
-        .map(humanize_approval)
-        .collect()
-}
+    pub fn deployment_profile(&self, deps: Deps) -> StdResult<DeploymentProfileResponse> {
+        Ok(DeploymentProfileResponse {
+            profile: self
+                .deployment_profile
+                .may_load(deps.storage)?
+                .unwrap_or_default(),
+        })
+    }
 
-fn humanize_approval(approval: &Approval) -> cw721::Approval {
-    cw721::Approval {
-        spender: approval.spender.to_string(),
-        expires: approval.expires,
+    pub fn drop_limit(&self, deps: Deps) -> StdResult<DropLimitResponse> {
+        Ok(DropLimitResponse {
+            limit: self.drop_limit.may_load(deps.storage)?,
+        })
+    }
+
+    pub fn drop_acquisitions(
+        &self,
+        deps: Deps,
+        address: String,
+    ) -> StdResult<DropAcquisitionsResponse> {
+        let addr = self.resolve_addr(deps, &address)?;
+        let (drop_id, acquired, remaining) = match self.drop_acquired(deps.storage, &addr)? {
+            Some((drop, acquired)) => (
+                Some(drop.drop_id),
+                acquired,
+                Some(drop.max_per_address.saturating_sub(acquired)),
+            ),
+            None => (None, 0, None),
+        };
+        Ok(DropAcquisitionsResponse {
+            address: addr.into_string(),
+            drop_id,
+            acquired,
+            remaining,
+        })
+    }
+
+    pub fn minted_by_address(
+        &self,
+        deps: Deps,
+        address: String,
+    ) -> StdResult<MintedByAddressResponse> {
+        let addr = self.resolve_addr(deps, &address)?;
+        let minted = self
+            .mints_per_address
+            .may_load(deps.storage, &addr)?
+            .unwrap_or_default();
+        let remaining = self
+            .max_mints_per_address
+            .may_load(deps.storage)?
+            .map(|limit| limit.saturating_sub(minted));
+        Ok(MintedByAddressResponse {
+            address: addr.into_string(),
+            minted,
+            remaining,
+        })
+    }
+
+    pub fn profile(&self, deps: Deps, address: String) -> StdResult<ProfileResponse> {
+        let addr = self.resolve_addr(deps, &address)?;
+        let profile = self
+            .profiles
+            .may_load(deps.storage, &addr)?
+            .filter(|p| !p.suspended);
+        Ok(ProfileResponse {
+            address,
+            display_name: profile.as_ref().map(|p| p.display_name.clone()),
+            profile_uri: profile.and_then(|p| p.profile_uri),
+        })
+    }
+
+    /// display name for an address, used to decorate history queries
+    pub fn display_name(&self, deps: Deps, addr: &Addr) -> StdResult<Option<String>> {
+        Ok(self
+            .profiles
+            .may_load(deps.storage, addr)?
+            .filter(|p| !p.suspended)
+            .map(|p| p.display_name))
+    }
+
+    pub fn send_allowlist(
+        &self,
+        deps: Deps,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<SendAllowlistResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start_addr = maybe_addr(deps.api, start_after)?;
+        let start = start_addr.as_ref().map(Bound::exclusive);
+
+        let contracts = self
+            .send_allowlist
+            .keys(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|k| k.map(String::from))
+            .collect::<StdResult<Vec<_>>>()?;
+
+        Ok(SendAllowlistResponse {
+            enabled: self
+                .send_allowlist_enabled
+                .may_load(deps.storage)?
+                .unwrap_or(false),
+            contracts,
+        })
+    }
+
+    pub fn installment_plan(&self, deps: Deps) -> StdResult<InstallmentPlanResponse> {
+        Ok(InstallmentPlanResponse {
+            plan: self.installment_plan.may_load(deps.storage)?,
+        })
+    }
+
+    pub fn reservation(
+        &self,
+        deps: Deps,
+        env: Env,
+        token_id: String,
+    ) -> StdResult<ReservationResponse> {
+        let reservation = self.reservations.load(deps.storage, &token_id)?;
+        Ok(humanize_reservation(&env.block, token_id, reservation))
+    }
+
+    pub fn reservations_by_buyer(
+        &self,
+        deps: Deps,
+        env: Env,
+        buyer: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<ReservationsResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start = start_after.map(|s| Bound::ExclusiveRaw(s.into()));
+
+        let buyer_addr = self.resolve_addr(deps, &buyer)?;
+        let reservations = self
+            .reservations
+            .idx
+            .buyer
+            .prefix(buyer_addr)
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| item.map(|(token_id, r)| humanize_reservation(&env.block, token_id, r)))
+            .collect::<StdResult<Vec<_>>>()?;
+
+        Ok(ReservationsResponse { reservations })
+    }
+
+    pub fn withholding_rule(
+        &self,
+        deps: Deps,
+        jurisdiction: String,
+    ) -> StdResult<WithholdingRuleResponse> {
+        let rule = self
+            .withholding_rules
+            .may_load(deps.storage, &jurisdiction)?;
+        Ok(WithholdingRuleResponse { jurisdiction, rule })
+    }
+
+    pub fn account_jurisdiction(
+        &self,
+        deps: Deps,
+        address: String,
+    ) -> StdResult<AccountJurisdictionResponse> {
+        let addr = self.resolve_addr(deps, &address)?;
+        let jurisdiction = self.account_jurisdictions.may_load(deps.storage, &addr)?;
+        Ok(AccountJurisdictionResponse {
+            address,
+            jurisdiction,
+        })
+    }
+
+    pub fn sale_receipt(&self, deps: Deps, token_id: String) -> StdResult<SaleReceiptResponse> {
+        if self.is_redacted(deps.storage, &token_id, RedactedField::PriorOwners)? {
+            return Err(StdError::generic_err(
+                "Sale history is redacted by the owner",
+            ));
+        }
+        let receipt = self.sale_receipts.load(deps.storage, &token_id)?;
+        Ok(SaleReceiptResponse { token_id, receipt })
+    }
+
+    pub fn tombstone(&self, deps: Deps, token_id: String) -> StdResult<TombstoneResponse> {
+        let tombstone = self.tombstones.load(deps.storage, &token_id)?;
+        Ok(TombstoneResponse {
+            token_id,
+            tombstone,
+        })
+    }
+
+    pub fn attestor(&self, deps: Deps, address: String) -> StdResult<AttestorResponse> {
+        let addr = self.resolve_addr(deps, &address)?;
+        let attestor = self.attestors.load(deps.storage, &addr)?;
+        Ok(AttestorResponse { address, attestor })
+    }
+
+    pub fn attestors(
+        &self,
+        deps: Deps,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<AttestorsResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start_addr = maybe_addr(deps.api, start_after)?;
+        let start = start_addr.as_ref().map(Bound::exclusive);
+
+        let attestors = self
+            .attestors
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| {
+                item.map(|(addr, attestor)| AttestorResponse {
+                    address: addr.into_string(),
+                    attestor,
+                })
+            })
+            .collect::<StdResult<Vec<_>>>()?;
+
+        Ok(AttestorsResponse { attestors })
+    }
+
+    pub fn attestations(
+        &self,
+        deps: Deps,
+        token_id: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<AttestationsResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start = start_after.map(Bound::exclusive);
+
+        let attestations = self
+            .attestations
+            .prefix(&token_id)
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| item.map(|(index, attestation)| AttestationResponse { index, attestation }))
+            .collect::<StdResult<Vec<_>>>()?;
+
+        Ok(AttestationsResponse { attestations })
+    }
+
+    pub fn venue(&self, deps: Deps, address: String) -> StdResult<VenueResponse> {
+        let addr = self.resolve_addr(deps, &address)?;
+        let venue = self.venues.load(deps.storage, &addr)?;
+        Ok(VenueResponse { address, venue })
+    }
+
+    pub fn exhibitions(
+        &self,
+        deps: Deps,
+        token_id: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<ExhibitionsResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start = start_after.map(Bound::exclusive);
+        if self.is_redacted(deps.storage, &token_id, RedactedField::Exhibitions)? {
+            return Ok(ExhibitionsResponse {
+                exhibitions: vec![],
+            });
+        }
+
+        let exhibitions = self
+            .exhibitions
+            .prefix(&token_id)
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| item.map(|(index, exhibition)| ExhibitionResponse { index, exhibition }))
+            .collect::<StdResult<Vec<_>>>()?;
+
+        Ok(ExhibitionsResponse { exhibitions })
+    }
+
+    pub fn event(&self, deps: Deps, event_id: u64) -> StdResult<EventResponse> {
+        let event = self.events.load(deps.storage, event_id)?;
+        Ok(EventResponse { event_id, event })
+    }
+
+    pub fn event_works(
+        &self,
+        deps: Deps,
+        event_id: u64,
+        start_after: Option<(String, String)>,
+        limit: Option<u32>,
+    ) -> StdResult<EventWorksResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        // only used as a range bound, so it needs no validation
+        let start_after =
+            start_after.map(|(collection, token_id)| (Addr::unchecked(collection), token_id));
+        let start = start_after
+            .as_ref()
+            .map(|(collection, token_id)| Bound::exclusive((collection, token_id.as_str())));
+
+        let works = self
+            .event_works
+            .sub_prefix(event_id)
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| {
+                item.map(|((collection, token_id), work)| EventWorkResponse {
+                    collection,
+                    token_id,
+                    work,
+                })
+            })
+            .collect::<StdResult<Vec<_>>>()?;
+
+        Ok(EventWorksResponse { works })
+    }
+
+    pub fn ibc_channels(&self, deps: Deps) -> StdResult<IbcChannelsResponse> {
+        let channels = self
+            .ibc_channels
+            .range(deps.storage, None, None, Order::Ascending)
+            .map(|item| item.map(|(_, channel)| channel))
+            .collect::<StdResult<Vec<_>>>()?;
+        Ok(IbcChannelsResponse { channels })
+    }
+
+    pub fn ibc_token(&self, deps: Deps, token_id: String) -> StdResult<IbcTokenResponse> {
+        Ok(IbcTokenResponse {
+            escrow: self.ibc_escrows.may_load(deps.storage, &token_id)?,
+            voucher: self.ibc_vouchers.may_load(deps.storage, &token_id)?,
+            token_id,
+        })
+    }
+
+    pub fn token_links(
+        &self,
+        deps: Deps,
+        token_id: String,
+        start_after: Option<(String, String)>,
+        limit: Option<u32>,
+    ) -> StdResult<TokenLinksResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        // only used as a range bound, so it needs no validation
+        let start_after =
+            start_after.map(|(contract, token_id)| (Addr::unchecked(contract), token_id));
+        let start = start_after
+            .as_ref()
+            .map(|(contract, token_id)| Bound::exclusive((contract, token_id.as_str())));
+
+        let links = self
+            .token_links
+            .sub_prefix(&token_id)
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| {
+                item.map(|((contract, linked_token_id), link)| TokenLinkResponse {
+                    contract,
+                    linked_token_id,
+                    link,
+                })
+            })
+            .collect::<StdResult<Vec<_>>>()?;
+
+        Ok(TokenLinksResponse { token_id, links })
+    }
+
+    pub fn pins(
+        &self,
+        deps: Deps,
+        token_id: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<PinsResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start = start_after.as_deref().map(Bound::exclusive);
+
+        let pins = self
+            .pins
+            .prefix(&token_id)
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| {
+                item.map(|(provider, pin)| PinResponse {
+                    token_id: token_id.clone(),
+                    provider,
+                    pin,
+                })
+            })
+            .collect::<StdResult<Vec<_>>>()?;
+
+        Ok(PinsResponse { pins })
+    }
+
+    pub fn expiring_pins(
+        &self,
+        deps: Deps,
+        env: &Env,
+        within: u64,
+        start_after: Option<(u64, String, String)>,
+        limit: Option<u32>,
+    ) -> StdResult<PinsResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let cutoff = env.block.time.plus_seconds(within).seconds();
+        let start = start_after.as_ref().map(|(expiry, token_id, provider)| {
+            Bound::exclusive((*expiry, token_id.as_str(), provider.as_str()))
+        });
+
+        let pins = self
+            .pin_expiries
+            .keys(deps.storage, start, None, Order::Ascending)
+            .take_while(|key| {
+                key.as_ref()
+                    .map_or(true, |(expiry, _, _)| *expiry <= cutoff)
+            })
+            .take(limit)
+            .map(|key| {
+                let (_, token_id, provider) = key?;
+                let pin = self.pins.load(deps.storage, (&token_id, &provider))?;
+                Ok(PinResponse {
+                    token_id,
+                    provider,
+                    pin,
+                })
+            })
+            .collect::<StdResult<Vec<_>>>()?;
+
+        Ok(PinsResponse { pins })
+    }
+
+    pub fn inbox(&self, deps: Deps, env: Env, address: String) -> StdResult<InboxResponse> {
+        let addr = self.resolve_addr(deps, &address)?;
+
+        let mut installments_due = self
+            .reservations
+            .idx
+            .buyer
+            .prefix(addr.clone())
+            .range(deps.storage, None, None, Order::Ascending)
+            .take(MAX_LIMIT as usize)
+            .map(|item| item.map(|(token_id, r)| humanize_reservation(&env.block, token_id, r)))
+            .collect::<StdResult<Vec<_>>>()?;
+        installments_due.sort_by_key(|r| r.next_due);
+
+        let gifts_to_claim = self
+            .gifts
+            .idx
+            .recipient
+            .prefix(addr.clone())
+            .range(deps.storage, None, None, Order::Ascending)
+            .take(MAX_LIMIT as usize)
+            .map(|item| item.map(|(token_id, gift)| GiftResponse { token_id, gift }))
+            .collect::<StdResult<Vec<_>>>()?;
+
+        let mut offers_received = vec![];
+        let owned =
+            self.tokens
+                .idx
+                .owner
+                .prefix(addr)
+                .keys(deps.storage, None, None, Order::Ascending);
+        for token_id in owned {
+            let token_id = token_id?.to_string();
+            let offers = self
+                .offers
+                .prefix(&token_id)
+                .range(deps.storage, None, None, Order::Ascending)
+                .take(MAX_LIMIT as usize - offers_received.len());
+            for item in offers {
+                let (bidder, offer) = item?;
+                offers_received.push(OfferResponse {
+                    token_id: token_id.clone(),
+                    bidder,
+                    offer,
+                });
+            }
+            if offers_received.len() >= MAX_LIMIT as usize {
+                break;
+            }
+        }
+
+        Ok(InboxResponse {
+            installments_due,
+            gifts_to_claim,
+            offers_received,
+        })
+    }
+
+    pub fn auction(&self, deps: Deps, auction_id: u64) -> StdResult<AuctionResponse> {
+        let auction = self.auctions.load(deps.storage, auction_id)?;
+        Ok(AuctionResponse {
+            auction_id,
+            auction,
+        })
+    }
+
+    pub fn auctions(
+        &self,
+        deps: Deps,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<AuctionsResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start = start_after.map(Bound::exclusive);
+
+        let auctions = self
+            .auctions
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| {
+                item.map(|(auction_id, auction)| AuctionResponse {
+                    auction_id,
+                    auction,
+                })
+            })
+            .collect::<StdResult<Vec<_>>>()?;
+
+        Ok(AuctionsResponse { auctions })
+    }
+
+    pub fn collection_bid(&self, deps: Deps, bid_id: u64) -> StdResult<CollectionBidResponse> {
+        let bid = self.collection_bids.load(deps.storage, bid_id)?;
+        Ok(CollectionBidResponse { bid_id, bid })
+    }
+
+    pub fn collection_bids(
+        &self,
+        deps: Deps,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<CollectionBidsResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start = start_after.map(Bound::exclusive);
+
+        let bids = self
+            .collection_bids
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| item.map(|(bid_id, bid)| CollectionBidResponse { bid_id, bid }))
+            .collect::<StdResult<Vec<_>>>()?;
+
+        Ok(CollectionBidsResponse { bids })
+    }
+
+    pub fn listing(&self, deps: Deps, token_id: String) -> StdResult<ListingResponse> {
+        let listing = self.listings.load(deps.storage, &token_id)?;
+        Ok(ListingResponse { token_id, listing })
+    }
+
+    pub fn all_listings(
+        &self,
+        deps: Deps,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<ListingsResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start = start_after.as_deref().map(Bound::exclusive);
+
+        let listings = self
+            .listings
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| item.map(|(token_id, listing)| ListingResponse { token_id, listing }))
+            .collect::<StdResult<Vec<_>>>()?;
+
+        Ok(ListingsResponse { listings })
+    }
+
+    pub fn offers_by_token(
+        &self,
+        deps: Deps,
+        token_id: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<OffersResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        // only used as a range bound, so it needs no validation
+        let start_after = start_after.map(Addr::unchecked);
+        let start = start_after.as_ref().map(Bound::exclusive);
+
+        let offers = self
+            .offers
+            .prefix(&token_id)
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| {
+                item.map(|(bidder, offer)| OfferResponse {
+                    token_id: token_id.clone(),
+                    bidder,
+                    offer,
+                })
+            })
+            .collect::<StdResult<Vec<_>>>()?;
+
+        Ok(OffersResponse { offers })
+    }
+
+    pub fn offers_by_bidder(
+        &self,
+        deps: Deps,
+        bidder: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<OffersResponse> {
+        let bidder = self.resolve_addr(deps, &bidder)?;
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start = start_after.as_deref().map(Bound::exclusive);
+
+        let offers = self
+            .bidder_offers
+            .prefix(&bidder)
+            .keys(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|token_id| {
+                let token_id = token_id?;
+                let offer = self.offers.load(deps.storage, (&token_id, &bidder))?;
+                Ok(OfferResponse {
+                    token_id,
+                    bidder: bidder.clone(),
+                    offer,
+                })
+            })
+            .collect::<StdResult<Vec<_>>>()?;
+
+        Ok(OffersResponse { offers })
+    }
+
+    pub fn resolve_alias(&self, deps: Deps, alias: String) -> StdResult<TokenAliasResponse> {
+        let token_id = self.token_aliases.load(deps.storage, &alias)?;
+        Ok(TokenAliasResponse { alias, token_id })
+    }
+
+    pub fn frozen_status(&self, deps: Deps, token_id: String) -> StdResult<FrozenStatusResponse> {
+        Ok(FrozenStatusResponse {
+            frozen: self.frozen_tokens.may_load(deps.storage, &token_id)?,
+        })
+    }
+
+    pub fn auto_buyer(&self, deps: Deps, collector: String) -> StdResult<AutoBuyerResponse> {
+        let collector = self.resolve_addr(deps, &collector)?;
+        Ok(AutoBuyerResponse {
+            auto_buyer: self.auto_buyers.may_load(deps.storage, &collector)?,
+        })
+    }
+
+    pub fn first_refusal(&self, deps: Deps, token_id: String) -> StdResult<FirstRefusalResponse> {
+        let window = match self.token_artists.may_load(deps.storage, &token_id)? {
+            Some(artist) => self.first_refusal_windows.may_load(deps.storage, &artist)?,
+            None => None,
+        };
+        let pending = self.first_refusals.may_load(deps.storage, &token_id)?;
+        Ok(FirstRefusalResponse {
+            token_id,
+            window,
+            pending,
+        })
+    }
+
+    pub fn trade_in(&self, deps: Deps, token_id: String) -> StdResult<TradeInResponse> {
+        let trade_in = self.trade_ins.load(deps.storage, &token_id)?;
+        Ok(TradeInResponse { token_id, trade_in })
+    }
+
+    pub fn trade_ins(
+        &self,
+        deps: Deps,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<TradeInsResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start = start_after.as_deref().map(Bound::exclusive);
+
+        let trade_ins = self
+            .trade_ins
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| item.map(|(token_id, trade_in)| TradeInResponse { token_id, trade_in }))
+            .collect::<StdResult<Vec<_>>>()?;
+
+        Ok(TradeInsResponse { trade_ins })
+    }
+
+    pub fn royalty_waiver(&self, deps: Deps, waiver_id: u64) -> StdResult<RoyaltyWaiverResponse> {
+        let waiver = self.royalty_waivers.load(deps.storage, waiver_id)?;
+        Ok(RoyaltyWaiverResponse { waiver_id, waiver })
+    }
+
+    pub fn royalty_waivers(
+        &self,
+        deps: Deps,
+        holder: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<RoyaltyWaiversResponse> {
+        let holder = self.resolve_addr(deps, &holder)?;
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start = start_after.map(Bound::exclusive);
+
+        let waivers = self
+            .holder_waivers
+            .prefix(&holder)
+            .keys(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|waiver_id| {
+                let waiver_id = waiver_id?;
+                let waiver = self.royalty_waivers.load(deps.storage, waiver_id)?;
+                Ok(RoyaltyWaiverResponse { waiver_id, waiver })
+            })
+            .collect::<StdResult<Vec<_>>>()?;
+
+        Ok(RoyaltyWaiversResponse { waivers })
+    }
+
+    pub fn marketplace_fee(&self, deps: Deps) -> StdResult<MarketplaceFeeResponse> {
+        Ok(MarketplaceFeeResponse {
+            fee: self.marketplace_fee.may_load(deps.storage)?,
+        })
+    }
+
+    pub fn lender(&self, deps: Deps) -> StdResult<LenderResponse> {
+        Ok(LenderResponse {
+            lender: self.lender.may_load(deps.storage)?,
+        })
+    }
+
+    pub fn bid_history(
+        &self,
+        deps: Deps,
+        auction_id: u64,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<BidHistoryResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start = start_after.map(Bound::exclusive);
+
+        let bids = self
+            .bid_history
+            .prefix(auction_id)
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| item.map(|(seq, bid)| BidHistoryEntry { seq, bid }))
+            .collect::<StdResult<_>>()?;
+
+        Ok(BidHistoryResponse { auction_id, bids })
+    }
+
+    pub fn sealed_bid(
+        &self,
+        deps: Deps,
+        auction_id: u64,
+        bidder: String,
+    ) -> StdResult<SealedBidResponse> {
+        let bidder_addr = self.resolve_addr(deps, &bidder)?;
+        let bid = self
+            .sealed_bids
+            .load(deps.storage, (auction_id, &bidder_addr))?;
+        Ok(SealedBidResponse {
+            auction_id,
+            bidder,
+            bid,
+        })
+    }
+
+    pub fn operator_grants(
+        &self,
+        deps: Deps,
+        env: Env,
+        operator: String,
+        include_expired: bool,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<OperatorGrantsResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start_addr = maybe_addr(deps.api, start_after)?;
+        let start = start_addr.as_ref().map(Bound::exclusive);
+
+        let operator_addr = self.resolve_addr(deps, &operator)?;
+        let grants = self
+            .operator_grants
+            .prefix(&operator_addr)
+            .range(deps.storage, start, None, Order::Ascending)
+            .filter(|r| {
+                include_expired || r.is_err() || !r.as_ref().unwrap().1.is_expired(&env.block)
+            })
+            .take(limit)
+            .map(|item| {
+                let (owner, expires) = item?;
+                let owner_holds_tokens = self
+                    .tokens
+                    .idx
+                    .owner
+                    .prefix(owner.clone())
+                    .keys_raw(deps.storage, None, None, Order::Ascending)
+                    .next()
+                    .is_some();
+                Ok(OperatorGrant {
+                    owner: owner.into_string(),
+                    expires,
+                    owner_holds_tokens,
+                })
+            })
+            .collect::<StdResult<Vec<_>>>()?;
+
+        Ok(OperatorGrantsResponse { grants })
+    }
+
+    pub fn storage_fee(&self, deps: Deps) -> StdResult<StorageFeeResponse> {
+        Ok(StorageFeeResponse {
+            schedule: self.storage_fee.may_load(deps.storage)?,
+            custodied: self
+                .storage_custody_count
+                .may_load(deps.storage)?
+                .unwrap_or_default(),
+        })
+    }
+
+    pub fn storage_account(
+        &self,
+        deps: Deps,
+        env: Env,
+        token_id: String,
+    ) -> StdResult<StorageAccountResponse> {
+        Ok(StorageAccountResponse {
+            account: self.accrue_storage_fee(deps.storage, env.block.time, &token_id)?,
+        })
+    }
+
+    pub fn token_rights(&self, deps: Deps, token_id: String) -> StdResult<TokenRightsResponse> {
+        let owner = self.tokens.load(deps.storage, token_key(&token_id)?)?.owner;
+        let rights = [TokenRight::Physical, TokenRight::Display]
+            .into_iter()
+            .map(|right| {
+                let holder = match self
+                    .token_rights
+                    .may_load(deps.storage, (&token_id, right.key()))?
+                {
+                    Some(a) => RightHolder {
+                        right,
+                        holder: a.holder,
+                        split: true,
+                        transferable: a.transferable,
+                    },
+                    None => RightHolder {
+                        right,
+                        holder: owner.clone(),
+                        split: false,
+                        transferable: false,
+                    },
+                };
+                Ok(holder)
+            })
+            .collect::<StdResult<Vec<_>>>()?;
+
+        Ok(TokenRightsResponse { owner, rights })
+    }
+
+    pub fn sales_feed(
+        &self,
+        deps: Deps,
+        secondary_only: bool,
+        start_before: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<SalesFeedResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let end = start_before.map(Bound::exclusive);
+
+        let sales = self
+            .sales_feed
+            .range(deps.storage, None, end, Order::Descending)
+            .filter(|r| !secondary_only || r.is_err() || r.as_ref().unwrap().1.secondary)
+            // sales of tokens whose owner redacted them are left out
+            .filter(|r| match r {
+                Ok((_, sale)) => !self
+                    .is_redacted(deps.storage, &sale.token_id, RedactedField::PriorOwners)
+                    .unwrap_or(false),
+                Err(_) => true,
+            })
+            .take(limit)
+            .map(|item| {
+                let (seq, sale) = item?;
+                Ok(SalesFeedEntry {
+                    seq,
+                    buyer_display_name: self.display_name(deps, &sale.receipt.buyer)?,
+                    sale,
+                })
+            })
+            .collect::<StdResult<Vec<_>>>()?;
+
+        Ok(SalesFeedResponse { sales })
+    }
+
+    pub fn listing_approvals(
+        &self,
+        deps: Deps,
+        env: Env,
+        token_id: String,
+        include_expired: bool,
+    ) -> StdResult<ListingApprovalsResponse> {
+        let approvals = self
+            .listing_approvals
+            .prefix(&token_id)
+            .range(deps.storage, None, None, Order::Ascending)
+            .filter(|r| {
+                include_expired
+                    || r.is_err()
+                    || !r.as_ref().unwrap().1.expires.is_expired(&env.block)
+            })
+            .take(MAX_LIMIT as usize)
+            .map(|item| {
+                item.map(|(spender, approval)| Approval {
+                    spender,
+                    expires: approval.expires,
+                })
+            })
+            .collect::<StdResult<Vec<_>>>()?;
+
+        Ok(ListingApprovalsResponse { approvals })
+    }
+
+    pub fn export_status(&self, deps: Deps) -> StdResult<ExportStatusResponse> {
+        let token_count = self.token_count(deps.storage)?;
+        Ok(ExportStatusResponse {
+            export: self.export_mode.may_load(deps.storage)?,
+            schema_version: EXPORT_SCHEMA_VERSION,
+            token_count,
+            minted: self
+                .mint_count
+                .may_load(deps.storage)?
+                .unwrap_or(token_count),
+        })
+    }
+
+    pub fn event_schema(&self) -> EventSchemaResponse {
+        EventSchemaResponse {
+            version: EVENT_SCHEMA_VERSION,
+            subsystems: EventSubsystem::ALL
+                .iter()
+                .map(|subsystem| EventSubsystemInfo {
+                    subsystem: *subsystem,
+                    prefix: subsystem.prefix().to_string(),
+                })
+                .collect(),
+        }
+    }
+
+    fn assert_exporting(&self, deps: Deps) -> StdResult<()> {
+        if self.export_mode.may_load(deps.storage)?.is_none() {
+            return Err(StdError::generic_err("Contract is not being exported"));
+        }
+        Ok(())
+    }
+
+    pub fn export_tokens(
+        &self,
+        deps: Deps,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<ExportTokensResponse<T>> {
+        self.assert_exporting(deps)?;
+        let limit = limit.unwrap_or(DEFAULT_EXPORT_LIMIT).min(MAX_EXPORT_LIMIT) as usize;
+        let start = start_after
+            .map(|s| token_key(&s))
+            .transpose()?
+            .map(Bound::exclusive);
+
+        let tokens = self
+            .tokens
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| {
+                let (key, token) = item?;
+                let token_id = key.to_string();
+                Ok(ExportedToken {
+                    artist: self.token_artists.may_load(deps.storage, &token_id)?,
+                    sealed_uri: self.sealed_tokens.may_load(deps.storage, &token_id)?,
+                    provenance: self.provenance_digests.may_load(deps.storage, &token_id)?,
+                    token_id,
+                    owner: token.owner,
+                    approvals: token.approvals,
+                    token_uri: token.token_uri,
+                    extension: token.extension,
+                })
+            })
+            .collect::<StdResult<Vec<_>>>()?;
+
+        Ok(ExportTokensResponse { tokens })
+    }
+
+    pub fn export_operators(
+        &self,
+        deps: Deps,
+        start_after: Option<(String, String)>,
+        limit: Option<u32>,
+    ) -> StdResult<ExportOperatorsResponse> {
+        self.assert_exporting(deps)?;
+        let limit = limit.unwrap_or(DEFAULT_EXPORT_LIMIT).min(MAX_EXPORT_LIMIT) as usize;
+        let start_after = start_after
+            .map(|(owner, operator)| -> StdResult<_> {
+                Ok((
+                    deps.api.addr_validate(&owner)?,
+                    deps.api.addr_validate(&operator)?,
+                ))
+            })
+            .transpose()?;
+        let start = start_after
+            .as_ref()
+            .map(|(owner, operator)| Bound::exclusive((owner, operator)));
+
+        let operators = self
+            .operators
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| {
+                item.map(|((owner, operator), expires)| ExportedOperator {
+                    owner,
+                    operator,
+                    expires,
+                })
+            })
+            .collect::<StdResult<Vec<_>>>()?;
+
+        Ok(ExportOperatorsResponse { operators })
+    }
+
+    pub fn operator_revocations(
+        &self,
+        deps: Deps,
+        operator: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<OperatorRevocationsResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start = start_after.map(Bound::exclusive);
+
+        let operator_addr = self.resolve_addr(deps, &operator)?;
+        let revocations = self
+            .operator_revocations
+            .prefix(&operator_addr)
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| {
+                item.map(|(seq, revocation)| OperatorRevocationResponse { seq, revocation })
+            })
+            .collect::<StdResult<Vec<_>>>()?;
+
+        Ok(OperatorRevocationsResponse { revocations })
+    }
+
+    pub fn artwork_info(&self, deps: Deps, token_id: String) -> StdResult<ArtworkInfoResponse> {
+        let token = self.tokens.load(deps.storage, token_key(&token_id)?)?;
+        let extension = self.public_extension(deps.storage, &token_id, token.extension)?;
+        Ok(ArtworkInfoResponse {
+            artwork: self.artwork(&extension)?,
+            token_id,
+        })
+    }
+
+    pub fn redactions(&self, deps: Deps, token_id: String) -> StdResult<RedactionsResponse> {
+        let token = self.tokens.load(deps.storage, token_key(&token_id)?)?;
+        let fields = self
+            .redactions
+            .may_load(deps.storage, &token_id)?
+            .unwrap_or_default();
+        let provenance_notes_hash = if fields.contains(&RedactedField::ProvenanceNotes) {
+            self.artwork(&token.extension)?
+                .and_then(|artwork| artwork.provenance_notes)
+                .map(|notes| HexBinary::from(Sha256::digest(notes.as_bytes()).as_slice()))
+        } else {
+            None
+        };
+        Ok(RedactionsResponse {
+            token_id,
+            fields,
+            provenance_notes_hash,
+        })
+    }
+
+    /// NftInfo with the token_uri of the metadata variant active at the current block
+    pub fn active_nft_info(
+        &self,
+        deps: Deps,
+        env: &Env,
+        token_id: String,
+    ) -> StdResult<TokenNftInfoResponse<T>> {
+        let info = self.nft_info(deps, token_id.clone())?;
+        Ok(TokenNftInfoResponse {
+            token_uri: self.active_token_uri(
+                deps.storage,
+                &token_id,
+                info.token_uri,
+                env.block.time,
+            )?,
+            extension: info.extension,
+            transferable: !self.non_transferable.has(deps.storage, &token_id),
+            metadata_frozen: self.metadata_frozen.has(deps.storage, &token_id),
+            series_id: self.token_series.may_load(deps.storage, &token_id)?,
+        })
+    }
+
+    pub fn metadata_variants(
+        &self,
+        deps: Deps,
+        token_id: String,
+    ) -> StdResult<MetadataVariantsResponse> {
+        let variants = self
+            .metadata_variants
+            .may_load(deps.storage, &token_id)?
+            .unwrap_or_default();
+        Ok(MetadataVariantsResponse { variants })
+    }
+
+    pub fn holding_score(
+        &self,
+        deps: Deps,
+        env: Env,
+        address: String,
+    ) -> StdResult<HoldingScoreResponse> {
+        let address = self.resolve_addr(deps, &address)?;
+        let res = match self.holding_scores.may_load(deps.storage, &address)? {
+            Some(mut holding) => {
+                holding.accrue(env.block.time);
+                HoldingScoreResponse {
+                    held: holding.held,
+                    score: holding.score,
+                }
+            }
+            None => HoldingScoreResponse {
+                held: 0,
+                score: Uint128::zero(),
+            },
+        };
+        Ok(res)
+    }
+
+    pub fn buyback(&self, deps: Deps) -> StdResult<BuyBackResponse> {
+        Ok(BuyBackResponse {
+            program: self.buyback.may_load(deps.storage)?,
+            pool: self.refund_pool.may_load(deps.storage)?.unwrap_or_default(),
+        })
+    }
+
+    pub fn lazy_mint_nonce(
+        &self,
+        deps: Deps,
+        artist: String,
+        nonce: u64,
+    ) -> StdResult<LazyMintNonceResponse> {
+        let artist = self.resolve_addr(deps, &artist)?;
+        Ok(LazyMintNonceResponse {
+            used: self.lazy_mint_nonces.has(deps.storage, (&artist, nonce)),
+        })
+    }
+
+    pub fn refund(&self, deps: Deps, token_id: String) -> StdResult<RefundResponse> {
+        let refund = self.refunds.load(deps.storage, &token_id)?;
+        Ok(RefundResponse { token_id, refund })
+    }
+
+    pub fn provenance_certificate(
+        &self,
+        deps: Deps,
+        token_id: String,
+    ) -> StdResult<ProvenanceCertificateResponse> {
+        let digest = self.provenance_digests.load(deps.storage, &token_id)?;
+        Ok(ProvenanceCertificateResponse {
+            token_id,
+            hash: digest.hash,
+            entries: digest.entries,
+        })
+    }
+
+    pub fn provenance(
+        &self,
+        deps: Deps,
+        token_id: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<ProvenanceResponse> {
+        if self.is_redacted(deps.storage, &token_id, RedactedField::PriorOwners)? {
+            return Err(StdError::generic_err(
+                "Provenance log is redacted by the owner",
+            ));
+        }
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start = start_after.map(Bound::exclusive);
+
+        let entries = self
+            .provenance_log
+            .prefix(&token_id)
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| {
+                let (seq, entry) = item?;
+                let holder_display_name = match entry.event.holder() {
+                    Some(holder) => self.display_name(deps, holder)?,
+                    None => None,
+                };
+                Ok(ProvenanceLogEntry {
+                    seq,
+                    entry,
+                    holder_display_name,
+                })
+            })
+            .collect::<StdResult<_>>()?;
+
+        Ok(ProvenanceResponse { token_id, entries })
+    }
+
+    pub fn vault_deposit(&self, deps: Deps, token_id: String) -> StdResult<VaultDepositResponse> {
+        let deposit = self.vault_deposits.load(deps.storage, &token_id)?;
+        Ok(VaultDepositResponse { token_id, deposit })
+    }
+
+    pub fn vault_collections(
+        &self,
+        deps: Deps,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<VaultCollectionsResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start_addr = maybe_addr(deps.api, start_after)?;
+        let start = start_addr.as_ref().map(Bound::exclusive);
+
+        let collections = self
+            .vault_collections
+            .keys(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|k| k.map(String::from))
+            .collect::<StdResult<Vec<_>>>()?;
+
+        Ok(VaultCollectionsResponse { collections })
+    }
+
+    pub fn partner_galleries(
+        &self,
+        deps: Deps,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<PartnerGalleriesResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start_addr = maybe_addr(deps.api, start_after)?;
+        let start = start_addr.as_ref().map(Bound::exclusive);
+
+        let galleries = self
+            .partner_galleries
+            .keys(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|k| k.map(String::from))
+            .collect::<StdResult<Vec<_>>>()?;
+
+        Ok(PartnerGalleriesResponse { galleries })
+    }
+
+    pub fn voucher(&self, deps: Deps, token_id: String) -> StdResult<VoucherResponse> {
+        let voucher = self.vouchers.load(deps.storage, &token_id)?;
+        Ok(VoucherResponse { token_id, voucher })
+    }
+
+    pub fn voucher_issuance(
+        &self,
+        deps: Deps,
+        parent_token_id: String,
+    ) -> StdResult<VoucherIssuanceResponse> {
+        let issuance = self
+            .voucher_issuance
+            .may_load(deps.storage, &parent_token_id)?
+            .unwrap_or_default();
+        Ok(VoucherIssuanceResponse {
+            parent_token_id,
+            issuance,
+        })
+    }
+
+    pub fn voucher_redemptions(
+        &self,
+        deps: Deps,
+        parent_token_id: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<VoucherRedemptionsResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start = start_after.as_deref().map(Bound::exclusive);
+
+        let redemptions = self
+            .voucher_redemptions
+            .prefix(&parent_token_id)
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| item.map(|(_, redemption)| redemption))
+            .collect::<StdResult<_>>()?;
+
+        Ok(VoucherRedemptionsResponse { redemptions })
+    }
+
+    pub fn size_limits(&self, deps: Deps) -> StdResult<SizeLimitsResponse> {
+        Ok(SizeLimitsResponse {
+            limits: self.load_size_limits(deps.storage)?,
+        })
+    }
+
+    pub fn tokens_by_artist(
+        &self,
+        deps: Deps,
+        artist: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<TokensResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start = start_after
+            .map(|s| token_key(&s))
+            .transpose()?
+            .map(Bound::exclusive);
+
+        let artist = self.resolve_addr(deps, &artist)?;
+        let tokens = self
+            .artist_tokens
+            .prefix(&artist)
+            .keys(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|k| k.map(|id| id.to_string()))
+            .collect::<StdResult<Vec<_>>>()?;
+
+        Ok(TokensResponse { tokens })
+    }
+
+    pub fn artist_stats(&self, deps: Deps, artist: String) -> StdResult<ArtistStatsResponse> {
+        let addr = self.resolve_addr(deps, &artist)?;
+        let stats = self
+            .artist_stats
+            .may_load(deps.storage, &addr)?
+            .unwrap_or_default();
+        Ok(ArtistStatsResponse { artist, stats })
+    }
+
+    pub fn price_floor_of(
+        &self,
+        deps: Deps,
+        env: Env,
+        token_id: String,
+    ) -> StdResult<PriceFloorResponse> {
+        Ok(PriceFloorResponse {
+            floor: self.price_floor(deps.storage, &env.block, &token_id)?,
+        })
+    }
+
+    pub fn artist_keys(
+        &self,
+        deps: Deps,
+        artist: String,
+        start_after: Option<u32>,
+        limit: Option<u32>,
+    ) -> StdResult<ArtistKeysResponse> {
+        let addr = self.resolve_addr(deps, &artist)?;
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start = start_after.map(Bound::exclusive);
+
+        let keys = self
+            .artist_keys
+            .prefix(&addr)
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| item.map(|(version, key)| ArtistKeyResponse { version, key }))
+            .collect::<StdResult<Vec<_>>>()?;
+        let current = self
+            .artist_key_count
+            .may_load(deps.storage, &addr)?
+            .map(|count| count - 1);
+
+        Ok(ArtistKeysResponse { keys, current })
+    }
+
+    pub fn certificate(&self, deps: Deps, token_id: String) -> StdResult<CertificateResponse> {
+        let certificate = self.certificates.load(deps.storage, &token_id)?;
+        let key = self
+            .artist_keys
+            .load(deps.storage, (&certificate.artist, certificate.key_version))?;
+        let current_version = self
+            .artist_key_count
+            .load(deps.storage, &certificate.artist)?
+            - 1;
+        Ok(CertificateResponse {
+            certificate,
+            key,
+            current_version,
+        })
+    }
+
+    pub fn private_sales(
+        &self,
+        deps: Deps,
+        token_id: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<PrivateSalesResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start = start_after.map(Bound::exclusive);
+        if self.is_redacted(deps.storage, &token_id, RedactedField::PriorOwners)? {
+            return Ok(PrivateSalesResponse { sales: vec![] });
+        }
+
+        let sales = self
+            .private_sales
+            .prefix(&token_id)
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| item.map(|(sale_id, sale)| PrivateSaleResponse { sale_id, sale }))
+            .collect::<StdResult<_>>()?;
+
+        Ok(PrivateSalesResponse { sales })
+    }
+
+    pub fn gift(&self, deps: Deps, token_id: String) -> StdResult<GiftResponse> {
+        let gift = self.gifts.load(deps.storage, &token_id)?;
+        Ok(GiftResponse { token_id, gift })
+    }
+
+    pub fn conditional_transfer(
+        &self,
+        deps: Deps,
+        env: Env,
+        token_id: String,
+    ) -> StdResult<ConditionalTransferResponse> {
+        let transfer = self.conditional_transfers.load(deps.storage, &token_id)?;
+        // an unreachable oracle reads as not met
+        let condition_met = transfer.expires > env.block.time
+            && self
+                .condition_met(deps, &token_id, &transfer)
+                .unwrap_or(false);
+        Ok(ConditionalTransferResponse {
+            token_id,
+            transfer,
+            condition_met,
+        })
+    }
+
+    pub fn reveal_status(&self, deps: Deps, token_id: String) -> StdResult<RevealStatusResponse> {
+        self.tokens.load(deps.storage, token_key(&token_id)?)?;
+        let sealed = self.sealed_tokens.has(deps.storage, &token_id);
+        Ok(RevealStatusResponse { token_id, sealed })
+    }
+
+    pub fn series_info(&self, deps: Deps, series_id: u64) -> StdResult<SeriesResponse> {
+        let series = self.series.load(deps.storage, series_id)?;
+        Ok(series_response(series_id, series))
+    }
+
+    pub fn all_series(
+        &self,
+        deps: Deps,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<AllSeriesResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start = start_after.map(Bound::exclusive);
+
+        let series = self
+            .series
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| item.map(|(series_id, series)| series_response(series_id, series)))
+            .collect::<StdResult<Vec<_>>>()?;
+
+        Ok(AllSeriesResponse { series })
+    }
+
+    pub fn blind_drop(&self, deps: Deps) -> StdResult<BlindDropResponse> {
+        Ok(BlindDropResponse {
+            blind_drop: self.blind_drop.may_load(deps.storage)?,
+        })
+    }
+
+    pub fn mystery_pool(&self, deps: Deps) -> StdResult<MysteryPoolResponse> {
+        Ok(MysteryPoolResponse {
+            sealed_uri: self.mystery_sealed_uri.may_load(deps.storage)?,
+            remaining: self
+                .mystery_pool_size
+                .may_load(deps.storage)?
+                .unwrap_or_default(),
+        })
+    }
+
+    pub fn due_before(
+        &self,
+        deps: Deps,
+        timestamp: Timestamp,
+        start_after: Option<(u64, u64)>,
+        limit: Option<u32>,
+    ) -> StdResult<DueBeforeResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start = start_after.map(Bound::exclusive);
+        let end = Some(Bound::inclusive((timestamp.nanos(), u64::MAX)));
+
+        let entries = self
+            .expiries
+            .range(deps.storage, start, end, Order::Ascending)
+            .take(limit)
+            .map(|item| item.map(|((at, seq), kind)| DueEntry { at, seq, kind }))
+            .collect::<StdResult<Vec<_>>>()?;
+
+        Ok(DueBeforeResponse { entries })
+    }
+
+    pub fn range_approvals(
+        &self,
+        deps: Deps,
+        owner: String,
+        operator: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<RangeApprovalsResponse> {
+        let owner = self.resolve_addr(deps, &owner)?;
+        let operator = self.resolve_addr(deps, &operator)?;
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start = start_after.map(Bound::exclusive);
+
+        let ranges = self
+            .range_approvals
+            .prefix((&owner, &operator))
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| {
+                item.map(|(first, range)| RangeApprovalResponse {
+                    first,
+                    last: range.last,
+                    expires: range.expires,
+                })
+            })
+            .collect::<StdResult<Vec<_>>>()?;
+
+        Ok(RangeApprovalsResponse { ranges })
+    }
+
+    pub fn treasury_reconciliation(
+        &self,
+        deps: Deps,
+        env: Env,
+    ) -> StdResult<TreasuryReconciliationResponse> {
+        let accounts = self.treasury_entries(deps.storage)?;
+
+        let mut denoms: Vec<DenomReconciliation> = deps
+            .querier
+            .query_all_balances(&env.contract.address)?
+            .into_iter()
+            .map(|coin| DenomReconciliation {
+                denom: coin.denom,
+                held: coin.amount,
+                accounted: Uint128::zero(),
+                unaccounted: Uint128::zero(),
+                shortfall: Uint128::zero(),
+            })
+            .collect();
+        for entry in &accounts {
+            match denoms.iter_mut().find(|d| d.denom == entry.denom) {
+                Some(denom) => denom.accounted += entry.amount,
+                None => denoms.push(DenomReconciliation {
+                    denom: entry.denom.clone(),
+                    held: Uint128::zero(),
+                    accounted: entry.amount,
+                    unaccounted: Uint128::zero(),
+                    shortfall: Uint128::zero(),
+                }),
+            }
+        }
+        for denom in denoms.iter_mut() {
+            denom.unaccounted = denom.held.saturating_sub(denom.accounted);
+            denom.shortfall = denom.accounted.saturating_sub(denom.held);
+        }
+
+        Ok(TreasuryReconciliationResponse { accounts, denoms })
+    }
+
+    pub fn collected_funds(&self, deps: Deps, env: Env) -> StdResult<CollectedFundsResponse> {
+        let mut denoms: Vec<String> = deps
+            .querier
+            .query_all_balances(&env.contract.address)?
+            .into_iter()
+            .map(|coin| coin.denom)
+            .collect();
+        for item in self
+            .proceeds_withdrawn
+            .keys(deps.storage, None, None, Order::Ascending)
+        {
+            let denom = item?;
+            if !denoms.contains(&denom) {
+                denoms.push(denom);
+            }
+        }
+
+        let funds = denoms
+            .into_iter()
+            .map(|denom| {
+                Ok(CollectedFunds {
+                    proceeds: self
+                        .treasury_ledger
+                        .may_load(deps.storage, (TreasuryAccount::Proceeds.key(), &denom))?
+                        .unwrap_or_default(),
+                    withdrawable: self.withdrawable(deps, &env, &denom)?,
+                    withdrawn: self
+                        .proceeds_withdrawn
+                        .may_load(deps.storage, &denom)?
+                        .unwrap_or_default(),
+                    denom,
+                })
+            })
+            .collect::<StdResult<Vec<_>>>()?;
+
+        Ok(CollectedFundsResponse { funds })
+    }
+
+    pub fn permission_sweep(
+        &self,
+        deps: Deps,
+        address: String,
+    ) -> StdResult<PermissionSweepResponse> {
+        let addr = self.resolve_addr(deps, &address)?;
+        Ok(PermissionSweepResponse {
+            sweep: self.permission_sweeps.may_load(deps.storage, &addr)?,
+        })
+    }
+
+    pub fn keeper_treasury(&self, deps: Deps) -> StdResult<KeeperTreasuryResponse> {
+        Ok(KeeperTreasuryResponse {
+            bounty: self.keeper_bounty.may_load(deps.storage)?,
+            treasury: self
+                .keeper_treasury
+                .may_load(deps.storage)?
+                .unwrap_or_default(),
+        })
+    }
+
+    pub fn metadata_resolver(&self, deps: Deps) -> StdResult<MetadataResolverResponse> {
+        Ok(MetadataResolverResponse {
+            resolver: self.metadata_resolver.may_load(deps.storage)?,
+        })
+    }
+
+    pub fn name_resolver(&self, deps: Deps) -> StdResult<NameResolverResponse> {
+        Ok(NameResolverResponse {
+            resolver: self.name_resolver.may_load(deps.storage)?,
+        })
+    }
+
+    pub fn resolve_address(
+        &self,
+        deps: Deps,
+        address: String,
+    ) -> StdResult<ResolveAddressResponse> {
+        Ok(ResolveAddressResponse {
+            address: self.resolve_addr(deps, &address)?,
+        })
+    }
+
+    pub fn simulate_mint(
+        &self,
+        deps: Deps,
+        env: Env,
+        quantity: u32,
+        buyer: Option<String>,
+    ) -> StdResult<SimulateMintResponse> {
+        let buyer = buyer
+            .map(|buyer| self.resolve_addr(deps, &buyer))
+            .transpose()?;
+        let mut remaining = self.remaining_mints(deps.storage)?;
+        let price = match self.allowlist_phase(deps.storage, &env.block)? {
+            Some(phase) => {
+                if let Some(buyer) = &buyer {
+                    let allowance = match self.allowlist.may_load(deps.storage, buyer)? {
+                        Some(entry) => phase.per_address_limit.saturating_sub(entry.minted),
+                        None => 0,
+                    };
+                    remaining = remaining.min(allowance as u64);
+                }
+                phase.price
+            }
+            None => self.public_mint_price(deps, &env.block)?,
+        };
+        if let Some((drop, acquired)) = match &buyer {
+            Some(buyer) => self.drop_acquired(deps.storage, buyer)?,
+            None => None,
+        } {
+            remaining = remaining.min(drop.max_per_address.saturating_sub(acquired));
+        }
+        if let (Some(buyer), Some(limit)) =
+            (&buyer, self.max_mints_per_address.may_load(deps.storage)?)
+        {
+            let minted = self
+                .mints_per_address
+                .may_load(deps.storage, buyer)?
+                .unwrap_or_default();
+            remaining = remaining.min(limit.saturating_sub(minted));
+        }
+        let quantity = (quantity.min(MAX_LIMIT) as u64).min(remaining);
+
+        let minted = match self.mint_count.may_load(deps.storage)? {
+            Some(minted) => minted,
+            None => self.token_count(deps.storage)?,
+        };
+        let withholding_per_mint = match &buyer {
+            Some(buyer) => self.withholding_for(deps.storage, buyer, &price)?,
+            None => None,
+        };
+
+        Ok(SimulateMintResponse {
+            minting_allowed: self.mint_allowed.load(deps.storage)?,
+            quantity: quantity as u32,
+            total: Coin {
+                denom: price.denom.clone(),
+                amount: price.amount.checked_mul(Uint128::from(quantity))?,
+            },
+            funds_per_mint: price,
+            withholding_per_mint,
+            token_ids: (1..=quantity).map(|i| (minted + i).to_string()).collect(),
+        })
+    }
+
+    pub fn presale(&self, deps: Deps) -> StdResult<PresaleResponse> {
+        Ok(PresaleResponse {
+            presale: self.presale.may_load(deps.storage)?,
+        })
+    }
+
+    pub fn presale_commitment(
+        &self,
+        deps: Deps,
+        address: String,
+    ) -> StdResult<PresaleCommitmentResponse> {
+        let addr = self.resolve_addr(deps, &address)?;
+        let outcome = match self.presale.may_load(deps.storage)? {
+            Some(presale) => self
+                .presale_outcomes
+                .may_load(deps.storage, (presale.id, &addr))?,
+            None => None,
+        };
+        Ok(PresaleCommitmentResponse { outcome })
+    }
+
+    pub fn is_allowlisted(&self, deps: Deps, address: String) -> StdResult<IsAllowlistedResponse> {
+        let addr = self.resolve_addr(deps, &address)?;
+        let entry = self.allowlist.may_load(deps.storage, &addr)?;
+        Ok(IsAllowlistedResponse {
+            allowlisted: entry.is_some(),
+            minted: entry.map(|e| e.minted).unwrap_or_default(),
+        })
+    }
+
+    pub fn allowlist_config(&self, deps: Deps, env: Env) -> StdResult<AllowlistConfigResponse> {
+        Ok(AllowlistConfigResponse {
+            config: self.allowlist_config.may_load(deps.storage)?,
+            merkle_root: self.allowlist_merkle_root.may_load(deps.storage)?,
+            active: self.allowlist_phase(deps.storage, &env.block)?.is_some(),
+        })
+    }
+
+    pub fn current_mint_price(&self, deps: Deps, env: Env) -> StdResult<CurrentMintPriceResponse> {
+        let price = match self.allowlist_phase(deps.storage, &env.block)? {
+            Some(phase) => phase.price,
+            None => self.public_mint_price(deps, &env.block)?,
+        };
+        let peg = self.mint_price_peg.may_load(deps.storage)?;
+        let peg_fallback = match &peg {
+            Some(peg) => self.pegged_mint_price(deps, &env.block, peg).is_none(),
+            None => false,
+        };
+        Ok(CurrentMintPriceResponse {
+            price,
+            dutch_auction: self.dutch_auction.may_load(deps.storage)?,
+            peg,
+            peg_fallback,
+        })
+    }
+
+    pub fn featured(
+        &self,
+        deps: Deps,
+        start_after: Option<u32>,
+        limit: Option<u32>,
+    ) -> StdResult<FeaturedResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start = start_after.map(|p| p as usize + 1).unwrap_or_default();
+
+        let tokens = self
+            .featured
+            .may_load(deps.storage)?
+            .unwrap_or_default()
+            .into_iter()
+            .enumerate()
+            .skip(start)
+            .take(limit)
+            .map(|(position, f)| FeaturedEntry {
+                position: position as u32,
+                token_id: f.token_id,
+                blurb: f.blurb,
+            })
+            .collect();
+
+        Ok(FeaturedResponse { tokens })
+    }
+
+    pub fn curator(&self, deps: Deps) -> StdResult<CuratorResponse> {
+        Ok(CuratorResponse {
+            curator: self.curator.may_load(deps.storage)?,
+        })
+    }
+
+    pub fn curation_fee(&self, deps: Deps) -> StdResult<CurationFeeResponse> {
+        Ok(CurationFeeResponse {
+            bps: self
+                .curation_fee_bps
+                .may_load(deps.storage)?
+                .unwrap_or_default(),
+        })
+    }
+
+    pub fn referral(&self, deps: Deps, referral_id: String) -> StdResult<ReferralResponse> {
+        Ok(ReferralResponse {
+            curator: self.referrals.may_load(deps.storage, &referral_id)?,
+        })
+    }
+
+    pub fn curator_earnings(
+        &self,
+        deps: Deps,
+        curator: String,
+    ) -> StdResult<CuratorEarningsResponse> {
+        let curator = self.resolve_addr(deps, &curator)?;
+        let earnings = self
+            .curator_earnings
+            .prefix(&curator)
+            .range(deps.storage, None, None, Order::Ascending)
+            .map(|item| item.map(|(denom, amount)| Coin { denom, amount }))
+            .collect::<StdResult<_>>()?;
+        Ok(CuratorEarningsResponse { earnings })
+    }
+
+    pub fn royalty_info(
+        &self,
+        deps: Deps,
+        env: Env,
+        token_id: String,
+        sale_price: Uint128,
+    ) -> StdResult<RoyaltiesInfoResponse> {
+        let token = self.tokens.load(deps.storage, token_key(&token_id)?)?;
+        let royalties = self.sale_royalties(deps, &env, &token_id, &token, sale_price)?;
+
+        let payees: Vec<_> = royalties
+            .into_iter()
+            .map(|(address, amount)| RoyaltyPayee {
+                address: address.into_string(),
+                amount,
+            })
+            .collect();
+        Ok(RoyaltiesInfoResponse {
+            address: payees
+                .first()
+                .map(|p| p.address.clone())
+                .unwrap_or_default(),
+            royalty_amount: payees.iter().map(|p| p.amount).sum(),
+            payees,
+        })
+    }
+
+    pub fn royalty_split(
+        &self,
+        deps: Deps,
+        env: Env,
+        token_id: String,
+    ) -> StdResult<RoyaltySplitResponse> {
+        let split = self.royalty_split_at(deps.storage, &token_id, env.block.time)?;
+        let upcoming = self
+            .royalty_successions
+            .may_load(deps.storage, &token_id)?
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|s| s.effective_at > env.block.time)
+            .collect();
+        Ok(RoyaltySplitResponse {
+            token_id,
+            split,
+            upcoming,
+        })
+    }
+
+    pub fn royalty_payout(&self, deps: Deps, address: String) -> StdResult<RoyaltyPayoutResponse> {
+        let addr = self.resolve_addr(deps, &address)?;
+        let accrued = self
+            .accrued_royalties
+            .prefix(&addr)
+            .range(deps.storage, None, None, Order::Ascending)
+            .map(|item| item.map(|(denom, amount)| Coin { denom, amount }))
+            .collect::<StdResult<Vec<_>>>()?;
+        Ok(RoyaltyPayoutResponse {
+            address,
+            denom: self.payout_denoms.may_load(deps.storage, &addr)?,
+            swap: self.royalty_swap.may_load(deps.storage)?,
+            accrued,
+        })
+    }
+
+    pub fn withdrawal_recipients(
+        &self,
+        deps: Deps,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<WithdrawalRecipientsResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start_addr = maybe_addr(deps.api, start_after)?;
+        let start = start_addr.as_ref().map(Bound::exclusive);
+
+        let recipients = self
+            .withdrawal_recipients
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| {
+                item.map(|(address, active_from)| WithdrawalRecipient {
+                    address: address.into_string(),
+                    active_from,
+                })
+            })
+            .collect::<StdResult<_>>()?;
+
+        Ok(WithdrawalRecipientsResponse {
+            guarded: self.withdrawal_guard.may_load(deps.storage)?.is_some(),
+            recipients,
+        })
+    }
+
+    pub fn withdrawal_cap(
+        &self,
+        deps: Deps,
+        env: Env,
+        denom: String,
+    ) -> StdResult<WithdrawalCapResponse> {
+        let since = env.block.time.minus_seconds(WITHDRAWAL_WINDOW);
+        Ok(WithdrawalCapResponse {
+            cap: self.withdrawal_caps.may_load(deps.storage, &denom)?,
+            withdrawn: self.withdrawn_since(deps.storage, &denom, since)?,
+            allowance: self.withdrawal_allowance(deps.storage, env.block.time, &denom)?,
+        })
+    }
+
+    pub fn query(&self, deps: Deps, env: Env, mut msg: QueryMsg) -> StdResult<Binary> {
+        for token_id in msg.token_ids_mut() {
+            self.resolve_token_alias(deps.storage, token_id)?;
+        }
+        match msg {
+            QueryMsg::ContractInfo {} => to_json_binary(&self.contract_info(deps)?),
+            QueryMsg::NftInfo { token_id } => {
+                to_json_binary(&self.active_nft_info(deps, &env, token_id)?)
+            }
+            QueryMsg::ArtworkInfo { token_id } => {
+                to_json_binary(&self.artwork_info(deps, token_id)?)
+            }
+            QueryMsg::Redactions { token_id } => to_json_binary(&self.redactions(deps, token_id)?),
+            QueryMsg::NumTokens {} => to_json_binary(&self.num_tokens(deps)?),
+            QueryMsg::Minter {} => to_json_binary(&self.minter(deps)?),
+            QueryMsg::PendingMinter {} => to_json_binary(&self.pending_minter(deps)?),
+            QueryMsg::HasRole { address, role } => {
+                to_json_binary(&self.holds_role(deps, address, role)?)
+            }
+            QueryMsg::SizeLimits {} => to_json_binary(&self.size_limits(deps)?),
+            QueryMsg::NftDetails {} => to_json_binary(&self.nft_details(deps)?),
+            QueryMsg::TokenByContent {
+                token_uri,
+                content_hash,
+            } => to_json_binary(&self.token_by_content(deps, token_uri, content_hash)?),
+            QueryMsg::Paused {} => to_json_binary(&self.paused(deps)?),
+            QueryMsg::DeploymentProfile {} => to_json_binary(&self.deployment_profile(deps)?),
+            QueryMsg::MintedByAddress { address } => {
+                to_json_binary(&self.minted_by_address(deps, address)?)
+            }
+            QueryMsg::DropLimit {} => to_json_binary(&self.drop_limit(deps)?),
+            QueryMsg::DropAcquisitions { address } => {
+                to_json_binary(&self.drop_acquisitions(deps, address)?)
+            }
+            QueryMsg::Profile { address } => to_json_binary(&self.profile(deps, address)?),
+            QueryMsg::SendAllowlist { start_after, limit } => {
+                to_json_binary(&self.send_allowlist(deps, start_after, limit)?)
+            }
+            QueryMsg::InstallmentPlan {} => to_json_binary(&self.installment_plan(deps)?),
+            QueryMsg::Reservation { token_id } => {
+                to_json_binary(&self.reservation(deps, env, token_id)?)
+            }
+            QueryMsg::ReservationsByBuyer {
+                buyer,
+                start_after,
+                limit,
+            } => {
+                to_json_binary(&self.reservations_by_buyer(deps, env, buyer, start_after, limit)?)
+            }
+            QueryMsg::WithholdingRule { jurisdiction } => {
+                to_json_binary(&self.withholding_rule(deps, jurisdiction)?)
+            }
+            QueryMsg::AccountJurisdiction { address } => {
+                to_json_binary(&self.account_jurisdiction(deps, address)?)
+            }
+            QueryMsg::SaleReceipt { token_id } => {
+                to_json_binary(&self.sale_receipt(deps, token_id)?)
+            }
+            QueryMsg::Tombstone { token_id } => to_json_binary(&self.tombstone(deps, token_id)?),
+            QueryMsg::Attestor { address } => to_json_binary(&self.attestor(deps, address)?),
+            QueryMsg::Attestors { start_after, limit } => {
+                to_json_binary(&self.attestors(deps, start_after, limit)?)
+            }
+            QueryMsg::Attestations {
+                token_id,
+                start_after,
+                limit,
+            } => to_json_binary(&self.attestations(deps, token_id, start_after, limit)?),
+            QueryMsg::Inbox { address } => to_json_binary(&self.inbox(deps, env, address)?),
+            QueryMsg::Auction { auction_id } => to_json_binary(&self.auction(deps, auction_id)?),
+            QueryMsg::Auctions { start_after, limit } => {
+                to_json_binary(&self.auctions(deps, start_after, limit)?)
+            }
+            QueryMsg::SealedBid { auction_id, bidder } => {
+                to_json_binary(&self.sealed_bid(deps, auction_id, bidder)?)
+            }
+            QueryMsg::BidHistory {
+                auction_id,
+                start_after,
+                limit,
+            } => to_json_binary(&self.bid_history(deps, auction_id, start_after, limit)?),
+            QueryMsg::OperatorGrants {
+                operator,
+                include_expired,
+                start_after,
+                limit,
+            } => to_json_binary(&self.operator_grants(
+                deps,
+                env,
+                operator,
+                include_expired.unwrap_or(false),
+                start_after,
+                limit,
+            )?),
+            QueryMsg::StorageFee {} => to_json_binary(&self.storage_fee(deps)?),
+            QueryMsg::StorageAccount { token_id } => {
+                to_json_binary(&self.storage_account(deps, env, token_id)?)
+            }
+            QueryMsg::TokenRights { token_id } => {
+                to_json_binary(&self.token_rights(deps, token_id)?)
+            }
+            QueryMsg::SalesFeed {
+                secondary_only,
+                start_before,
+                limit,
+            } => to_json_binary(&self.sales_feed(
+                deps,
+                secondary_only.unwrap_or(false),
+                start_before,
+                limit,
+            )?),
+            QueryMsg::ListingApprovals {
+                token_id,
+                include_expired,
+            } => to_json_binary(&self.listing_approvals(
+                deps,
+                env,
+                token_id,
+                include_expired.unwrap_or(false),
+            )?),
+            QueryMsg::ExportStatus {} => to_json_binary(&self.export_status(deps)?),
+            QueryMsg::EventSchema {} => to_json_binary(&self.event_schema()),
+            QueryMsg::ExportTokens { start_after, limit } => {
+                to_json_binary(&self.export_tokens(deps, start_after, limit)?)
+            }
+            QueryMsg::ExportOperators { start_after, limit } => {
+                to_json_binary(&self.export_operators(deps, start_after, limit)?)
+            }
+            QueryMsg::OperatorRevocations {
+                operator,
+                start_after,
+                limit,
+            } => to_json_binary(&self.operator_revocations(deps, operator, start_after, limit)?),
+            QueryMsg::MetadataVariants { token_id } => {
+                to_json_binary(&self.metadata_variants(deps, token_id)?)
+            }
+            QueryMsg::HoldingScore { address } => {
+                to_json_binary(&self.holding_score(deps, env, address)?)
+            }
+            QueryMsg::BuyBack {} => to_json_binary(&self.buyback(deps)?),
+            QueryMsg::Refund { token_id } => to_json_binary(&self.refund(deps, token_id)?),
+            QueryMsg::Provenance {
+                token_id,
+                start_after,
+                limit,
+            } => to_json_binary(&self.provenance(deps, token_id, start_after, limit)?),
+            QueryMsg::ProvenanceCertificate { token_id } => {
+                to_json_binary(&self.provenance_certificate(deps, token_id)?)
+            }
+            QueryMsg::VaultDeposit { token_id } => {
+                to_json_binary(&self.vault_deposit(deps, token_id)?)
+            }
+            QueryMsg::VaultCollections { start_after, limit } => {
+                to_json_binary(&self.vault_collections(deps, start_after, limit)?)
+            }
+            QueryMsg::PartnerGalleries { start_after, limit } => {
+                to_json_binary(&self.partner_galleries(deps, start_after, limit)?)
+            }
+            QueryMsg::PermissionSweep { address } => {
+                to_json_binary(&self.permission_sweep(deps, address)?)
+            }
+            QueryMsg::Voucher { token_id } => to_json_binary(&self.voucher(deps, token_id)?),
+            QueryMsg::VoucherIssuance { parent_token_id } => {
+                to_json_binary(&self.voucher_issuance(deps, parent_token_id)?)
+            }
+            QueryMsg::VoucherRedemptions {
+                parent_token_id,
+                start_after,
+                limit,
+            } => to_json_binary(&self.voucher_redemptions(
+                deps,
+                parent_token_id,
+                start_after,
+                limit,
+            )?),
+            QueryMsg::TokensByArtist {
+                artist,
+                start_after,
+                limit,
+            } => to_json_binary(&self.tokens_by_artist(deps, artist, start_after, limit)?),
+            QueryMsg::ArtistStats { artist } => to_json_binary(&self.artist_stats(deps, artist)?),
+            QueryMsg::PriceFloor { token_id } => {
+                to_json_binary(&self.price_floor_of(deps, env, token_id)?)
+            }
+            QueryMsg::ArtistKeys {
+                artist,
+                start_after,
+                limit,
+            } => to_json_binary(&self.artist_keys(deps, artist, start_after, limit)?),
+            QueryMsg::Certificate { token_id } => {
+                to_json_binary(&self.certificate(deps, token_id)?)
+            }
+            QueryMsg::LazyMintNonce { artist, nonce } => {
+                to_json_binary(&self.lazy_mint_nonce(deps, artist, nonce)?)
+            }
+            QueryMsg::Gift { token_id } => to_json_binary(&self.gift(deps, token_id)?),
+            QueryMsg::PrivateSales {
+                token_id,
+                start_after,
+                limit,
+            } => to_json_binary(&self.private_sales(deps, token_id, start_after, limit)?),
+            QueryMsg::ConditionalTransfer { token_id } => {
+                to_json_binary(&self.conditional_transfer(deps, env, token_id)?)
+            }
+            QueryMsg::RevealStatus { token_id } => {
+                to_json_binary(&self.reveal_status(deps, token_id)?)
+            }
+            QueryMsg::MysteryPool {} => to_json_binary(&self.mystery_pool(deps)?),
+            QueryMsg::BlindDrop {} => to_json_binary(&self.blind_drop(deps)?),
+            QueryMsg::SeriesInfo { series_id } => {
+                to_json_binary(&self.series_info(deps, series_id)?)
+            }
+            QueryMsg::AllSeries { start_after, limit } => {
+                to_json_binary(&self.all_series(deps, start_after, limit)?)
+            }
+            QueryMsg::DueBefore {
+                timestamp,
+                start_after,
+                limit,
+            } => to_json_binary(&self.due_before(deps, timestamp, start_after, limit)?),
+            QueryMsg::RangeApprovals {
+                owner,
+                operator,
+                start_after,
+                limit,
+            } => {
+                to_json_binary(&self.range_approvals(deps, owner, operator, start_after, limit)?)
+            }
+            QueryMsg::CollectedFunds {} => to_json_binary(&self.collected_funds(deps, env)?),
+            QueryMsg::WithdrawalRecipients { start_after, limit } => {
+                to_json_binary(&self.withdrawal_recipients(deps, start_after, limit)?)
+            }
+            QueryMsg::WithdrawalCap { denom } => {
+                to_json_binary(&self.withdrawal_cap(deps, env, denom)?)
+            }
+            QueryMsg::TreasuryReconciliation {} => {
+                to_json_binary(&self.treasury_reconciliation(deps, env)?)
+            }
+            QueryMsg::KeeperTreasury {} => to_json_binary(&self.keeper_treasury(deps)?),
+            QueryMsg::NameResolver {} => to_json_binary(&self.name_resolver(deps)?),
+            QueryMsg::MetadataResolver {} => to_json_binary(&self.metadata_resolver(deps)?),
+            QueryMsg::ResolveAddress { address } => {
+                to_json_binary(&self.resolve_address(deps, address)?)
+            }
+            QueryMsg::SimulateMint { quantity, buyer } => {
+                to_json_binary(&self.simulate_mint(deps, env, quantity, buyer)?)
+            }
+            QueryMsg::Presale {} => to_json_binary(&self.presale(deps)?),
+            QueryMsg::PresaleCommitment { address } => {
+                to_json_binary(&self.presale_commitment(deps, address)?)
+            }
+            QueryMsg::IsAllowlisted { address } => {
+                to_json_binary(&self.is_allowlisted(deps, address)?)
+            }
+            QueryMsg::AllowlistConfig {} => to_json_binary(&self.allowlist_config(deps, env)?),
+            QueryMsg::CurrentMintPrice {} => to_json_binary(&self.current_mint_price(deps, env)?),
+            QueryMsg::Featured { start_after, limit } => {
+                to_json_binary(&self.featured(deps, start_after, limit)?)
+            }
+            QueryMsg::Curator {} => to_json_binary(&self.curator(deps)?),
+            QueryMsg::CurationFee {} => to_json_binary(&self.curation_fee(deps)?),
+            QueryMsg::Referral { referral_id } => {
+                to_json_binary(&self.referral(deps, referral_id)?)
+            }
+            QueryMsg::CuratorEarnings { curator } => {
+                to_json_binary(&self.curator_earnings(deps, curator)?)
+            }
+            QueryMsg::RoyaltyInfo {
+                token_id,
+                sale_price,
+            } => to_json_binary(&self.royalty_info(deps, env, token_id, sale_price)?),
+            QueryMsg::CheckRoyalties {} => to_json_binary(&CheckRoyaltiesResponse {
+                royalty_payments: true,
+            }),
+            QueryMsg::RoyaltySplit { token_id } => {
+                to_json_binary(&self.royalty_split(deps, env, token_id)?)
+            }
+            QueryMsg::RoyaltyPayout { address } => {
+                to_json_binary(&self.royalty_payout(deps, address)?)
+            }
+            QueryMsg::CollectionBid { bid_id } => {
+                to_json_binary(&self.collection_bid(deps, bid_id)?)
+            }
+            QueryMsg::CollectionBids { start_after, limit } => {
+                to_json_binary(&self.collection_bids(deps, start_after, limit)?)
+            }
+            QueryMsg::Listing { token_id } => to_json_binary(&self.listing(deps, token_id)?),
+            QueryMsg::AllListings { start_after, limit } => {
+                to_json_binary(&self.all_listings(deps, start_after, limit)?)
+            }
+            QueryMsg::MarketplaceFee {} => to_json_binary(&self.marketplace_fee(deps)?),
+            QueryMsg::Lender {} => to_json_binary(&self.lender(deps)?),
+            QueryMsg::OffersByToken {
+                token_id,
+                start_after,
+                limit,
+            } => to_json_binary(&self.offers_by_token(deps, token_id, start_after, limit)?),
+            QueryMsg::OffersByBidder {
+                bidder,
+                start_after,
+                limit,
+            } => to_json_binary(&self.offers_by_bidder(deps, bidder, start_after, limit)?),
+            QueryMsg::TradeIn { token_id } => to_json_binary(&self.trade_in(deps, token_id)?),
+            QueryMsg::FirstRefusal { token_id } => {
+                to_json_binary(&self.first_refusal(deps, token_id)?)
+            }
+            QueryMsg::AutoBuyer { collector } => to_json_binary(&self.auto_buyer(deps, collector)?),
+            QueryMsg::ResolveAlias { alias } => to_json_binary(&self.resolve_alias(deps, alias)?),
+            QueryMsg::FrozenStatus { token_id } => {
+                to_json_binary(&self.frozen_status(deps, token_id)?)
+            }
+            QueryMsg::TradeIns { start_after, limit } => {
+                to_json_binary(&self.trade_ins(deps, start_after, limit)?)
+            }
+            QueryMsg::RoyaltyWaiver { waiver_id } => {
+                to_json_binary(&self.royalty_waiver(deps, waiver_id)?)
+            }
+            QueryMsg::RoyaltyWaivers {
+                holder,
+                start_after,
+                limit,
+            } => to_json_binary(&self.royalty_waivers(deps, holder, start_after, limit)?),
+            QueryMsg::Event { event_id } => to_json_binary(&self.event(deps, event_id)?),
+            QueryMsg::EventWorks {
+                event_id,
+                start_after,
+                limit,
+            } => to_json_binary(&self.event_works(deps, event_id, start_after, limit)?),
+            QueryMsg::Venue { address } => to_json_binary(&self.venue(deps, address)?),
+            QueryMsg::Exhibitions {
+                token_id,
+                start_after,
+                limit,
+            } => to_json_binary(&self.exhibitions(deps, token_id, start_after, limit)?),
+            QueryMsg::IbcChannels {} => to_json_binary(&self.ibc_channels(deps)?),
+            QueryMsg::IbcToken { token_id } => to_json_binary(&self.ibc_token(deps, token_id)?),
+            QueryMsg::TokenLinks {
+                token_id,
+                start_after,
+                limit,
+            } => to_json_binary(&self.token_links(deps, token_id, start_after, limit)?),
+            QueryMsg::Pins {
+                token_id,
+                start_after,
+                limit,
+            } => to_json_binary(&self.pins(deps, token_id, start_after, limit)?),
+            QueryMsg::ExpiringPins {
+                within,
+                start_after,
+                limit,
+            } => to_json_binary(&self.expiring_pins(deps, &env, within, start_after, limit)?),
+            QueryMsg::AllNftInfo {
+                token_id,
+                include_expired,
+            } => to_json_binary(&self.all_nft_info(
+                deps,
+                env,
+                token_id,
+                include_expired.unwrap_or(false),
+            )?),
+            QueryMsg::OwnerOf {
+                token_id,
+                include_expired,
+            } => to_json_binary(&self.owner_of(
+                deps,
+                env,
+                token_id,
+                include_expired.unwrap_or(false),
+            )?),
+            QueryMsg::AllOperators {
+                owner,
+                include_expired,
+                start_after,
+                limit,
+            } => to_json_binary(&self.operators(
+                deps,
+                env,
+                owner,
+                include_expired.unwrap_or(false),
+                start_after,
+                limit,
+            )?),
+            QueryMsg::Tokens {
+                owner,
+                start_after,
+                limit,
+            } => to_json_binary(&self.tokens(deps, owner, start_after, limit)?),
+            QueryMsg::AllTokens { start_after, limit } => {
+                to_json_binary(&self.all_tokens(deps, start_after, limit)?)
+            }
+            QueryMsg::Approval {
+                token_id,
+                spender,
+                include_expired,
+            } => to_json_binary(&self.approval(
+                deps,
+                env,
+                token_id,
+                spender,
+                include_expired.unwrap_or(false),
+            )?),
+            QueryMsg::Approvals {
+                token_id,
+                include_expired,
+            } => to_json_binary(&self.approvals(
+                deps,
+                env,
+                token_id,
+                include_expired.unwrap_or(false),
+            )?),
+        }
+    }
+}
+
+fn series_response(series_id: u64, series: Series) -> SeriesResponse {
+    SeriesResponse {
+        series_id,
+        remaining: series.max_editions - series.minted,
+        series,
+    }
+}
+
+fn parse_approval(item: StdResult<(Addr, Expiration)>) -> StdResult<cw721::Approval> {
+    item.map(|(spender, expires)| cw721::Approval {
+        spender: spender.to_string(),
+        expires,
+    })
+}
+
+fn humanize_approvals<T>(
+    block: &BlockInfo,
+    info: &TokenInfo<T>,
+    include_expired: bool,
+) -> Vec<cw721::Approval> {
+    info.approvals
+        .iter()
+        .filter(|apr| include_expired || !apr.is_expired(block))
+        .map(humanize_approval)
+        .collect()
+}
+
+fn humanize_approval(approval: &Approval) -> cw721::Approval {
+    cw721::Approval {
+        spender: approval.spender.to_string(),
+        expires: approval.expires,
+    }
+}
+
+fn humanize_reservation(
+    block: &BlockInfo,
+    token_id: String,
+    reservation: Reservation,
+) -> ReservationResponse {
+    ReservationResponse {
+        grace_until: reservation.grace_until(),
+        late_fee: reservation.late_fee_at(block.time),
+        late_fees_paid: reservation.late_fees_paid,
+        token_id,
+        buyer: reservation.buyer.into_string(),
+        recipient: reservation.recipient.into_string(),
+        price: reservation.price,
+        paid: reservation.paid,
+        installment_amount: reservation.installment_amount,
+        interval: reservation.interval,
+        next_due: reservation.next_due,
     }
 }
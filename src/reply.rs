@@ -0,0 +1,50 @@
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use cosmwasm_std::{DepsMut, Env, Reply, Response, StdResult, Storage};
+
+use cw721::CustomMsg;
+
+use crate::error::ContractError;
+use crate::state::{Cw721Contract, PendingReply};
+
+// submessage replies
+impl<'a, T, C> Cw721Contract<'a, T, C>
+where
+    T: Serialize + DeserializeOwned + Clone,
+    C: CustomMsg,
+{
+    pub fn reply(&self, deps: DepsMut, env: Env, msg: Reply) -> Result<Response<C>, ContractError> {
+        let pending = self.pending_replies.load(deps.storage, msg.id)?;
+        self.pending_replies.remove(deps.storage, msg.id);
+        let res = match pending {
+            PendingReply::RoyaltySwap { recipient, payment } => {
+                self._finish_royalty_swap(deps.storage, recipient, payment, msg.result)?
+            }
+            PendingReply::BundleCompanion { buyer, contract } => {
+                self._finish_companion_purchase(buyer, contract, msg.result)?
+            }
+            PendingReply::FinancedPurchase {
+                buyer,
+                token_id,
+                price,
+                loan,
+                funded,
+            } => self._finish_financed_purchase(deps, env, buyer, token_id, price, loan, funded)?,
+        };
+        Ok(res)
+    }
+
+    /// records what a submessage is for and returns the reply id to send it
+    /// with
+    pub fn _expect_reply(
+        &self,
+        storage: &mut dyn Storage,
+        pending: &PendingReply,
+    ) -> StdResult<u64> {
+        let id = self.reply_count.may_load(storage)?.unwrap_or_default() + 1;
+        self.reply_count.save(storage, &id)?;
+        self.pending_replies.save(storage, id, pending)?;
+        Ok(id)
+    }
+}